@@ -1,14 +1,14 @@
-use std::ffi::OsString;
-
+#[cfg(windows)]
 use winapi::shared::minwindef::DWORD;
-use winapi::shared::ntdef::{LANG_NEUTRAL, LPWSTR, MAKELANGID, SUBLANG_NEUTRAL};
-use winapi::shared::winerror::E_FAIL;
-use winapi::shared::winerror::{HRESULT, HRESULT_FROM_WIN32, SUCCEEDED};
-use winapi::um::winbase::{
-    FormatMessageW, LocalFree, FORMAT_MESSAGE_ALLOCATE_BUFFER, FORMAT_MESSAGE_FROM_SYSTEM,
-    FORMAT_MESSAGE_IGNORE_INSERTS,
-};
-use wio::wide::FromWide;
+#[cfg(windows)]
+use winapi::shared::winerror::{E_FAIL, HRESULT_FROM_WIN32};
+
+use crate::error::winerror::HRESULT;
+
+use std::str::FromStr;
+
+pub use crate::error::context::{ContextError, ResultExt};
+pub use crate::error::hresult::{Decoded, ErrorInfo, Facility, ParseError, Severity};
 
 #[repr(transparent)]
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
@@ -20,7 +20,7 @@ impl Error {
     /// Returns Ok if the HRESULT represents a success state, otherwise returns
     /// Err with the HRESULT value.
     pub fn map<T>(hr: HRESULT, success_value: T) -> Result<T, Error> {
-        if SUCCEEDED(hr) {
+        if hr >= 0 {
             Ok(success_value)
         } else {
             Err(Error(hr))
@@ -31,7 +31,7 @@ impl Error {
     /// Returns Ok with the HRESULT if the operation was a success,
     /// and Err with the HRESULT if the operation failed.
     pub fn map_status(hr: HRESULT) -> Result<Status, Error> {
-        if SUCCEEDED(hr) {
+        if hr >= 0 {
             Ok(Status(hr))
         } else {
             Err(Error(hr))
@@ -46,7 +46,7 @@ impl Error {
     where
         F: FnOnce() -> T,
     {
-        if SUCCEEDED(hr) {
+        if hr >= 0 {
             Ok(if_success())
         } else {
             Err(Error(hr))
@@ -54,6 +54,7 @@ impl Error {
     }
 
     #[inline]
+    #[cfg(windows)]
     /// Creates an Error from a Win32 error code, such as a raw error code
     /// from `std::io::Error`.
     pub fn from_win32(err: DWORD) -> Error {
@@ -65,6 +66,26 @@ impl Error {
     pub fn message(&self) -> String {
         format_err(self.0)
     }
+
+    #[inline]
+    /// Splits the HRESULT into its severity, facility and code.
+    pub fn decode(&self) -> Decoded {
+        Decoded::new(self.0)
+    }
+
+    #[inline]
+    /// Gets the static information for this HRESULT, if it is one of the
+    /// constants declared on `Error`.
+    pub fn info(&self) -> Option<&'static ErrorInfo> {
+        hresult::lookup(self.0)
+    }
+
+    #[inline]
+    /// Gets the symbolic name of the HRESULT as it appears in the Windows
+    /// SDK headers, e.g. `WINCODEC_ERR_PROPERTYSIZE`.
+    pub fn name(&self) -> Option<&'static str> {
+        self.info().map(|info| info.name)
+    }
 }
 
 impl From<HRESULT> for Error {
@@ -75,6 +96,20 @@ impl From<HRESULT> for Error {
     }
 }
 
+impl FromStr for Error {
+    type Err = ParseError;
+
+    /// Parses either a symbolic name such as `DXGI_ERROR_DEVICE_REMOVED` or
+    /// a hexadecimal value such as `0x887A0005`.
+    fn from_str(s: &str) -> Result<Error, ParseError> {
+        match hresult::parse(s)? {
+            hr if hr < 0 => Ok(Error(hr)),
+            hr => Err(ParseError::WrongSeverity(hr)),
+        }
+    }
+}
+
+#[cfg(windows)]
 impl From<std::io::Error> for Error {
     #[inline]
     fn from(err: std::io::Error) -> Error {
@@ -84,7 +119,11 @@ impl From<std::io::Error> for Error {
             .map(HRESULT_FROM_WIN32)
             .unwrap_or_else(|| {
                 err.get_ref()
-                    .and_then(|e| e.downcast_ref::<Error>())
+                    .and_then(|e| {
+                        e.downcast_ref::<Error>()
+                            .copied()
+                            .or_else(|| e.downcast_ref::<ContextError>().map(|e| e.error()))
+                    })
                     .map(|e| e.0)
                     .unwrap_or(E_FAIL)
             });
@@ -113,6 +152,7 @@ impl std::fmt::Display for Error {
     }
 }
 
+#[cfg(windows)]
 impl From<Error> for std::io::Error {
     fn from(error: Error) -> std::io::Error {
         use std::io::{Error as IoError, ErrorKind};
@@ -148,6 +188,26 @@ impl Status {
     pub fn message(&self) -> String {
         format_err(self.0)
     }
+
+    #[inline]
+    /// Splits the HRESULT into its severity, facility and code.
+    pub fn decode(&self) -> Decoded {
+        Decoded::new(self.0)
+    }
+
+    #[inline]
+    /// Gets the static information for this HRESULT, if it is one of the
+    /// constants declared on `Status`.
+    pub fn info(&self) -> Option<&'static ErrorInfo> {
+        hresult::lookup(self.0)
+    }
+
+    #[inline]
+    /// Gets the symbolic name of the HRESULT as it appears in the Windows
+    /// SDK headers, e.g. `DXGI_STATUS_OCCLUDED`.
+    pub fn name(&self) -> Option<&'static str> {
+        self.info().map(|info| info.name)
+    }
 }
 
 impl From<HRESULT> for Status {
@@ -158,6 +218,19 @@ impl From<HRESULT> for Status {
     }
 }
 
+impl FromStr for Status {
+    type Err = ParseError;
+
+    /// Parses either a symbolic name such as `S_FALSE` or a hexadecimal value
+    /// such as `0x087A0001`.
+    fn from_str(s: &str) -> Result<Status, ParseError> {
+        match hresult::parse(s)? {
+            hr if hr >= 0 => Ok(Status(hr)),
+            hr => Err(ParseError::WrongSeverity(hr)),
+        }
+    }
+}
+
 impl std::fmt::Debug for Status {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
         fmt.debug_tuple("Error")
@@ -174,6 +247,22 @@ impl std::fmt::Display for Status {
 }
 
 fn format_err(hr: HRESULT) -> String {
+    match hresult::lookup(hr) {
+        Some(info) => info.description.to_string(),
+        None => format_system_err(hr),
+    }
+}
+
+#[cfg(windows)]
+fn format_system_err(hr: HRESULT) -> String {
+    use std::ffi::OsString;
+    use winapi::shared::ntdef::{LANG_NEUTRAL, LPWSTR, MAKELANGID, SUBLANG_NEUTRAL};
+    use winapi::um::winbase::{
+        FormatMessageW, LocalFree, FORMAT_MESSAGE_ALLOCATE_BUFFER, FORMAT_MESSAGE_FROM_SYSTEM,
+        FORMAT_MESSAGE_IGNORE_INSERTS,
+    };
+    use wio::wide::FromWide;
+
     unsafe {
         let flags = FORMAT_MESSAGE_FROM_SYSTEM
             | FORMAT_MESSAGE_ALLOCATE_BUFFER
@@ -201,7 +290,21 @@ fn format_err(hr: HRESULT) -> String {
     }
 }
 
+#[cfg(not(windows))]
+fn format_system_err(hr: HRESULT) -> String {
+    hresult::describe(hr)
+}
+
+pub mod context;
+#[macro_use]
+pub mod hresult;
+
 mod fixme;
+#[cfg(windows)]
+use winapi::shared::winerror;
+#[cfg(not(windows))]
+#[allow(clippy::upper_case_acronyms, clippy::unreadable_literal)]
+mod winerror;
 
 #[doc(hidden)]
 pub mod common;
@@ -216,12 +319,28 @@ pub mod dwrite;
 #[doc(hidden)]
 pub mod dxgi;
 #[doc(hidden)]
+pub mod stg;
+#[doc(hidden)]
 pub mod wic;
 #[doc(hidden)]
 pub mod win32;
 
 #[cfg(test)]
 #[test]
+fn parse_error_names() {
+    let err: Error = "DXGI_ERROR_DEVICE_REMOVED".parse().unwrap();
+    assert_eq!(err, Error(0x887A_0005u32 as i32));
+    assert_eq!(err.name(), Some("DXGI_ERROR_DEVICE_REMOVED"));
+    assert_eq!("0x887A0005".parse::<Error>(), Ok(err));
+    assert_eq!(
+        "S_FALSE".parse::<Error>(),
+        Err(ParseError::WrongSeverity(1))
+    );
+    assert_eq!("S_FALSE".parse::<Status>(), Ok(Status(1)));
+}
+
+#[cfg(all(test, windows))]
+#[test]
 fn roundtrip_error_io() {
     fn rt(e: Error) {
         let err: std::io::Error = e.into();
@@ -233,4 +352,36 @@ fn roundtrip_error_io() {
     rt(Error::WIN32_FILE_NOT_FOUND);
     rt(Error::D2D_WRONG_STATE);
     rt(Error::WIC_PROPERTYSIZE);
+
+    let err: std::io::Error = ContextError::new(Error::WIC_PROPERTYSIZE, "test").into();
+    let err: crate::Error = err.into();
+    assert_eq!(err, Error::WIC_PROPERTYSIZE);
+}
+
+#[cfg(test)]
+#[test]
+fn table_matches_constants() {
+    assert_eq!(
+        Error::DXGI_DEVICE_REMOVED.info().unwrap().name,
+        "DXGI_ERROR_DEVICE_REMOVED"
+    );
+    assert_eq!(
+        Error::WIC_PROPERTYSIZE.info().unwrap().name,
+        "WINCODEC_ERR_PROPERTYSIZE"
+    );
+    assert_eq!(
+        Error::WIN32_FILE_NOT_FOUND.info().unwrap().name,
+        "ERROR_FILE_NOT_FOUND"
+    );
+    assert_eq!(
+        Error::D2D_INVALID_GLYPH_IMAGE.info().unwrap().name,
+        "D2DERR_INVALID_GLYPH_IMAGE"
+    );
+    assert_eq!(
+        Status::DXGI_OCCLUDED.info().unwrap().name,
+        "DXGI_STATUS_OCCLUDED"
+    );
+    assert_eq!(Error::D2D_INVALID_GLYPH_IMAGE, Error(0x8899_009Eu32 as i32));
+    assert_eq!(Error::DXGI_NOT_CURRENT, Error(0x887A_002Eu32 as i32));
+    assert_eq!(Error::DISP_OVERFLOW.message(), "Out of present range.");
 }
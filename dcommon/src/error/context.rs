@@ -0,0 +1,219 @@
+//! Opt-in context for failed API calls.
+//!
+//! The wrappers in this collection return bare `Result<_, Error>`. When the
+//! HRESULT alone isn't enough to track down a failure, `ResultExt` can attach
+//! the name of the call and its interesting arguments:
+//!
+//! ```ignore
+//! use dcommon::error::ResultExt;
+//!
+//! let frame = decoder
+//!     .frame(index)
+//!     .with_context("BitmapDecoder::frame", |ctx| ctx.arg("index", index))?;
+//! ```
+//!
+//! Adding context to a result that already has some pushes a new outer layer,
+//! so the full chain of calls is kept. The HRESULT is never altered and can
+//! always be recovered with `ContextError::error` or `Error::from`.
+
+use crate::error::Error;
+
+use std::fmt;
+
+/// An `Error` annotated with the API call that produced it and, optionally,
+/// the context of the calls that led there.
+pub struct ContextError {
+    error: Error,
+    api: &'static str,
+    args: Vec<(&'static str, String)>,
+    source: Option<Box<ContextError>>,
+}
+
+impl ContextError {
+    /// Wraps an error with the name of the API that returned it.
+    pub fn new(error: Error, api: &'static str) -> ContextError {
+        ContextError {
+            error,
+            api,
+            args: Vec::new(),
+            source: None,
+        }
+    }
+
+    /// Records an argument of the call, such as a query string or a frame
+    /// index.
+    pub fn arg(mut self, name: &'static str, value: impl fmt::Debug) -> ContextError {
+        self.args.push((name, format!("{:?}", value)));
+        self
+    }
+
+    /// Pushes a new outer layer of context onto this error.
+    pub fn wrap(self, api: &'static str) -> ContextError {
+        ContextError {
+            error: self.error,
+            api,
+            args: Vec::new(),
+            source: Some(Box::new(self)),
+        }
+    }
+
+    #[inline]
+    /// The underlying error. This is the same for every layer of the chain.
+    pub fn error(&self) -> Error {
+        self.error
+    }
+
+    #[inline]
+    /// The name of the API call recorded by this layer.
+    pub fn api(&self) -> &'static str {
+        self.api
+    }
+
+    #[inline]
+    /// The arguments recorded by this layer, formatted with `Debug`.
+    pub fn args(&self) -> &[(&'static str, String)] {
+        &self.args
+    }
+
+    #[inline]
+    /// The next inner layer of context, if any.
+    pub fn inner(&self) -> Option<&ContextError> {
+        self.source.as_deref()
+    }
+
+    /// Iterates over this layer and every inner layer, outermost first.
+    pub fn chain(&self) -> impl Iterator<Item = &ContextError> {
+        let mut next = Some(self);
+        std::iter::from_fn(move || {
+            let current = next?;
+            next = current.inner();
+            Some(current)
+        })
+    }
+
+    fn fmt_call(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.api)?;
+        fmt.write_str("(")?;
+        for (i, (name, value)) in self.args.iter().enumerate() {
+            if i > 0 {
+                fmt.write_str(", ")?;
+            }
+            write!(fmt, "{} = {}", name, value)?;
+        }
+        fmt.write_str(")")
+    }
+}
+
+impl From<ContextError> for Error {
+    #[inline]
+    fn from(err: ContextError) -> Error {
+        err.error
+    }
+}
+
+#[cfg(windows)]
+impl From<ContextError> for std::io::Error {
+    fn from(err: ContextError) -> std::io::Error {
+        let kind = std::io::Error::from(err.error).kind();
+        std::io::Error::new(kind, err)
+    }
+}
+
+impl fmt::Debug for ContextError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("ContextError")
+            .field("error", &self.error)
+            .field("api", &self.api)
+            .field("args", &self.args)
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for (i, layer) in self.chain().enumerate() {
+            if i > 0 {
+                fmt.write_str(" -> ")?;
+            }
+            layer.fmt_call(fmt)?;
+        }
+        write!(fmt, ": {}", self.error)
+    }
+}
+
+impl std::error::Error for ContextError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.source {
+            Some(inner) => Some(&**inner),
+            None => Some(&self.error),
+        }
+    }
+}
+
+/// Extension methods for attaching context to results.
+pub trait ResultExt<T> {
+    /// Records the name of the API call that produced the error.
+    fn context(self, api: &'static str) -> Result<T, ContextError>;
+
+    /// Records the name of the API call and lets `f` add arguments. `f` is
+    /// only called when the result is an error.
+    fn with_context<F>(self, api: &'static str, f: F) -> Result<T, ContextError>
+    where
+        F: FnOnce(ContextError) -> ContextError;
+}
+
+impl<T> ResultExt<T> for Result<T, Error> {
+    #[inline]
+    fn context(self, api: &'static str) -> Result<T, ContextError> {
+        self.map_err(|err| ContextError::new(err, api))
+    }
+
+    #[inline]
+    fn with_context<F>(self, api: &'static str, f: F) -> Result<T, ContextError>
+    where
+        F: FnOnce(ContextError) -> ContextError,
+    {
+        self.map_err(|err| f(ContextError::new(err, api)))
+    }
+}
+
+impl<T> ResultExt<T> for Result<T, ContextError> {
+    #[inline]
+    fn context(self, api: &'static str) -> Result<T, ContextError> {
+        self.map_err(|err| err.wrap(api))
+    }
+
+    #[inline]
+    fn with_context<F>(self, api: &'static str, f: F) -> Result<T, ContextError>
+    where
+        F: FnOnce(ContextError) -> ContextError,
+    {
+        self.map_err(|err| f(err.wrap(api)))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn context_chain() {
+    let hr = 0x8898_2F8Eu32 as i32;
+    let res: Result<(), Error> = Err(Error(hr));
+    let err = res
+        .with_context("MetadataQueryReader::metadata_by_name", |ctx| {
+            ctx.arg("name", "/app1/ifd/exif")
+        })
+        .context("load_exif")
+        .unwrap_err();
+
+    assert_eq!(err.error(), Error(hr));
+    assert_eq!(err.api(), "load_exif");
+    assert_eq!(err.chain().count(), 2);
+    assert_eq!(
+        err.inner().unwrap().args(),
+        &[("name", "\"/app1/ifd/exif\"".to_string())]
+    );
+    assert!(err.to_string().starts_with(
+        "load_exif() -> MetadataQueryReader::metadata_by_name(name = \"/app1/ifd/exif\"): "
+    ));
+    assert_eq!(Error::from(err), Error(hr));
+}
@@ -1,9 +1,13 @@
-use winapi::shared::minwindef::DWORD;
-use winapi::shared::winerror::HRESULT;
+use crate::error::winerror::HRESULT;
 
-pub const D2DERR_INVALID_GLYPH_IMAGE: HRESULT = 0x8899002u32 as i32;
+#[allow(clippy::upper_case_acronyms)]
+type DWORD = u32;
 
-pub const DXGI_ERROR_NOT_CURRENT: HRESULT = 0x887A002u32 as i32;
+// Values from d2d1.h and dxgi.h. Earlier versions of this file dropped the
+// last hex digit of these two (0x8899002 and 0x887A002).
+pub const D2DERR_INVALID_GLYPH_IMAGE: HRESULT = 0x8899009Eu32 as i32;
+
+pub const DXGI_ERROR_NOT_CURRENT: HRESULT = 0x887A002Eu32 as i32;
 pub const DXGI_ERROR_HW_PROTECTION_OUTOFMEMORY: HRESULT = 0x887A0030u32 as i32;
 pub const DXGI_ERROR_DYNAMIC_CODE_POLICY_VIOLATION: HRESULT = 0x887A0031u32 as i32;
 pub const DXGI_ERROR_NON_COMPOSITED_UI: HRESULT = 0x887A0032u32 as i32;
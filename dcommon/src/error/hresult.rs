@@ -0,0 +1,318 @@
+//! Platform-independent decoding of HRESULT values.
+//!
+//! Everything in this module works without calling into Windows, so it can be
+//! used to name and describe HRESULTs found in logs or crash dumps on any
+//! platform.
+
+mod table;
+
+/// Declares `Error` and `Status` constants together with their entries in
+/// the table. An entry takes its name from the SDK constant the value is built
+/// from, and its description from the doc comment.
+macro_rules! hresults {
+    ($(
+        $(#[$impl_attr:meta])*
+        impl $ty:ident {
+            $(
+                $(#[doc = $doc:literal])+
+                pub const $name:ident: $cty:ident = $ctor:ident($($value:tt)+);
+            )*
+        }
+    )*) => {
+        $(
+            $(#[$impl_attr])*
+            impl $ty {
+                $(
+                    $(#[doc = $doc])+
+                    pub const $name: $cty = $ctor($($value)+);
+                )*
+            }
+        )*
+
+        pub(super) const ENTRIES: &[crate::error::ErrorInfo] = &[$($(
+            crate::error::ErrorInfo {
+                code: $ty::$name.0,
+                name: sdk_name!($($value)+),
+                description: concat!($($doc),+).trim_ascii(),
+            },
+        )*)*];
+    };
+}
+
+/// The name of the SDK constant in an expression like `E_FAIL`,
+/// `fixme::DXGI_ERROR_NOT_CURRENT` or `hresult_from_win32(ERROR_FILE_NOT_FOUND)`.
+macro_rules! sdk_name {
+    ($name:ident) => {
+        stringify!($name)
+    };
+    ($module:ident :: $($rest:tt)+) => {
+        sdk_name!($($rest)+)
+    };
+    ($wrap:ident ($($rest:tt)+)) => {
+        sdk_name!($($rest)+)
+    };
+}
+
+/// Whether an HRESULT represents success or failure.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// The high bit is clear.
+    Success,
+    /// The high bit is set.
+    Failure,
+}
+
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// The facility portion of an HRESULT, identifying the subsystem that
+/// produced the code.
+pub struct Facility(pub u16);
+
+impl Facility {
+    /// Generic codes such as `E_FAIL` and `S_OK`.
+    pub const NULL: Facility = Facility(0);
+    /// OLE Automation dispatch errors (`DISP_E_*`).
+    pub const DISPATCH: Facility = Facility(2);
+    /// Interface-specific codes.
+    pub const ITF: Facility = Facility(4);
+    /// Win32 error codes wrapped with `HRESULT_FROM_WIN32`.
+    pub const WIN32: Facility = Facility(7);
+    /// DXGI errors and statuses.
+    pub const DXGI: Facility = Facility(0x87a);
+    /// Direct3D 11 errors.
+    pub const D3D11: Facility = Facility(0x87c);
+    /// Windows Imaging Component and DirectWrite errors.
+    pub const WINCODEC: Facility = Facility(0x898);
+    /// Direct2D errors.
+    pub const D2D: Facility = Facility(0x899);
+
+    /// The SDK name of the facility, if it is one of the known ones.
+    pub fn name(self) -> Option<&'static str> {
+        Some(match self {
+            Facility::NULL => "FACILITY_NULL",
+            Facility::DISPATCH => "FACILITY_DISPATCH",
+            Facility::ITF => "FACILITY_ITF",
+            Facility::WIN32 => "FACILITY_WIN32",
+            Facility::DXGI => "FACILITY_DXGI",
+            Facility::D3D11 => "FACILITY_DIRECT3D11",
+            Facility::WINCODEC => "FACILITY_WINCODEC_ERR",
+            Facility::D2D => "FACILITY_D2D",
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// An HRESULT split into its individual fields.
+pub struct Decoded {
+    /// The severity bit.
+    pub severity: Severity,
+    /// Set for customer-defined (non-Microsoft) codes.
+    pub customer: bool,
+    /// The facility that produced the code.
+    pub facility: Facility,
+    /// The facility-specific code.
+    pub code: u16,
+}
+
+impl Decoded {
+    /// Splits an HRESULT into severity, facility and code.
+    pub fn new(hr: i32) -> Decoded {
+        let bits = hr as u32;
+        Decoded {
+            severity: if hr < 0 {
+                Severity::Failure
+            } else {
+                Severity::Success
+            },
+            customer: bits & 0x2000_0000 != 0,
+            facility: Facility(((bits >> 16) & 0x1fff) as u16),
+            code: (bits & 0xffff) as u16,
+        }
+    }
+
+    /// Reassembles the HRESULT described by these fields.
+    pub fn to_hresult(&self) -> i32 {
+        let mut bits = (u32::from(self.facility.0 & 0x1fff) << 16) | u32::from(self.code);
+        if self.severity == Severity::Failure {
+            bits |= 0x8000_0000;
+        }
+        if self.customer {
+            bits |= 0x2000_0000;
+        }
+        bits as i32
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// Static information about one of the HRESULT constants declared on
+/// `Error` and `Status`.
+pub struct ErrorInfo {
+    /// The HRESULT value.
+    pub code: i32,
+    /// The name of the constant in the Windows SDK headers, e.g.
+    /// `DXGI_ERROR_DEVICE_REMOVED`. Win32 errors use the name of the
+    /// `ERROR_*` code they wrap.
+    pub name: &'static str,
+    /// The built-in description of the code.
+    pub description: &'static str,
+}
+
+impl ErrorInfo {
+    #[inline]
+    /// The facility that produced this code.
+    pub fn facility(&self) -> Facility {
+        Decoded::new(self.code).facility
+    }
+
+    #[inline]
+    /// Whether this code is an error or a status.
+    pub fn severity(&self) -> Severity {
+        Decoded::new(self.code).severity
+    }
+}
+
+/// All known HRESULT constants, sorted by value. Values declared under more
+/// than one name have an entry for each of them.
+pub fn all() -> &'static [ErrorInfo] {
+    &table::TABLE
+}
+
+/// Looks up the static information for an HRESULT. Some values are declared
+/// under more than one name (e.g. `E_ACCESSDENIED` and `ERROR_ACCESS_DENIED`);
+/// the generic name is returned in that case.
+pub fn lookup(hr: i32) -> Option<&'static ErrorInfo> {
+    let index = table::TABLE.partition_point(|info| info.code < hr);
+    table::TABLE.get(index).filter(|info| info.code == hr)
+}
+
+/// Looks up the static information for a symbolic name such as
+/// `DXGI_ERROR_DEVICE_REMOVED`. The comparison ignores ASCII case.
+pub fn lookup_name(name: &str) -> Option<&'static ErrorInfo> {
+    table::TABLE
+        .iter()
+        .find(|info| info.name.eq_ignore_ascii_case(name))
+}
+
+/// Parses an HRESULT from either a symbolic name (`WINCODEC_ERR_PROPERTYSIZE`),
+/// a hexadecimal value (`0x887A0005`) or a signed decimal value.
+pub fn parse(s: &str) -> Result<i32, ParseError> {
+    let s = s.trim();
+    if s.starts_with("0x") || s.starts_with("0X") {
+        return u32::from_str_radix(&s[2..], 16)
+            .map(|hr| hr as i32)
+            .map_err(|_| ParseError::InvalidNumber(s.to_string()));
+    }
+    if s.starts_with(|c: char| c == '-' || c.is_ascii_digit()) {
+        return s
+            .parse::<i32>()
+            .map_err(|_| ParseError::InvalidNumber(s.to_string()));
+    }
+    lookup_name(s)
+        .map(|info| info.code)
+        .ok_or_else(|| ParseError::UnknownName(s.to_string()))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The reasons an HRESULT may fail to parse from a string.
+pub enum ParseError {
+    /// The string is not the name of any known constant.
+    UnknownName(String),
+    /// The string looked like a number but could not be parsed as one.
+    InvalidNumber(String),
+    /// The value parsed, but is a success code where an error was expected
+    /// or vice versa.
+    WrongSeverity(i32),
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParseError::UnknownName(name) => write!(fmt, "unknown HRESULT name `{}`", name),
+            ParseError::InvalidNumber(num) => write!(fmt, "invalid HRESULT value `{}`", num),
+            ParseError::WrongSeverity(hr) => {
+                write!(fmt, "HRESULT 0x{:x} has the wrong severity", hr)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Describes an HRESULT using only the static table. Unknown values are
+/// formatted from their decoded fields.
+pub fn describe(hr: i32) -> String {
+    match lookup(hr) {
+        Some(info) => info.description.to_string(),
+        None => {
+            let decoded = Decoded::new(hr);
+            match decoded.facility.name() {
+                Some(facility) => format!(
+                    "Unknown Error 0x{:x} ({} code {})",
+                    hr, facility, decoded.code
+                ),
+                None => format!("Unknown Error 0x{:x}", hr),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn decode_hresults() {
+    let hr = 0x887A_0005u32 as i32;
+    let decoded = Decoded::new(hr);
+    assert_eq!(decoded.severity, Severity::Failure);
+    assert_eq!(decoded.facility, Facility::DXGI);
+    assert_eq!(decoded.code, 5);
+    assert_eq!(decoded.to_hresult(), hr);
+
+    assert_eq!(lookup(hr).unwrap().name, "DXGI_ERROR_DEVICE_REMOVED");
+    assert_eq!(
+        lookup(0x8007_0005u32 as i32).unwrap().name,
+        "E_ACCESSDENIED"
+    );
+    assert_eq!(lookup(0).unwrap().name, "S_OK");
+    assert_eq!(
+        describe(0x8007_0002u32 as i32),
+        "The system cannot find the file specified."
+    );
+    assert!(all().iter().all(|info| !Decoded::new(info.code).customer));
+    assert!(all().len() > 400);
+    assert!(all().windows(2).all(|pair| pair[0].code <= pair[1].code));
+    assert_eq!(
+        lookup_name("ERROR_ACCESS_DENIED").unwrap().code,
+        0x8007_0005u32 as i32
+    );
+    assert_eq!(
+        lookup_name("D3D11_ERROR_TOO_MANY_UNIQUE_VIEW_OBJECTS")
+            .unwrap()
+            .description,
+        "The application has exceeded the maximum number of unique view objects per \
+         Direct3D device. The limit is 2^20 for feature levels up to 11.1."
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn parse_hresults() {
+    assert_eq!(parse("0x887A0005"), Ok(0x887A_0005u32 as i32));
+    assert_eq!(
+        parse("DXGI_ERROR_DEVICE_REMOVED"),
+        Ok(0x887A_0005u32 as i32)
+    );
+    assert_eq!(parse(" e_fail "), Ok(0x8000_4005u32 as i32));
+    assert_eq!(parse("-2147467259"), Ok(0x8000_4005u32 as i32));
+    assert_eq!(
+        parse("E_NOPE"),
+        Err(ParseError::UnknownName("E_NOPE".to_string()))
+    );
+    assert!(parse("0xZZ").is_err());
+    assert_eq!(
+        lookup_name("WINCODEC_ERR_PROPERTYSIZE").unwrap().facility(),
+        Facility::WINCODEC
+    );
+    assert!(all()
+        .iter()
+        .all(|info| lookup_name(info.name).map(|i| i.code) == Some(info.code)));
+}
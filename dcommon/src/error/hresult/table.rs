@@ -0,0 +1,57 @@
+//! Names and descriptions for the `Error` and `Status` constants declared in
+//! the sibling modules of `dcommon::error`. Each module lists the entries for
+//! its constants through `hresults!`; they are merged here into one table,
+//! sorted by value.
+
+use super::ErrorInfo;
+use crate::error::{common, d2d1, d3d11, disp, dwrite, dxgi, stg, wic, win32};
+
+// `common` comes first so that its generic names, like `E_ACCESSDENIED`, are
+// the first of the entries that share a value.
+const MODULES: &[&[ErrorInfo]] = &[
+    common::ENTRIES,
+    d2d1::ENTRIES,
+    d3d11::ENTRIES,
+    disp::ENTRIES,
+    dwrite::ENTRIES,
+    dxgi::ENTRIES,
+    stg::ENTRIES,
+    wic::ENTRIES,
+    win32::ENTRIES,
+];
+
+const LEN: usize = {
+    let mut len = 0;
+    let mut i = 0;
+    while i < MODULES.len() {
+        len += MODULES[i].len();
+        i += 1;
+    }
+    len
+};
+
+pub static TABLE: [ErrorInfo; LEN] = sorted();
+
+/// Concatenates the entries of every module with an insertion sort, which
+/// keeps entries that share a value in declaration order.
+const fn sorted() -> [ErrorInfo; LEN] {
+    let mut table = [MODULES[0][0]; LEN];
+    let mut len = 0;
+    let mut m = 0;
+    while m < MODULES.len() {
+        let entries = MODULES[m];
+        let mut i = 0;
+        while i < entries.len() {
+            let mut j = len;
+            while j > 0 && table[j - 1].code > entries[i].code {
+                table[j] = table[j - 1];
+                j -= 1;
+            }
+            table[j] = entries[i];
+            len += 1;
+            i += 1;
+        }
+        m += 1;
+    }
+    table
+}
@@ -0,0 +1,127 @@
+use crate::Error;
+
+use crate::error::winerror::*;
+
+hresults! {
+    impl Error {
+        /// Unable to perform requested operation.
+        pub const STG_INVALIDFUNCTION: Error = Error(STG_E_INVALIDFUNCTION);
+
+        /// %1 could not be found.
+        pub const STG_FILENOTFOUND: Error = Error(STG_E_FILENOTFOUND);
+
+        /// The path %1 could not be found.
+        pub const STG_PATHNOTFOUND: Error = Error(STG_E_PATHNOTFOUND);
+
+        /// There are insufficient resources to open another file.
+        pub const STG_TOOMANYOPENFILES: Error = Error(STG_E_TOOMANYOPENFILES);
+
+        /// Access Denied.
+        pub const STG_ACCESSDENIED: Error = Error(STG_E_ACCESSDENIED);
+
+        /// Attempted an operation on an invalid object.
+        pub const STG_INVALIDHANDLE: Error = Error(STG_E_INVALIDHANDLE);
+
+        /// There is insufficient memory available to complete operation.
+        pub const STG_INSUFFICIENTMEMORY: Error = Error(STG_E_INSUFFICIENTMEMORY);
+
+        /// Invalid pointer error.
+        pub const STG_INVALIDPOINTER: Error = Error(STG_E_INVALIDPOINTER);
+
+        /// There are no more entries to return.
+        pub const STG_NOMOREFILES: Error = Error(STG_E_NOMOREFILES);
+
+        /// Disk is write-protected.
+        pub const STG_DISKISWRITEPROTECTED: Error = Error(STG_E_DISKISWRITEPROTECTED);
+
+        /// An error occurred during a seek operation.
+        pub const STG_SEEKERROR: Error = Error(STG_E_SEEKERROR);
+
+        /// A disk error occurred during a write operation.
+        pub const STG_WRITEFAULT: Error = Error(STG_E_WRITEFAULT);
+
+        /// A disk error occurred during a read operation.
+        pub const STG_READFAULT: Error = Error(STG_E_READFAULT);
+
+        /// A share violation has occurred.
+        pub const STG_SHAREVIOLATION: Error = Error(STG_E_SHAREVIOLATION);
+
+        /// A lock violation has occurred.
+        pub const STG_LOCKVIOLATION: Error = Error(STG_E_LOCKVIOLATION);
+
+        /// %1 already exists.
+        pub const STG_FILEALREADYEXISTS: Error = Error(STG_E_FILEALREADYEXISTS);
+
+        /// Invalid parameter error.
+        pub const STG_INVALIDPARAMETER: Error = Error(STG_E_INVALIDPARAMETER);
+
+        /// There is insufficient disk space to complete operation.
+        pub const STG_MEDIUMFULL: Error = Error(STG_E_MEDIUMFULL);
+
+        /// Illegal write of non-simple property to simple property set.
+        pub const STG_PROPSETMISMATCHED: Error = Error(STG_E_PROPSETMISMATCHED);
+
+        /// An API call exited abnormally.
+        pub const STG_ABNORMALAPIEXIT: Error = Error(STG_E_ABNORMALAPIEXIT);
+
+        /// The file %1 is not a valid compound file.
+        pub const STG_INVALIDHEADER: Error = Error(STG_E_INVALIDHEADER);
+
+        /// The name %1 is not valid.
+        pub const STG_INVALIDNAME: Error = Error(STG_E_INVALIDNAME);
+
+        /// An unexpected error occurred.
+        pub const STG_UNKNOWN: Error = Error(STG_E_UNKNOWN);
+
+        /// That function is not implemented.
+        pub const STG_UNIMPLEMENTEDFUNCTION: Error = Error(STG_E_UNIMPLEMENTEDFUNCTION);
+
+        /// Invalid flag error.
+        pub const STG_INVALIDFLAG: Error = Error(STG_E_INVALIDFLAG);
+
+        /// Attempted to use an object that is busy.
+        pub const STG_INUSE: Error = Error(STG_E_INUSE);
+
+        /// The storage has been changed since the last commit.
+        pub const STG_NOTCURRENT: Error = Error(STG_E_NOTCURRENT);
+
+        /// Attempted to use an object that has ceased to exist.
+        pub const STG_REVERTED: Error = Error(STG_E_REVERTED);
+
+        /// Can't save.
+        pub const STG_CANTSAVE: Error = Error(STG_E_CANTSAVE);
+
+        /// The compound file %1 was produced with an incompatible version of storage.
+        pub const STG_OLDFORMAT: Error = Error(STG_E_OLDFORMAT);
+
+        /// The compound file %1 was produced with a newer version of storage.
+        pub const STG_OLDDLL: Error = Error(STG_E_OLDDLL);
+
+        /// Share.exe or equivalent is required for operation.
+        pub const STG_SHAREREQUIRED: Error = Error(STG_E_SHAREREQUIRED);
+
+        /// Illegal operation called on non-file based storage.
+        pub const STG_NOTFILEBASEDSTORAGE: Error = Error(STG_E_NOTFILEBASEDSTORAGE);
+
+        /// Illegal operation called on object with extant marshallings.
+        pub const STG_EXTANTMARSHALLINGS: Error = Error(STG_E_EXTANTMARSHALLINGS);
+
+        /// The docfile has been corrupted.
+        pub const STG_DOCFILECORRUPT: Error = Error(STG_E_DOCFILECORRUPT);
+
+        /// OLE32.DLL has been loaded at the wrong address.
+        pub const STG_BADBASEADDRESS: Error = Error(STG_E_BADBASEADDRESS);
+
+        /// The compound file is too large for the current implementation.
+        pub const STG_DOCFILETOOLARGE: Error = Error(STG_E_DOCFILETOOLARGE);
+
+        /// The compound file was not created with the STGM_SIMPLE flag.
+        pub const STG_NOTSIMPLEFORMAT: Error = Error(STG_E_NOTSIMPLEFORMAT);
+
+        /// The file download was aborted abnormally. The file is incomplete.
+        pub const STG_INCOMPLETE: Error = Error(STG_E_INCOMPLETE);
+
+        /// The file download has been terminated.
+        pub const STG_TERMINATED: Error = Error(STG_E_TERMINATED);
+    }
+}
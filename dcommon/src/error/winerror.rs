@@ -0,0 +1,461 @@
+//! The `winerror.h` constants used by the error modules, for targets where
+//! winapi is unavailable. The values match `winapi::shared::winerror`.
+
+#![allow(dead_code, non_upper_case_globals)]
+
+pub type HRESULT = i32;
+
+pub const E_UNEXPECTED: HRESULT = 0x8000FFFFu32 as i32;
+pub const E_NOTIMPL: HRESULT = 0x80004001u32 as i32;
+pub const E_OUTOFMEMORY: HRESULT = 0x8007000Eu32 as i32;
+pub const E_INVALIDARG: HRESULT = 0x80070057u32 as i32;
+pub const E_NOINTERFACE: HRESULT = 0x80004002u32 as i32;
+pub const E_POINTER: HRESULT = 0x80004003u32 as i32;
+pub const E_HANDLE: HRESULT = 0x80070006u32 as i32;
+pub const E_ABORT: HRESULT = 0x80004004u32 as i32;
+pub const E_FAIL: HRESULT = 0x80004005u32 as i32;
+pub const E_ACCESSDENIED: HRESULT = 0x80070005u32 as i32;
+pub const E_PENDING: HRESULT = 0x8000000Au32 as i32;
+pub const E_BOUNDS: HRESULT = 0x8000000Bu32 as i32;
+pub const E_CHANGED_STATE: HRESULT = 0x8000000Cu32 as i32;
+pub const E_ILLEGAL_STATE_CHANGE: HRESULT = 0x8000000Du32 as i32;
+pub const E_ILLEGAL_METHOD_CALL: HRESULT = 0x8000000Eu32 as i32;
+pub const E_STRING_NOT_NULL_TERMINATED: HRESULT = 0x80000017u32 as i32;
+pub const E_ILLEGAL_DELEGATE_ASSIGNMENT: HRESULT = 0x80000018u32 as i32;
+pub const E_ASYNC_OPERATION_NOT_STARTED: HRESULT = 0x80000019u32 as i32;
+pub const E_APPLICATION_EXITING: HRESULT = 0x8000001Au32 as i32;
+pub const E_APPLICATION_VIEW_EXITING: HRESULT = 0x8000001Bu32 as i32;
+pub const D2DERR_WRONG_STATE: HRESULT = 0x88990001u32 as i32;
+pub const D2DERR_NOT_INITIALIZED: HRESULT = 0x88990002u32 as i32;
+pub const D2DERR_UNSUPPORTED_OPERATION: HRESULT = 0x88990003u32 as i32;
+pub const D2DERR_SCANNER_FAILED: HRESULT = 0x88990004u32 as i32;
+pub const D2DERR_SCREEN_ACCESS_DENIED: HRESULT = 0x88990005u32 as i32;
+pub const D2DERR_DISPLAY_STATE_INVALID: HRESULT = 0x88990006u32 as i32;
+pub const D2DERR_ZERO_VECTOR: HRESULT = 0x88990007u32 as i32;
+pub const D2DERR_INTERNAL_ERROR: HRESULT = 0x88990008u32 as i32;
+pub const D2DERR_DISPLAY_FORMAT_NOT_SUPPORTED: HRESULT = 0x88990009u32 as i32;
+pub const D2DERR_INVALID_CALL: HRESULT = 0x8899000Au32 as i32;
+pub const D2DERR_NO_HARDWARE_DEVICE: HRESULT = 0x8899000Bu32 as i32;
+pub const D2DERR_RECREATE_TARGET: HRESULT = 0x8899000Cu32 as i32;
+pub const D2DERR_TOO_MANY_SHADER_ELEMENTS: HRESULT = 0x8899000Du32 as i32;
+pub const D2DERR_SHADER_COMPILE_FAILED: HRESULT = 0x8899000Eu32 as i32;
+pub const D2DERR_MAX_TEXTURE_SIZE_EXCEEDED: HRESULT = 0x8899000Fu32 as i32;
+pub const D2DERR_UNSUPPORTED_VERSION: HRESULT = 0x88990010u32 as i32;
+pub const D2DERR_BAD_NUMBER: HRESULT = 0x88990011u32 as i32;
+pub const D2DERR_WRONG_FACTORY: HRESULT = 0x88990012u32 as i32;
+pub const D2DERR_LAYER_ALREADY_IN_USE: HRESULT = 0x88990013u32 as i32;
+pub const D2DERR_POP_CALL_DID_NOT_MATCH_PUSH: HRESULT = 0x88990014u32 as i32;
+pub const D2DERR_WRONG_RESOURCE_DOMAIN: HRESULT = 0x88990015u32 as i32;
+pub const D2DERR_PUSH_POP_UNBALANCED: HRESULT = 0x88990016u32 as i32;
+pub const D2DERR_RENDER_TARGET_HAS_LAYER_OR_CLIPRECT: HRESULT = 0x88990017u32 as i32;
+pub const D2DERR_INCOMPATIBLE_BRUSH_TYPES: HRESULT = 0x88990018u32 as i32;
+pub const D2DERR_WIN32_ERROR: HRESULT = 0x88990019u32 as i32;
+pub const D2DERR_TARGET_NOT_GDI_COMPATIBLE: HRESULT = 0x8899001Au32 as i32;
+pub const D2DERR_TEXT_EFFECT_IS_WRONG_TYPE: HRESULT = 0x8899001Bu32 as i32;
+pub const D2DERR_TEXT_RENDERER_NOT_RELEASED: HRESULT = 0x8899001Cu32 as i32;
+pub const D2DERR_EXCEEDS_MAX_BITMAP_SIZE: HRESULT = 0x8899001Du32 as i32;
+pub const D2DERR_INVALID_GRAPH_CONFIGURATION: HRESULT = 0x8899001Eu32 as i32;
+pub const D2DERR_INVALID_INTERNAL_GRAPH_CONFIGURATION: HRESULT = 0x8899001Fu32 as i32;
+pub const D2DERR_CYCLIC_GRAPH: HRESULT = 0x88990020u32 as i32;
+pub const D2DERR_BITMAP_CANNOT_DRAW: HRESULT = 0x88990021u32 as i32;
+pub const D2DERR_OUTSTANDING_BITMAP_REFERENCES: HRESULT = 0x88990022u32 as i32;
+pub const D2DERR_ORIGINAL_TARGET_NOT_BOUND: HRESULT = 0x88990023u32 as i32;
+pub const D2DERR_INVALID_TARGET: HRESULT = 0x88990024u32 as i32;
+pub const D2DERR_BITMAP_BOUND_AS_TARGET: HRESULT = 0x88990025u32 as i32;
+pub const D2DERR_INSUFFICIENT_DEVICE_CAPABILITIES: HRESULT = 0x88990026u32 as i32;
+pub const D2DERR_INTERMEDIATE_TOO_LARGE: HRESULT = 0x88990027u32 as i32;
+pub const D2DERR_EFFECT_IS_NOT_REGISTERED: HRESULT = 0x88990028u32 as i32;
+pub const D2DERR_INVALID_PROPERTY: HRESULT = 0x88990029u32 as i32;
+pub const D2DERR_NO_SUBPROPERTIES: HRESULT = 0x8899002Au32 as i32;
+pub const D2DERR_PRINT_JOB_CLOSED: HRESULT = 0x8899002Bu32 as i32;
+pub const D2DERR_PRINT_FORMAT_NOT_SUPPORTED: HRESULT = 0x8899002Cu32 as i32;
+pub const D2DERR_TOO_MANY_TRANSFORM_INPUTS: HRESULT = 0x8899002Du32 as i32;
+pub const D3D11_ERROR_TOO_MANY_UNIQUE_STATE_OBJECTS: HRESULT = 0x887C0001u32 as i32;
+pub const D3D11_ERROR_FILE_NOT_FOUND: HRESULT = 0x887C0002u32 as i32;
+pub const D3D11_ERROR_TOO_MANY_UNIQUE_VIEW_OBJECTS: HRESULT = 0x887C0003u32 as i32;
+pub const D3D11_ERROR_DEFERRED_CONTEXT_MAP_WITHOUT_INITIAL_DISCARD: HRESULT = 0x887C0004u32 as i32;
+pub const DISP_E_UNKNOWNINTERFACE: HRESULT = 0x80020001u32 as i32;
+pub const DISP_E_MEMBERNOTFOUND: HRESULT = 0x80020003u32 as i32;
+pub const DISP_E_PARAMNOTFOUND: HRESULT = 0x80020004u32 as i32;
+pub const DISP_E_TYPEMISMATCH: HRESULT = 0x80020005u32 as i32;
+pub const DISP_E_UNKNOWNNAME: HRESULT = 0x80020006u32 as i32;
+pub const DISP_E_NONAMEDARGS: HRESULT = 0x80020007u32 as i32;
+pub const DISP_E_BADVARTYPE: HRESULT = 0x80020008u32 as i32;
+pub const DISP_E_EXCEPTION: HRESULT = 0x80020009u32 as i32;
+pub const DISP_E_OVERFLOW: HRESULT = 0x8002000Au32 as i32;
+pub const DISP_E_BADINDEX: HRESULT = 0x8002000Bu32 as i32;
+pub const DISP_E_UNKNOWNLCID: HRESULT = 0x8002000Cu32 as i32;
+pub const DISP_E_ARRAYISLOCKED: HRESULT = 0x8002000Du32 as i32;
+pub const DISP_E_BADPARAMCOUNT: HRESULT = 0x8002000Eu32 as i32;
+pub const DISP_E_PARAMNOTOPTIONAL: HRESULT = 0x8002000Fu32 as i32;
+pub const DISP_E_BADCALLEE: HRESULT = 0x80020010u32 as i32;
+pub const DISP_E_NOTACOLLECTION: HRESULT = 0x80020011u32 as i32;
+pub const DISP_E_DIVBYZERO: HRESULT = 0x80020012u32 as i32;
+pub const DISP_E_BUFFERTOOSMALL: HRESULT = 0x80020013u32 as i32;
+pub const DWRITE_E_FILEFORMAT: HRESULT = 0x88985000u32 as i32;
+pub const DWRITE_E_UNEXPECTED: HRESULT = 0x88985001u32 as i32;
+pub const DWRITE_E_NOFONT: HRESULT = 0x88985002u32 as i32;
+pub const DWRITE_E_FILENOTFOUND: HRESULT = 0x88985003u32 as i32;
+pub const DWRITE_E_FILEACCESS: HRESULT = 0x88985004u32 as i32;
+pub const DWRITE_E_FONTCOLLECTIONOBSOLETE: HRESULT = 0x88985005u32 as i32;
+pub const DWRITE_E_ALREADYREGISTERED: HRESULT = 0x88985006u32 as i32;
+pub const DWRITE_E_CACHEFORMAT: HRESULT = 0x88985007u32 as i32;
+pub const DWRITE_E_CACHEVERSION: HRESULT = 0x88985008u32 as i32;
+pub const DWRITE_E_UNSUPPORTEDOPERATION: HRESULT = 0x88985009u32 as i32;
+pub const DWRITE_E_TEXTRENDERERINCOMPATIBLE: HRESULT = 0x8898500Au32 as i32;
+pub const DWRITE_E_FLOWDIRECTIONCONFLICTS: HRESULT = 0x8898500Bu32 as i32;
+pub const DWRITE_E_NOCOLOR: HRESULT = 0x8898500Cu32 as i32;
+pub const DXGI_ERROR_NOT_FOUND: HRESULT = 0x887A0002u32 as i32;
+pub const DXGI_ERROR_MORE_DATA: HRESULT = 0x887A0003u32 as i32;
+pub const DXGI_ERROR_UNSUPPORTED: HRESULT = 0x887A0004u32 as i32;
+pub const DXGI_ERROR_DEVICE_REMOVED: HRESULT = 0x887A0005u32 as i32;
+pub const DXGI_ERROR_DEVICE_HUNG: HRESULT = 0x887A0006u32 as i32;
+pub const DXGI_ERROR_WAS_STILL_DRAWING: HRESULT = 0x887A000Au32 as i32;
+pub const DXGI_ERROR_GRAPHICS_VIDPN_SOURCE_IN_USE: HRESULT = 0x887A000Cu32 as i32;
+pub const DXGI_ERROR_NONEXCLUSIVE: HRESULT = 0x887A0021u32 as i32;
+pub const DXGI_ERROR_NOT_CURRENTLY_AVAILABLE: HRESULT = 0x887A0022u32 as i32;
+pub const DXGI_ERROR_REMOTE_OUTOFMEMORY: HRESULT = 0x887A0024u32 as i32;
+pub const DXGI_ERROR_ACCESS_LOST: HRESULT = 0x887A0026u32 as i32;
+pub const DXGI_ERROR_WAIT_TIMEOUT: HRESULT = 0x887A0027u32 as i32;
+pub const DXGI_ERROR_RESTRICT_TO_OUTPUT_STALE: HRESULT = 0x887A0029u32 as i32;
+pub const DXGI_ERROR_NAME_ALREADY_EXISTS: HRESULT = 0x887A002Cu32 as i32;
+pub const DXGI_ERROR_SDK_COMPONENT_MISSING: HRESULT = 0x887A002Du32 as i32;
+pub const DXGI_ERROR_MODE_CHANGE_IN_PROGRESS: HRESULT = 0x887A0025u32 as i32;
+pub const DXGI_STATUS_OCCLUDED: HRESULT = 0x087A0001;
+pub const DXGI_STATUS_CLIPPED: HRESULT = 0x087A0002;
+pub const DXGI_STATUS_NO_REDIRECTION: HRESULT = 0x087A0004;
+pub const DXGI_STATUS_NO_DESKTOP_ACCESS: HRESULT = 0x087A0005;
+pub const DXGI_STATUS_GRAPHICS_VIDPN_SOURCE_IN_USE: HRESULT = 0x087A0006;
+pub const DXGI_STATUS_MODE_CHANGED: HRESULT = 0x087A0007;
+pub const DXGI_STATUS_MODE_CHANGE_IN_PROGRESS: HRESULT = 0x087A0008;
+pub const DXGI_STATUS_UNOCCLUDED: HRESULT = 0x087A0009;
+pub const DXGI_STATUS_DDA_WAS_STILL_DRAWING: HRESULT = 0x087A000A;
+pub const STG_E_INVALIDFUNCTION: HRESULT = 0x80030001u32 as i32;
+pub const STG_E_FILENOTFOUND: HRESULT = 0x80030002u32 as i32;
+pub const STG_E_PATHNOTFOUND: HRESULT = 0x80030003u32 as i32;
+pub const STG_E_TOOMANYOPENFILES: HRESULT = 0x80030004u32 as i32;
+pub const STG_E_ACCESSDENIED: HRESULT = 0x80030005u32 as i32;
+pub const STG_E_INVALIDHANDLE: HRESULT = 0x80030006u32 as i32;
+pub const STG_E_INSUFFICIENTMEMORY: HRESULT = 0x80030008u32 as i32;
+pub const STG_E_INVALIDPOINTER: HRESULT = 0x80030009u32 as i32;
+pub const STG_E_NOMOREFILES: HRESULT = 0x80030012u32 as i32;
+pub const STG_E_DISKISWRITEPROTECTED: HRESULT = 0x80030013u32 as i32;
+pub const STG_E_SEEKERROR: HRESULT = 0x80030019u32 as i32;
+pub const STG_E_WRITEFAULT: HRESULT = 0x8003001Du32 as i32;
+pub const STG_E_READFAULT: HRESULT = 0x8003001Eu32 as i32;
+pub const STG_E_SHAREVIOLATION: HRESULT = 0x80030020u32 as i32;
+pub const STG_E_LOCKVIOLATION: HRESULT = 0x80030021u32 as i32;
+pub const STG_E_FILEALREADYEXISTS: HRESULT = 0x80030050u32 as i32;
+pub const STG_E_INVALIDPARAMETER: HRESULT = 0x80030057u32 as i32;
+pub const STG_E_MEDIUMFULL: HRESULT = 0x80030070u32 as i32;
+pub const STG_E_PROPSETMISMATCHED: HRESULT = 0x800300F0u32 as i32;
+pub const STG_E_ABNORMALAPIEXIT: HRESULT = 0x800300FAu32 as i32;
+pub const STG_E_INVALIDHEADER: HRESULT = 0x800300FBu32 as i32;
+pub const STG_E_INVALIDNAME: HRESULT = 0x800300FCu32 as i32;
+pub const STG_E_UNKNOWN: HRESULT = 0x800300FDu32 as i32;
+pub const STG_E_UNIMPLEMENTEDFUNCTION: HRESULT = 0x800300FEu32 as i32;
+pub const STG_E_INVALIDFLAG: HRESULT = 0x800300FFu32 as i32;
+pub const STG_E_INUSE: HRESULT = 0x80030100u32 as i32;
+pub const STG_E_NOTCURRENT: HRESULT = 0x80030101u32 as i32;
+pub const STG_E_REVERTED: HRESULT = 0x80030102u32 as i32;
+pub const STG_E_CANTSAVE: HRESULT = 0x80030103u32 as i32;
+pub const STG_E_OLDFORMAT: HRESULT = 0x80030104u32 as i32;
+pub const STG_E_OLDDLL: HRESULT = 0x80030105u32 as i32;
+pub const STG_E_SHAREREQUIRED: HRESULT = 0x80030106u32 as i32;
+pub const STG_E_NOTFILEBASEDSTORAGE: HRESULT = 0x80030107u32 as i32;
+pub const STG_E_EXTANTMARSHALLINGS: HRESULT = 0x80030108u32 as i32;
+pub const STG_E_DOCFILECORRUPT: HRESULT = 0x80030109u32 as i32;
+pub const STG_E_BADBASEADDRESS: HRESULT = 0x80030110u32 as i32;
+pub const STG_E_DOCFILETOOLARGE: HRESULT = 0x80030111u32 as i32;
+pub const STG_E_NOTSIMPLEFORMAT: HRESULT = 0x80030112u32 as i32;
+pub const STG_E_INCOMPLETE: HRESULT = 0x80030201u32 as i32;
+pub const STG_E_TERMINATED: HRESULT = 0x80030202u32 as i32;
+pub const WINCODEC_ERR_WRONGSTATE: HRESULT = 0x88982F04u32 as i32;
+pub const WINCODEC_ERR_VALUEOUTOFRANGE: HRESULT = 0x88982F05u32 as i32;
+pub const WINCODEC_ERR_UNKNOWNIMAGEFORMAT: HRESULT = 0x88982F07u32 as i32;
+pub const WINCODEC_ERR_UNSUPPORTEDVERSION: HRESULT = 0x88982F0Bu32 as i32;
+pub const WINCODEC_ERR_NOTINITIALIZED: HRESULT = 0x88982F0Cu32 as i32;
+pub const WINCODEC_ERR_ALREADYLOCKED: HRESULT = 0x88982F0Du32 as i32;
+pub const WINCODEC_ERR_PROPERTYNOTFOUND: HRESULT = 0x88982F40u32 as i32;
+pub const WINCODEC_ERR_PROPERTYNOTSUPPORTED: HRESULT = 0x88982F41u32 as i32;
+pub const WINCODEC_ERR_PROPERTYSIZE: HRESULT = 0x88982F42u32 as i32;
+pub const WINCODEC_ERR_CODECPRESENT: HRESULT = 0x88982F43u32 as i32;
+pub const WINCODEC_ERR_CODECNOTHUMBNAIL: HRESULT = 0x88982F44u32 as i32;
+pub const WINCODEC_ERR_PALETTEUNAVAILABLE: HRESULT = 0x88982F45u32 as i32;
+pub const WINCODEC_ERR_CODECTOOMANYSCANLINES: HRESULT = 0x88982F46u32 as i32;
+pub const WINCODEC_ERR_INTERNALERROR: HRESULT = 0x88982F48u32 as i32;
+pub const WINCODEC_ERR_SOURCERECTDOESNOTMATCHDIMENSIONS: HRESULT = 0x88982F49u32 as i32;
+pub const WINCODEC_ERR_COMPONENTNOTFOUND: HRESULT = 0x88982F50u32 as i32;
+pub const WINCODEC_ERR_IMAGESIZEOUTOFRANGE: HRESULT = 0x88982F51u32 as i32;
+pub const WINCODEC_ERR_TOOMUCHMETADATA: HRESULT = 0x88982F52u32 as i32;
+pub const WINCODEC_ERR_BADIMAGE: HRESULT = 0x88982F60u32 as i32;
+pub const WINCODEC_ERR_BADHEADER: HRESULT = 0x88982F61u32 as i32;
+pub const WINCODEC_ERR_FRAMEMISSING: HRESULT = 0x88982F62u32 as i32;
+pub const WINCODEC_ERR_BADMETADATAHEADER: HRESULT = 0x88982F63u32 as i32;
+pub const WINCODEC_ERR_BADSTREAMDATA: HRESULT = 0x88982F70u32 as i32;
+pub const WINCODEC_ERR_STREAMWRITE: HRESULT = 0x88982F71u32 as i32;
+pub const WINCODEC_ERR_STREAMREAD: HRESULT = 0x88982F72u32 as i32;
+pub const WINCODEC_ERR_STREAMNOTAVAILABLE: HRESULT = 0x88982F73u32 as i32;
+pub const WINCODEC_ERR_UNSUPPORTEDPIXELFORMAT: HRESULT = 0x88982F80u32 as i32;
+pub const WINCODEC_ERR_UNSUPPORTEDOPERATION: HRESULT = 0x88982F81u32 as i32;
+pub const WINCODEC_ERR_INVALIDREGISTRATION: HRESULT = 0x88982F8Au32 as i32;
+pub const WINCODEC_ERR_COMPONENTINITIALIZEFAILURE: HRESULT = 0x88982F8Bu32 as i32;
+pub const WINCODEC_ERR_INSUFFICIENTBUFFER: HRESULT = 0x88982F8Cu32 as i32;
+pub const WINCODEC_ERR_DUPLICATEMETADATAPRESENT: HRESULT = 0x88982F8Du32 as i32;
+pub const WINCODEC_ERR_PROPERTYUNEXPECTEDTYPE: HRESULT = 0x88982F8Eu32 as i32;
+pub const WINCODEC_ERR_UNEXPECTEDSIZE: HRESULT = 0x88982F8Fu32 as i32;
+pub const WINCODEC_ERR_INVALIDQUERYREQUEST: HRESULT = 0x88982F90u32 as i32;
+pub const WINCODEC_ERR_UNEXPECTEDMETADATATYPE: HRESULT = 0x88982F91u32 as i32;
+pub const WINCODEC_ERR_REQUESTONLYVALIDATMETADATAROOT: HRESULT = 0x88982F92u32 as i32;
+pub const WINCODEC_ERR_INVALIDQUERYCHARACTER: HRESULT = 0x88982F93u32 as i32;
+pub const WINCODEC_ERR_WIN32ERROR: HRESULT = 0x88982F94u32 as i32;
+pub const WINCODEC_ERR_INVALIDPROGRESSIVELEVEL: HRESULT = 0x88982F95u32 as i32;
+pub const S_OK: HRESULT = 0x00000000;
+pub const S_FALSE: HRESULT = 0x00000001;
+pub const ERROR_INVALID_FUNCTION: u32 = 1;
+pub const ERROR_FILE_NOT_FOUND: u32 = 2;
+pub const ERROR_PATH_NOT_FOUND: u32 = 3;
+pub const ERROR_TOO_MANY_OPEN_FILES: u32 = 4;
+pub const ERROR_ACCESS_DENIED: u32 = 5;
+pub const ERROR_INVALID_HANDLE: u32 = 6;
+pub const ERROR_ARENA_TRASHED: u32 = 7;
+pub const ERROR_NOT_ENOUGH_MEMORY: u32 = 8;
+pub const ERROR_INVALID_BLOCK: u32 = 9;
+pub const ERROR_BAD_ENVIRONMENT: u32 = 10;
+pub const ERROR_BAD_FORMAT: u32 = 11;
+pub const ERROR_INVALID_ACCESS: u32 = 12;
+pub const ERROR_INVALID_DATA: u32 = 13;
+pub const ERROR_OUTOFMEMORY: u32 = 14;
+pub const ERROR_INVALID_DRIVE: u32 = 15;
+pub const ERROR_CURRENT_DIRECTORY: u32 = 16;
+pub const ERROR_NOT_SAME_DEVICE: u32 = 17;
+pub const ERROR_NO_MORE_FILES: u32 = 18;
+pub const ERROR_WRITE_PROTECT: u32 = 19;
+pub const ERROR_BAD_UNIT: u32 = 20;
+pub const ERROR_NOT_READY: u32 = 21;
+pub const ERROR_BAD_COMMAND: u32 = 22;
+pub const ERROR_CRC: u32 = 23;
+pub const ERROR_BAD_LENGTH: u32 = 24;
+pub const ERROR_SEEK: u32 = 25;
+pub const ERROR_NOT_DOS_DISK: u32 = 26;
+pub const ERROR_SECTOR_NOT_FOUND: u32 = 27;
+pub const ERROR_OUT_OF_PAPER: u32 = 28;
+pub const ERROR_WRITE_FAULT: u32 = 29;
+pub const ERROR_READ_FAULT: u32 = 30;
+pub const ERROR_GEN_FAILURE: u32 = 31;
+pub const ERROR_SHARING_VIOLATION: u32 = 32;
+pub const ERROR_LOCK_VIOLATION: u32 = 33;
+pub const ERROR_WRONG_DISK: u32 = 34;
+pub const ERROR_SHARING_BUFFER_EXCEEDED: u32 = 36;
+pub const ERROR_HANDLE_EOF: u32 = 38;
+pub const ERROR_HANDLE_DISK_FULL: u32 = 39;
+pub const ERROR_NOT_SUPPORTED: u32 = 50;
+pub const ERROR_REM_NOT_LIST: u32 = 51;
+pub const ERROR_DUP_NAME: u32 = 52;
+pub const ERROR_BAD_NETPATH: u32 = 53;
+pub const ERROR_NETWORK_BUSY: u32 = 54;
+pub const ERROR_DEV_NOT_EXIST: u32 = 55;
+pub const ERROR_TOO_MANY_CMDS: u32 = 56;
+pub const ERROR_ADAP_HDW_ERR: u32 = 57;
+pub const ERROR_BAD_NET_RESP: u32 = 58;
+pub const ERROR_UNEXP_NET_ERR: u32 = 59;
+pub const ERROR_BAD_REM_ADAP: u32 = 60;
+pub const ERROR_PRINTQ_FULL: u32 = 61;
+pub const ERROR_NO_SPOOL_SPACE: u32 = 62;
+pub const ERROR_PRINT_CANCELLED: u32 = 63;
+pub const ERROR_NETNAME_DELETED: u32 = 64;
+pub const ERROR_NETWORK_ACCESS_DENIED: u32 = 65;
+pub const ERROR_BAD_DEV_TYPE: u32 = 66;
+pub const ERROR_BAD_NET_NAME: u32 = 67;
+pub const ERROR_TOO_MANY_NAMES: u32 = 68;
+pub const ERROR_TOO_MANY_SESS: u32 = 69;
+pub const ERROR_SHARING_PAUSED: u32 = 70;
+pub const ERROR_REQ_NOT_ACCEP: u32 = 71;
+pub const ERROR_REDIR_PAUSED: u32 = 72;
+pub const ERROR_FILE_EXISTS: u32 = 80;
+pub const ERROR_CANNOT_MAKE: u32 = 82;
+pub const ERROR_FAIL_I24: u32 = 83;
+pub const ERROR_OUT_OF_STRUCTURES: u32 = 84;
+pub const ERROR_ALREADY_ASSIGNED: u32 = 85;
+pub const ERROR_INVALID_PASSWORD: u32 = 86;
+pub const ERROR_INVALID_PARAMETER: u32 = 87;
+pub const ERROR_NET_WRITE_FAULT: u32 = 88;
+pub const ERROR_NO_PROC_SLOTS: u32 = 89;
+pub const ERROR_TOO_MANY_SEMAPHORES: u32 = 100;
+pub const ERROR_EXCL_SEM_ALREADY_OWNED: u32 = 101;
+pub const ERROR_SEM_IS_SET: u32 = 102;
+pub const ERROR_TOO_MANY_SEM_REQUESTS: u32 = 103;
+pub const ERROR_INVALID_AT_INTERRUPT_TIME: u32 = 104;
+pub const ERROR_SEM_OWNER_DIED: u32 = 105;
+pub const ERROR_SEM_USER_LIMIT: u32 = 106;
+pub const ERROR_DISK_CHANGE: u32 = 107;
+pub const ERROR_DRIVE_LOCKED: u32 = 108;
+pub const ERROR_BROKEN_PIPE: u32 = 109;
+pub const ERROR_OPEN_FAILED: u32 = 110;
+pub const ERROR_BUFFER_OVERFLOW: u32 = 111;
+pub const ERROR_DISK_FULL: u32 = 112;
+pub const ERROR_NO_MORE_SEARCH_HANDLES: u32 = 113;
+pub const ERROR_INVALID_TARGET_HANDLE: u32 = 114;
+pub const ERROR_INVALID_CATEGORY: u32 = 117;
+pub const ERROR_INVALID_VERIFY_SWITCH: u32 = 118;
+pub const ERROR_BAD_DRIVER_LEVEL: u32 = 119;
+pub const ERROR_CALL_NOT_IMPLEMENTED: u32 = 120;
+pub const ERROR_SEM_TIMEOUT: u32 = 121;
+pub const ERROR_INSUFFICIENT_BUFFER: u32 = 122;
+pub const ERROR_INVALID_NAME: u32 = 123;
+pub const ERROR_INVALID_LEVEL: u32 = 124;
+pub const ERROR_NO_VOLUME_LABEL: u32 = 125;
+pub const ERROR_MOD_NOT_FOUND: u32 = 126;
+pub const ERROR_PROC_NOT_FOUND: u32 = 127;
+pub const ERROR_WAIT_NO_CHILDREN: u32 = 128;
+pub const ERROR_CHILD_NOT_COMPLETE: u32 = 129;
+pub const ERROR_DIRECT_ACCESS_HANDLE: u32 = 130;
+pub const ERROR_NEGATIVE_SEEK: u32 = 131;
+pub const ERROR_SEEK_ON_DEVICE: u32 = 132;
+pub const ERROR_IS_JOIN_TARGET: u32 = 133;
+pub const ERROR_IS_JOINED: u32 = 134;
+pub const ERROR_IS_SUBSTED: u32 = 135;
+pub const ERROR_NOT_JOINED: u32 = 136;
+pub const ERROR_NOT_SUBSTED: u32 = 137;
+pub const ERROR_JOIN_TO_JOIN: u32 = 138;
+pub const ERROR_SUBST_TO_SUBST: u32 = 139;
+pub const ERROR_JOIN_TO_SUBST: u32 = 140;
+pub const ERROR_SUBST_TO_JOIN: u32 = 141;
+pub const ERROR_BUSY_DRIVE: u32 = 142;
+pub const ERROR_SAME_DRIVE: u32 = 143;
+pub const ERROR_DIR_NOT_ROOT: u32 = 144;
+pub const ERROR_DIR_NOT_EMPTY: u32 = 145;
+pub const ERROR_IS_SUBST_PATH: u32 = 146;
+pub const ERROR_IS_JOIN_PATH: u32 = 147;
+pub const ERROR_PATH_BUSY: u32 = 148;
+pub const ERROR_IS_SUBST_TARGET: u32 = 149;
+pub const ERROR_SYSTEM_TRACE: u32 = 150;
+pub const ERROR_INVALID_EVENT_COUNT: u32 = 151;
+pub const ERROR_TOO_MANY_MUXWAITERS: u32 = 152;
+pub const ERROR_INVALID_LIST_FORMAT: u32 = 153;
+pub const ERROR_LABEL_TOO_LONG: u32 = 154;
+pub const ERROR_TOO_MANY_TCBS: u32 = 155;
+pub const ERROR_SIGNAL_REFUSED: u32 = 156;
+pub const ERROR_DISCARDED: u32 = 157;
+pub const ERROR_NOT_LOCKED: u32 = 158;
+pub const ERROR_BAD_THREADID_ADDR: u32 = 159;
+pub const ERROR_BAD_ARGUMENTS: u32 = 160;
+pub const ERROR_BAD_PATHNAME: u32 = 161;
+pub const ERROR_SIGNAL_PENDING: u32 = 162;
+pub const ERROR_MAX_THRDS_REACHED: u32 = 164;
+pub const ERROR_LOCK_FAILED: u32 = 167;
+pub const ERROR_BUSY: u32 = 170;
+pub const ERROR_DEVICE_SUPPORT_IN_PROGRESS: u32 = 171;
+pub const ERROR_CANCEL_VIOLATION: u32 = 173;
+pub const ERROR_ATOMIC_LOCKS_NOT_SUPPORTED: u32 = 174;
+pub const ERROR_INVALID_SEGMENT_NUMBER: u32 = 180;
+pub const ERROR_INVALID_ORDINAL: u32 = 182;
+pub const ERROR_ALREADY_EXISTS: u32 = 183;
+pub const ERROR_INVALID_FLAG_NUMBER: u32 = 186;
+pub const ERROR_SEM_NOT_FOUND: u32 = 187;
+pub const ERROR_INVALID_STARTING_CODESEG: u32 = 188;
+pub const ERROR_INVALID_STACKSEG: u32 = 189;
+pub const ERROR_INVALID_MODULETYPE: u32 = 190;
+pub const ERROR_INVALID_EXE_SIGNATURE: u32 = 191;
+pub const ERROR_EXE_MARKED_INVALID: u32 = 192;
+pub const ERROR_BAD_EXE_FORMAT: u32 = 193;
+pub const ERROR_ITERATED_DATA_EXCEEDS_64k: u32 = 194;
+pub const ERROR_INVALID_MINALLOCSIZE: u32 = 195;
+pub const ERROR_DYNLINK_FROM_INVALID_RING: u32 = 196;
+pub const ERROR_IOPL_NOT_ENABLED: u32 = 197;
+pub const ERROR_INVALID_SEGDPL: u32 = 198;
+pub const ERROR_AUTODATASEG_EXCEEDS_64k: u32 = 199;
+pub const ERROR_RING2SEG_MUST_BE_MOVABLE: u32 = 200;
+pub const ERROR_RELOC_CHAIN_XEEDS_SEGLIM: u32 = 201;
+pub const ERROR_INFLOOP_IN_RELOC_CHAIN: u32 = 202;
+pub const ERROR_ENVVAR_NOT_FOUND: u32 = 203;
+pub const ERROR_NO_SIGNAL_SENT: u32 = 205;
+pub const ERROR_FILENAME_EXCED_RANGE: u32 = 206;
+pub const ERROR_RING2_STACK_IN_USE: u32 = 207;
+pub const ERROR_META_EXPANSION_TOO_LONG: u32 = 208;
+pub const ERROR_INVALID_SIGNAL_NUMBER: u32 = 209;
+pub const ERROR_THREAD_1_INACTIVE: u32 = 210;
+pub const ERROR_LOCKED: u32 = 212;
+pub const ERROR_TOO_MANY_MODULES: u32 = 214;
+pub const ERROR_NESTING_NOT_ALLOWED: u32 = 215;
+pub const ERROR_EXE_MACHINE_TYPE_MISMATCH: u32 = 216;
+pub const ERROR_EXE_CANNOT_MODIFY_SIGNED_BINARY: u32 = 217;
+pub const ERROR_EXE_CANNOT_MODIFY_STRONG_SIGNED_BINARY: u32 = 218;
+pub const ERROR_FILE_CHECKED_OUT: u32 = 220;
+pub const ERROR_CHECKOUT_REQUIRED: u32 = 221;
+pub const ERROR_BAD_FILE_TYPE: u32 = 222;
+pub const ERROR_FILE_TOO_LARGE: u32 = 223;
+pub const ERROR_FORMS_AUTH_REQUIRED: u32 = 224;
+pub const ERROR_VIRUS_INFECTED: u32 = 225;
+pub const ERROR_VIRUS_DELETED: u32 = 226;
+pub const ERROR_PIPE_LOCAL: u32 = 229;
+pub const ERROR_BAD_PIPE: u32 = 230;
+pub const ERROR_PIPE_BUSY: u32 = 231;
+pub const ERROR_NO_DATA: u32 = 232;
+pub const ERROR_PIPE_NOT_CONNECTED: u32 = 233;
+pub const ERROR_MORE_DATA: u32 = 234;
+pub const ERROR_VC_DISCONNECTED: u32 = 240;
+pub const ERROR_INVALID_EA_NAME: u32 = 254;
+pub const ERROR_EA_LIST_INCONSISTENT: u32 = 255;
+pub const ERROR_NO_MORE_ITEMS: u32 = 259;
+pub const ERROR_CANNOT_COPY: u32 = 266;
+pub const ERROR_DIRECTORY: u32 = 267;
+pub const ERROR_EAS_DIDNT_FIT: u32 = 275;
+pub const ERROR_EA_FILE_CORRUPT: u32 = 276;
+pub const ERROR_EA_TABLE_FULL: u32 = 277;
+pub const ERROR_INVALID_EA_HANDLE: u32 = 278;
+pub const ERROR_EAS_NOT_SUPPORTED: u32 = 282;
+pub const ERROR_TOO_MANY_POSTS: u32 = 298;
+pub const ERROR_PARTIAL_COPY: u32 = 299;
+pub const ERROR_OPLOCK_NOT_GRANTED: u32 = 300;
+pub const ERROR_INVALID_OPLOCK_PROTOCOL: u32 = 301;
+pub const ERROR_DISK_TOO_FRAGMENTED: u32 = 302;
+pub const ERROR_DELETE_PENDING: u32 = 303;
+pub const ERROR_INCOMPATIBLE_WITH_GLOBAL_SHORT_NAME_REGISTRY_SETTING: u32 = 304;
+pub const ERROR_SHORT_NAMES_NOT_ENABLED_ON_VOLUME: u32 = 305;
+pub const ERROR_SECURITY_STREAM_IS_INCONSISTENT: u32 = 306;
+pub const ERROR_INVALID_LOCK_RANGE: u32 = 307;
+pub const ERROR_IMAGE_SUBSYSTEM_NOT_PRESENT: u32 = 308;
+pub const ERROR_NOTIFICATION_GUID_ALREADY_DEFINED: u32 = 309;
+pub const ERROR_INVALID_EXCEPTION_HANDLER: u32 = 310;
+pub const ERROR_DUPLICATE_PRIVILEGES: u32 = 311;
+pub const ERROR_NO_RANGES_PROCESSED: u32 = 312;
+pub const ERROR_NOT_ALLOWED_ON_SYSTEM_FILE: u32 = 313;
+pub const ERROR_DISK_RESOURCES_EXHAUSTED: u32 = 314;
+pub const ERROR_INVALID_TOKEN: u32 = 315;
+pub const ERROR_DEVICE_FEATURE_NOT_SUPPORTED: u32 = 316;
+pub const ERROR_MR_MID_NOT_FOUND: u32 = 317;
+pub const ERROR_SCOPE_NOT_FOUND: u32 = 318;
+pub const ERROR_UNDEFINED_SCOPE: u32 = 319;
+pub const ERROR_INVALID_CAP: u32 = 320;
+pub const ERROR_DEVICE_UNREACHABLE: u32 = 321;
+pub const ERROR_DEVICE_NO_RESOURCES: u32 = 322;
+pub const ERROR_DATA_CHECKSUM_ERROR: u32 = 323;
+pub const ERROR_INTERMIXED_KERNEL_EA_OPERATION: u32 = 324;
+pub const ERROR_FILE_LEVEL_TRIM_NOT_SUPPORTED: u32 = 326;
+pub const ERROR_OFFSET_ALIGNMENT_VIOLATION: u32 = 327;
+pub const ERROR_INVALID_FIELD_IN_PARAMETER_LIST: u32 = 328;
+pub const ERROR_OPERATION_IN_PROGRESS: u32 = 329;
+pub const ERROR_BAD_DEVICE_PATH: u32 = 330;
+pub const ERROR_TOO_MANY_DESCRIPTORS: u32 = 331;
+pub const ERROR_SCRUB_DATA_DISABLED: u32 = 332;
+pub const ERROR_NOT_REDUNDANT_STORAGE: u32 = 333;
+pub const ERROR_RESIDENT_FILE_NOT_SUPPORTED: u32 = 334;
+pub const ERROR_COMPRESSED_FILE_NOT_SUPPORTED: u32 = 335;
+pub const ERROR_DIRECTORY_NOT_SUPPORTED: u32 = 336;
+pub const ERROR_NOT_READ_FROM_COPY: u32 = 337;
+pub const ERROR_FT_WRITE_FAILURE: u32 = 338;
+pub const ERROR_FT_DI_SCAN_REQUIRED: u32 = 339;
+pub const ERROR_INVALID_KERNEL_INFO_VERSION: u32 = 340;
+pub const ERROR_INVALID_PEP_INFO_VERSION: u32 = 341;
+pub const ERROR_OBJECT_NOT_EXTERNALLY_BACKED: u32 = 342;
+pub const ERROR_EXTERNAL_BACKING_PROVIDER_UNKNOWN: u32 = 343;
+pub const ERROR_FAIL_NOACTION_REBOOT: u32 = 350;
+pub const ERROR_FAIL_SHUTDOWN: u32 = 351;
+pub const ERROR_FAIL_RESTART: u32 = 352;
+pub const ERROR_MAX_SESSIONS_REACHED: u32 = 353;
+pub const ERROR_THREAD_MODE_ALREADY_BACKGROUND: u32 = 400;
+pub const ERROR_THREAD_MODE_NOT_BACKGROUND: u32 = 401;
+pub const ERROR_PROCESS_MODE_ALREADY_BACKGROUND: u32 = 402;
+pub const ERROR_PROCESS_MODE_NOT_BACKGROUND: u32 = 403;
+pub const FACILITY_WIN32: HRESULT = 0x00000007;
@@ -0,0 +1,12 @@
+//! A stand-in for winapi's `GUID` on targets without winapi, with the same
+//! layout and field names.
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+#[allow(non_snake_case)]
+pub struct GUID {
+    pub Data1: u32,
+    pub Data2: u16,
+    pub Data3: u16,
+    pub Data4: [u8; 8],
+}
@@ -0,0 +1,174 @@
+//! Allocators for memory that is handed across a COM boundary.
+//!
+//! `CoTaskWString` and `BStr` take their allocator as a type parameter. It
+//! defaults to `DefaultAllocator`, which is `ComAllocator` on Windows and
+//! `HeapAllocator` elsewhere, so the same code can run in unit tests on any
+//! target.
+
+use std::alloc::Layout;
+
+/// Allocates the memory for strings and arrays owned by the callee, in the
+/// way `CoTaskMemAlloc` does.
+///
+/// Allocators are marker types; all state is global.
+///
+/// # Safety
+///
+/// `alloc` must return null or a block of at least `size` bytes that stays
+/// valid until it is passed to `free`.
+pub unsafe trait TaskAllocator {
+    /// Allocates `size` bytes aligned for any primitive type. Returns null
+    /// when out of memory.
+    fn alloc(size: usize) -> *mut u8;
+
+    /// Frees memory returned by `alloc`. Does nothing for null.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be null or returned by this allocator's `alloc`, and not
+    /// already freed.
+    unsafe fn free(ptr: *mut u8);
+}
+
+/// Allocates `BSTR`s, in the way `SysAllocStringLen` does.
+///
+/// A `BSTR` points at the first character. The length in bytes is stored as
+/// a `u32` just before it, and the string is followed by a nul terminator.
+///
+/// # Safety
+///
+/// `alloc_string` must return null or a string laid out as above, which
+/// stays valid until it is passed to `free_string`.
+pub unsafe trait BStrAllocator {
+    /// Allocates a string of `len` characters, which are left zeroed.
+    /// Returns null when out of memory.
+    fn alloc_string(len: u32) -> *mut u16;
+
+    /// Frees a string returned by `alloc_string`. Does nothing for null.
+    ///
+    /// # Safety
+    ///
+    /// `bstr` must be null or returned by this allocator's `alloc_string`,
+    /// and not already freed.
+    unsafe fn free_string(bstr: *mut u16);
+
+    /// The length of the string, in characters.
+    ///
+    /// # Safety
+    ///
+    /// `bstr` must be null or a live string from this allocator.
+    unsafe fn string_len(bstr: *mut u16) -> u32 {
+        if bstr.is_null() {
+            0
+        } else {
+            *(bstr as *const u32).offset(-1) / 2
+        }
+    }
+}
+
+#[cfg(windows)]
+/// The allocator used by COM, backed by `CoTaskMemAlloc` and
+/// `SysAllocStringLen`.
+pub struct ComAllocator;
+
+#[cfg(windows)]
+unsafe impl TaskAllocator for ComAllocator {
+    fn alloc(size: usize) -> *mut u8 {
+        unsafe { winapi::um::combaseapi::CoTaskMemAlloc(size) as *mut u8 }
+    }
+
+    unsafe fn free(ptr: *mut u8) {
+        winapi::um::combaseapi::CoTaskMemFree(ptr as _)
+    }
+}
+
+#[cfg(windows)]
+unsafe impl BStrAllocator for ComAllocator {
+    fn alloc_string(len: u32) -> *mut u16 {
+        unsafe { SysAllocStringLen(std::ptr::null(), len) }
+    }
+
+    unsafe fn free_string(bstr: *mut u16) {
+        SysFreeString(bstr)
+    }
+
+    unsafe fn string_len(bstr: *mut u16) -> u32 {
+        SysStringLen(bstr)
+    }
+}
+
+#[cfg(windows)]
+extern "system" {
+    fn SysAllocStringLen(str_in: *const u16, ui: u32) -> *mut u16;
+    fn SysFreeString(b: *mut u16);
+    fn SysStringLen(b: *mut u16) -> u32;
+}
+
+/// An allocator backed by the Rust global allocator. Its memory must never
+/// be passed to code that frees it with the COM allocator.
+pub struct HeapAllocator;
+
+/// Room in front of each block for its size, keeping the block aligned.
+const HEADER: usize = 16;
+
+unsafe impl TaskAllocator for HeapAllocator {
+    fn alloc(size: usize) -> *mut u8 {
+        let layout = match size
+            .checked_add(HEADER)
+            .and_then(|total| Layout::from_size_align(total, HEADER).ok())
+        {
+            Some(layout) => layout,
+            None => return std::ptr::null_mut(),
+        };
+        unsafe {
+            let base = std::alloc::alloc(layout);
+            if base.is_null() {
+                return base;
+            }
+            *(base as *mut usize) = layout.size();
+            base.add(HEADER)
+        }
+    }
+
+    unsafe fn free(ptr: *mut u8) {
+        if ptr.is_null() {
+            return;
+        }
+        let base = ptr.sub(HEADER);
+        let size = *(base as *const usize);
+        std::alloc::dealloc(base, Layout::from_size_align_unchecked(size, HEADER));
+    }
+}
+
+unsafe impl BStrAllocator for HeapAllocator {
+    fn alloc_string(len: u32) -> *mut u16 {
+        let bytes = match len.checked_mul(2) {
+            Some(bytes) => bytes,
+            None => return std::ptr::null_mut(),
+        };
+        // The prefix, the characters and the terminator.
+        let size = 4 + bytes as usize + 2;
+        unsafe {
+            let base = HeapAllocator::alloc(size);
+            if base.is_null() {
+                return std::ptr::null_mut();
+            }
+            std::ptr::write_bytes(base, 0, size);
+            *(base as *mut u32) = bytes;
+            base.add(4) as *mut u16
+        }
+    }
+
+    unsafe fn free_string(bstr: *mut u16) {
+        if !bstr.is_null() {
+            HeapAllocator::free((bstr as *mut u8).sub(4));
+        }
+    }
+}
+
+#[cfg(windows)]
+/// The allocator `CoTaskWString` and `BStr` use unless told otherwise.
+pub type DefaultAllocator = ComAllocator;
+#[cfg(not(windows))]
+/// The allocator `CoTaskWString` and `BStr` use unless told otherwise.
+pub type DefaultAllocator = HeapAllocator;
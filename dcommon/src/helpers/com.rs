@@ -1,3 +1,11 @@
+//! Conversions between raw interface pointers and `ComWrapper` types.
+//!
+//! Every function here has the same requirement: the pointers must be null
+//! or valid pointers to `T::Interface` (or `U::Interface`). Functions that
+//! return an owned wrapper take over the reference the pointer holds.
+
+#![allow(clippy::missing_safety_doc)]
+
 use com_wrapper::ComWrapper;
 use std::ops::Deref;
 
@@ -1,6 +1,10 @@
+#[cfg(windows)]
 pub use self::com::*;
 pub use self::wide::*;
 
+pub mod alloc;
+#[cfg(windows)]
 pub mod com;
+#[cfg(windows)]
 pub mod member_compat;
 pub mod wide;
@@ -1,10 +1,19 @@
-use std::ffi::OsString;
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
 use std::ptr::NonNull;
 
-use winapi::shared::wtypesbase::LPOLESTR;
-use winapi::um::combaseapi::{CoTaskMemAlloc, CoTaskMemFree};
-use wio::wide::FromWide;
+use crate::helpers::alloc::{DefaultAllocator, TaskAllocator};
 
+#[cfg(windows)]
+use wio::wide::{FromWide, ToWide};
+
+/// The number of characters before the nul terminator.
+///
+/// # Safety
+///
+/// `pwstr` must point to a nul-terminated wide string.
 pub unsafe fn wstrlen(mut pwstr: *const u16) -> usize {
     let mut len = 0;
     while *pwstr != 0 {
@@ -34,7 +43,7 @@ pub struct WideStr<'a> {
 impl<'a> WideStr<'a> {
     /// Convert this value to an OsString.
     pub fn to_os_string(&self) -> OsString {
-        OsString::from_wide(self.data)
+        os_string_from_wide(self.data)
     }
 
     /// Attempt to convert this string to UTF-8. Will replace bad codepoints with the question
@@ -46,6 +55,11 @@ impl<'a> WideStr<'a> {
 
 impl<'a> WideStr<'a> {
     /// Construct a WideStr from a pointer to an array and its length.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to `len` characters that stay valid and unchanged
+    /// for `'a`.
     pub unsafe fn from_raw(ptr: *const u16, len: usize) -> WideStr<'a> {
         WideStr {
             data: std::slice::from_raw_parts(ptr, len),
@@ -66,7 +80,7 @@ impl WideCStr {
             let ptr = self.as_ptr();
             let len = wstrlen(ptr);
             let slice = std::slice::from_raw_parts(ptr, len);
-            OsString::from_wide(slice)
+            os_string_from_wide(slice)
         }
     }
 
@@ -84,34 +98,74 @@ impl WideCStr {
 
 impl WideCStr {
     /// Construct the value from a c-style string pointer.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a nul-terminated wide string that stays valid and
+    /// unchanged for `'a`.
     pub unsafe fn from_ptr<'a>(ptr: *const u16) -> &'a WideCStr {
         &*(ptr as *const WideCStr)
     }
 
     /// Convert this back to a c-style string pointer.
+    ///
+    /// # Safety
+    ///
+    /// The pointer is only valid while `self` is borrowed.
     pub unsafe fn as_ptr(&self) -> *const u16 {
         self as *const WideCStr as *const u16
     }
 }
 
 #[repr(transparent)]
-pub struct CoTaskWString {
+/// An owned, nul-terminated wide string allocated with `A`, for strings
+/// that are handed to or received from COM as an `LPOLESTR`.
+pub struct CoTaskWString<A: TaskAllocator = DefaultAllocator> {
     ptr: NonNull<u16>,
+    alloc: PhantomData<A>,
 }
 
 impl CoTaskWString {
     pub fn create(data: &[u16]) -> CoTaskWString {
+        CoTaskWString::create_in(data)
+    }
+
+    /// Takes ownership of a string allocated with the default allocator.
+    ///
+    /// # Safety
+    ///
+    /// Same as `from_raw_in`.
+    pub unsafe fn from_raw(ptr: *mut u16) -> CoTaskWString {
+        CoTaskWString::from_raw_in(ptr)
+    }
+}
+
+impl<A: TaskAllocator> CoTaskWString<A> {
+    /// Like `create`, but allocates with `A`.
+    pub fn create_in(data: &[u16]) -> CoTaskWString<A> {
         unsafe {
             let size = (data.len() + 1) * 2;
-            let mem = CoTaskMemAlloc(size) as *mut u16;
+            let mem = A::alloc(size) as *mut u16;
             if mem.is_null() {
                 panic!("oom: failed to allocate {} bytes for string", size);
             }
             std::ptr::copy_nonoverlapping(data.as_ptr(), mem, data.len());
-            *mem.offset(data.len() as isize) = 0;
-            CoTaskWString {
-                ptr: NonNull::new_unchecked(mem),
-            }
+            *mem.add(data.len()) = 0;
+            CoTaskWString::from_raw_in(mem)
+        }
+    }
+
+    /// Takes ownership of a string allocated with `A`. Panics if `ptr` is
+    /// null.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a nul-terminated string allocated with `A` that nothing
+    /// else owns; it is freed when the `CoTaskWString` is dropped.
+    pub unsafe fn from_raw_in(ptr: *mut u16) -> CoTaskWString<A> {
+        CoTaskWString {
+            ptr: NonNull::new(ptr).expect("null CoTaskWString"),
+            alloc: PhantomData,
         }
     }
 
@@ -119,36 +173,57 @@ impl CoTaskWString {
         unsafe { wstrlen(self.ptr.as_ptr()) }
     }
 
+    pub fn is_empty(&self) -> bool {
+        unsafe { *self.ptr.as_ptr() == 0 }
+    }
+
     pub fn data(&self) -> &[u16] {
         let len = self.len();
         unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), len) }
     }
 
-    pub fn to_string(&self) -> String {
-        String::from_utf16_lossy(self.data())
-    }
-
-    pub unsafe fn into_raw(self) -> LPOLESTR {
+    /// Gives up ownership of the string.
+    ///
+    /// # Safety
+    ///
+    /// The caller must free the string with `A`, or hand it to something that
+    /// will.
+    pub unsafe fn into_raw(self) -> *mut u16 {
         let ptr = self.as_ptr();
         std::mem::forget(self);
         ptr
     }
 
-    pub unsafe fn as_ptr(&self) -> LPOLESTR {
+    /// # Safety
+    ///
+    /// The pointer is only valid while `self` is alive, and must not be freed.
+    pub unsafe fn as_ptr(&self) -> *mut u16 {
         self.ptr.as_ptr()
     }
 
-    pub unsafe fn slice_as_ptr(slice: &[Option<CoTaskWString>]) -> *const LPOLESTR {
-        slice.as_ptr() as *const LPOLESTR
+    /// Views the strings as an array of `LPOLESTR`, with `None` as null.
+    ///
+    /// # Safety
+    ///
+    /// The pointers are only valid while `slice` is borrowed, and must not be
+    /// freed.
+    pub unsafe fn slice_as_ptr(slice: &[Option<CoTaskWString<A>>]) -> *const *mut u16 {
+        slice.as_ptr() as *const *mut u16
     }
 
-    pub unsafe fn slice_as_mut_ptr(slice: &mut [Option<CoTaskWString>]) -> *mut LPOLESTR {
-        slice.as_mut_ptr() as *mut LPOLESTR
+    /// Views the strings as an array of `LPOLESTR` that a callee may fill in.
+    ///
+    /// # Safety
+    ///
+    /// Any pointer written to the array must be null or a string allocated
+    /// with `A`, and any string it replaces must be freed first.
+    pub unsafe fn slice_as_mut_ptr(slice: &mut [Option<CoTaskWString<A>>]) -> *mut *mut u16 {
+        slice.as_mut_ptr() as *mut *mut u16
     }
 }
 
-impl From<&'_ CoTaskWString> for String {
-    fn from(s: &'_ CoTaskWString) -> String {
+impl<A: TaskAllocator> From<&'_ CoTaskWString<A>> for String {
+    fn from(s: &'_ CoTaskWString<A>) -> String {
         s.to_string()
     }
 }
@@ -160,20 +235,26 @@ impl From<&'_ str> for CoTaskWString {
     }
 }
 
-impl Clone for CoTaskWString {
+impl<A: TaskAllocator> Clone for CoTaskWString<A> {
     fn clone(&self) -> Self {
-        CoTaskWString::create(self.data())
+        CoTaskWString::create_in(self.data())
     }
 }
 
-impl Drop for CoTaskWString {
+impl<A: TaskAllocator> Drop for CoTaskWString<A> {
     fn drop(&mut self) {
-        unsafe { CoTaskMemFree(self.ptr.as_ptr() as _) }
+        unsafe { A::free(self.ptr.as_ptr() as *mut u8) }
+    }
+}
+
+impl<A: TaskAllocator> PartialEq for CoTaskWString<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data() == other.data()
     }
 }
 
-impl std::fmt::Display for CoTaskWString {
-    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl<A: TaskAllocator> fmt::Display for CoTaskWString<A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         use std::char::{decode_utf16, REPLACEMENT_CHARACTER};
         use std::fmt::Write;
         let data = self.data();
@@ -183,3 +264,318 @@ impl std::fmt::Display for CoTaskWString {
         Ok(())
     }
 }
+
+impl<A: TaskAllocator> fmt::Debug for CoTaskWString<A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_string(), fmt)
+    }
+}
+
+/// Converts UTF-16 to an `OsString`, replacing unpaired surrogates with
+/// U+FFFD off Windows.
+fn os_string_from_wide(data: &[u16]) -> OsString {
+    #[cfg(windows)]
+    {
+        OsString::from_wide(data)
+    }
+    #[cfg(not(windows))]
+    {
+        OsString::from(String::from_utf16_lossy(data))
+    }
+}
+
+fn wide_from_os_str(s: &OsStr) -> Vec<u16> {
+    #[cfg(windows)]
+    {
+        s.to_wide()
+    }
+    #[cfg(not(windows))]
+    {
+        s.to_string_lossy().encode_utf16().collect()
+    }
+}
+
+/// An owned, growable, nul-terminated UTF-16 string, for passing to APIs
+/// that take an `LPCWSTR`.
+///
+/// The string never contains a nul before its terminator. The fallible
+/// constructors and `push` methods fail with a `NulError` instead of
+/// creating one, while the `From` impls and `wformat!` panic.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WideString {
+    /// Always ends with the terminator.
+    data: Vec<u16>,
+}
+
+/// The error for a string that contains a nul, which a nul-terminated
+/// string can't hold.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NulError {
+    position: usize,
+    data: Vec<u16>,
+}
+
+impl NulError {
+    /// The index of the first nul, in UTF-16 units.
+    pub fn nul_position(&self) -> usize {
+        self.position
+    }
+
+    /// The UTF-16 units that were rejected.
+    pub fn into_vec(self) -> Vec<u16> {
+        self.data
+    }
+}
+
+impl fmt::Display for NulError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "nul character found at position {}", self.position)
+    }
+}
+
+impl std::error::Error for NulError {}
+
+impl From<NulError> for crate::Error {
+    fn from(_: NulError) -> crate::Error {
+        crate::Error::INVALIDARG
+    }
+}
+
+impl WideString {
+    /// Encodes `s` as UTF-16. Fails if it contains a nul.
+    ///
+    /// Off Windows, an `OsStr` is not UTF-16, so any bytes of `s` that
+    /// aren't valid UTF-8 become U+FFFD.
+    pub fn new(s: impl AsRef<OsStr>) -> Result<WideString, NulError> {
+        WideString::from_vec(wide_from_os_str(s.as_ref()))
+    }
+
+    /// Takes ownership of UTF-16 units that don't include a terminator.
+    /// Fails if they contain a nul.
+    pub fn from_vec(mut data: Vec<u16>) -> Result<WideString, NulError> {
+        if let Some(position) = data.iter().position(|&c| c == 0) {
+            return Err(NulError { position, data });
+        }
+        data.push(0);
+        Ok(WideString { data })
+    }
+
+    /// Copies UTF-16 units that don't include a terminator. Fails if they
+    /// contain a nul.
+    pub fn from_wide(data: &[u16]) -> Result<WideString, NulError> {
+        WideString::from_vec(data.to_vec())
+    }
+
+    /// Builds the string `format_args!` describes; see `wformat!`.
+    ///
+    /// Panics if the text contains a nul, or, as `format!` does, if a
+    /// formatting trait implementation returns an error.
+    pub fn format(args: fmt::Arguments) -> WideString {
+        let text = fmt::format(args);
+        match WideString::from_vec(text.encode_utf16().collect()) {
+            Ok(s) => s,
+            Err(err) => panic!(
+                "formatted string contains a nul at position {}",
+                err.nul_position()
+            ),
+        }
+    }
+
+    /// The length in UTF-16 units, not counting the terminator.
+    pub fn len(&self) -> usize {
+        self.data.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The UTF-16 units, without the terminator.
+    pub fn as_slice(&self) -> &[u16] {
+        &self.data[..self.len()]
+    }
+
+    /// The UTF-16 units, including the terminator.
+    pub fn as_slice_with_nul(&self) -> &[u16] {
+        &self.data
+    }
+
+    pub fn as_ptr(&self) -> *const u16 {
+        self.data.as_ptr()
+    }
+
+    /// For APIs that take an `LPWSTR` but don't write through it.
+    pub fn as_mut_ptr(&mut self) -> *mut u16 {
+        self.data.as_mut_ptr()
+    }
+
+    /// Appends a character. Fails if it is a nul.
+    pub fn push(&mut self, c: char) -> Result<(), NulError> {
+        let mut buf = [0; 2];
+        self.push_wide(c.encode_utf16(&mut buf))
+    }
+
+    /// Appends a string. Fails if it contains a nul, leaving `self`
+    /// unchanged. Off Windows, this is lossy in the same way as `new`.
+    pub fn push_str(&mut self, s: impl AsRef<OsStr>) -> Result<(), NulError> {
+        self.push_wide(&wide_from_os_str(s.as_ref()))
+    }
+
+    /// Appends UTF-16 units. Fails if they contain a nul, leaving `self`
+    /// unchanged.
+    pub fn push_wide(&mut self, data: &[u16]) -> Result<(), NulError> {
+        if let Some(position) = data.iter().position(|&c| c == 0) {
+            let data = data.to_vec();
+            return Err(NulError { position, data });
+        }
+        self.data.pop();
+        self.data.extend_from_slice(data);
+        self.data.push(0);
+        Ok(())
+    }
+
+    /// Appends another wide string, which can't contain a nul.
+    pub fn append(&mut self, other: &WideString) {
+        self.data.pop();
+        self.data.extend_from_slice(&other.data);
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.data.push(0);
+    }
+
+    pub fn to_os_string(&self) -> OsString {
+        os_string_from_wide(self.as_slice())
+    }
+
+    /// Converts the string to UTF-8, replacing unpaired surrogates with
+    /// U+FFFD.
+    pub fn to_string_lossy(&self) -> String {
+        String::from_utf16_lossy(self.as_slice())
+    }
+
+    /// The UTF-16 units, without the terminator.
+    pub fn into_vec(mut self) -> Vec<u16> {
+        self.data.pop();
+        self.data
+    }
+
+    /// The UTF-16 units, including the terminator.
+    pub fn into_vec_with_nul(self) -> Vec<u16> {
+        self.data
+    }
+}
+
+impl Default for WideString {
+    fn default() -> WideString {
+        WideString { data: vec![0] }
+    }
+}
+
+impl Deref for WideString {
+    type Target = WideCStr;
+
+    fn deref(&self) -> &WideCStr {
+        unsafe { WideCStr::from_ptr(self.data.as_ptr()) }
+    }
+}
+
+impl From<&'_ str> for WideString {
+    /// Panics if `s` contains a nul; use `WideString::new` to check.
+    fn from(s: &'_ str) -> WideString {
+        WideString::new(s).expect("string contains a nul")
+    }
+}
+
+impl From<&'_ String> for WideString {
+    /// Panics if `s` contains a nul; use `WideString::new` to check.
+    fn from(s: &'_ String) -> WideString {
+        WideString::from(&s[..])
+    }
+}
+
+impl From<&'_ OsStr> for WideString {
+    /// Panics if `s` contains a nul; use `WideString::new` to check. Off
+    /// Windows, bytes that aren't valid UTF-8 become U+FFFD.
+    fn from(s: &'_ OsStr) -> WideString {
+        WideString::new(s).expect("string contains a nul")
+    }
+}
+
+impl fmt::Write for WideString {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl fmt::Display for WideString {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use std::char::{decode_utf16, REPLACEMENT_CHARACTER};
+        use std::fmt::Write;
+        for r in decode_utf16(self.as_slice().iter().cloned()) {
+            fmt.write_char(r.unwrap_or(REPLACEMENT_CHARACTER))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for WideString {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_string_lossy(), fmt)
+    }
+}
+
+/// Like `format!`, but builds a nul-terminated `WideString`.
+///
+/// Panics if the formatted text contains a nul, or if a formatting trait
+/// implementation returns an error.
+#[macro_export]
+macro_rules! wformat {
+    ($($arg:tt)*) => {
+        $crate::helpers::wide::WideString::format(format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+#[test]
+fn wide_string() {
+    let mut s = WideString::from("ab");
+    assert_eq!(s.as_slice_with_nul(), [97, 98, 0]);
+    s.push('€').unwrap();
+    s.push_str("c").unwrap();
+    let err = s.push_str("d\0e").unwrap_err();
+    assert_eq!(err.nul_position(), 1);
+    assert_eq!(s.to_string_lossy(), "ab€c");
+    assert_eq!(s.to_string_lossy(), s.to_os_string().to_string_lossy());
+    assert_eq!(unsafe { crate::helpers::wstrlen(s.as_ptr()) }, 4);
+
+    s.append(&crate::wformat!("-{:02}", 7));
+    assert_eq!(s.to_string(), "ab€c-07");
+    assert_eq!(format!("{:?}", s), "\"ab€c-07\"");
+    assert_eq!(s.len(), 7);
+    assert_eq!(s.to_string_lossy(), (*s).to_string_lossy());
+
+    assert!(WideString::new("x\0").is_err());
+    assert_eq!(
+        WideString::from_vec(vec![0x41])
+            .unwrap()
+            .into_vec_with_nul(),
+        [0x41, 0]
+    );
+    s.clear();
+    assert!(s.is_empty());
+}
+
+#[cfg(test)]
+#[test]
+#[should_panic(expected = "a formatting trait implementation returned an error")]
+fn wformat_display_error() {
+    struct Failing;
+    impl fmt::Display for Failing {
+        fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+            Err(fmt::Error)
+        }
+    }
+    crate::wformat!("{}", Failing);
+}
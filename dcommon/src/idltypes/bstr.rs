@@ -1,36 +1,112 @@
-use winapi::shared::wtypes::BSTR;
+use crate::error::Error;
+use crate::helpers::alloc::{BStrAllocator, DefaultAllocator};
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+
+/// The raw `BSTR` type: a pointer to the first character of a
+/// length-prefixed, nul-terminated wide string.
+pub type BSTR = *mut u16;
 
 #[repr(transparent)]
-pub struct BStr {
+/// An owned `BSTR` allocated with `A`.
+pub struct BStr<A: BStrAllocator = DefaultAllocator> {
     bstr: BSTR,
+    alloc: PhantomData<A>,
 }
 
 impl BStr {
     pub fn new(s: &str) -> BStr {
+        BStr::new_in(s)
+    }
+
+    /// Takes ownership of a `BSTR` allocated with the default allocator.
+    ///
+    /// # Safety
+    ///
+    /// Same as `from_raw_in`.
+    pub unsafe fn from_raw(bstr: BSTR) -> Self {
+        BStr::from_raw_in(bstr)
+    }
+
+    /// Copies a `BSTR` into a `String` without taking ownership of it. A
+    /// null `BSTR` is the empty string. Fails with
+    /// `Error::WIN32_INVALID_DATA` if it isn't valid UTF-16.
+    ///
+    /// # Safety
+    ///
+    /// `bstr` must be null or a live `BSTR` from the default allocator.
+    pub unsafe fn read_raw(bstr: BSTR) -> Result<String, Error> {
+        if bstr.is_null() {
+            return Ok(String::new());
+        }
+        let bstr = ManuallyDrop::new(BStr::from_raw(bstr));
+        String::from_utf16(bstr.as_slice()).map_err(|_| Error::WIN32_INVALID_DATA)
+    }
+
+    /// Like `read_raw`, but replaces invalid UTF-16 with U+FFFD.
+    ///
+    /// # Safety
+    ///
+    /// Same as `read_raw`.
+    pub unsafe fn read_raw_lossy(bstr: BSTR) -> String {
+        if bstr.is_null() {
+            return String::new();
+        }
+        ManuallyDrop::new(BStr::from_raw(bstr)).to_string()
+    }
+}
+
+impl<A: BStrAllocator> BStr<A> {
+    /// Like `new`, but allocates with `A`.
+    pub fn new_in(s: &str) -> BStr<A> {
         let len = s.encode_utf16().count();
-        assert!(len <= std::u32::MAX as usize);
+        assert!(len <= u32::MAX as usize);
 
         unsafe {
-            let bstr = SysAllocStringLen(0 as _, len as u32);
+            let bstr = A::alloc_string(len as u32);
+            if bstr.is_null() {
+                panic!("oom: failed to allocate BSTR of {} characters", len);
+            }
             let slice = std::slice::from_raw_parts_mut(bstr, len);
 
             for (dst, src) in slice.iter_mut().zip(s.encode_utf16()) {
                 *dst = src;
             }
 
-            BStr { bstr }
+            BStr::from_raw_in(bstr)
         }
     }
 
-    pub unsafe fn from_raw(bstr: BSTR) -> Self {
+    /// Takes ownership of a `BSTR` allocated with `A`. Panics if `bstr` is
+    /// null.
+    ///
+    /// # Safety
+    ///
+    /// `bstr` must have been allocated with `A` and not be owned by anything
+    /// else; it is freed when the `BStr` is dropped.
+    pub unsafe fn from_raw_in(bstr: BSTR) -> Self {
         assert!(!bstr.is_null());
-        BStr { bstr }
+        BStr {
+            bstr,
+            alloc: PhantomData,
+        }
     }
 
+    /// # Safety
+    ///
+    /// The pointer is only valid while `self` is alive, and must not be freed.
     pub unsafe fn get_raw(&self) -> BSTR {
         self.bstr
     }
 
+    /// Gives up ownership of the string.
+    ///
+    /// # Safety
+    ///
+    /// The caller must free the string with `A`, or hand it to something that
+    /// will.
     pub unsafe fn into_raw(self) -> BSTR {
         let bstr = self.get_raw();
         std::mem::forget(self);
@@ -38,7 +114,11 @@ impl BStr {
     }
 
     pub fn len(&self) -> usize {
-        unsafe { SysStringLen(self.bstr) as usize }
+        unsafe { A::string_len(self.bstr) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
     pub fn as_slice(&self) -> &[u16] {
@@ -46,24 +126,82 @@ impl BStr {
     }
 }
 
-impl PartialEq for BStr {
-    fn eq(&self, other: &BStr) -> bool {
+impl<A: BStrAllocator> PartialEq for BStr<A> {
+    fn eq(&self, other: &BStr<A>) -> bool {
         self.as_slice() == other.as_slice()
     }
 }
 
-impl Drop for BStr {
-    fn drop(&mut self) {
+impl<A: BStrAllocator> Clone for BStr<A> {
+    fn clone(&self) -> Self {
         unsafe {
-            let hr = SysFreeString(self.bstr);
-            assert!(hr >= 0);
+            let len = self.len();
+            let bstr = A::alloc_string(len as u32);
+            if bstr.is_null() {
+                panic!("oom: failed to allocate BSTR of {} characters", len);
+            }
+            std::ptr::copy_nonoverlapping(self.bstr, bstr, len);
+            BStr::from_raw_in(bstr)
         }
     }
 }
 
-extern "system" {
-    fn SysAllocStringLen(str_in: *const u16, ui: u32) -> BSTR;
-    fn SysFreeString(b: BSTR) -> i32;
+impl<A: BStrAllocator> Drop for BStr<A> {
+    fn drop(&mut self) {
+        unsafe { A::free_string(self.bstr) }
+    }
+}
+
+impl From<&'_ str> for BStr {
+    fn from(s: &'_ str) -> BStr {
+        BStr::new(s)
+    }
+}
+
+impl<A: BStrAllocator> fmt::Display for BStr<A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use std::char::{decode_utf16, REPLACEMENT_CHARACTER};
+        use std::fmt::Write;
+        for r in decode_utf16(self.as_slice().iter().cloned()) {
+            fmt.write_char(r.unwrap_or(REPLACEMENT_CHARACTER))?;
+        }
+        Ok(())
+    }
+}
+
+impl<A: BStrAllocator> fmt::Debug for BStr<A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_string(), fmt)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn bstr_use() {
+    use crate::helpers::alloc::HeapAllocator;
+    use crate::helpers::CoTaskWString;
+
+    let s = BStr::from("héllo");
+    assert_eq!(s.len(), 5);
+    assert_eq!(s.to_string(), "héllo");
+    assert_eq!(s.clone(), s);
+    unsafe {
+        // The byte length prefix and the terminator of the BSTR layout.
+        let raw = s.get_raw();
+        assert_eq!(*(raw as *const u32).offset(-1), 10);
+        assert_eq!(*raw.add(5), 0);
+        let s = BStr::from_raw(s.into_raw());
+        assert_eq!(format!("{:?}", s), "\"héllo\"");
+    }
+
+    let empty = BStr::<HeapAllocator>::new_in("");
+    assert!(empty.is_empty());
 
-    fn SysStringLen(b: BSTR) -> u32;
+    let w = CoTaskWString::from("wide");
+    assert_eq!(w.len(), 4);
+    assert_eq!(w.clone().to_string(), "wide");
+    let data: Vec<u16> = "heap".encode_utf16().collect();
+    let w = CoTaskWString::<HeapAllocator>::create_in(&data);
+    let w = unsafe { CoTaskWString::<HeapAllocator>::from_raw_in(w.into_raw()) };
+    assert_eq!(String::from(&w), "heap");
 }
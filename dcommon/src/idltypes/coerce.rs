@@ -0,0 +1,686 @@
+//! A pure-Rust implementation of the OLE Automation coercion rules used by
+//! `VariantChangeType`, with the invariant locale.
+//!
+//! Conversions to integer types use banker's rounding, booleans are `-1` and
+//! `0`, and strings are parsed and formatted the way oleaut32 does for
+//! `LOCALE_INVARIANT`.
+
+use crate::error::Error;
+use crate::idltypes::decimal::Decimal;
+use crate::idltypes::variant_value::VariantValue;
+use crate::idltypes::vartype::VarType;
+use crate::minwindef::datetime::{
+    civil_from_days, days_from_civil, days_in_month, OLE_DATE_MAX, OLE_DATE_MIN, OLE_EPOCH_DAYS,
+};
+
+use std::convert::TryFrom;
+
+/// Converts `value` to `vt`. See `VariantValue::change_type`.
+pub fn change_type(value: &VariantValue, vt: VarType) -> Result<VariantValue, Error> {
+    use crate::idltypes::variant_value::VariantValue as V;
+
+    if value.vartype() == vt {
+        return Ok(value.clone());
+    }
+
+    match vt {
+        VarType::EMPTY => return Ok(V::Empty),
+        VarType::NULL => {
+            return match value {
+                V::Empty => Ok(V::Null),
+                _ => Err(Error::DISP_TYPEMISMATCH),
+            }
+        }
+        VarType::I1
+        | VarType::UI1
+        | VarType::I2
+        | VarType::UI2
+        | VarType::I4
+        | VarType::UI4
+        | VarType::INT
+        | VarType::UINT
+        | VarType::I8
+        | VarType::UI8
+        | VarType::R4
+        | VarType::R8
+        | VarType::BOOL
+        | VarType::CY
+        | VarType::DATE
+        | VarType::DECIMAL
+        | VarType::BSTR => {}
+        VarType::ERROR => return Err(Error::DISP_TYPEMISMATCH),
+        _ => return Err(Error::DISP_BADVARTYPE),
+    }
+
+    if vt == VarType::BSTR {
+        return format(value).map(V::BStr);
+    }
+    if let V::BStr(s) = value {
+        return from_str(s, vt);
+    }
+
+    let num = match *value {
+        V::Empty => Num::Int(0),
+        V::I1(v) => Num::Int(v.into()),
+        V::UI1(v) => Num::Int(v.into()),
+        V::I2(v) => Num::Int(v.into()),
+        V::UI2(v) => Num::Int(v.into()),
+        V::I4(v) | V::Int(v) => Num::Int(v.into()),
+        V::UI4(v) | V::UInt(v) => Num::Int(v.into()),
+        V::I8(v) => Num::Int(v.into()),
+        V::UI8(v) => Num::Int(v.into()),
+        V::R4(v) => Num::Float(v.into()),
+        V::R8(v) | V::Date(v) => Num::Float(v),
+        V::Currency(v) => Num::Decimal(Decimal::from_currency(v)),
+        V::Decimal(v) => Num::Decimal(v),
+        V::Bool(true) => {
+            // VARIANT_TRUE is all bits set, which unsigned targets keep.
+            match vt {
+                VarType::UI1 => return Ok(V::UI1(u8::MAX)),
+                VarType::UI2 => return Ok(V::UI2(u16::MAX)),
+                VarType::UI4 => return Ok(V::UI4(u32::MAX)),
+                VarType::UINT => return Ok(V::UInt(u32::MAX)),
+                VarType::UI8 => return Ok(V::UI8(u64::MAX)),
+                _ => Num::Int(-1),
+            }
+        }
+        V::Bool(false) => Num::Int(0),
+        _ => return Err(Error::DISP_TYPEMISMATCH),
+    };
+
+    from_num(num, vt)
+}
+
+/// An intermediate numeric value.
+#[derive(Copy, Clone, Debug)]
+enum Num {
+    Int(i128),
+    Float(f64),
+    Decimal(Decimal),
+}
+
+fn round_half_even(f: f64) -> f64 {
+    let r = f.round();
+    if (r - f).abs() == 0.5 {
+        2.0 * (f / 2.0).round()
+    } else {
+        r
+    }
+}
+
+impl Num {
+    fn to_int(self) -> Result<i128, Error> {
+        match self {
+            Num::Int(i) => Ok(i),
+            Num::Float(f) => {
+                if !f.is_finite() {
+                    return Err(Error::DISP_OVERFLOW);
+                }
+                let r = round_half_even(f);
+                if r.abs() >= 1.0e38 {
+                    return Err(Error::DISP_OVERFLOW);
+                }
+                Ok(r as i128)
+            }
+            Num::Decimal(d) => {
+                // The mantissa has at most 96 bits, so this can't overflow.
+                let int = d.round(0).mantissa() as i128;
+                Ok(if d.is_negative() { -int } else { int })
+            }
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        match self {
+            Num::Int(i) => i as f64,
+            Num::Float(f) => f,
+            Num::Decimal(d) => d.into(),
+        }
+    }
+
+    fn to_decimal(self) -> Result<Decimal, Error> {
+        match self {
+            Num::Int(i) => {
+                Decimal::from_parts(i < 0, i.unsigned_abs(), 0).ok_or(Error::DISP_OVERFLOW)
+            }
+            Num::Float(f) => Decimal::try_from(f),
+            Num::Decimal(d) => Ok(d),
+        }
+    }
+
+    fn is_zero(self) -> bool {
+        match self {
+            Num::Int(i) => i == 0,
+            Num::Float(f) => f == 0.0,
+            Num::Decimal(d) => d.is_zero(),
+        }
+    }
+}
+
+fn from_num(num: Num, vt: VarType) -> Result<VariantValue, Error> {
+    use crate::idltypes::variant_value::VariantValue as V;
+
+    fn int<T: TryFrom<i128>>(num: Num) -> Result<T, Error> {
+        T::try_from(num.to_int()?).map_err(|_| Error::DISP_OVERFLOW)
+    }
+
+    Ok(match vt {
+        VarType::I1 => V::I1(int(num)?),
+        VarType::UI1 => V::UI1(int(num)?),
+        VarType::I2 => V::I2(int(num)?),
+        VarType::UI2 => V::UI2(int(num)?),
+        VarType::I4 => V::I4(int(num)?),
+        VarType::UI4 => V::UI4(int(num)?),
+        VarType::INT => V::Int(int(num)?),
+        VarType::UINT => V::UInt(int(num)?),
+        VarType::I8 => V::I8(int(num)?),
+        VarType::UI8 => V::UI8(int(num)?),
+        VarType::R4 => {
+            let f = num.to_f64();
+            if f.is_finite() && f.abs() > f64::from(f32::MAX) {
+                return Err(Error::DISP_OVERFLOW);
+            }
+            V::R4(f as f32)
+        }
+        VarType::R8 => V::R8(num.to_f64()),
+        VarType::BOOL => V::Bool(!num.is_zero()),
+        VarType::CY => V::Currency(match num {
+            Num::Float(f) => {
+                let cy = round_half_even(f * 10_000.0);
+                if !(-9.223_372_036_854_776e18..9.223_372_036_854_776e18).contains(&cy) {
+                    return Err(Error::DISP_OVERFLOW);
+                }
+                cy as i64
+            }
+            _ => num.to_decimal()?.to_currency()?,
+        }),
+        VarType::DATE => {
+            let f = num.to_f64();
+            if !(f > OLE_DATE_MIN && f < OLE_DATE_MAX) {
+                return Err(Error::DISP_OVERFLOW);
+            }
+            V::Date(f)
+        }
+        VarType::DECIMAL => V::Decimal(num.to_decimal()?),
+        _ => return Err(Error::DISP_BADVARTYPE),
+    })
+}
+
+fn from_str(s: &str, vt: VarType) -> Result<VariantValue, Error> {
+    use crate::idltypes::variant_value::VariantValue as V;
+
+    match vt {
+        VarType::BOOL => {
+            let t = s.trim();
+            if t.eq_ignore_ascii_case("true") {
+                return Ok(V::Bool(true));
+            }
+            if t.eq_ignore_ascii_case("false") {
+                return Ok(V::Bool(false));
+            }
+        }
+        VarType::DATE => {
+            if let Some(date) = parse_date(s) {
+                return from_num(Num::Float(date), vt);
+            }
+            return Err(Error::DISP_TYPEMISMATCH);
+        }
+        _ => {}
+    }
+
+    // Like oleaut32, booleans are parsed as doubles and then compared to
+    // zero.
+    let num = match vt {
+        VarType::BOOL | VarType::R4 | VarType::R8 => {
+            let f = parse_float(s).ok_or(Error::DISP_TYPEMISMATCH)?;
+            if f.is_infinite() {
+                return Err(Error::DISP_OVERFLOW);
+            }
+            Num::Float(f)
+        }
+        _ => parse_number(s)?,
+    };
+    from_num(num, vt)
+}
+
+/// Strips whitespace and thousands separators, returning the sign and the
+/// remaining text. Returns None for an empty string.
+fn normalize(s: &str) -> Option<(bool, String)> {
+    let s = s.trim();
+    let (negative, rest) = match s.as_bytes().first()? {
+        b'-' => (true, &s[1..]),
+        b'+' => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let rest: String = rest.trim_start().chars().filter(|&c| c != ',').collect();
+    if rest.is_empty() {
+        None
+    } else {
+        Some((negative, rest))
+    }
+}
+
+/// Parses `&H` hexadecimal and `&O` octal literals, which are 32-bit two's
+/// complement values when they fit in 32 bits.
+fn parse_radix(rest: &str) -> Option<Option<i128>> {
+    let radix = match rest.get(..2) {
+        Some(p) if p.eq_ignore_ascii_case("&h") => 16,
+        Some(p) if p.eq_ignore_ascii_case("&o") => 8,
+        _ => return None,
+    };
+    let value = u64::from_str_radix(&rest[2..], radix).ok();
+    Some(value.map(|v| {
+        if v <= u64::from(u32::MAX) {
+            i128::from(v as u32 as i32)
+        } else {
+            i128::from(v as i64)
+        }
+    }))
+}
+
+fn parse_float(s: &str) -> Option<f64> {
+    let (negative, rest) = normalize(s)?;
+    if let Some(radix) = parse_radix(&rest) {
+        let v = radix? as f64;
+        return Some(if negative { -v } else { v });
+    }
+    if !rest.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+    if rest.contains(|c: char| !(c.is_ascii_digit() || "eE.+-".contains(c))) {
+        return None;
+    }
+    let v: f64 = rest.parse().ok()?;
+    Some(if negative { -v } else { v })
+}
+
+/// Parses a number exactly, as a `Decimal` or a `&H`/`&O` integer.
+fn parse_number(s: &str) -> Result<Num, Error> {
+    let (negative, rest) = normalize(s).ok_or(Error::DISP_TYPEMISMATCH)?;
+    if let Some(radix) = parse_radix(&rest) {
+        let v = radix.ok_or(Error::DISP_TYPEMISMATCH)?;
+        return Ok(Num::Int(if negative { -v } else { v }));
+    }
+    // The sign has already been taken off.
+    if rest.starts_with(['+', '-']) {
+        return Err(Error::DISP_TYPEMISMATCH);
+    }
+    let dec: Decimal = rest.parse()?;
+    Ok(Num::Decimal(if negative { -dec } else { dec }))
+}
+
+fn format(value: &VariantValue) -> Result<String, Error> {
+    use crate::idltypes::variant_value::VariantValue as V;
+
+    Ok(match *value {
+        V::Empty => String::new(),
+        V::I1(v) => v.to_string(),
+        V::UI1(v) => v.to_string(),
+        V::I2(v) => v.to_string(),
+        V::UI2(v) => v.to_string(),
+        V::I4(v) | V::Int(v) => v.to_string(),
+        V::UI4(v) | V::UInt(v) => v.to_string(),
+        V::I8(v) => v.to_string(),
+        V::UI8(v) => v.to_string(),
+        V::R4(v) => format_float(v.into(), 7),
+        V::R8(v) => format_float(v, 15),
+        V::Bool(v) => if v { "True" } else { "False" }.to_string(),
+        V::Currency(v) => Decimal::from_currency(v).normalize().to_string(),
+        V::Decimal(v) => v.normalize().to_string(),
+        V::Date(v) => format_date(v).ok_or(Error::DISP_OVERFLOW)?,
+        V::BStr(ref s) => s.clone(),
+        _ => return Err(Error::DISP_TYPEMISMATCH),
+    })
+}
+
+/// Formats like the C `%.{precision}G` specifier, which is what oleaut32
+/// uses for R4 (7 digits) and R8 (15 digits).
+fn format_float(f: f64, precision: usize) -> String {
+    if f == 0.0 {
+        return "0".to_string();
+    }
+    if !f.is_finite() {
+        return f.to_string();
+    }
+
+    let sci = format!("{:.*e}", precision - 1, f);
+    let e = sci.find('e').unwrap();
+    let exp: i32 = sci[e + 1..].parse().unwrap();
+
+    if exp < -4 || exp >= precision as i32 {
+        let mantissa = trim_zeros(&sci[..e]);
+        let sign = if exp < 0 { '-' } else { '+' };
+        format!("{}E{}{:02}", mantissa, sign, exp.abs())
+    } else {
+        let decimals = (precision as i32 - 1 - exp) as usize;
+        trim_zeros(&format!("{:.*}", decimals, f)).to_string()
+    }
+}
+
+fn trim_zeros(s: &str) -> &str {
+    if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        s
+    }
+}
+
+/// Formats a DATE the way the invariant locale does: `MM/DD/YYYY HH:MM:SS`,
+/// leaving out the date when the value has no whole days and the time when
+/// it is midnight.
+fn format_date(date: f64) -> Option<String> {
+    if !(date > OLE_DATE_MIN && date < OLE_DATE_MAX) {
+        return None;
+    }
+
+    // The fractional part is always the time of day, even for negative
+    // dates, so -1.25 is 1899-12-29 06:00. A time that rounds up to midnight
+    // moves to the next day either way: -1.9999999 is 1899-12-30.
+    let mut days = date.trunc() as i64;
+    let mut secs = ((date - date.trunc()).abs() * 86_400.0).round() as i64;
+    if secs >= 86_400 {
+        secs -= 86_400;
+        days += 1;
+    }
+    let time_only = days == 0 && date.trunc() == 0.0;
+    let (y, m, d) = civil_from_days(days + OLE_EPOCH_DAYS);
+    let time = format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+    let day = format!("{:02}/{:02}/{:04}", m, d, y);
+
+    Some(match (time_only, secs) {
+        (true, 0) => "00:00:00".to_string(),
+        (true, _) => time,
+        (false, 0) => day,
+        (false, _) => format!("{} {}", day, time),
+    })
+}
+
+/// Parses `MM/DD/YYYY` or `YYYY-MM-DD` dates, optionally followed by a
+/// `HH:MM[:SS]` time and an AM/PM designator, or a time on its own.
+fn parse_date(s: &str) -> Option<f64> {
+    let s = s.trim();
+    let (date_part, time_part) = match s.find([' ', 'T']) {
+        Some(i) => (&s[..i], Some(s[i + 1..].trim())),
+        None if s.contains(':') => ("", Some(s)),
+        None => (s, None),
+    };
+
+    let days = if date_part.is_empty() {
+        0
+    } else {
+        let (y, m, d) = if date_part.contains('/') {
+            let mut it = date_part.split('/');
+            let m = it.next()?.parse().ok()?;
+            let d = it.next()?.parse().ok()?;
+            let y = it.next()?.parse().ok()?;
+            if it.next().is_some() {
+                return None;
+            }
+            (y, m, d)
+        } else {
+            let mut it = date_part.split('-');
+            let y = it.next()?.parse().ok()?;
+            let m = it.next()?.parse().ok()?;
+            let d = it.next()?.parse().ok()?;
+            if it.next().is_some() {
+                return None;
+            }
+            (y, m, d)
+        };
+        if !(1..=12).contains(&m) || d < 1 || d > days_in_month(y, m) {
+            return None;
+        }
+        days_from_civil(y, m, d) - OLE_EPOCH_DAYS
+    };
+
+    let secs = match time_part {
+        Some(t) => parse_time(t)?,
+        None => 0,
+    };
+
+    let frac = secs as f64 / 86_400.0;
+    Some(if days < 0 {
+        days as f64 - frac
+    } else {
+        days as f64 + frac
+    })
+}
+
+fn parse_time(t: &str) -> Option<u32> {
+    let lower = t.to_ascii_lowercase();
+    let (t, pm) = if lower.ends_with("pm") {
+        (t[..t.len() - 2].trim_end(), Some(true))
+    } else if lower.ends_with("am") {
+        (t[..t.len() - 2].trim_end(), Some(false))
+    } else {
+        (t, None)
+    };
+
+    let mut it = t.split(':');
+    let mut h: u32 = it.next()?.parse().ok()?;
+    let m: u32 = it.next()?.parse().ok()?;
+    let s: u32 = match it.next() {
+        Some(s) => s.parse().ok()?,
+        None => 0,
+    };
+    if it.next().is_some() || m > 59 || s > 59 {
+        return None;
+    }
+    match pm {
+        Some(pm) => {
+            if !(1..=12).contains(&h) {
+                return None;
+            }
+            h = h % 12 + if pm { 12 } else { 0 };
+        }
+        None if h > 23 => return None,
+        None => {}
+    }
+    Some(h * 3600 + m * 60 + s)
+}
+
+#[cfg(test)]
+#[test]
+fn string_to_numbers() {
+    use crate::idltypes::variant_value::VariantValue as V;
+
+    match V::BStr("85".into()).change_type(VarType::UI1) {
+        Ok(V::UI1(85)) => {}
+        r => panic!("{:?}", r),
+    }
+    match V::BStr(" 2.5 ".into()).change_type(VarType::I4) {
+        Ok(V::I4(2)) => {}
+        r => panic!("{:?}", r),
+    }
+    match V::BStr("1,234".into()).change_type(VarType::I2) {
+        Ok(V::I2(1234)) => {}
+        r => panic!("{:?}", r),
+    }
+    match V::BStr("&HFF".into()).change_type(VarType::I4) {
+        Ok(V::I4(255)) => {}
+        r => panic!("{:?}", r),
+    }
+    match V::BStr("1e3".into()).change_type(VarType::R8) {
+        Ok(V::R8(1000.0)) => {}
+        r => panic!("{:?}", r),
+    }
+    assert_eq!(
+        V::BStr("256".into()).change_type(VarType::UI1).unwrap_err(),
+        Error::DISP_OVERFLOW
+    );
+    assert_eq!(
+        V::BStr("abc".into()).change_type(VarType::I4).unwrap_err(),
+        Error::DISP_TYPEMISMATCH
+    );
+    assert_eq!(
+        V::BStr("".into()).change_type(VarType::I4).unwrap_err(),
+        Error::DISP_TYPEMISMATCH
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn numbers_to_numbers() {
+    use crate::idltypes::variant_value::VariantValue as V;
+
+    match V::R8(0.5).change_type(VarType::R4) {
+        Ok(V::R4(0.5)) => {}
+        r => panic!("{:?}", r),
+    }
+    match V::R8(3.5).change_type(VarType::I2) {
+        Ok(V::I2(4)) => {}
+        r => panic!("{:?}", r),
+    }
+    match V::R8(-2.5).change_type(VarType::I8) {
+        Ok(V::I8(-2)) => {}
+        r => panic!("{:?}", r),
+    }
+    match V::Bool(true).change_type(VarType::UI2) {
+        Ok(V::UI2(0xFFFF)) => {}
+        r => panic!("{:?}", r),
+    }
+    match V::Bool(true).change_type(VarType::I4) {
+        Ok(V::I4(-1)) => {}
+        r => panic!("{:?}", r),
+    }
+    match V::Currency(15_000).change_type(VarType::I4) {
+        Ok(V::I4(2)) => {}
+        r => panic!("{:?}", r),
+    }
+    match V::R8(1.23456).change_type(VarType::CY) {
+        Ok(V::Currency(12_346)) => {}
+        r => panic!("{:?}", r),
+    }
+    assert_eq!(
+        V::R8(1e40).change_type(VarType::R4).unwrap_err(),
+        Error::DISP_OVERFLOW
+    );
+    assert_eq!(
+        V::I4(-1).change_type(VarType::UI4).unwrap_err(),
+        Error::DISP_OVERFLOW
+    );
+    assert_eq!(
+        V::Error(5).change_type(VarType::I4).unwrap_err(),
+        Error::DISP_TYPEMISMATCH
+    );
+    assert_eq!(
+        V::Null.change_type(VarType::I4).unwrap_err(),
+        Error::DISP_TYPEMISMATCH
+    );
+    assert_eq!(
+        V::I4(1).change_type(VarType::VARIANT).unwrap_err(),
+        Error::DISP_BADVARTYPE
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn decimals() {
+    use crate::idltypes::variant_value::VariantValue as V;
+
+    let dec = match V::BStr("-12.340".into()).change_type(VarType::DECIMAL) {
+        Ok(V::Decimal(d)) => d,
+        r => panic!("{:?}", r),
+    };
+    assert!(dec.is_negative());
+    assert_eq!((dec.mantissa(), dec.scale), (12340, 3));
+    match V::Decimal(dec).change_type(VarType::BSTR) {
+        Ok(V::BStr(ref s)) if s == "-12.34" => {}
+        r => panic!("{:?}", r),
+    }
+    match V::Decimal(dec).change_type(VarType::I4) {
+        Ok(V::I4(-12)) => {}
+        r => panic!("{:?}", r),
+    }
+    match V::R8(0.1).change_type(VarType::DECIMAL) {
+        Ok(V::Decimal(d)) => assert_eq!((d.mantissa(), d.scale), (1, 1)),
+        r => panic!("{:?}", r),
+    }
+    match V::BStr("-1,234.5678".into()).change_type(VarType::CY) {
+        Ok(V::Currency(-12_345_678)) => {}
+        r => panic!("{:?}", r),
+    }
+    match V::Currency(-12_345_000).change_type(VarType::BSTR) {
+        Ok(V::BStr(ref s)) if s == "-1234.5" => {}
+        r => panic!("{:?}", r),
+    }
+    match V::I8(i64::MIN).change_type(VarType::DECIMAL) {
+        Ok(V::Decimal(d)) => assert_eq!(i64::try_from(d), Ok(i64::MIN)),
+        r => panic!("{:?}", r),
+    }
+    assert_eq!(
+        V::BStr("1e30".into())
+            .change_type(VarType::DECIMAL)
+            .unwrap_err(),
+        Error::DISP_OVERFLOW
+    );
+    match V::BStr("1e30".into()).change_type(VarType::BOOL) {
+        Ok(V::Bool(true)) => {}
+        r => panic!("{:?}", r),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn to_strings() {
+    use crate::idltypes::variant_value::VariantValue as V;
+
+    let s = |v: V| match v.change_type(VarType::BSTR) {
+        Ok(V::BStr(s)) => s,
+        r => panic!("{:?}", r),
+    };
+    assert_eq!(s(V::R8(0.1)), "0.1");
+    assert_eq!(s(V::R8(1e20)), "1E+20");
+    assert_eq!(s(V::R8(1.0 / 3.0)), "0.333333333333333");
+    assert_eq!(s(V::R4(0.1)), "0.1");
+    assert_eq!(s(V::Bool(false)), "False");
+    assert_eq!(s(V::Currency(12_345)), "1.2345");
+    assert_eq!(s(V::Empty), "");
+    assert_eq!(s(V::Date(2.0)), "01/01/1900");
+    assert_eq!(s(V::Date(-1.25)), "12/29/1899 06:00:00");
+    assert_eq!(s(V::Date(0.5)), "12:00:00");
+    assert_eq!(s(V::Date(0.0)), "00:00:00");
+}
+
+#[cfg(test)]
+#[test]
+fn dates_rounding_to_midnight() {
+    use crate::idltypes::variant_value::VariantValue as V;
+
+    let s = |v: V| match v.change_type(VarType::BSTR) {
+        Ok(V::BStr(s)) => s,
+        r => panic!("{:?}", r),
+    };
+    // The time of day rounds up to the next midnight, which is always the
+    // following day, also for negative dates.
+    assert_eq!(s(V::Date(-1.999_999_999)), "12/30/1899");
+    assert_eq!(s(V::Date(-2.999_999_999)), "12/29/1899");
+    assert_eq!(s(V::Date(-0.999_999_999)), "12/31/1899");
+    assert_eq!(s(V::Date(1.999_999_999)), "01/01/1900");
+    assert_eq!(s(V::Date(-1.999_99)), "12/29/1899 23:59:59");
+}
+
+#[cfg(test)]
+#[test]
+fn dates() {
+    use crate::idltypes::variant_value::VariantValue as V;
+
+    match V::BStr("2019-03-04 12:00".into()).change_type(VarType::DATE) {
+        Ok(V::Date(d)) => assert_eq!(d, 43_528.5),
+        r => panic!("{:?}", r),
+    }
+    match V::BStr("12/29/1899 6:00 AM".into()).change_type(VarType::DATE) {
+        Ok(V::Date(d)) => assert_eq!(d, -1.25),
+        r => panic!("{:?}", r),
+    }
+    assert_eq!(
+        V::R8(3e6).change_type(VarType::DATE).unwrap_err(),
+        Error::DISP_OVERFLOW
+    );
+    assert_eq!(
+        V::BStr("02/30/2019".into())
+            .change_type(VarType::DATE)
+            .unwrap_err(),
+        Error::DISP_TYPEMISMATCH
+    );
+}
@@ -1,5 +1,22 @@
+//! The OLE Automation `DECIMAL` type: a 96-bit unsigned mantissa, a sign and
+//! a power-of-ten scale between 0 and 28.
+//!
+//! Arithmetic follows the rules of `VarDecAdd`, `VarDecMul` and friends: the
+//! exact result is computed and then, if it doesn't fit, digits are dropped
+//! from the right with round-half-to-even until it does. An operation only
+//! fails when the integer part of the result needs more than 96 bits.
+
+use crate::error::Error;
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
 #[repr(C)]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub struct Decimal {
     pub reserved: u16,
     pub scale: u8,
@@ -7,3 +24,743 @@ pub struct Decimal {
     pub high: u32,
     pub low: u64,
 }
+
+impl Decimal {
+    /// The sign bit stored in `sign`.
+    pub const SIGN_NEGATIVE: u8 = 0x80;
+    /// The largest scale a DECIMAL may have.
+    pub const MAX_SCALE: u8 = 28;
+    /// The largest 96-bit mantissa.
+    pub const MAX_MANTISSA: u128 = (1 << 96) - 1;
+
+    /// 0
+    pub const ZERO: Decimal = Decimal::from_parts_unchecked(false, 0, 0);
+    /// 1
+    pub const ONE: Decimal = Decimal::from_parts_unchecked(false, 1, 0);
+    /// The smallest representable value, -79228162514264337593543950335.
+    pub const MIN: Decimal = Decimal::from_parts_unchecked(true, Decimal::MAX_MANTISSA, 0);
+    /// The largest representable value, 79228162514264337593543950335.
+    pub const MAX: Decimal = Decimal::from_parts_unchecked(false, Decimal::MAX_MANTISSA, 0);
+
+    /// Builds a value equal to `mantissa * 10^-scale`, negated if `negative`.
+    /// Returns None if the mantissa doesn't fit in 96 bits or the scale is
+    /// larger than 28.
+    pub fn from_parts(negative: bool, mantissa: u128, scale: u8) -> Option<Decimal> {
+        if mantissa > Decimal::MAX_MANTISSA || scale > Decimal::MAX_SCALE {
+            return None;
+        }
+        Some(Decimal::from_parts_unchecked(negative, mantissa, scale))
+    }
+
+    const fn from_parts_unchecked(negative: bool, mantissa: u128, scale: u8) -> Decimal {
+        Decimal {
+            reserved: 0,
+            scale,
+            sign: if negative { Decimal::SIGN_NEGATIVE } else { 0 },
+            high: (mantissa >> 64) as u32,
+            low: mantissa as u64,
+        }
+    }
+
+    /// The unsigned 96-bit mantissa.
+    pub fn mantissa(&self) -> u128 {
+        (u128::from(self.high) << 64) | u128::from(self.low)
+    }
+
+    /// Whether the sign bit is set. Note that zero may be negative.
+    pub fn is_negative(&self) -> bool {
+        self.sign & Decimal::SIGN_NEGATIVE != 0
+    }
+
+    /// Whether the value is zero, regardless of sign and scale.
+    pub fn is_zero(&self) -> bool {
+        self.mantissa() == 0
+    }
+
+    /// The absolute value.
+    pub fn abs(self) -> Decimal {
+        Decimal { sign: 0, ..self }
+    }
+
+    /// Whether the scale is in the range a DECIMAL allows. The fields are
+    /// public, so values built by hand or read from memory may not be.
+    fn has_valid_scale(&self) -> bool {
+        self.scale <= Decimal::MAX_SCALE
+    }
+
+    /// Adds two values, returning None on overflow or if either scale is
+    /// larger than 28. Like `VarDecAdd`.
+    pub fn checked_add(self, rhs: Decimal) -> Option<Decimal> {
+        if !self.has_valid_scale() || !rhs.has_valid_scale() {
+            return None;
+        }
+        let scale = self.scale.max(rhs.scale);
+        let a = Wide::from(self.mantissa()).mul_pow10(u32::from(scale - self.scale))?;
+        let b = Wide::from(rhs.mantissa()).mul_pow10(u32::from(scale - rhs.scale))?;
+
+        let (negative, sum) = if self.is_negative() == rhs.is_negative() {
+            (self.is_negative(), a.add(&b))
+        } else {
+            match a.cmp(&b) {
+                Ordering::Less => (rhs.is_negative(), b.sub(&a)),
+                _ => (self.is_negative(), a.sub(&b)),
+            }
+        };
+        fit(negative, sum, u32::from(scale), false)
+    }
+
+    /// Subtracts `rhs`, returning None on overflow or if either scale is
+    /// larger than 28. Like `VarDecSub`.
+    pub fn checked_sub(self, rhs: Decimal) -> Option<Decimal> {
+        self.checked_add(-rhs)
+    }
+
+    /// Multiplies two values, returning None on overflow or if either scale
+    /// is larger than 28. Like `VarDecMul`.
+    pub fn checked_mul(self, rhs: Decimal) -> Option<Decimal> {
+        if !self.has_valid_scale() || !rhs.has_valid_scale() {
+            return None;
+        }
+        let product = Wide::from(self.mantissa()).mul(&Wide::from(rhs.mantissa()));
+        let negative = self.is_negative() != rhs.is_negative();
+        fit(
+            negative,
+            product,
+            u32::from(self.scale) + u32::from(rhs.scale),
+            false,
+        )
+    }
+
+    /// Divides by `rhs`, returning None on overflow, division by zero or if
+    /// either scale is larger than 28. Like `VarDecDiv`, the quotient keeps
+    /// as many digits as fit, and trailing zeros beyond the scale of the
+    /// operands are dropped.
+    pub fn checked_div(self, rhs: Decimal) -> Option<Decimal> {
+        if rhs.is_zero() || !self.has_valid_scale() || !rhs.has_valid_scale() {
+            return None;
+        }
+
+        // Scale the dividend so that the quotient has one more digit than
+        // the largest scale; fit() then rounds away whatever doesn't fit.
+        let scale = u32::from(Decimal::MAX_SCALE) + 1;
+        let shift = (scale + u32::from(rhs.scale)).checked_sub(u32::from(self.scale))?;
+        let dividend = Wide::from(self.mantissa()).mul_pow10(shift)?;
+        let (quotient, remainder) = dividend.div_rem(&Wide::from(rhs.mantissa()));
+
+        let negative = self.is_negative() != rhs.is_negative();
+        let exact = fit(negative, quotient, scale, !remainder.is_zero())?;
+        let natural = self.scale.saturating_sub(rhs.scale);
+        Some(exact.trim_to(natural))
+    }
+
+    /// Rounds to `scale` digits after the decimal point, half to even. Like
+    /// `VarDecRound`. Values that already have fewer digits are unchanged.
+    pub fn round(self, scale: u8) -> Decimal {
+        if scale >= self.scale {
+            return self;
+        }
+        let (quotient, digit, sticky) = div_pow10(self.mantissa(), u32::from(self.scale - scale));
+        let mantissa = round_half_even(quotient, digit, sticky);
+        Decimal::from_parts_unchecked(self.is_negative(), mantissa, scale)
+    }
+
+    /// Drops the fractional digits. Like `VarDecFix`.
+    pub fn trunc(self) -> Decimal {
+        let (quotient, _, _) = div_pow10(self.mantissa(), u32::from(self.scale));
+        Decimal::from_parts_unchecked(self.is_negative(), quotient, 0)
+    }
+
+    /// Removes trailing zeros after the decimal point without changing the
+    /// value.
+    pub fn normalize(self) -> Decimal {
+        self.trim_to(0)
+    }
+
+    fn trim_to(self, min_scale: u8) -> Decimal {
+        let mut mantissa = self.mantissa();
+        let mut scale = self.scale;
+        while scale > min_scale && mantissa % 10 == 0 {
+            mantissa /= 10;
+            scale -= 1;
+        }
+        Decimal::from_parts_unchecked(self.is_negative(), mantissa, scale)
+    }
+
+    /// Converts a `CY` value, which counts units of 1/10000.
+    pub fn from_currency(cy: i64) -> Decimal {
+        Decimal::from_parts_unchecked(cy < 0, u128::from(cy.unsigned_abs()), 4)
+    }
+
+    /// Converts to a `CY` value, rounding to 4 decimal places. Fails with
+    /// `Error::DISP_OVERFLOW` if the value is out of range, or
+    /// `Error::INVALIDARG` if the scale is larger than 28. Like
+    /// `VarCyFromDec`.
+    pub fn to_currency(self) -> Result<i64, Error> {
+        if !self.has_valid_scale() {
+            return Err(Error::INVALIDARG);
+        }
+        let cy = if self.scale > 4 {
+            self.round(4).mantissa()
+        } else {
+            self.mantissa() * 10u128.pow(u32::from(4 - self.scale))
+        };
+        to_i64(self.is_negative(), cy)
+    }
+}
+
+impl Default for Decimal {
+    fn default() -> Decimal {
+        Decimal::ZERO
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Decimal) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Decimal) -> Ordering {
+        match (self.is_zero(), other.is_zero()) {
+            (true, true) => return Ordering::Equal,
+            (true, false) if other.is_negative() => return Ordering::Greater,
+            (true, false) => return Ordering::Less,
+            (false, true) if self.is_negative() => return Ordering::Less,
+            (false, true) => return Ordering::Greater,
+            _ => {}
+        }
+        match (self.is_negative(), other.is_negative()) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (negative, _) => {
+                let scale = self.scale.max(other.scale);
+                let a = Wide::from(self.mantissa()).mul_pow10(u32::from(scale - self.scale));
+                let b = Wide::from(other.mantissa()).mul_pow10(u32::from(scale - other.scale));
+                // Only the side with the smaller scale is multiplied, so if
+                // that overflows (a scale above 28) it is the larger one.
+                let ord = match (a, b) {
+                    (Some(a), Some(b)) => a.cmp(&b),
+                    (None, _) => Ordering::Greater,
+                    (_, None) => Ordering::Less,
+                };
+                if negative {
+                    ord.reverse()
+                } else {
+                    ord
+                }
+            }
+        }
+    }
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Equal values must hash alike, whatever their scale or the sign of
+        // zero.
+        let n = self.normalize();
+        (n.is_negative() && !n.is_zero()).hash(state);
+        n.mantissa().hash(state);
+        n.scale.hash(state);
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        Decimal {
+            sign: self.sign ^ Decimal::SIGN_NEGATIVE,
+            ..self
+        }
+    }
+}
+
+macro_rules! impl_op {
+    ($tr:ident, $method:ident, $checked:ident, $msg:expr) => {
+        impl $tr for Decimal {
+            type Output = Decimal;
+
+            /// Panics on overflow. Use the `checked_` method to handle it.
+            fn $method(self, rhs: Decimal) -> Decimal {
+                self.$checked(rhs).expect($msg)
+            }
+        }
+    };
+}
+
+impl_op!(Add, add, checked_add, "Decimal addition overflowed");
+impl_op!(Sub, sub, checked_sub, "Decimal subtraction overflowed");
+impl_op!(Mul, mul, checked_mul, "Decimal multiplication overflowed");
+impl_op!(
+    Div,
+    div,
+    checked_div,
+    "Decimal division by zero or overflow"
+);
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let digits = self.mantissa().to_string();
+        let scale = usize::from(self.scale);
+        let (int, frac) = if digits.len() > scale {
+            digits.split_at(digits.len() - scale)
+        } else {
+            ("0", digits.as_str())
+        };
+
+        let mut s = String::with_capacity(digits.len() + scale + 2);
+        s.push_str(int);
+        if scale > 0 {
+            s.push('.');
+            s.extend(std::iter::repeat('0').take(scale - frac.len()));
+            s.push_str(frac);
+        }
+        fmt.pad_integral(!self.is_negative() || self.is_zero(), "", &s)
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = Error;
+
+    /// Parses `[+-]digits[.digits][e[+-]digits]`. Digits beyond the 96-bit
+    /// precision are rounded half to even. Fails with
+    /// `Error::DISP_TYPEMISMATCH` on malformed input and with
+    /// `Error::DISP_OVERFLOW` if the integer part is too large.
+    fn from_str(s: &str) -> Result<Decimal, Error> {
+        let (negative, rest) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (number, exp) = match rest.find(['e', 'E']) {
+            Some(i) => {
+                let exp = rest[i + 1..]
+                    .parse::<i32>()
+                    .map_err(|_| Error::DISP_TYPEMISMATCH)?;
+                (&rest[..i], exp)
+            }
+            None => (rest, 0),
+        };
+        let (int, frac) = match number.find('.') {
+            Some(i) => (&number[..i], &number[i + 1..]),
+            None => (number, ""),
+        };
+        if int.is_empty() && frac.is_empty() {
+            return Err(Error::DISP_TYPEMISMATCH);
+        }
+
+        let mut mantissa = Wide::ZERO;
+        let mut scale: i64 = -i64::from(exp);
+        let mut sticky = false;
+        for (i, c) in int.bytes().chain(frac.bytes()).enumerate() {
+            if !c.is_ascii_digit() {
+                return Err(Error::DISP_TYPEMISMATCH);
+            }
+            if mantissa.bits() > 128 {
+                if i < int.len() {
+                    return Err(Error::DISP_OVERFLOW);
+                }
+                // Far more digits than a DECIMAL can hold have been read;
+                // the rest only matter for rounding.
+                sticky |= c != b'0';
+                continue;
+            }
+            if i >= int.len() {
+                scale += 1;
+            }
+            // At most 128 bits, so multiplying by 10 can't overflow.
+            mantissa = mantissa
+                .mul_small(10)
+                .ok_or(Error::DISP_OVERFLOW)?
+                .add(&Wide::from(u128::from(c - b'0')));
+        }
+
+        if mantissa.is_zero() {
+            return Ok(Decimal::from_parts_unchecked(negative, 0, 0));
+        }
+        if scale < 0 {
+            let shift = u32::try_from(-scale).map_err(|_| Error::DISP_OVERFLOW)?;
+            if shift > 40 {
+                return Err(Error::DISP_OVERFLOW);
+            }
+            mantissa = mantissa.mul_pow10(shift).ok_or(Error::DISP_OVERFLOW)?;
+            scale = 0;
+        }
+        // Values this small round to zero; keep the arithmetic bounded.
+        let scale = u32::try_from(scale.min(200)).unwrap_or(200);
+        fit(negative, mantissa, scale, sticky).ok_or(Error::DISP_OVERFLOW)
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(v: i64) -> Decimal {
+        Decimal::from_parts_unchecked(v < 0, u128::from(v.unsigned_abs()), 0)
+    }
+}
+
+impl From<u64> for Decimal {
+    fn from(v: u64) -> Decimal {
+        Decimal::from_parts_unchecked(false, u128::from(v), 0)
+    }
+}
+
+impl TryFrom<f64> for Decimal {
+    type Error = Error;
+
+    /// Converts a double keeping 15 significant digits, like `VarDecFromR8`.
+    /// Fails with `Error::DISP_OVERFLOW` for values out of range, infinities
+    /// and NaN.
+    fn try_from(v: f64) -> Result<Decimal, Error> {
+        if !v.is_finite() {
+            return Err(Error::DISP_OVERFLOW);
+        }
+        let dec: Decimal = format!("{:.14e}", v).parse()?;
+        Ok(dec.normalize())
+    }
+}
+
+impl From<Decimal> for f64 {
+    fn from(v: Decimal) -> f64 {
+        // Parsing the exact decimal representation rounds correctly, unlike
+        // dividing the mantissa by a power of ten.
+        let s = format!(
+            "{}{}e-{}",
+            if v.is_negative() { "-" } else { "" },
+            v.mantissa(),
+            v.scale
+        );
+        s.parse().unwrap_or(0.0)
+    }
+}
+
+impl TryFrom<Decimal> for i64 {
+    type Error = Error;
+
+    /// Rounds half to even, like `VarI8FromDec`. Fails with
+    /// `Error::DISP_OVERFLOW` if the value is out of range.
+    fn try_from(v: Decimal) -> Result<i64, Error> {
+        to_i64(v.is_negative(), v.round(0).mantissa())
+    }
+}
+
+impl TryFrom<Decimal> for u64 {
+    type Error = Error;
+
+    /// Rounds half to even, like `VarUI8FromDec`. Fails with
+    /// `Error::DISP_OVERFLOW` if the value is out of range.
+    fn try_from(v: Decimal) -> Result<u64, Error> {
+        let int = v.round(0).mantissa();
+        if v.is_negative() && int != 0 {
+            return Err(Error::DISP_OVERFLOW);
+        }
+        u64::try_from(int).map_err(|_| Error::DISP_OVERFLOW)
+    }
+}
+
+fn to_i64(negative: bool, magnitude: u128) -> Result<i64, Error> {
+    if negative {
+        if magnitude > u128::from(i64::MIN.unsigned_abs()) {
+            return Err(Error::DISP_OVERFLOW);
+        }
+        Ok((magnitude as i64).wrapping_neg())
+    } else {
+        i64::try_from(magnitude).map_err(|_| Error::DISP_OVERFLOW)
+    }
+}
+
+/// Divides by 10^n, returning the quotient, the last digit dropped and
+/// whether any digit before it was non-zero.
+fn div_pow10(mut value: u128, n: u32) -> (u128, u32, bool) {
+    let mut digit = 0;
+    let mut sticky = false;
+    for _ in 0..n {
+        sticky |= digit != 0;
+        digit = (value % 10) as u32;
+        value /= 10;
+        if value == 0 && digit == 0 {
+            break;
+        }
+    }
+    (value, digit, sticky)
+}
+
+fn round_half_even(quotient: u128, digit: u32, sticky: bool) -> u128 {
+    if digit > 5 || (digit == 5 && (sticky || quotient % 2 == 1)) {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
+
+/// Reduces `mantissa * 10^-scale` to a DECIMAL, dropping digits with
+/// round-half-to-even. `sticky` says whether non-zero digits were already
+/// lost to the right of `mantissa`.
+fn fit(negative: bool, mut mantissa: Wide, mut scale: u32, mut sticky: bool) -> Option<Decimal> {
+    let max = Wide::from(Decimal::MAX_MANTISSA);
+    loop {
+        let mut digit = 0;
+        let mut dropped = false;
+        while scale > u32::from(Decimal::MAX_SCALE) || mantissa.cmp(&max) == Ordering::Greater {
+            if scale == 0 {
+                return None;
+            }
+            sticky |= digit != 0;
+            let (q, r) = mantissa.div_small(10);
+            mantissa = q;
+            digit = r;
+            scale -= 1;
+            dropped = true;
+        }
+        if dropped && (digit > 5 || (digit == 5 && (sticky || mantissa.is_odd()))) {
+            mantissa = mantissa.add(&Wide::from(1));
+            // Rounding up may carry past 96 bits, in which case another
+            // digit has to go.
+            sticky |= digit != 0;
+            continue;
+        }
+        let value = mantissa.to_u128()?;
+        return Some(Decimal::from_parts_unchecked(negative, value, scale as u8));
+    }
+}
+
+/// A fixed-width unsigned integer large enough for the intermediate results
+/// of DECIMAL arithmetic: a 96-bit mantissa scaled by up to 10^57 during
+/// division. Limbs are little-endian.
+#[derive(Copy, Clone, PartialEq, Eq)]
+struct Wide([u32; 10]);
+
+impl Wide {
+    const ZERO: Wide = Wide([0; 10]);
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(|&l| l == 0)
+    }
+
+    fn is_odd(&self) -> bool {
+        self.0[0] & 1 == 1
+    }
+
+    fn bits(&self) -> u32 {
+        match self.0.iter().rposition(|&l| l != 0) {
+            Some(i) => 32 * i as u32 + (32 - self.0[i].leading_zeros()),
+            None => 0,
+        }
+    }
+
+    fn to_u128(self) -> Option<u128> {
+        if self.0[4..].iter().any(|&l| l != 0) {
+            return None;
+        }
+        Some(
+            self.0[..4]
+                .iter()
+                .rev()
+                .fold(0u128, |acc, &l| (acc << 32) | u128::from(l)),
+        )
+    }
+
+    fn add(&self, rhs: &Wide) -> Wide {
+        let mut out = Wide::ZERO;
+        let mut carry = 0u64;
+        for i in 0..10 {
+            let sum = u64::from(self.0[i]) + u64::from(rhs.0[i]) + carry;
+            out.0[i] = sum as u32;
+            carry = sum >> 32;
+        }
+        out
+    }
+
+    /// `self - rhs`, where `self >= rhs`.
+    fn sub(&self, rhs: &Wide) -> Wide {
+        let mut out = Wide::ZERO;
+        let mut borrow = 0i64;
+        for i in 0..10 {
+            let diff = i64::from(self.0[i]) - i64::from(rhs.0[i]) - borrow;
+            out.0[i] = diff as u32;
+            borrow = if diff < 0 { 1 } else { 0 };
+        }
+        out
+    }
+
+    /// `self * rhs`, or None if it doesn't fit.
+    fn mul_small(&self, rhs: u32) -> Option<Wide> {
+        let mut out = Wide::ZERO;
+        let mut carry = 0u64;
+        for i in 0..10 {
+            let prod = u64::from(self.0[i]) * u64::from(rhs) + carry;
+            out.0[i] = prod as u32;
+            carry = prod >> 32;
+        }
+        match carry {
+            0 => Some(out),
+            _ => None,
+        }
+    }
+
+    /// `self * 10^n`, or None if it doesn't fit.
+    fn mul_pow10(&self, n: u32) -> Option<Wide> {
+        (0..n).try_fold(*self, |acc, _| acc.mul_small(10))
+    }
+
+    fn mul(&self, rhs: &Wide) -> Wide {
+        let mut out = [0u64; 10];
+        for i in 0..10 {
+            let mut carry = 0u64;
+            for j in 0..10 - i {
+                let cur = out[i + j] + u64::from(self.0[i]) * u64::from(rhs.0[j]) + carry;
+                out[i + j] = cur & 0xFFFF_FFFF;
+                carry = cur >> 32;
+            }
+        }
+        let mut wide = Wide::ZERO;
+        for (dst, src) in wide.0.iter_mut().zip(out.iter()) {
+            *dst = *src as u32;
+        }
+        wide
+    }
+
+    fn div_small(&self, rhs: u32) -> (Wide, u32) {
+        let mut out = Wide::ZERO;
+        let mut rem = 0u64;
+        for i in (0..10).rev() {
+            let cur = (rem << 32) | u64::from(self.0[i]);
+            out.0[i] = (cur / u64::from(rhs)) as u32;
+            rem = cur % u64::from(rhs);
+        }
+        (out, rem as u32)
+    }
+
+    fn shl1(&self) -> Wide {
+        let mut out = Wide::ZERO;
+        let mut carry = 0;
+        for i in 0..10 {
+            out.0[i] = (self.0[i] << 1) | carry;
+            carry = self.0[i] >> 31;
+        }
+        out
+    }
+
+    /// Schoolbook binary long division.
+    fn div_rem(&self, rhs: &Wide) -> (Wide, Wide) {
+        let mut quotient = Wide::ZERO;
+        let mut rem = Wide::ZERO;
+        for bit in (0..self.bits()).rev() {
+            rem = rem.shl1();
+            rem.0[0] |= (self.0[bit as usize / 32] >> (bit % 32)) & 1;
+            if rem.cmp(rhs) != Ordering::Less {
+                rem = rem.sub(rhs);
+                quotient.0[bit as usize / 32] |= 1 << (bit % 32);
+            }
+        }
+        (quotient, rem)
+    }
+
+    fn cmp(&self, rhs: &Wide) -> Ordering {
+        self.0.iter().rev().cmp(rhs.0.iter().rev())
+    }
+}
+
+impl From<u128> for Wide {
+    fn from(v: u128) -> Wide {
+        let mut wide = Wide::ZERO;
+        for (i, limb) in wide.0.iter_mut().take(4).enumerate() {
+            *limb = (v >> (32 * i)) as u32;
+        }
+        wide
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn decimal_arithmetic() {
+    let d = |s: &str| s.parse::<Decimal>().unwrap();
+
+    assert_eq!(d("1.5") + d("2.25"), d("3.75"));
+    assert_eq!((d("1.5") - d("2.25")).to_string(), "-0.75");
+    assert_eq!((d("1.50") * d("2")).to_string(), "3.00");
+    assert_eq!((d("1") / d("4")).to_string(), "0.25");
+    assert_eq!(
+        (d("1") / d("3")).to_string(),
+        "0.3333333333333333333333333333"
+    );
+    assert_eq!(
+        (d("2") / d("3")).to_string(),
+        "0.6666666666666666666666666667"
+    );
+    assert_eq!(d("10.00") / d("4"), d("2.5"));
+    assert_eq!(Decimal::MAX.checked_add(Decimal::ONE), None);
+    assert_eq!(Decimal::ONE.checked_div(Decimal::ZERO), None);
+    assert_eq!(
+        (Decimal::MAX * d("0.5")).to_string(),
+        "39614081257132168796771975168"
+    );
+
+    assert!(d("-1") < d("0.5"));
+    assert!(d("-2") < d("-1.5"));
+    assert_eq!(d("0.10"), d("0.1"));
+    assert_eq!(d("-0"), Decimal::ZERO);
+    assert_eq!(d("2.5").round(0), d("2"));
+    assert_eq!(d("3.5").round(0), d("4"));
+    assert_eq!(d("-3.7").trunc(), d("-3"));
+}
+
+#[cfg(test)]
+#[test]
+fn decimal_conversions() {
+    assert_eq!("1.2e3".parse::<Decimal>().unwrap().to_string(), "1200");
+    assert_eq!("-.5".parse::<Decimal>().unwrap().to_string(), "-0.5");
+    assert_eq!("abc".parse::<Decimal>(), Err(Error::DISP_TYPEMISMATCH));
+    assert_eq!("1e30".parse::<Decimal>(), Err(Error::DISP_OVERFLOW));
+    assert_eq!(format!("{:>6}", Decimal::from(-12i64)), "   -12");
+
+    assert_eq!(i64::try_from(Decimal::from(i64::MIN)), Ok(i64::MIN));
+    assert_eq!(u64::try_from(Decimal::from(u64::MAX)), Ok(u64::MAX));
+    assert_eq!(
+        u64::try_from(Decimal::from(-1i64)),
+        Err(Error::DISP_OVERFLOW)
+    );
+    assert_eq!(i64::try_from("2.5".parse::<Decimal>().unwrap()), Ok(2));
+
+    let tenth = Decimal::try_from(0.1).unwrap();
+    assert_eq!((tenth.mantissa(), tenth.scale), (1, 1));
+    assert_eq!(f64::from(tenth), 0.1);
+
+    let cy = Decimal::from_currency(-12_3456);
+    assert_eq!(cy.to_string(), "-12.3456");
+    assert_eq!(cy.to_currency(), Ok(-12_3456));
+    assert_eq!("0.00005".parse::<Decimal>().unwrap().to_currency(), Ok(0));
+    assert_eq!("0.00015".parse::<Decimal>().unwrap().to_currency(), Ok(2));
+}
+
+#[cfg(test)]
+#[test]
+fn decimal_invalid_scale() {
+    let bad = Decimal {
+        scale: 29,
+        ..Decimal::ONE
+    };
+    let worse = Decimal {
+        scale: 255,
+        ..Decimal::MAX
+    };
+    for &d in &[bad, worse] {
+        assert_eq!(d.checked_add(Decimal::ONE), None);
+        assert_eq!(Decimal::ONE.checked_sub(d), None);
+        assert_eq!(d.checked_mul(Decimal::ONE), None);
+        assert_eq!(d.checked_div(Decimal::ONE), None);
+        assert_eq!(Decimal::ONE.checked_div(d), None);
+        assert_eq!(d.to_currency(), Err(Error::INVALIDARG));
+        assert!(d > Decimal::ZERO);
+        assert!(d < Decimal::ONE);
+    }
+    assert!(worse < bad);
+}
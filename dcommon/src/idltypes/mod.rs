@@ -1,6 +1,12 @@
 pub mod bstr;
+pub mod coerce;
 pub mod decimal;
+pub mod propvalue;
+#[cfg(windows)]
 pub mod propvariant;
+#[cfg(windows)]
+pub(crate) mod raw_read;
 pub mod safearray;
 pub mod variant;
+pub mod variant_value;
 pub mod vartype;
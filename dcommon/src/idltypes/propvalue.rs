@@ -0,0 +1,735 @@
+//! An owned, safe counterpart to `PropVariant`.
+
+use crate::idltypes::decimal::Decimal;
+use crate::idltypes::vartype::VarType;
+use crate::minwindef::filetime::FileTime;
+use crate::GUID;
+
+#[cfg(windows)]
+use crate::idltypes::safearray::OwnedSafeArray;
+#[cfg(windows)]
+use winapi::um::oaidl::IDispatch;
+#[cfg(windows)]
+use winapi::um::objidl::IStorage;
+#[cfg(windows)]
+use winapi::um::objidlbase::IStream;
+#[cfg(windows)]
+use winapi::um::unknwnbase::IUnknown;
+#[cfg(windows)]
+use wio::com::ComPtr;
+
+/// An owned value read out of a `PropVariant`. Strings, blobs and arrays are
+/// copied into Rust memory and interfaces are reference counted, so dropping a
+/// `PropValue` never leaks.
+///
+/// Converting a raw `PropVariant` with `TryFrom` only borrows it; use
+/// `PropValue::from_raw` to also free the raw value. `into_raw` produces a
+/// `PropVariant` whose memory is allocated the way `PropVariantClear` expects.
+/// Values that don't involve COM are also available on other targets, e.g.
+/// for the serialized property sets in `objidl::property_set`.
+#[derive(Clone, Debug)]
+pub enum PropValue {
+    /// VarType::EMPTY
+    Empty,
+    /// VarType::NULL
+    Null,
+    /// VarType::I1
+    I1(i8),
+    /// VarType::UI1
+    UI1(u8),
+    /// VarType::I2
+    I2(i16),
+    /// VarType::UI2
+    UI2(u16),
+    /// VarType::I4
+    I4(i32),
+    /// VarType::UI4
+    UI4(u32),
+    /// VarType::INT
+    Int(i32),
+    /// VarType::UINT
+    UInt(u32),
+    /// VarType::I8
+    I8(i64),
+    /// VarType::UI8
+    UI8(u64),
+    /// VarType::R4
+    R4(f32),
+    /// VarType::R8
+    R8(f64),
+    /// VarType::BOOL
+    Bool(bool),
+    /// VarType::ERROR
+    Error(i32),
+    /// VarType::CY, in units of 1/10000.
+    Currency(i64),
+    /// VarType::DATE, as an OLE Automation date.
+    Date(f64),
+    /// VarType::DECIMAL
+    Decimal(Decimal),
+    /// VarType::FILETIME
+    FileTime(FileTime),
+    /// VarType::CLSID
+    Clsid(GUID),
+    /// VarType::CF
+    ClipData(ClipboardData),
+    /// VarType::BSTR
+    BStr(String),
+    /// VarType::LPSTR. The bytes are in the system ANSI code page and do not
+    /// include the nul terminator.
+    AnsiStr(Vec<u8>),
+    /// VarType::LPWSTR
+    Str(String),
+    /// VarType::BLOB
+    Blob(Vec<u8>),
+    /// VarType::BLOB_OBJECT
+    BlobObject(Vec<u8>),
+    #[cfg(windows)]
+    /// VarType::UNKNOWN
+    Unknown(Option<ComPtr<IUnknown>>),
+    #[cfg(windows)]
+    /// VarType::DISPATCH
+    Dispatch(Option<ComPtr<IDispatch>>),
+    #[cfg(windows)]
+    /// VarType::STREAM
+    Stream(ComPtr<IStream>),
+    #[cfg(windows)]
+    /// VarType::STREAMED_OBJECT
+    StreamedObject(ComPtr<IStream>),
+    #[cfg(windows)]
+    /// VarType::STORAGE
+    Storage(ComPtr<IStorage>),
+    #[cfg(windows)]
+    /// VarType::STORED_OBJECT
+    StoredObject(ComPtr<IStorage>),
+    #[cfg(windows)]
+    /// VarType::VERSIONED_STREAM
+    VersionedStream {
+        version: GUID,
+        stream: ComPtr<IStream>,
+    },
+    #[cfg(windows)]
+    /// VarType::ARRAY combined with the element type.
+    Array(VarType, OwnedSafeArray),
+    /// VarType::VECTOR combined with the element type. Every element must
+    /// have that type, except for `VarType::VARIANT` vectors, which may hold
+    /// any value that isn't itself a vector or array.
+    Vector(VarType, Vec<PropValue>),
+    /// VarType::BYREF. The value is copied out of the referenced memory, so it
+    /// can be read but not converted back into a raw `PropVariant`.
+    ByRef(Box<PropValue>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The owned contents of a `ClipData`.
+pub struct ClipboardData {
+    pub format: i32,
+    pub data: Vec<u8>,
+}
+
+impl PropValue {
+    /// The `VarType` this value will have when converted into a `PropVariant`.
+    pub fn vartype(&self) -> VarType {
+        match self {
+            PropValue::Empty => VarType::EMPTY,
+            PropValue::Null => VarType::NULL,
+            PropValue::I1(_) => VarType::I1,
+            PropValue::UI1(_) => VarType::UI1,
+            PropValue::I2(_) => VarType::I2,
+            PropValue::UI2(_) => VarType::UI2,
+            PropValue::I4(_) => VarType::I4,
+            PropValue::UI4(_) => VarType::UI4,
+            PropValue::Int(_) => VarType::INT,
+            PropValue::UInt(_) => VarType::UINT,
+            PropValue::I8(_) => VarType::I8,
+            PropValue::UI8(_) => VarType::UI8,
+            PropValue::R4(_) => VarType::R4,
+            PropValue::R8(_) => VarType::R8,
+            PropValue::Bool(_) => VarType::BOOL,
+            PropValue::Error(_) => VarType::ERROR,
+            PropValue::Currency(_) => VarType::CY,
+            PropValue::Date(_) => VarType::DATE,
+            PropValue::Decimal(_) => VarType::DECIMAL,
+            PropValue::FileTime(_) => VarType::FILETIME,
+            PropValue::Clsid(_) => VarType::CLSID,
+            PropValue::ClipData(_) => VarType::CF,
+            PropValue::BStr(_) => VarType::BSTR,
+            PropValue::AnsiStr(_) => VarType::LPSTR,
+            PropValue::Str(_) => VarType::LPWSTR,
+            PropValue::Blob(_) => VarType::BLOB,
+            PropValue::BlobObject(_) => VarType::BLOB_OBJECT,
+            #[cfg(windows)]
+            PropValue::Unknown(_) => VarType::UNKNOWN,
+            #[cfg(windows)]
+            PropValue::Dispatch(_) => VarType::DISPATCH,
+            #[cfg(windows)]
+            PropValue::Stream(_) => VarType::STREAM,
+            #[cfg(windows)]
+            PropValue::StreamedObject(_) => VarType::STREAMED_OBJECT,
+            #[cfg(windows)]
+            PropValue::Storage(_) => VarType::STORAGE,
+            #[cfg(windows)]
+            PropValue::StoredObject(_) => VarType::STORED_OBJECT,
+            #[cfg(windows)]
+            PropValue::VersionedStream { .. } => VarType::VERSIONED_STREAM,
+            #[cfg(windows)]
+            PropValue::Array(vt, _) => VarType::ARRAY | *vt,
+            PropValue::Vector(vt, _) => VarType::VECTOR | *vt,
+            PropValue::ByRef(inner) => VarType::BYREF | inner.vartype(),
+        }
+    }
+}
+
+#[cfg(windows)]
+mod raw {
+    use super::{ClipboardData, PropValue};
+    use crate::idltypes::bstr::BStr;
+    use crate::idltypes::decimal::Decimal;
+    use crate::idltypes::propvariant::{
+        self, Blob, ClipData, CountedArray, InnerPropVariantData, PropVariant,
+    };
+    use crate::idltypes::raw_read::{add_ref, byref_size};
+    use crate::idltypes::safearray::OwnedSafeArray;
+    use crate::idltypes::vartype::VarType;
+    use crate::minwindef::filetime::FileTime;
+    use crate::{Error, GUID};
+
+    use std::convert::TryFrom;
+    use std::ptr::NonNull;
+
+    use winapi::um::combaseapi::CoTaskMemAlloc;
+    use wio::com::ComPtr;
+
+    impl PropValue {
+        /// Reads the value and then frees the memory owned by `raw` with
+        /// `PropVariantClear`. `raw` is cleared even if it can't be represented
+        /// as a `PropValue`.
+        ///
+        /// # Safety
+        ///
+        /// `raw` must be a valid `PROPVARIANT` that owns its memory.
+        pub unsafe fn from_raw(mut raw: PropVariant) -> Result<PropValue, Error> {
+            let value = PropValue::try_from(&raw);
+            raw.clear()?;
+            value
+        }
+
+        /// Converts the value into a raw `PropVariant` that owns its memory. The
+        /// caller is responsible for eventually calling `PropVariant::clear`.
+        ///
+        /// Fails with `Error::DISP_BADVARTYPE` for `ByRef` values, and with
+        /// `Error::DISP_TYPEMISMATCH` when a vector holds elements of the wrong
+        /// type.
+        pub fn into_raw(self) -> Result<PropVariant, Error> {
+            unsafe {
+                let mut raw: PropVariant = std::mem::zeroed();
+                raw.pv.vartype = self.vartype();
+                let data = &mut raw.pv.data;
+                match self {
+                    PropValue::Empty | PropValue::Null => {}
+                    PropValue::I1(v) => data.c_val = v,
+                    PropValue::UI1(v) => data.b_val = v,
+                    PropValue::I2(v) => data.i_val = v,
+                    PropValue::UI2(v) => data.ui_val = v,
+                    PropValue::I4(v) => data.l_val = v,
+                    PropValue::UI4(v) => data.ul_val = v,
+                    PropValue::Int(v) => data.int_val = v,
+                    PropValue::UInt(v) => data.uint_val = v,
+                    PropValue::I8(v) => data.h_val = v,
+                    PropValue::UI8(v) => data.uh_val = v,
+                    PropValue::R4(v) => data.flt_val = v,
+                    PropValue::R8(v) => data.dbl_val = v,
+                    PropValue::Bool(v) => data.bool_val = variant_bool(v),
+                    PropValue::Error(v) => data.scode = v,
+                    PropValue::Currency(v) => data.cy_val = v,
+                    PropValue::Date(v) => data.date = v,
+                    PropValue::Decimal(v) => {
+                        // DECIMAL overlaps the vartype field, so it has to be
+                        // restored afterwards.
+                        raw.dec = v;
+                        raw.pv.vartype = VarType::DECIMAL;
+                    }
+                    PropValue::FileTime(v) => data.filetime = v,
+                    PropValue::Clsid(v) => data.puuid = co_alloc(&[v]),
+                    PropValue::ClipData(v) => data.pclipdata = co_alloc(&[clip_data(&v)]),
+                    PropValue::BStr(v) => data.bstr_val = BStr::new(&v).into_raw(),
+                    PropValue::AnsiStr(v) => data.psz_val = ansi_str(&v),
+                    PropValue::Str(v) => data.pwsz_val = wide_str(&v),
+                    PropValue::Blob(v) | PropValue::BlobObject(v) => data.blob = blob(&v),
+                    PropValue::Unknown(v) => data.punk_val = into_raw_opt(v),
+                    PropValue::Dispatch(v) => data.pdisp_val = into_raw_opt(v),
+                    PropValue::Stream(v) | PropValue::StreamedObject(v) => {
+                        data.pstream = v.into_raw()
+                    }
+                    PropValue::Storage(v) | PropValue::StoredObject(v) => {
+                        data.pstorage = v.into_raw()
+                    }
+                    PropValue::VersionedStream { version, stream } => {
+                        data.pversioned_stream = co_alloc(&[propvariant::VersionedStream {
+                            version,
+                            stream: stream.into_raw(),
+                        }]);
+                    }
+                    PropValue::Array(_, v) => data.parray = v.into_raw(),
+                    PropValue::Vector(vt, v) => write_vector(vt, v, data)?,
+                    PropValue::ByRef(_) => return Err(Error::DISP_BADVARTYPE),
+                }
+                Ok(raw)
+            }
+        }
+    }
+
+    impl<'a> TryFrom<&'a PropVariant> for PropValue {
+        type Error = Error;
+
+        /// Copies the value out of `raw`, leaving `raw` untouched. Fails with
+        /// `Error::DISP_BADVARTYPE` for types a `PropVariant` cannot hold and with
+        /// `Error::WIN32_INVALID_DATA` for strings that aren't valid UTF-16.
+        fn try_from(raw: &'a PropVariant) -> Result<PropValue, Error> {
+            unsafe { read(raw) }
+        }
+    }
+
+    unsafe fn read(raw: &PropVariant) -> Result<PropValue, Error> {
+        let vt = raw.vartype();
+        let data = &raw.pv.data;
+
+        if vt.container_type() == VarType::VECTOR {
+            return read_vector(vt.primitive_type(), data);
+        }
+        if vt.container_type() == VarType::ARRAY {
+            if data.parray.is_null() {
+                return Err(Error::POINTER);
+            }
+            let array = OwnedSafeArray::copy_from(data.parray)?;
+            return Ok(PropValue::Array(vt.primitive_type(), array));
+        }
+        if vt.container_type().0 & VarType::BYREF.0 != 0 {
+            return read_byref(VarType(vt.0 & !VarType::BYREF.0), data);
+        }
+
+        Ok(match vt {
+            VarType::EMPTY => PropValue::Empty,
+            VarType::NULL => PropValue::Null,
+            VarType::I1 => PropValue::I1(data.c_val),
+            VarType::UI1 => PropValue::UI1(data.b_val),
+            VarType::I2 => PropValue::I2(data.i_val),
+            VarType::UI2 => PropValue::UI2(data.ui_val),
+            VarType::I4 => PropValue::I4(data.l_val),
+            VarType::UI4 => PropValue::UI4(data.ul_val),
+            VarType::INT => PropValue::Int(data.int_val),
+            VarType::UINT => PropValue::UInt(data.uint_val),
+            VarType::I8 => PropValue::I8(data.h_val),
+            VarType::UI8 => PropValue::UI8(data.uh_val),
+            VarType::R4 => PropValue::R4(data.flt_val),
+            VarType::R8 => PropValue::R8(data.dbl_val),
+            VarType::BOOL => PropValue::Bool(data.bool_val != 0),
+            VarType::ERROR => PropValue::Error(data.scode),
+            VarType::CY => PropValue::Currency(data.cy_val),
+            VarType::DATE => PropValue::Date(data.date),
+            // The reserved field of the DECIMAL holds the vartype.
+            VarType::DECIMAL => PropValue::Decimal(Decimal {
+                reserved: 0,
+                ..raw.dec
+            }),
+            VarType::FILETIME => PropValue::FileTime(data.filetime),
+            VarType::CLSID => PropValue::Clsid(*non_null(data.puuid)?),
+            VarType::CF => PropValue::ClipData(read_clip_data(&*non_null(data.pclipdata)?)),
+            VarType::BSTR => PropValue::BStr(BStr::read_raw(data.bstr_val)?),
+            VarType::LPSTR => PropValue::AnsiStr(read_ansi_str(data.psz_val)),
+            VarType::LPWSTR => PropValue::Str(read_wide_str(data.pwsz_val)?),
+            VarType::BLOB => PropValue::Blob(read_blob(&data.blob)),
+            VarType::BLOB_OBJECT => PropValue::BlobObject(read_blob(&data.blob)),
+            VarType::UNKNOWN => PropValue::Unknown(add_ref(data.punk_val)),
+            VarType::DISPATCH => PropValue::Dispatch(add_ref(data.pdisp_val)),
+            VarType::STREAM => PropValue::Stream(add_ref_non_null(data.pstream)?),
+            VarType::STREAMED_OBJECT => PropValue::StreamedObject(add_ref_non_null(data.pstream)?),
+            VarType::STORAGE => PropValue::Storage(add_ref_non_null(data.pstorage)?),
+            VarType::STORED_OBJECT => PropValue::StoredObject(add_ref_non_null(data.pstorage)?),
+            VarType::VERSIONED_STREAM => {
+                let vs = &*non_null(data.pversioned_stream)?;
+                PropValue::VersionedStream {
+                    version: vs.version,
+                    stream: add_ref_non_null(vs.stream)?,
+                }
+            }
+            _ => return Err(Error::DISP_BADVARTYPE),
+        })
+    }
+
+    unsafe fn read_byref(vt: VarType, data: &InnerPropVariantData) -> Result<PropValue, Error> {
+        let ptr = data.pc_val as *const u8;
+        if ptr.is_null() {
+            return Err(Error::POINTER);
+        }
+
+        // Build a temporary by-value PropVariant out of the referenced memory.
+        // It only borrows the referenced data, so it must not be cleared.
+        let mut tmp: PropVariant = std::mem::zeroed();
+        let size = match vt {
+            VarType::VARIANT => {
+                return read(&*data.pvar_val).map(|v| PropValue::ByRef(Box::new(v)));
+            }
+            VarType::DECIMAL => {
+                tmp.dec = *data.pdec_val;
+                0
+            }
+            _ => byref_size(vt).ok_or(Error::DISP_BADVARTYPE)?,
+        };
+        let dst = &mut tmp.pv.data as *mut InnerPropVariantData as *mut u8;
+        std::ptr::copy_nonoverlapping(ptr, dst, size);
+        tmp.pv.vartype = vt;
+
+        read(&tmp).map(|v| PropValue::ByRef(Box::new(v)))
+    }
+
+    unsafe fn read_vector(vt: VarType, data: &InnerPropVariantData) -> Result<PropValue, Error> {
+        fn map<T, F>(array: &CountedArray<T>, f: F) -> Result<Vec<PropValue>, Error>
+        where
+            F: FnMut(&T) -> Result<PropValue, Error>,
+        {
+            unsafe { counted_slice(array).iter().map(f).collect() }
+        }
+
+        let values = match vt {
+            VarType::I1 => map(&data.ca_c, |&v| Ok(PropValue::I1(v)))?,
+            VarType::UI1 => map(&data.ca_ub, |&v| Ok(PropValue::UI1(v)))?,
+            VarType::I2 => map(&data.ca_i, |&v| Ok(PropValue::I2(v)))?,
+            VarType::UI2 => map(&data.ca_ui, |&v| Ok(PropValue::UI2(v)))?,
+            VarType::I4 => map(&data.ca_l, |&v| Ok(PropValue::I4(v)))?,
+            VarType::UI4 => map(&data.ca_ul, |&v| Ok(PropValue::UI4(v)))?,
+            VarType::I8 => map(&data.ca_h, |&v| Ok(PropValue::I8(v)))?,
+            VarType::UI8 => map(&data.ca_uh, |&v| Ok(PropValue::UI8(v)))?,
+            VarType::R4 => map(&data.ca_flt, |&v| Ok(PropValue::R4(v)))?,
+            VarType::R8 => map(&data.ca_dbl, |&v| Ok(PropValue::R8(v)))?,
+            VarType::BOOL => map(&data.ca_bool, |&v| Ok(PropValue::Bool(v != 0)))?,
+            VarType::ERROR => map(&data.ca_scode, |&v| Ok(PropValue::Error(v)))?,
+            VarType::CY => map(&data.ca_cy, |&v| Ok(PropValue::Currency(v)))?,
+            VarType::DATE => map(&data.ca_date, |&v| Ok(PropValue::Date(v)))?,
+            VarType::FILETIME => map(&data.ca_filetime, |&v| Ok(PropValue::FileTime(v)))?,
+            VarType::CLSID => map(&data.ca_uuid, |&v| Ok(PropValue::Clsid(v)))?,
+            VarType::CF => map(&data.ca_clipdata, |v| {
+                Ok(PropValue::ClipData(read_clip_data(v)))
+            })?,
+            VarType::BSTR => map(&data.ca_bstr, |&v| BStr::read_raw(v).map(PropValue::BStr))?,
+            VarType::LPSTR => map(&data.ca_lpstr, |&v| {
+                Ok(PropValue::AnsiStr(read_ansi_str(v)))
+            })?,
+            VarType::LPWSTR => map(&data.ca_lpwstr, |&v| read_wide_str(v).map(PropValue::Str))?,
+            VarType::VARIANT => map(&data.ca_propvar, |v| read(v))?,
+            _ => return Err(Error::DISP_BADVARTYPE),
+        };
+
+        Ok(PropValue::Vector(vt, values))
+    }
+
+    unsafe fn write_vector(
+        vt: VarType,
+        values: Vec<PropValue>,
+        data: &mut InnerPropVariantData,
+    ) -> Result<(), Error> {
+        // Check every element up front so nothing needs to be freed when the
+        // types don't line up.
+        let valid = match vt {
+            VarType::VARIANT => values.iter().all(|v| {
+                let container = v.vartype().container_type();
+                container != VarType::VECTOR && container != VarType::ARRAY
+            }),
+            _ => values.iter().all(|v| v.vartype() == vt),
+        };
+        if !valid {
+            return Err(Error::DISP_TYPEMISMATCH);
+        }
+
+        macro_rules! simple {
+            ($field:ident, $variant:ident, $conv:expr) => {{
+                let items: Vec<_> = values
+                    .iter()
+                    .map(|v| match v {
+                        PropValue::$variant(v) => $conv(v),
+                        _ => unreachable!(),
+                    })
+                    .collect();
+                data.$field = counted_array(&items);
+            }};
+        }
+
+        match vt {
+            VarType::I1 => simple!(ca_c, I1, |v: &i8| *v),
+            VarType::UI1 => simple!(ca_ub, UI1, |v: &u8| *v),
+            VarType::I2 => simple!(ca_i, I2, |v: &i16| *v),
+            VarType::UI2 => simple!(ca_ui, UI2, |v: &u16| *v),
+            VarType::I4 => simple!(ca_l, I4, |v: &i32| *v),
+            VarType::UI4 => simple!(ca_ul, UI4, |v: &u32| *v),
+            VarType::I8 => simple!(ca_h, I8, |v: &i64| *v),
+            VarType::UI8 => simple!(ca_uh, UI8, |v: &u64| *v),
+            VarType::R4 => simple!(ca_flt, R4, |v: &f32| *v),
+            VarType::R8 => simple!(ca_dbl, R8, |v: &f64| *v),
+            VarType::BOOL => simple!(ca_bool, Bool, |v: &bool| variant_bool(*v)),
+            VarType::ERROR => simple!(ca_scode, Error, |v: &i32| *v),
+            VarType::CY => simple!(ca_cy, Currency, |v: &i64| *v),
+            VarType::DATE => simple!(ca_date, Date, |v: &f64| *v),
+            VarType::FILETIME => simple!(ca_filetime, FileTime, |v: &FileTime| *v),
+            VarType::CLSID => simple!(ca_uuid, Clsid, |v: &GUID| *v),
+            VarType::CF => simple!(ca_clipdata, ClipData, clip_data),
+            VarType::BSTR => simple!(ca_bstr, BStr, |v: &String| BStr::new(v).into_raw()),
+            VarType::LPSTR => simple!(ca_lpstr, AnsiStr, |v: &Vec<u8>| ansi_str(v)),
+            VarType::LPWSTR => simple!(ca_lpwstr, Str, |v: &String| wide_str(v)),
+            VarType::VARIANT => {
+                let mut items = Vec::with_capacity(values.len());
+                for value in values {
+                    match value.into_raw() {
+                        Ok(raw) => items.push(raw),
+                        Err(e) => {
+                            for item in &mut items {
+                                item.clear().ok();
+                            }
+                            return Err(e);
+                        }
+                    }
+                }
+                data.ca_propvar = counted_array(&items);
+            }
+            _ => return Err(Error::DISP_BADVARTYPE),
+        }
+        Ok(())
+    }
+
+    fn variant_bool(b: bool) -> i16 {
+        if b {
+            -1
+        } else {
+            0
+        }
+    }
+
+    unsafe fn non_null<T>(ptr: *mut T) -> Result<&'static T, Error> {
+        if ptr.is_null() {
+            Err(Error::POINTER)
+        } else {
+            Ok(&*ptr)
+        }
+    }
+
+    unsafe fn counted_slice<T>(array: &CountedArray<T>) -> &[T] {
+        if array.data.is_null() || array.elements == 0 {
+            &[]
+        } else {
+            std::slice::from_raw_parts(array.data, array.elements as usize)
+        }
+    }
+
+    unsafe fn read_bytes<'a>(ptr: Option<NonNull<u8>>, len: usize) -> &'a [u8] {
+        match ptr {
+            Some(ptr) if len > 0 => std::slice::from_raw_parts(ptr.as_ptr(), len),
+            _ => &[],
+        }
+    }
+
+    unsafe fn read_blob(blob: &Blob) -> Vec<u8> {
+        read_bytes(blob.data, blob.size as usize).to_vec()
+    }
+
+    unsafe fn read_clip_data(clip: &ClipData) -> ClipboardData {
+        // The size includes the 4 bytes of the clipboard format.
+        let len = (clip.size as usize).saturating_sub(4);
+        ClipboardData {
+            format: clip.format,
+            data: read_bytes(clip.data, len).to_vec(),
+        }
+    }
+
+    unsafe fn read_ansi_str(psz: *mut i8) -> Vec<u8> {
+        if psz.is_null() {
+            return Vec::new();
+        }
+        std::ffi::CStr::from_ptr(psz).to_bytes().to_vec()
+    }
+
+    unsafe fn read_wide_str(pwsz: *mut u16) -> Result<String, Error> {
+        if pwsz.is_null() {
+            return Ok(String::new());
+        }
+        let len = crate::helpers::wstrlen(pwsz);
+        let slice = std::slice::from_raw_parts(pwsz, len);
+        String::from_utf16(slice).map_err(|_| Error::WIN32_INVALID_DATA)
+    }
+
+    unsafe fn add_ref_non_null<T>(ptr: *mut T) -> Result<ComPtr<T>, Error>
+    where
+        T: winapi::Interface,
+    {
+        add_ref(ptr).ok_or(Error::POINTER)
+    }
+
+    fn into_raw_opt<T>(ptr: Option<ComPtr<T>>) -> *mut T {
+        ptr.map(ComPtr::into_raw).unwrap_or(std::ptr::null_mut())
+    }
+
+    /// Copies `items` into memory allocated with `CoTaskMemAlloc`.
+    unsafe fn co_alloc<T: Copy>(items: &[T]) -> *mut T {
+        let size = std::mem::size_of::<T>() * items.len();
+        let mem = CoTaskMemAlloc(size.max(1)) as *mut T;
+        if mem.is_null() {
+            panic!("oom: failed to allocate {} bytes for PROPVARIANT", size);
+        }
+        std::ptr::copy_nonoverlapping(items.as_ptr(), mem, items.len());
+        mem
+    }
+
+    unsafe fn counted_array<T: Copy>(items: &[T]) -> CountedArray<T> {
+        if items.is_empty() {
+            return CountedArray {
+                elements: 0,
+                data: std::ptr::null_mut(),
+            };
+        }
+        assert!(items.len() <= std::u32::MAX as usize);
+        CountedArray {
+            elements: items.len() as u32,
+            data: co_alloc(items),
+        }
+    }
+
+    unsafe fn co_alloc_bytes(data: &[u8]) -> Option<NonNull<u8>> {
+        if data.is_empty() {
+            None
+        } else {
+            NonNull::new(co_alloc(data))
+        }
+    }
+
+    unsafe fn blob(data: &[u8]) -> Blob {
+        assert!(data.len() <= std::u32::MAX as usize);
+        Blob {
+            size: data.len() as u32,
+            data: co_alloc_bytes(data),
+        }
+    }
+
+    unsafe fn clip_data(clip: &ClipboardData) -> ClipData {
+        assert!(clip.data.len() <= std::u32::MAX as usize - 4);
+        ClipData {
+            size: clip.data.len() as u32 + 4,
+            format: clip.format,
+            data: co_alloc_bytes(&clip.data),
+        }
+    }
+
+    unsafe fn ansi_str(data: &[u8]) -> *mut i8 {
+        let mut buf = Vec::with_capacity(data.len() + 1);
+        buf.extend(data.iter().cloned().take_while(|&c| c != 0));
+        buf.push(0);
+        co_alloc(&buf) as *mut i8
+    }
+
+    unsafe fn wide_str(s: &str) -> *mut u16 {
+        let mut buf: Vec<u16> = s.encode_utf16().collect();
+        buf.push(0);
+        co_alloc(&buf)
+    }
+}
+
+#[cfg(all(test, windows))]
+#[test]
+fn propvalue_roundtrip() {
+    use std::convert::TryFrom;
+
+    let guid = GUID {
+        Data1: 0x1234_5678,
+        Data2: 0x9abc,
+        Data3: 0xdef0,
+        Data4: [1, 2, 3, 4, 5, 6, 7, 8],
+    };
+    let values = vec![
+        PropValue::Empty,
+        PropValue::Null,
+        PropValue::I1(-1),
+        PropValue::UI1(0xfe),
+        PropValue::I2(-300),
+        PropValue::UI2(0xfedc),
+        PropValue::I4(-70_000),
+        PropValue::UI4(0xfedc_ba98),
+        PropValue::Int(-5),
+        PropValue::UInt(5),
+        PropValue::I8(-1 << 40),
+        PropValue::UI8(1 << 63),
+        PropValue::R4(0.25),
+        PropValue::R8(-1.5e300),
+        PropValue::Bool(true),
+        PropValue::Bool(false),
+        PropValue::Error(0x8000_4005u32 as i32),
+        PropValue::Currency(12_3456),
+        PropValue::Date(43831.5),
+        PropValue::Decimal(Decimal::from_parts(true, 123_456_789, 4).unwrap()),
+        PropValue::FileTime(FileTime {
+            low_part: 1,
+            high_part: 2,
+        }),
+        PropValue::Clsid(guid),
+        PropValue::ClipData(ClipboardData {
+            format: 13,
+            data: vec![1, 2, 3],
+        }),
+        PropValue::BStr("héllo".into()),
+        PropValue::BStr(String::new()),
+        PropValue::AnsiStr(b"ansi".to_vec()),
+        PropValue::Str("wide ✓".into()),
+        PropValue::Blob(vec![0, 1, 2, 3]),
+        PropValue::Blob(Vec::new()),
+        PropValue::BlobObject(vec![9; 3]),
+        PropValue::Unknown(None),
+        PropValue::Dispatch(None),
+        PropValue::Vector(VarType::I4, vec![PropValue::I4(1), PropValue::I4(2)]),
+        PropValue::Vector(VarType::R8, Vec::new()),
+        PropValue::Vector(VarType::BSTR, vec![PropValue::BStr("a".into())]),
+        PropValue::Vector(VarType::LPWSTR, vec![PropValue::Str("b".into())]),
+        PropValue::Vector(VarType::LPSTR, vec![PropValue::AnsiStr(b"c".to_vec())]),
+        PropValue::Vector(VarType::CLSID, vec![PropValue::Clsid(guid)]),
+        PropValue::Vector(
+            VarType::VARIANT,
+            vec![PropValue::UI1(1), PropValue::BStr("mixed".into())],
+        ),
+    ];
+
+    for value in values {
+        let expected = format!("{:?}", value);
+        let vt = value.vartype();
+        let raw = value.into_raw().unwrap();
+        assert_eq!(raw.vartype(), vt, "{}", expected);
+
+        let copy = PropValue::try_from(&raw).unwrap();
+        assert_eq!(format!("{:?}", copy), expected);
+        let value = unsafe { PropValue::from_raw(raw) }.unwrap();
+        assert_eq!(format!("{:?}", value), expected);
+    }
+}
+
+#[cfg(all(test, windows))]
+#[test]
+fn propvalue_raw_errors() {
+    use crate::idltypes::propvariant::PropVariant;
+    use crate::Error;
+    use std::convert::TryFrom;
+
+    for &vt in &[VarType::VOID, VarType::PTR, VarType::RECORD, VarType(0x7ff)] {
+        let mut raw: PropVariant = unsafe { std::mem::zeroed() };
+        raw.pv.vartype = vt;
+        assert_eq!(
+            PropValue::try_from(&raw).unwrap_err(),
+            Error::DISP_BADVARTYPE
+        );
+    }
+
+    let mut raw: PropVariant = unsafe { std::mem::zeroed() };
+    raw.pv.vartype = VarType::VECTOR | VarType::DECIMAL;
+    assert_eq!(
+        PropValue::try_from(&raw).unwrap_err(),
+        Error::DISP_BADVARTYPE
+    );
+
+    let byref = PropValue::ByRef(Box::new(PropValue::I4(1)));
+    assert_eq!(byref.into_raw().unwrap_err(), Error::DISP_BADVARTYPE);
+    let mixed = PropValue::Vector(VarType::I4, vec![PropValue::I4(1), PropValue::I2(2)]);
+    assert_eq!(mixed.into_raw().unwrap_err(), Error::DISP_TYPEMISMATCH);
+}
@@ -2,7 +2,7 @@ use crate::idltypes::decimal::Decimal;
 use crate::idltypes::safearray::SafeArray;
 use crate::idltypes::vartype::VarType;
 use crate::minwindef::filetime::FileTime;
-use crate::GUID;
+use crate::{Error, Status, GUID};
 
 use std::ptr::NonNull;
 
@@ -25,6 +25,17 @@ impl PropVariant {
         unsafe { self.pv.vartype }
     }
 
+    /// Frees any memory and interfaces owned by the value and resets it to
+    /// `VarType::EMPTY`.
+    ///
+    /// # Safety
+    ///
+    /// The value must own its memory; borrowed BSTRs and interfaces would be
+    /// freed out from under their owner.
+    pub unsafe fn clear(&mut self) -> Result<Status, Error> {
+        Error::map_status(PropVariantClear(self))
+    }
+
     pub fn new_i8(val: i8) -> PropVariant {
         unsafe {
             let mut v: PropVariant = std::mem::zeroed();
@@ -299,3 +310,7 @@ pub struct CountedArray<T> {
     pub elements: u32,
     pub data: *mut T,
 }
+
+extern "system" {
+    fn PropVariantClear(pvar: *mut PropVariant) -> i32;
+}
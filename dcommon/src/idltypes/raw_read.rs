@@ -0,0 +1,37 @@
+//! Helpers shared by the `TryFrom` conversions of `PropValue` and
+//! `VariantValue` for reading the memory owned by a raw variant.
+
+use crate::idltypes::vartype::VarType;
+
+use winapi::um::unknwnbase::IUnknown;
+use wio::com::ComPtr;
+
+/// Takes a new reference to a possibly null interface pointer.
+pub(crate) unsafe fn add_ref<T>(ptr: *mut T) -> Option<ComPtr<T>>
+where
+    T: winapi::Interface,
+{
+    if ptr.is_null() {
+        None
+    } else {
+        (*(ptr as *mut IUnknown)).AddRef();
+        Some(ComPtr::from_raw(ptr))
+    }
+}
+
+/// The number of bytes a `BYREF` pointer to a `vt` value refers to, for the
+/// types that are copied into the data field of a temporary by-value variant.
+/// `VARIANT` and `DECIMAL` references don't fit there and return `None`, as do
+/// types that can't be referenced at all.
+pub(crate) fn byref_size(vt: VarType) -> Option<usize> {
+    Some(match vt {
+        VarType::I1 | VarType::UI1 => 1,
+        VarType::I2 | VarType::UI2 | VarType::BOOL => 2,
+        VarType::I4 | VarType::UI4 | VarType::INT | VarType::UINT => 4,
+        VarType::R4 | VarType::ERROR => 4,
+        VarType::I8 | VarType::UI8 | VarType::R8 | VarType::CY | VarType::DATE => 8,
+        VarType::BSTR | VarType::UNKNOWN | VarType::DISPATCH => std::mem::size_of::<usize>(),
+        _ if vt.container_type() == VarType::ARRAY => std::mem::size_of::<usize>(),
+        _ => return None,
+    })
+}
@@ -1,12 +1,33 @@
+//! SAFEARRAY descriptors and an owned, typed wrapper around them.
+//!
+//! On Windows the functions here are the ones exported by oleaut32, so arrays
+//! can be handed to and received from any COM API. Other targets get a
+//! pure-Rust implementation of the same functions that produces the same
+//! memory layout, including the hidden `VARTYPE` stored in front of the
+//! descriptor.
+
+use crate::idltypes::decimal::Decimal;
 use crate::idltypes::vartype::VarType;
 use crate::{Error, Status};
 
+use std::convert::TryFrom;
 use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::os::raw::c_void;
+use std::ptr::NonNull;
+
+#[cfg(windows)]
+use self::oleaut32 as sys;
+#[cfg(not(windows))]
+use self::portable as sys;
 
-use winapi::ctypes::c_void;
+#[cfg(not(windows))]
+mod portable;
 
 #[repr(C)]
 #[derive(Copy, Clone)]
+/// The header of a SAFEARRAY. `bounds` is followed by `dims - 1` more
+/// bounds, stored in the reverse of the order they were passed to `create`.
 pub struct SafeArray {
     pub dims: u16,
     pub features: u16,
@@ -17,13 +38,71 @@ pub struct SafeArray {
     _rest: PhantomData<SafeArrayBound>,
 }
 
+// The functions here are thin wrappers over the oleaut32 calls, with the same
+// requirements: `array` must point to a valid SAFEARRAY, and `T` must be the
+// type of its elements.
+#[allow(clippy::missing_safety_doc)]
 impl SafeArray {
+    /// The array is allocated on the stack.
+    pub const FADF_AUTO: u16 = 0x0001;
+    /// The array is statically allocated.
+    pub const FADF_STATIC: u16 = 0x0002;
+    /// The array is embedded in a structure.
+    pub const FADF_EMBEDDED: u16 = 0x0004;
+    /// The array may not be resized or reallocated.
+    pub const FADF_FIXEDSIZE: u16 = 0x0010;
+    /// The array contains records.
+    pub const FADF_RECORD: u16 = 0x0020;
+    /// An interface identifier is stored in front of the descriptor.
+    pub const FADF_HAVEIID: u16 = 0x0040;
+    /// A `VARTYPE` is stored in front of the descriptor.
+    pub const FADF_HAVEVARTYPE: u16 = 0x0080;
+    /// The array contains BSTRs.
+    pub const FADF_BSTR: u16 = 0x0100;
+    /// The array contains IUnknown pointers.
+    pub const FADF_UNKNOWN: u16 = 0x0200;
+    /// The array contains IDispatch pointers.
+    pub const FADF_DISPATCH: u16 = 0x0400;
+    /// The array contains VARIANTs.
+    pub const FADF_VARIANT: u16 = 0x0800;
+
     pub unsafe fn create(vt: VarType, dims: &[SafeArrayBound]) -> *mut SafeArray {
-        SafeArrayCreate(vt, dims.len() as u32, dims.as_ptr() as _)
+        sys::SafeArrayCreate(vt, dims.len() as u32, dims.as_ptr() as _)
     }
 
     pub unsafe fn destroy(array: *mut SafeArray) -> Result<Status, Error> {
-        Error::map_status(SafeArrayDestroy(array))
+        Error::map_status(sys::SafeArrayDestroy(array))
+    }
+
+    /// Makes a deep copy of the array and its data.
+    pub unsafe fn copy(array: *const SafeArray) -> Result<*mut SafeArray, Error> {
+        let mut copy = std::ptr::null_mut();
+        let hr = sys::SafeArrayCopy(array, &mut copy);
+        Error::map(hr, copy)
+    }
+
+    /// The type of the elements, if the array records it.
+    pub unsafe fn vartype(array: *const SafeArray) -> Result<VarType, Error> {
+        let mut vt = VarType::EMPTY;
+        let hr = sys::SafeArrayGetVartype(array, &mut vt);
+        Error::map(hr, vt)
+    }
+
+    /// The bounds of each dimension, in the order they were passed to
+    /// `create`.
+    pub unsafe fn dim_bounds<'a>(
+        array: *const SafeArray,
+    ) -> impl Iterator<Item = SafeArrayBound> + 'a {
+        let array = &*array;
+        let bounds = std::slice::from_raw_parts(array.bounds.as_ptr(), array.dims as usize);
+        bounds.iter().rev().cloned()
+    }
+
+    /// The total number of elements in all dimensions, or None if it
+    /// doesn't fit in a `usize`.
+    pub unsafe fn element_count(array: *const SafeArray) -> Option<usize> {
+        SafeArray::dim_bounds(array)
+            .try_fold(1usize, |count, b| count.checked_mul(b.elements as usize))
     }
 
     pub unsafe fn get_elem<T>(array: *mut SafeArray, index: &[i32]) -> Result<T, Error>
@@ -42,7 +121,7 @@ impl SafeArray {
         );
         let mut data = std::mem::zeroed();
         let p = (&mut data) as *mut T as *mut c_void;
-        let hr = SafeArrayGetElement(array, index.as_ptr(), p);
+        let hr = sys::SafeArrayGetElement(array, index.as_ptr(), p);
         Error::map(hr, data)
     }
 
@@ -64,16 +143,16 @@ impl SafeArray {
             std::mem::size_of::<T>(),
             "T is the wrong size"
         );
-        let hr = SafeArrayPutElement(array, index.as_ptr(), data as *const T as *const c_void);
+        let hr = sys::SafeArrayPutElement(array, index.as_ptr(), data as *const T as *const c_void);
         Error::map_status(hr)
     }
 
     pub unsafe fn lock(array: *mut SafeArray) -> Result<Status, Error> {
-        Error::map_status(SafeArrayLock(array))
+        Error::map_status(sys::SafeArrayLock(array))
     }
 
     pub unsafe fn unlock(array: *mut SafeArray) -> Result<Status, Error> {
-        Error::map_status(SafeArrayUnlock(array))
+        Error::map_status(sys::SafeArrayUnlock(array))
     }
 
     pub unsafe fn slice<'a, T>(array: *mut SafeArray, len: usize) -> &'a [T]
@@ -107,12 +186,331 @@ impl SafeArray {
         T: Copy,
     {
         let mut data = std::ptr::null_mut();
-        let hr = SafeArrayAccessData(array, &mut data);
+        let hr = sys::SafeArrayAccessData(array, &mut data);
         Error::map(hr, data as *mut T)
     }
 
     pub unsafe fn unaccess(array: *mut SafeArray) -> Result<Status, Error> {
-        Error::map_status(SafeArrayUnaccessData(array))
+        Error::map_status(sys::SafeArrayUnaccessData(array))
+    }
+}
+
+/// An owned SAFEARRAY which is destroyed when dropped.
+#[repr(transparent)]
+pub struct OwnedSafeArray {
+    ptr: NonNull<SafeArray>,
+}
+
+impl OwnedSafeArray {
+    /// Takes ownership of a raw array. Panics if `array` is null.
+    ///
+    /// # Safety
+    ///
+    /// `array` must be a valid SAFEARRAY that nothing else owns; it is
+    /// destroyed when the `OwnedSafeArray` is dropped.
+    pub unsafe fn from_raw(array: *mut SafeArray) -> OwnedSafeArray {
+        OwnedSafeArray {
+            ptr: NonNull::new(array).expect("SAFEARRAY pointer must not be null"),
+        }
+    }
+
+    /// Makes a deep copy of an array owned by someone else.
+    ///
+    /// # Safety
+    ///
+    /// `array` must point to a valid SAFEARRAY.
+    pub unsafe fn copy_from(array: *const SafeArray) -> Result<OwnedSafeArray, Error> {
+        SafeArray::copy(array).map(|copy| OwnedSafeArray::from_raw(copy))
+    }
+
+    pub fn as_ptr(&self) -> *mut SafeArray {
+        self.ptr.as_ptr()
+    }
+
+    pub fn into_raw(self) -> *mut SafeArray {
+        let ptr = self.as_ptr();
+        std::mem::forget(self);
+        ptr
+    }
+
+    /// The type of the elements, if the array records it.
+    pub fn vartype(&self) -> Result<VarType, Error> {
+        unsafe { SafeArray::vartype(self.as_ptr()) }
+    }
+
+    /// The bounds of each dimension, in the order they were passed to
+    /// `create`.
+    pub fn bounds(&self) -> Vec<SafeArrayBound> {
+        unsafe { SafeArray::dim_bounds(self.as_ptr()).collect() }
+    }
+}
+
+impl Clone for OwnedSafeArray {
+    fn clone(&self) -> Self {
+        unsafe { OwnedSafeArray::copy_from(self.as_ptr()).expect("failed to copy SAFEARRAY") }
+    }
+}
+
+impl Drop for OwnedSafeArray {
+    fn drop(&mut self) {
+        unsafe {
+            SafeArray::destroy(self.as_ptr()).ok();
+        }
+    }
+}
+
+impl std::fmt::Debug for OwnedSafeArray {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let element_size = unsafe { (*self.as_ptr()).element_size };
+        fmt.debug_struct("OwnedSafeArray")
+            .field("element_size", &element_size)
+            .field("bounds", &self.bounds())
+            .finish()
+    }
+}
+
+/// Element types that can be stored in a `TypedSafeArray`. They must be
+/// plain data with the size and layout of `VARTYPE`, since the array's
+/// memory is copied and freed without running any destructor.
+///
+/// # Safety
+///
+/// `VARTYPE` must be the variant type whose elements have the size and
+/// layout of `Self`.
+pub unsafe trait SafeArrayElement: Copy {
+    const VARTYPE: VarType;
+}
+
+macro_rules! safearray_element {
+    ($($ty:ty => $vt:ident,)*) => {$(
+        unsafe impl SafeArrayElement for $ty {
+            const VARTYPE: VarType = VarType::$vt;
+        }
+    )*};
+}
+
+safearray_element! {
+    i8 => I1,
+    u8 => UI1,
+    i16 => I2,
+    u16 => UI2,
+    i32 => I4,
+    u32 => UI4,
+    i64 => I8,
+    u64 => UI8,
+    f32 => R4,
+    f64 => R8,
+    Decimal => DECIMAL,
+}
+
+/// An owned, multi-dimensional SAFEARRAY of `T`.
+///
+/// Elements are stored with the first dimension varying fastest, so for a
+/// 2x3 array `as_slice` yields `[0,0], [1,0], [0,1], [1,1], [0,2], [1,2]`.
+/// Indices passed to `get` and `set` include the lower bound of each
+/// dimension.
+#[repr(transparent)]
+pub struct TypedSafeArray<T: SafeArrayElement> {
+    array: OwnedSafeArray,
+    _marker: PhantomData<T>,
+}
+
+impl<T: SafeArrayElement> TypedSafeArray<T> {
+    /// Creates a zero-filled array with the given dimensions.
+    pub fn new(dims: &[SafeArrayBound]) -> Result<TypedSafeArray<T>, Error> {
+        if dims.is_empty() {
+            return Err(Error::INVALIDARG);
+        }
+        unsafe {
+            let ptr = SafeArray::create(T::VARTYPE, dims);
+            if ptr.is_null() {
+                return Err(Error::OUTOFMEMORY);
+            }
+            Ok(TypedSafeArray {
+                array: OwnedSafeArray::from_raw(ptr),
+                _marker: PhantomData,
+            })
+        }
+    }
+
+    /// Creates a one-dimensional array holding a copy of `data`.
+    pub fn from_slice(data: &[T]) -> Result<TypedSafeArray<T>, Error> {
+        let mut array = TypedSafeArray::new(&[SafeArrayBound::new(data.len() as u32)])?;
+        array.as_mut_slice().copy_from_slice(data);
+        Ok(array)
+    }
+
+    /// Takes ownership of a raw array. Fails with `Error::DISP_BADVARTYPE`
+    /// if the array's elements are not `T`.
+    ///
+    /// # Safety
+    ///
+    /// Same as `OwnedSafeArray::from_raw`.
+    pub unsafe fn from_raw(array: *mut SafeArray) -> Result<TypedSafeArray<T>, Error> {
+        TypedSafeArray::try_from(OwnedSafeArray::from_raw(array))
+    }
+
+    pub fn as_ptr(&self) -> *mut SafeArray {
+        self.array.as_ptr()
+    }
+
+    pub fn into_raw(self) -> *mut SafeArray {
+        self.array.into_raw()
+    }
+
+    /// Releases the element type, e.g. to store the array in a variant.
+    pub fn into_untyped(self) -> OwnedSafeArray {
+        self.array
+    }
+
+    /// The bounds of each dimension, in the order they were passed to `new`.
+    pub fn bounds(&self) -> Vec<SafeArrayBound> {
+        self.array.bounds()
+    }
+
+    /// The total number of elements.
+    pub fn len(&self) -> usize {
+        // Arrays too large to count can't have been allocated.
+        unsafe { SafeArray::element_count(self.as_ptr()).unwrap_or(0) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reads an element, returning None if `index` is out of bounds or has
+    /// the wrong number of dimensions.
+    pub fn get(&self, index: &[i32]) -> Option<T> {
+        self.offset(index).map(|i| self.as_slice()[i])
+    }
+
+    /// Writes an element. Fails with `Error::DISP_BADINDEX` if `index` is
+    /// out of bounds or has the wrong number of dimensions.
+    pub fn set(&mut self, index: &[i32], value: T) -> Result<(), Error> {
+        let i = self.offset(index).ok_or(Error::DISP_BADINDEX)?;
+        self.as_mut_slice()[i] = value;
+        Ok(())
+    }
+
+    /// All elements, first dimension varying fastest.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice_or_empty(self.as_ptr(), self.len()) }
+    }
+
+    /// All elements, first dimension varying fastest.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe {
+            let len = self.len();
+            if len == 0 {
+                return &mut [];
+            }
+            SafeArray::slice_mut(self.as_ptr(), len)
+        }
+    }
+
+    /// Locks the array for the lifetime of the guard, the way
+    /// `SafeArrayAccessData` does. A locked array can't be destroyed, so
+    /// this is how to keep the data pinned while a raw pointer to it is
+    /// handed out.
+    pub fn lock(&mut self) -> Result<SafeArrayGuard<'_, T>, Error> {
+        unsafe { SafeArray::lock(self.as_ptr())? };
+        Ok(SafeArrayGuard { array: self })
+    }
+
+    fn offset(&self, index: &[i32]) -> Option<usize> {
+        let bounds = self.bounds();
+        if index.len() != bounds.len() {
+            return None;
+        }
+        let mut offset = 0;
+        let mut stride = 1;
+        for (&i, b) in index.iter().zip(&bounds) {
+            let i = i64::from(i) - i64::from(b.lbound);
+            if i < 0 || i >= i64::from(b.elements) {
+                return None;
+            }
+            offset += i as usize * stride;
+            stride *= b.elements as usize;
+        }
+        Some(offset)
+    }
+}
+
+unsafe fn slice_or_empty<'a, T: Copy>(array: *mut SafeArray, len: usize) -> &'a [T] {
+    if len == 0 {
+        &[]
+    } else {
+        SafeArray::slice(array, len)
+    }
+}
+
+impl<T: SafeArrayElement> TryFrom<OwnedSafeArray> for TypedSafeArray<T> {
+    type Error = Error;
+
+    /// Fails with `Error::DISP_BADVARTYPE` if the array's elements are not
+    /// `T`.
+    fn try_from(array: OwnedSafeArray) -> Result<TypedSafeArray<T>, Error> {
+        let header = unsafe { &*array.as_ptr() };
+        let vt = array.vartype().map_err(|_| Error::DISP_BADVARTYPE)?;
+        if vt != T::VARTYPE || header.element_size as usize != std::mem::size_of::<T>() {
+            return Err(Error::DISP_BADVARTYPE);
+        }
+        Ok(TypedSafeArray {
+            array,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T: SafeArrayElement> From<TypedSafeArray<T>> for OwnedSafeArray {
+    fn from(array: TypedSafeArray<T>) -> OwnedSafeArray {
+        array.into_untyped()
+    }
+}
+
+impl<T: SafeArrayElement> Clone for TypedSafeArray<T> {
+    fn clone(&self) -> Self {
+        TypedSafeArray {
+            array: self.array.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: SafeArrayElement + std::fmt::Debug> std::fmt::Debug for TypedSafeArray<T> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("TypedSafeArray")
+            .field("bounds", &self.bounds())
+            .field("data", &self.as_slice())
+            .finish()
+    }
+}
+
+/// A lock on a `TypedSafeArray`, released when dropped. Derefs to the
+/// elements.
+pub struct SafeArrayGuard<'a, T: SafeArrayElement> {
+    array: &'a mut TypedSafeArray<T>,
+}
+
+impl<'a, T: SafeArrayElement> Deref for SafeArrayGuard<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.array.as_slice()
+    }
+}
+
+impl<'a, T: SafeArrayElement> DerefMut for SafeArrayGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.array.as_mut_slice()
+    }
+}
+
+impl<'a, T: SafeArrayElement> Drop for SafeArrayGuard<'a, T> {
+    fn drop(&mut self) {
+        unsafe {
+            SafeArray::unlock(self.array.as_ptr()).ok();
+        }
     }
 }
 
@@ -120,7 +518,7 @@ impl SafeArray {
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct SafeArrayBound {
     pub elements: u32,
-    pub lbound: u32,
+    pub lbound: i32,
 }
 
 impl SafeArrayBound {
@@ -130,21 +528,51 @@ impl SafeArrayBound {
             lbound: 0,
         }
     }
+
+    /// A dimension of `c` elements whose first index is `lbound`.
+    pub fn with_lbound(c: u32, lbound: i32) -> SafeArrayBound {
+        SafeArrayBound {
+            elements: c,
+            lbound,
+        }
+    }
 }
 
-// The import lib is already added from winapi
-extern "system" {
-    fn SafeArrayCreate(vt: VarType, dims: u32, bounds: *const SafeArrayBound) -> *mut SafeArray;
-    fn SafeArrayDestroy(array: *mut SafeArray) -> i32;
+#[cfg(windows)]
+#[allow(non_snake_case)]
+mod oleaut32 {
+    use super::{SafeArray, SafeArrayBound};
+    use crate::idltypes::vartype::VarType;
+    use std::os::raw::c_void;
+
+    // The import lib is already added from winapi
+    extern "system" {
+        pub fn SafeArrayCreate(
+            vt: VarType,
+            dims: u32,
+            bounds: *const SafeArrayBound,
+        ) -> *mut SafeArray;
+        pub fn SafeArrayDestroy(array: *mut SafeArray) -> i32;
+        pub fn SafeArrayCopy(array: *const SafeArray, out: *mut *mut SafeArray) -> i32;
+        pub fn SafeArrayGetVartype(array: *const SafeArray, vt: *mut VarType) -> i32;
 
-    fn SafeArrayGetElement(array: *mut SafeArray, indices: *const i32, data: *mut c_void) -> i32;
-    fn SafeArrayPutElement(array: *mut SafeArray, indices: *const i32, data: *const c_void) -> i32;
+        pub fn SafeArrayGetElement(
+            array: *mut SafeArray,
+            indices: *const i32,
+            data: *mut c_void,
+        ) -> i32;
+        pub fn SafeArrayPutElement(
+            array: *mut SafeArray,
+            indices: *const i32,
+            data: *const c_void,
+        ) -> i32;
 
-    fn SafeArrayLock(array: *mut SafeArray) -> i32;
-    fn SafeArrayUnlock(array: *mut SafeArray) -> i32;
+        pub fn SafeArrayLock(array: *mut SafeArray) -> i32;
+        pub fn SafeArrayUnlock(array: *mut SafeArray) -> i32;
 
-    fn SafeArrayAccessData(array: *mut SafeArray, data: *mut *mut c_void) -> i32;
-    fn SafeArrayUnaccessData(array: *mut SafeArray) -> i32;
+        pub fn SafeArrayAccessData(array: *mut SafeArray, data: *mut *mut c_void) -> i32;
+        pub fn SafeArrayUnaccessData(array: *mut SafeArray) -> i32;
+    }
 }
 
 #[cfg(test)]
@@ -188,3 +616,69 @@ fn basic_safearray_use() {
         );
     }
 }
+
+#[cfg(test)]
+#[test]
+fn typed_safearray_use() {
+    let dims = [SafeArrayBound::new(2), SafeArrayBound::with_lbound(3, -1)];
+    let mut ary = TypedSafeArray::<f64>::new(&dims).unwrap();
+
+    assert_eq!(ary.len(), 6);
+    assert_eq!(ary.bounds(), dims);
+    assert_eq!(ary.set(&[1, 1], 2.5), Ok(()));
+    assert_eq!(ary.set(&[1, 2], 1.0), Err(Error::DISP_BADINDEX));
+    assert_eq!(ary.get(&[1, 1]), Some(2.5));
+    assert_eq!(ary.as_slice()[5], 2.5);
+
+    unsafe {
+        let raw = ary.as_ptr();
+        assert_eq!(SafeArray::vartype(raw), Ok(VarType::R8));
+        assert_eq!(SafeArray::get_elem::<f64>(raw, &[1, 1]), Ok(2.5));
+
+        // SAFEARRAY stores the bounds of the last dimension first.
+        assert_eq!((*raw).bounds[0], dims[1]);
+    }
+
+    let raw = ary.as_ptr();
+    {
+        let mut guard = ary.lock().unwrap();
+        guard[0] = 4.0;
+        unsafe {
+            assert_eq!((*raw).locks, 1);
+            assert_eq!(SafeArray::destroy(raw), Err(Error::DISP_ARRAYISLOCKED));
+        }
+    }
+    assert_eq!(unsafe { (*raw).locks }, 0);
+    assert_eq!(ary.get(&[0, -1]), Some(4.0));
+
+    let raw = ary.into_untyped();
+    assert!(TypedSafeArray::<i64>::try_from(raw.clone()).is_err());
+    let ary = TypedSafeArray::<f64>::try_from(raw).unwrap();
+    assert_eq!(ary.get(&[0, -1]), Some(4.0));
+}
+
+#[cfg(test)]
+#[test]
+fn huge_safearray() {
+    // The element count overflows a usize.
+    let dims = [SafeArrayBound::new(65536); 4];
+    assert_eq!(
+        TypedSafeArray::<f64>::new(&dims).err(),
+        Some(Error::OUTOFMEMORY)
+    );
+    unsafe {
+        let ary = SafeArray::create(VarType::R8, &dims);
+        assert!(ary.is_null());
+    }
+
+    // The count fits, but its size in bytes doesn't.
+    let dims = [SafeArrayBound::new(u32::MAX); 2];
+    assert_eq!(
+        TypedSafeArray::<f64>::new(&dims).err(),
+        Some(Error::OUTOFMEMORY)
+    );
+
+    let empty = TypedSafeArray::<f64>::new(&[SafeArrayBound::new(0)]).unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(empty.get(&[0]), None);
+}
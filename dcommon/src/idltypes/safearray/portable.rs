@@ -0,0 +1,302 @@
+//! The subset of the oleaut32 SAFEARRAY functions used by `SafeArray`,
+//! implemented in Rust for targets without oleaut32.
+//!
+//! Arrays are laid out the way oleaut32 lays them out: 16 bytes reserved in
+//! front of the descriptor, with the `VARTYPE` in the last 4 of them, then
+//! the descriptor with its bounds in reverse order. Only arrays of plain data
+//! are supported, since there is no COM to release BSTRs, interfaces or
+//! VARIANTs with. Arrays must have been created by these functions.
+
+#![allow(non_snake_case)]
+
+use super::{SafeArray, SafeArrayBound};
+use crate::idltypes::vartype::VarType;
+use crate::{Error, Status};
+
+use std::alloc::{alloc_zeroed, dealloc, Layout};
+use std::os::raw::c_void;
+
+/// Space reserved in front of the descriptor for the `VARTYPE` and IID.
+const PREFIX: usize = 16;
+const ALIGN: usize = 16;
+
+const NOT_OWNED: u16 = SafeArray::FADF_AUTO | SafeArray::FADF_STATIC | SafeArray::FADF_EMBEDDED;
+
+fn element_size(vt: VarType) -> Option<u32> {
+    Some(match vt {
+        VarType::I1 | VarType::UI1 => 1,
+        VarType::I2 | VarType::UI2 | VarType::BOOL => 2,
+        VarType::I4 | VarType::UI4 | VarType::INT | VarType::UINT => 4,
+        VarType::R4 | VarType::ERROR => 4,
+        VarType::I8 | VarType::UI8 | VarType::R8 | VarType::CY | VarType::DATE => 8,
+        VarType::DECIMAL => 16,
+        _ => return None,
+    })
+}
+
+fn descriptor_layout(dims: u16) -> Layout {
+    let size = PREFIX
+        + std::mem::size_of::<SafeArray>()
+        + (usize::from(dims) - 1) * std::mem::size_of::<SafeArrayBound>();
+    Layout::from_size_align(size, ALIGN).unwrap()
+}
+
+/// The layout of the element storage, `Ok(None)` if the array has no
+/// elements, or `Error::OUTOFMEMORY` if its size doesn't fit in memory.
+unsafe fn data_layout(array: *const SafeArray) -> Result<Option<Layout>, Error> {
+    let size = SafeArray::element_count(array)
+        .and_then(|count| count.checked_mul((*array).element_size as usize))
+        .ok_or(Error::OUTOFMEMORY)?;
+    if size == 0 {
+        return Ok(None);
+    }
+    match Layout::from_size_align(size, ALIGN) {
+        Ok(layout) => Ok(Some(layout)),
+        Err(_) => Err(Error::OUTOFMEMORY),
+    }
+}
+
+unsafe fn bounds<'a>(array: *const SafeArray) -> &'a [SafeArrayBound] {
+    std::slice::from_raw_parts((*array).bounds.as_ptr(), usize::from((*array).dims))
+}
+
+/// Allocates a zeroed descriptor with room for `dims` bounds.
+unsafe fn alloc_descriptor(dims: u16) -> *mut SafeArray {
+    let base = alloc_zeroed(descriptor_layout(dims));
+    if base.is_null() {
+        return std::ptr::null_mut();
+    }
+    let array = base.add(PREFIX) as *mut SafeArray;
+    (*array).dims = dims;
+    array
+}
+
+unsafe fn free_descriptor(array: *mut SafeArray) {
+    let base = (array as *mut u8).sub(PREFIX);
+    dealloc(base, descriptor_layout((*array).dims));
+}
+
+unsafe fn vartype_slot(array: *const SafeArray) -> *mut u32 {
+    (array as *mut u8).sub(4) as *mut u32
+}
+
+/// Allocates zeroed storage for the elements described by `array`.
+unsafe fn alloc_data(array: *mut SafeArray) -> Result<(), Error> {
+    (*array).data = std::ptr::null_mut();
+    if let Some(layout) = data_layout(array)? {
+        (*array).data = alloc_zeroed(layout) as *mut c_void;
+        if (*array).data.is_null() {
+            return Err(Error::OUTOFMEMORY);
+        }
+    }
+    Ok(())
+}
+
+/// The offset of an element, or None if an index is out of bounds.
+/// `indices[0]` belongs to the last bound stored in the descriptor and
+/// varies fastest.
+unsafe fn cell_offset(array: *const SafeArray, indices: *const i32) -> Option<usize> {
+    let bounds = bounds(array);
+    let indices = std::slice::from_raw_parts(indices, bounds.len());
+    let mut cell = 0;
+    let mut stride = 1;
+    for (&index, bound) in indices.iter().zip(bounds.iter().rev()) {
+        let i = i64::from(index) - i64::from(bound.lbound);
+        if i < 0 || i >= i64::from(bound.elements) {
+            return None;
+        }
+        cell += i as usize * stride;
+        stride *= bound.elements as usize;
+    }
+    Some(cell * (*array).element_size as usize)
+}
+
+pub unsafe fn SafeArrayCreate(
+    vt: VarType,
+    dims: u32,
+    bounds: *const SafeArrayBound,
+) -> *mut SafeArray {
+    let size = match element_size(vt) {
+        Some(size) => size,
+        None => return std::ptr::null_mut(),
+    };
+    if dims == 0 || dims > u32::from(u16::MAX) || bounds.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    let array = alloc_descriptor(dims as u16);
+    if array.is_null() {
+        return array;
+    }
+    (*array).features = SafeArray::FADF_HAVEVARTYPE;
+    (*array).element_size = size;
+    *vartype_slot(array) = u32::from(vt.0);
+
+    // The descriptor stores the bounds in reverse order.
+    let src = std::slice::from_raw_parts(bounds, dims as usize);
+    let dst = std::slice::from_raw_parts_mut((*array).bounds.as_mut_ptr(), dims as usize);
+    for (d, s) in dst.iter_mut().zip(src.iter().rev()) {
+        *d = *s;
+    }
+
+    if alloc_data(array).is_err() {
+        free_descriptor(array);
+        return std::ptr::null_mut();
+    }
+    array
+}
+
+pub unsafe fn SafeArrayDestroy(array: *mut SafeArray) -> i32 {
+    if array.is_null() {
+        return Status::OK.0;
+    }
+    if (*array).locks > 0 {
+        return Error::DISP_ARRAYISLOCKED.0;
+    }
+    if (*array).features & NOT_OWNED == 0 {
+        if let Ok(Some(layout)) = data_layout(array) {
+            dealloc((*array).data as *mut u8, layout);
+        }
+        free_descriptor(array);
+    }
+    Status::OK.0
+}
+
+pub unsafe fn SafeArrayCopy(array: *const SafeArray, out: *mut *mut SafeArray) -> i32 {
+    if out.is_null() {
+        return Error::INVALIDARG.0;
+    }
+    *out = std::ptr::null_mut();
+    if array.is_null() {
+        return Status::OK.0;
+    }
+
+    let copy = alloc_descriptor((*array).dims);
+    if copy.is_null() {
+        return Error::OUTOFMEMORY.0;
+    }
+    let size = descriptor_layout((*array).dims).size();
+    std::ptr::copy_nonoverlapping(
+        (array as *const u8).sub(PREFIX),
+        (copy as *mut u8).sub(PREFIX),
+        size,
+    );
+    (*copy).locks = 0;
+    (*copy).features &= !NOT_OWNED;
+
+    if let Err(error) = alloc_data(copy) {
+        free_descriptor(copy);
+        return error.0;
+    }
+    if let Ok(Some(layout)) = data_layout(array) {
+        std::ptr::copy_nonoverlapping(
+            (*array).data as *const u8,
+            (*copy).data as *mut u8,
+            layout.size(),
+        );
+    }
+    *out = copy;
+    Status::OK.0
+}
+
+pub unsafe fn SafeArrayGetVartype(array: *const SafeArray, vt: *mut VarType) -> i32 {
+    if array.is_null() || vt.is_null() {
+        return Error::INVALIDARG.0;
+    }
+    let features = (*array).features;
+    let found = if features & SafeArray::FADF_HAVEVARTYPE != 0 {
+        VarType(*vartype_slot(array) as u16)
+    } else if features & SafeArray::FADF_BSTR != 0 {
+        VarType::BSTR
+    } else if features & SafeArray::FADF_UNKNOWN != 0 {
+        VarType::UNKNOWN
+    } else if features & SafeArray::FADF_DISPATCH != 0 {
+        VarType::DISPATCH
+    } else if features & SafeArray::FADF_VARIANT != 0 {
+        VarType::VARIANT
+    } else {
+        *vt = VarType::EMPTY;
+        return Error::INVALIDARG.0;
+    };
+    *vt = found;
+    Status::OK.0
+}
+
+pub unsafe fn SafeArrayGetElement(
+    array: *mut SafeArray,
+    indices: *const i32,
+    data: *mut c_void,
+) -> i32 {
+    if array.is_null() || indices.is_null() || data.is_null() {
+        return Error::INVALIDARG.0;
+    }
+    match cell_offset(array, indices) {
+        Some(offset) => {
+            let src = ((*array).data as *const u8).add(offset);
+            std::ptr::copy_nonoverlapping(src, data as *mut u8, (*array).element_size as usize);
+            Status::OK.0
+        }
+        None => Error::DISP_BADINDEX.0,
+    }
+}
+
+pub unsafe fn SafeArrayPutElement(
+    array: *mut SafeArray,
+    indices: *const i32,
+    data: *const c_void,
+) -> i32 {
+    if array.is_null() || indices.is_null() || data.is_null() {
+        return Error::INVALIDARG.0;
+    }
+    match cell_offset(array, indices) {
+        Some(offset) => {
+            let dst = ((*array).data as *mut u8).add(offset);
+            std::ptr::copy_nonoverlapping(data as *const u8, dst, (*array).element_size as usize);
+            Status::OK.0
+        }
+        None => Error::DISP_BADINDEX.0,
+    }
+}
+
+pub unsafe fn SafeArrayLock(array: *mut SafeArray) -> i32 {
+    if array.is_null() {
+        return Error::INVALIDARG.0;
+    }
+    match (*array).locks.checked_add(1) {
+        Some(locks) => {
+            (*array).locks = locks;
+            Status::OK.0
+        }
+        None => Error::UNEXPECTED.0,
+    }
+}
+
+pub unsafe fn SafeArrayUnlock(array: *mut SafeArray) -> i32 {
+    if array.is_null() {
+        return Error::INVALIDARG.0;
+    }
+    match (*array).locks.checked_sub(1) {
+        Some(locks) => {
+            (*array).locks = locks;
+            Status::OK.0
+        }
+        None => Error::UNEXPECTED.0,
+    }
+}
+
+pub unsafe fn SafeArrayAccessData(array: *mut SafeArray, data: *mut *mut c_void) -> i32 {
+    if data.is_null() {
+        return Error::INVALIDARG.0;
+    }
+    let hr = SafeArrayLock(array);
+    *data = if hr < 0 {
+        std::ptr::null_mut()
+    } else {
+        (*array).data
+    };
+    hr
+}
+
+pub unsafe fn SafeArrayUnaccessData(array: *mut SafeArray) -> i32 {
+    SafeArrayUnlock(array)
+}
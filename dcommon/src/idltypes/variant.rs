@@ -1,10 +1,24 @@
+use crate::idltypes::bstr::{BStr, BSTR};
 use crate::idltypes::{decimal::Decimal, safearray::SafeArray, vartype::VarType};
 
+#[cfg(not(windows))]
+use std::os::raw::c_void;
+#[cfg(windows)]
 use winapi::ctypes::c_void;
-use winapi::shared::wtypes::BSTR;
+#[cfg(windows)]
 use winapi::um::oaidl::IDispatch;
+#[cfg(windows)]
 use winapi::um::unknwnbase::IUnknown;
 
+/// An opaque stand-in for the interface off Windows, where interface
+/// pointers in a variant can be carried around but not called.
+#[cfg(not(windows))]
+pub enum IUnknown {}
+
+/// An opaque stand-in for the interface off Windows, like `IUnknown`.
+#[cfg(not(windows))]
+pub enum IDispatch {}
+
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub union Variant {
@@ -106,6 +120,26 @@ impl Variant {
             v
         }
     }
+
+    pub fn new_bool(val: bool) -> Variant {
+        unsafe {
+            let mut v: Variant = std::mem::zeroed();
+            v.v.vartype = VarType::BOOL;
+            v.v.data.bool_val = if val { -1 } else { 0 };
+            v
+        }
+    }
+
+    /// Takes ownership of the string. The variant must be freed with
+    /// `VariantClear` to release it.
+    pub fn new_bstr(val: BStr) -> Variant {
+        unsafe {
+            let mut v: Variant = std::mem::zeroed();
+            v.v.vartype = VarType::BSTR;
+            v.v.data.bstr_val = val.into_raw();
+            v
+        }
+    }
 }
 
 #[repr(C)]
@@ -208,3 +242,50 @@ pub union InnerVariantData {
     /// VarType::BYREF|UINT
     pub puint_val: *mut u32,
 }
+
+#[cfg(windows)]
+#[allow(non_snake_case)]
+pub(crate) mod oleaut32 {
+    use super::Variant;
+    use crate::idltypes::vartype::VarType;
+
+    // The import lib is already added from winapi
+    extern "system" {
+        pub fn VariantClear(pvar: *mut Variant) -> i32;
+        pub fn VariantCopy(dest: *mut Variant, src: *const Variant) -> i32;
+        pub fn VariantChangeType(
+            dest: *mut Variant,
+            src: *const Variant,
+            flags: u16,
+            vt: VarType,
+        ) -> i32;
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn variant_bstr() {
+    use crate::error::Error;
+
+    let v = Variant::new_bstr(BStr::from("héllo"));
+    assert_eq!(v.vartype(), VarType::BSTR);
+    unsafe {
+        let raw = v.v.data.bstr_val;
+        assert_eq!(BStr::read_raw(raw).unwrap(), "héllo");
+        assert_eq!(BStr::read_raw_lossy(raw), "héllo");
+        // Reading borrows the string; the variant still owns it.
+        drop(BStr::from_raw(raw));
+
+        assert_eq!(BStr::read_raw(std::ptr::null_mut()).unwrap(), "");
+        assert_eq!(BStr::read_raw_lossy(std::ptr::null_mut()), "");
+
+        // An unpaired surrogate.
+        let bad = BStr::from("ab");
+        *bad.get_raw().add(1) = 0xD800;
+        let v = Variant::new_bstr(bad);
+        let raw = v.v.data.bstr_val;
+        assert_eq!(BStr::read_raw(raw).unwrap_err(), Error::WIN32_INVALID_DATA);
+        assert_eq!(BStr::read_raw_lossy(raw), "a\u{FFFD}");
+        drop(BStr::from_raw(raw));
+    }
+}
@@ -0,0 +1,272 @@
+//! An owned, safe counterpart to `Variant`.
+
+use crate::error::Error;
+use crate::idltypes::coerce;
+use crate::idltypes::decimal::Decimal;
+use crate::idltypes::vartype::VarType;
+
+#[cfg(windows)]
+use crate::idltypes::safearray::OwnedSafeArray;
+#[cfg(windows)]
+use winapi::um::oaidl::IDispatch;
+#[cfg(windows)]
+use winapi::um::unknwnbase::IUnknown;
+#[cfg(windows)]
+use wio::com::ComPtr;
+
+/// An owned OLE Automation value. Unlike the raw `Variant` union it frees
+/// its own memory, and it can be converted between types with
+/// `change_type`, which follows the same rules as `VariantChangeType`.
+#[derive(Clone, Debug)]
+pub enum VariantValue {
+    /// VarType::EMPTY
+    Empty,
+    /// VarType::NULL
+    Null,
+    /// VarType::I1
+    I1(i8),
+    /// VarType::UI1
+    UI1(u8),
+    /// VarType::I2
+    I2(i16),
+    /// VarType::UI2
+    UI2(u16),
+    /// VarType::I4
+    I4(i32),
+    /// VarType::UI4
+    UI4(u32),
+    /// VarType::INT
+    Int(i32),
+    /// VarType::UINT
+    UInt(u32),
+    /// VarType::I8
+    I8(i64),
+    /// VarType::UI8
+    UI8(u64),
+    /// VarType::R4
+    R4(f32),
+    /// VarType::R8
+    R8(f64),
+    /// VarType::BOOL
+    Bool(bool),
+    /// VarType::ERROR
+    Error(i32),
+    /// VarType::CY, in units of 1/10000.
+    Currency(i64),
+    /// VarType::DATE, as an OLE Automation date.
+    Date(f64),
+    /// VarType::DECIMAL
+    Decimal(Decimal),
+    /// VarType::BSTR
+    BStr(String),
+    #[cfg(windows)]
+    /// VarType::UNKNOWN
+    Unknown(Option<ComPtr<IUnknown>>),
+    #[cfg(windows)]
+    /// VarType::DISPATCH
+    Dispatch(Option<ComPtr<IDispatch>>),
+    #[cfg(windows)]
+    /// VarType::ARRAY combined with the element type.
+    Array(VarType, OwnedSafeArray),
+}
+
+impl VariantValue {
+    /// The `VarType` of the value.
+    pub fn vartype(&self) -> VarType {
+        match self {
+            VariantValue::Empty => VarType::EMPTY,
+            VariantValue::Null => VarType::NULL,
+            VariantValue::I1(_) => VarType::I1,
+            VariantValue::UI1(_) => VarType::UI1,
+            VariantValue::I2(_) => VarType::I2,
+            VariantValue::UI2(_) => VarType::UI2,
+            VariantValue::I4(_) => VarType::I4,
+            VariantValue::UI4(_) => VarType::UI4,
+            VariantValue::Int(_) => VarType::INT,
+            VariantValue::UInt(_) => VarType::UINT,
+            VariantValue::I8(_) => VarType::I8,
+            VariantValue::UI8(_) => VarType::UI8,
+            VariantValue::R4(_) => VarType::R4,
+            VariantValue::R8(_) => VarType::R8,
+            VariantValue::Bool(_) => VarType::BOOL,
+            VariantValue::Error(_) => VarType::ERROR,
+            VariantValue::Currency(_) => VarType::CY,
+            VariantValue::Date(_) => VarType::DATE,
+            VariantValue::Decimal(_) => VarType::DECIMAL,
+            VariantValue::BStr(_) => VarType::BSTR,
+            #[cfg(windows)]
+            VariantValue::Unknown(_) => VarType::UNKNOWN,
+            #[cfg(windows)]
+            VariantValue::Dispatch(_) => VarType::DISPATCH,
+            #[cfg(windows)]
+            VariantValue::Array(vt, _) => VarType::ARRAY | *vt,
+        }
+    }
+
+    /// Converts the value to another type using the OLE Automation coercion
+    /// rules, with the invariant locale.
+    ///
+    /// Fails with `Error::DISP_TYPEMISMATCH` when there is no conversion
+    /// between the two types (or a string can't be parsed), with
+    /// `Error::DISP_OVERFLOW` when the value doesn't fit in the target type,
+    /// and with `Error::DISP_BADVARTYPE` when `vt` is not a scalar type.
+    pub fn change_type(&self, vt: VarType) -> Result<VariantValue, Error> {
+        coerce::change_type(self, vt)
+    }
+}
+
+#[cfg(windows)]
+mod raw {
+    use super::VariantValue;
+    use crate::error::Error;
+    use crate::idltypes::bstr::BStr;
+    use crate::idltypes::decimal::Decimal;
+    use crate::idltypes::raw_read::{add_ref, byref_size};
+    use crate::idltypes::safearray::OwnedSafeArray;
+    use crate::idltypes::variant::oleaut32::VariantClear;
+    use crate::idltypes::variant::{InnerVariantData, Variant};
+    use crate::idltypes::vartype::VarType;
+
+    use std::convert::TryFrom;
+
+    use wio::com::ComPtr;
+
+    impl VariantValue {
+        /// Reads the value and then frees the memory owned by `raw` with
+        /// `VariantClear`. `raw` is cleared even if it can't be represented
+        /// as a `VariantValue`.
+        ///
+        /// # Safety
+        ///
+        /// `raw` must be a valid `VARIANT` that owns its memory.
+        pub unsafe fn from_raw(mut raw: Variant) -> Result<VariantValue, Error> {
+            let value = VariantValue::try_from(&raw);
+            Error::map_status(VariantClear(&mut raw))?;
+            value
+        }
+
+        /// Converts the value into a raw `Variant` that owns its memory. The
+        /// caller is responsible for eventually calling `VariantClear`.
+        pub fn into_raw(self) -> Variant {
+            unsafe {
+                let mut raw: Variant = std::mem::zeroed();
+                raw.v.vartype = self.vartype();
+                let data = &mut raw.v.data;
+                match self {
+                    VariantValue::Empty | VariantValue::Null => {}
+                    VariantValue::I1(v) => data.c_val = v,
+                    VariantValue::UI1(v) => data.b_val = v,
+                    VariantValue::I2(v) => data.i_val = v,
+                    VariantValue::UI2(v) => data.ui_val = v,
+                    VariantValue::I4(v) => data.l_val = v,
+                    VariantValue::UI4(v) => data.ul_val = v,
+                    VariantValue::Int(v) => data.int_val = v,
+                    VariantValue::UInt(v) => data.uint_val = v,
+                    VariantValue::I8(v) => data.ll_val = v,
+                    VariantValue::UI8(v) => data.ull_val = v,
+                    VariantValue::R4(v) => data.flt_val = v,
+                    VariantValue::R8(v) => data.dbl_val = v,
+                    VariantValue::Bool(v) => data.bool_val = if v { -1 } else { 0 },
+                    VariantValue::Error(v) => data.scode = v,
+                    VariantValue::Currency(v) => data.cy_val = v,
+                    VariantValue::Date(v) => data.date = v,
+                    VariantValue::Decimal(v) => {
+                        // DECIMAL overlaps the vartype field, so it has to be
+                        // restored afterwards.
+                        raw.dec = v;
+                        raw.v.vartype = VarType::DECIMAL;
+                    }
+                    VariantValue::BStr(v) => data.bstr_val = BStr::new(&v).into_raw(),
+                    VariantValue::Unknown(v) => {
+                        data.punk_val = v.map(ComPtr::into_raw).unwrap_or(std::ptr::null_mut())
+                    }
+                    VariantValue::Dispatch(v) => {
+                        data.pdisp_val = v.map(ComPtr::into_raw).unwrap_or(std::ptr::null_mut())
+                    }
+                    VariantValue::Array(_, v) => data.parray = v.into_raw(),
+                }
+                raw
+            }
+        }
+    }
+
+    impl<'a> TryFrom<&'a Variant> for VariantValue {
+        type Error = Error;
+
+        /// Copies the value out of `raw`, leaving `raw` untouched. `BYREF`
+        /// values are read through the reference, like `VariantCopyInd`.
+        fn try_from(raw: &'a Variant) -> Result<VariantValue, Error> {
+            unsafe { read(raw) }
+        }
+    }
+
+    unsafe fn read(raw: &Variant) -> Result<VariantValue, Error> {
+        let vt = raw.vartype();
+        let data = &raw.v.data;
+
+        if vt.0 & VarType::BYREF.0 != 0 {
+            return read_byref(VarType(vt.0 & !VarType::BYREF.0), data);
+        }
+        if vt.container_type() == VarType::ARRAY {
+            if data.parray.is_null() {
+                return Err(Error::POINTER);
+            }
+            let array = OwnedSafeArray::copy_from(data.parray)?;
+            return Ok(VariantValue::Array(vt.primitive_type(), array));
+        }
+
+        Ok(match vt {
+            VarType::EMPTY => VariantValue::Empty,
+            VarType::NULL => VariantValue::Null,
+            VarType::I1 => VariantValue::I1(data.c_val),
+            VarType::UI1 => VariantValue::UI1(data.b_val),
+            VarType::I2 => VariantValue::I2(data.i_val),
+            VarType::UI2 => VariantValue::UI2(data.ui_val),
+            VarType::I4 => VariantValue::I4(data.l_val),
+            VarType::UI4 => VariantValue::UI4(data.ul_val),
+            VarType::INT => VariantValue::Int(data.int_val),
+            VarType::UINT => VariantValue::UInt(data.uint_val),
+            VarType::I8 => VariantValue::I8(data.ll_val),
+            VarType::UI8 => VariantValue::UI8(data.ull_val),
+            VarType::R4 => VariantValue::R4(data.flt_val),
+            VarType::R8 => VariantValue::R8(data.dbl_val),
+            VarType::BOOL => VariantValue::Bool(data.bool_val != 0),
+            VarType::ERROR => VariantValue::Error(data.scode),
+            VarType::CY => VariantValue::Currency(data.cy_val),
+            VarType::DATE => VariantValue::Date(data.date),
+            // The reserved field of the DECIMAL holds the vartype.
+            VarType::DECIMAL => VariantValue::Decimal(Decimal {
+                reserved: 0,
+                ..raw.dec
+            }),
+            VarType::BSTR => VariantValue::BStr(BStr::read_raw(data.bstr_val)?),
+            VarType::UNKNOWN => VariantValue::Unknown(add_ref(data.punk_val)),
+            VarType::DISPATCH => VariantValue::Dispatch(add_ref(data.pdisp_val)),
+            _ => return Err(Error::DISP_BADVARTYPE),
+        })
+    }
+
+    unsafe fn read_byref(vt: VarType, data: &InnerVariantData) -> Result<VariantValue, Error> {
+        let ptr = data.byref as *const u8;
+        if ptr.is_null() {
+            return Err(Error::POINTER);
+        }
+
+        // Build a temporary by-value Variant out of the referenced memory.
+        // It only borrows the referenced data, so it must not be cleared.
+        let mut tmp: Variant = std::mem::zeroed();
+        let size = match vt {
+            VarType::VARIANT => return read(&*data.pvar_val),
+            VarType::DECIMAL => {
+                tmp.dec = *data.pdec_val;
+                0
+            }
+            _ => byref_size(vt).ok_or(Error::DISP_BADVARTYPE)?,
+        };
+        let dst = &mut tmp.v.data as *mut InnerVariantData as *mut u8;
+        std::ptr::copy_nonoverlapping(ptr, dst, size);
+        tmp.v.vartype = vt;
+
+        read(&tmp)
+    }
+}
@@ -1,15 +1,27 @@
-#![cfg(windows)]
+//! Common types and utilities shared by the DirectX-related crates.
+//!
+//! The HRESULT decoding in `error`, the time conversions in `minwindef`, the
+//! wide strings in `helpers::wide` and the portable parts of `idltypes` and
+//! `objidl` are also available on non-Windows targets.
 
+#[cfg(windows)]
 pub extern crate math2d;
 
 pub use crate::error::{Error, Status};
 
+#[cfg(not(windows))]
+pub use crate::guid::GUID;
+#[cfg(windows)]
 pub use winapi::shared::guiddef::GUID;
 
 pub mod error;
+#[cfg(not(windows))]
+mod guid;
 pub mod helpers;
 pub mod idltypes;
 pub mod minwindef;
+#[cfg(windows)]
 pub mod oaidl;
 pub mod objidl;
+#[cfg(windows)]
 pub mod ocidl;
@@ -0,0 +1,314 @@
+//! A broken-down UTC calendar time, and conversions to and from OLE
+//! Automation `DATE` values.
+//!
+//! A `DATE` is an `f64` counting days since 1899-12-30 00:00. The integer
+//! part is the day and the fractional part is always the time of day, even
+//! for negative values: -1.25 is 1899-12-29 06:00, not 1899-12-28 18:00.
+
+use crate::error::Error;
+
+use std::fmt;
+
+/// Days from 1970-01-01 to 1899-12-30, the OLE Automation epoch.
+pub(crate) const OLE_EPOCH_DAYS: i64 = -25_569;
+
+/// Valid `DATE` values lie strictly between `OLE_DATE_MIN` and
+/// `OLE_DATE_MAX`, which covers 0100-01-01 to 9999-12-31.
+pub const OLE_DATE_MIN: f64 = -657_435.0;
+/// See `OLE_DATE_MIN`.
+pub const OLE_DATE_MAX: f64 = 2_958_466.0;
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+/// A date and time of day in the proleptic Gregorian calendar, in UTC. This
+/// is the portable counterpart of `SYSTEMTIME`, with nanosecond precision.
+/// Years before 1 are numbered astronomically, so year 0 is 1 BC.
+pub struct UtcDateTime {
+    pub year: i32,
+    /// 1 to 12.
+    pub month: u8,
+    /// 1 to the number of days in the month.
+    pub day: u8,
+    /// 0 to 23.
+    pub hour: u8,
+    /// 0 to 59.
+    pub minute: u8,
+    /// 0 to 59. Leap seconds are not representable.
+    pub second: u8,
+    /// 0 to 999_999_999.
+    pub nanosecond: u32,
+}
+
+impl UtcDateTime {
+    /// Builds a calendar time, returning None if any field is out of range.
+    pub fn new(
+        year: i32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        nanosecond: u32,
+    ) -> Option<UtcDateTime> {
+        let dt = UtcDateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            nanosecond,
+        };
+        if dt.is_valid() {
+            Some(dt)
+        } else {
+            None
+        }
+    }
+
+    /// Whether every field is within its range.
+    pub fn is_valid(&self) -> bool {
+        (1..=12).contains(&self.month)
+            && self.day >= 1
+            && self.day <= days_in_month(i64::from(self.year), self.month.into()) as u8
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+            && u64::from(self.nanosecond) < NANOS_PER_SECOND
+    }
+
+    /// The day of the week, with 0 for Sunday as in `SYSTEMTIME`.
+    pub fn day_of_week(&self) -> u8 {
+        // 1970-01-01 was a Thursday.
+        (self.unix_days() + 4).rem_euclid(7) as u8
+    }
+
+    /// The day of the year, starting at 1 for January 1st.
+    pub fn day_of_year(&self) -> u16 {
+        let jan1 = days_from_civil(i64::from(self.year), 1, 1);
+        (self.unix_days() - jan1 + 1) as u16
+    }
+
+    /// Whether `year` has a February 29th.
+    pub fn is_leap_year(year: i32) -> bool {
+        is_leap_year(i64::from(year))
+    }
+
+    /// Converts an OLE Automation `DATE`, rounded to the nearest
+    /// millisecond. Fails with `Error::INVALIDARG` for values outside
+    /// 0100-01-01 to 9999-12-31 and for NaN.
+    pub fn from_ole_date(date: f64) -> Result<UtcDateTime, Error> {
+        if !(date > OLE_DATE_MIN && date < OLE_DATE_MAX) {
+            return Err(Error::INVALIDARG);
+        }
+
+        const MS_PER_DAY: i64 = 86_400_000;
+        let mut days = date.trunc() as i64;
+        let mut ms = ((date - date.trunc()).abs() * MS_PER_DAY as f64).round() as i64;
+        if ms >= MS_PER_DAY {
+            // The fraction counts forward from the start of the day even for
+            // negative dates, so rounding up always reaches the next day.
+            ms -= MS_PER_DAY;
+            days += 1;
+        }
+
+        let nanos = ms as u64 * 1_000_000;
+        let dt = UtcDateTime::from_unix_days(days + OLE_EPOCH_DAYS, nanos);
+        if dt.year < 100 || dt.year > 9999 {
+            // Rounding to the millisecond can step past either end.
+            return Err(Error::INVALIDARG);
+        }
+        Ok(dt)
+    }
+
+    /// Converts to an OLE Automation `DATE`. Fails with `Error::INVALIDARG`
+    /// if the date is invalid or outside 0100-01-01 to 9999-12-31.
+    pub fn to_ole_date(&self) -> Result<f64, Error> {
+        if !self.is_valid() || self.year < 100 || self.year > 9999 {
+            return Err(Error::INVALIDARG);
+        }
+        let days = self.unix_days() - OLE_EPOCH_DAYS;
+        let frac = self.nanos_of_day() as f64 / (SECONDS_PER_DAY * NANOS_PER_SECOND) as f64;
+        Ok(if days < 0 {
+            days as f64 - frac
+        } else {
+            days as f64 + frac
+        })
+    }
+
+    /// Builds the calendar time `nanos` nanoseconds into the day that is
+    /// `days` days after 1970-01-01. `nanos` must be less than a day.
+    pub(crate) fn from_unix_days(days: i64, nanos: u64) -> UtcDateTime {
+        let (year, month, day) = civil_from_days(days);
+        let secs = nanos / NANOS_PER_SECOND;
+        UtcDateTime {
+            year: year as i32,
+            month: month as u8,
+            day: day as u8,
+            hour: (secs / 3600) as u8,
+            minute: (secs / 60 % 60) as u8,
+            second: (secs % 60) as u8,
+            nanosecond: (nanos % NANOS_PER_SECOND) as u32,
+        }
+    }
+
+    /// The number of days from 1970-01-01 to this date.
+    pub(crate) fn unix_days(&self) -> i64 {
+        days_from_civil(i64::from(self.year), self.month.into(), self.day.into())
+    }
+
+    /// The number of nanoseconds since midnight.
+    pub(crate) fn nanos_of_day(&self) -> u64 {
+        let secs =
+            u64::from(self.hour) * 3600 + u64::from(self.minute) * 60 + u64::from(self.second);
+        secs * NANOS_PER_SECOND + u64::from(self.nanosecond)
+    }
+}
+
+impl fmt::Display for UtcDateTime {
+    /// Formats as ISO 8601, e.g. `2019-03-04T12:00:00Z`. Fractional seconds
+    /// are only shown when present, with trailing zeros removed.
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if (0..=9999).contains(&self.year) {
+            write!(fmt, "{:04}", self.year)?;
+        } else {
+            write!(fmt, "{:+05}", self.year)?;
+        }
+        write!(
+            fmt,
+            "-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.month, self.day, self.hour, self.minute, self.second
+        )?;
+        if self.nanosecond != 0 {
+            let frac = format!("{:09}", self.nanosecond);
+            write!(fmt, ".{}", frac.trim_end_matches('0'))?;
+        }
+        fmt.write_str("Z")
+    }
+}
+
+fn is_leap_year(y: i64) -> bool {
+    (y % 4 == 0 && y % 100 != 0) || y % 400 == 0
+}
+
+/// The number of days in month `m` of year `y`.
+pub(crate) fn days_in_month(y: i64, m: u32) -> u32 {
+    match m {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        _ if is_leap_year(y) => 29,
+        _ => 28,
+    }
+}
+
+/// The number of days from 1970-01-01 to the given proleptic Gregorian
+/// date. Negative for earlier dates.
+pub(crate) fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = i64::from(m);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(d) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// The inverse of `days_from_civil`, returning `(year, month, day)`.
+pub(crate) fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    (y, m, d)
+}
+
+#[cfg(test)]
+#[test]
+fn ole_dates() {
+    let dt = |y, mo, d, h, mi, s, ns| UtcDateTime::new(y, mo, d, h, mi, s, ns).unwrap();
+
+    assert_eq!(
+        UtcDateTime::from_ole_date(0.0),
+        Ok(dt(1899, 12, 30, 0, 0, 0, 0))
+    );
+    assert_eq!(
+        UtcDateTime::from_ole_date(-1.25),
+        Ok(dt(1899, 12, 29, 6, 0, 0, 0))
+    );
+    assert_eq!(
+        UtcDateTime::from_ole_date(-0.5),
+        Ok(dt(1899, 12, 30, 12, 0, 0, 0))
+    );
+    assert_eq!(
+        UtcDateTime::from_ole_date(43_528.5),
+        Ok(dt(2019, 3, 4, 12, 0, 0, 0))
+    );
+    assert_eq!(
+        UtcDateTime::from_ole_date(-657_434.5),
+        Ok(dt(100, 1, 1, 12, 0, 0, 0))
+    );
+    assert_eq!(
+        UtcDateTime::from_ole_date(OLE_DATE_MAX),
+        Err(Error::INVALIDARG)
+    );
+    assert_eq!(UtcDateTime::from_ole_date(f64::NAN), Err(Error::INVALIDARG));
+
+    assert_eq!(dt(1899, 12, 29, 6, 0, 0, 0).to_ole_date(), Ok(-1.25));
+    assert_eq!(dt(2019, 3, 4, 12, 0, 0, 0).to_ole_date(), Ok(43_528.5));
+    assert_eq!(
+        dt(99, 12, 31, 0, 0, 0, 0).to_ole_date(),
+        Err(Error::INVALIDARG)
+    );
+
+    // Times that round up to midnight move to the following day.
+    assert_eq!(
+        UtcDateTime::from_ole_date(-1.999_999_999_9),
+        Ok(dt(1899, 12, 30, 0, 0, 0, 0))
+    );
+    assert_eq!(
+        UtcDateTime::from_ole_date(-0.999_999_999_9),
+        Ok(dt(1899, 12, 31, 0, 0, 0, 0))
+    );
+    assert_eq!(
+        UtcDateTime::from_ole_date(-3.999_999_999_9),
+        Ok(dt(1899, 12, 28, 0, 0, 0, 0))
+    );
+    assert_eq!(
+        UtcDateTime::from_ole_date(1.999_999_999_9),
+        Ok(dt(1900, 1, 1, 0, 0, 0, 0))
+    );
+
+    let noon = dt(1700, 3, 1, 13, 14, 15, 16_000_000);
+    assert_eq!(
+        UtcDateTime::from_ole_date(noon.to_ole_date().unwrap()),
+        Ok(noon)
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn calendar_fields() {
+    let d = UtcDateTime::new(2000, 2, 29, 0, 0, 0, 0).unwrap();
+    assert_eq!(d.day_of_week(), 2);
+    assert_eq!(d.day_of_year(), 60);
+    assert_eq!(UtcDateTime::new(1900, 2, 29, 0, 0, 0, 0), None);
+    assert_eq!(
+        UtcDateTime::new(1601, 1, 1, 0, 0, 0, 0)
+            .unwrap()
+            .day_of_week(),
+        1
+    );
+    assert_eq!(
+        UtcDateTime::new(1969, 12, 31, 23, 59, 59, 500_000_000)
+            .unwrap()
+            .to_string(),
+        "1969-12-31T23:59:59.5Z"
+    );
+}
@@ -1,17 +1,73 @@
+use crate::error::Error;
+use crate::minwindef::datetime::UtcDateTime;
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+/// The number of 100-nanosecond intervals since 1601-01-01 00:00 UTC.
 pub struct FileTime {
     pub low_part: u32,
     pub high_part: u32,
 }
 
-impl From<u64> for FileTime {
-    fn from(u: u64) -> FileTime {
+const TICKS_PER_SECOND: u64 = 10_000_000;
+const TICKS_PER_DAY: u64 = 86_400 * TICKS_PER_SECOND;
+/// Days from 1970-01-01 to 1601-01-01.
+const FILETIME_EPOCH_DAYS: i64 = -134_774;
+
+impl FileTime {
+    /// 1970-01-01 00:00 UTC.
+    pub const UNIX_EPOCH: FileTime = FileTime::from_ticks(116_444_736_000_000_000);
+
+    const fn from_ticks(ticks: u64) -> FileTime {
         FileTime {
-            low_part: u as u32,
-            high_part: (u >> 32) as u32,
+            low_part: ticks as u32,
+            high_part: (ticks >> 32) as u32,
         }
     }
+
+    /// Splits the time into calendar fields. Every `FileTime` is
+    /// representable.
+    pub fn to_utc(self) -> UtcDateTime {
+        let ticks = u64::from(self);
+        let days = (ticks / TICKS_PER_DAY) as i64 + FILETIME_EPOCH_DAYS;
+        UtcDateTime::from_unix_days(days, ticks % TICKS_PER_DAY * 100)
+    }
+
+    /// Builds a `FileTime` from calendar fields, truncating to 100ns. Fails
+    /// with `Error::INVALIDARG` if the date is invalid or before 1601.
+    pub fn from_utc(dt: &UtcDateTime) -> Result<FileTime, Error> {
+        if !dt.is_valid() {
+            return Err(Error::INVALIDARG);
+        }
+        let days =
+            u64::try_from(dt.unix_days() - FILETIME_EPOCH_DAYS).map_err(|_| Error::INVALIDARG)?;
+        days.checked_mul(TICKS_PER_DAY)
+            .and_then(|t| t.checked_add(dt.nanos_of_day() / 100))
+            .map(FileTime::from)
+            .ok_or(Error::INVALIDARG)
+    }
+
+    /// Converts an OLE Automation `DATE`, rounded to the nearest
+    /// millisecond. Fails with `Error::INVALIDARG` if the date is out of the
+    /// range of either type, i.e. before 1601.
+    pub fn from_ole_date(date: f64) -> Result<FileTime, Error> {
+        FileTime::from_utc(&UtcDateTime::from_ole_date(date)?)
+    }
+
+    /// Converts to an OLE Automation `DATE`. Fails with `Error::INVALIDARG`
+    /// for times after 9999-12-31.
+    pub fn to_ole_date(self) -> Result<f64, Error> {
+        self.to_utc().to_ole_date()
+    }
+}
+
+impl From<u64> for FileTime {
+    fn from(u: u64) -> FileTime {
+        FileTime::from_ticks(u)
+    }
 }
 
 impl From<FileTime> for u64 {
@@ -19,3 +75,80 @@ impl From<FileTime> for u64 {
         ((ft.high_part as u64) << 32) | (ft.low_part as u64)
     }
 }
+
+impl TryFrom<SystemTime> for FileTime {
+    type Error = Error;
+
+    /// Rounds towards 1601 to a multiple of 100ns. Fails with
+    /// `Error::INVALIDARG` for times before 1601 or too far in the future.
+    fn try_from(time: SystemTime) -> Result<FileTime, Error> {
+        let epoch = u128::from(u64::from(FileTime::UNIX_EPOCH));
+        let ticks = match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => epoch + after.as_nanos() / 100,
+            Err(before) => {
+                let nanos = before.duration().as_nanos();
+                epoch
+                    .checked_sub(nanos.div_ceil(100))
+                    .ok_or(Error::INVALIDARG)?
+            }
+        };
+        u64::try_from(ticks)
+            .map(FileTime::from)
+            .map_err(|_| Error::INVALIDARG)
+    }
+}
+
+impl TryFrom<FileTime> for SystemTime {
+    type Error = Error;
+
+    /// Fails with `Error::INVALIDARG` if the platform's `SystemTime` can't
+    /// represent the time.
+    fn try_from(ft: FileTime) -> Result<SystemTime, Error> {
+        let ticks = u64::from(ft);
+        let epoch = u64::from(FileTime::UNIX_EPOCH);
+        let to_duration =
+            |t: u64| Duration::new(t / TICKS_PER_SECOND, (t % TICKS_PER_SECOND * 100) as u32);
+        let time = if ticks >= epoch {
+            UNIX_EPOCH.checked_add(to_duration(ticks - epoch))
+        } else {
+            UNIX_EPOCH.checked_sub(to_duration(epoch - ticks))
+        };
+        time.ok_or(Error::INVALIDARG)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn filetime_conversions() {
+    let epoch = FileTime::UNIX_EPOCH.to_utc();
+    assert_eq!(epoch, UtcDateTime::new(1970, 1, 1, 0, 0, 0, 0).unwrap());
+    assert_eq!(FileTime::from_utc(&epoch), Ok(FileTime::UNIX_EPOCH));
+    assert_eq!(SystemTime::try_from(FileTime::UNIX_EPOCH), Ok(UNIX_EPOCH));
+
+    let start = UtcDateTime::new(1601, 1, 1, 0, 0, 0, 0).unwrap();
+    assert_eq!(FileTime::from(0).to_utc(), start);
+    let before = UtcDateTime::new(1600, 12, 31, 23, 59, 59, 0).unwrap();
+    assert_eq!(FileTime::from_utc(&before), Err(Error::INVALIDARG));
+
+    // 1969-12-31 23:59:59.99999995 rounds down to the previous tick.
+    let time = UNIX_EPOCH - Duration::from_nanos(50);
+    let ft = FileTime::try_from(time).unwrap();
+    assert_eq!(u64::from(ft), u64::from(FileTime::UNIX_EPOCH) - 1);
+    assert_eq!(
+        ft.to_utc(),
+        UtcDateTime::new(1969, 12, 31, 23, 59, 59, 999_999_900).unwrap()
+    );
+    assert_eq!(
+        SystemTime::try_from(ft),
+        Ok(UNIX_EPOCH - Duration::from_nanos(100))
+    );
+
+    // 1800-01-01 12:00 is before both the OLE and Unix epochs.
+    let ft = FileTime::from_ole_date(-36_522.5).unwrap();
+    assert_eq!(
+        ft.to_utc(),
+        UtcDateTime::new(1800, 1, 1, 12, 0, 0, 0).unwrap()
+    );
+    assert_eq!(ft.to_ole_date(), Ok(-36_522.5));
+    assert_eq!(FileTime::from_ole_date(-109_207.0), Err(Error::INVALIDARG));
+}
@@ -1,3 +1,5 @@
+pub use self::datetime::*;
 pub use self::filetime::*;
 
+pub mod datetime;
 pub mod filetime;
@@ -1,7 +1,13 @@
+use crate::error::Error;
+use crate::helpers::WideString;
+use crate::oaidl::excep_info::ExcepInfo;
+
 use com_wrapper::ComWrapper;
 use winapi::um::oaidl::IErrorLog;
 use wio::com::ComPtr;
 
+pub mod custom;
+
 #[repr(transparent)]
 #[derive(ComWrapper)]
 #[com(send)]
@@ -9,4 +15,15 @@ pub struct ErrorLog {
     ptr: ComPtr<IErrorLog>,
 }
 
-impl ErrorLog {}
+impl ErrorLog {
+    /// Logs an error against the property called `property_name`.
+    pub fn add_error(&mut self, property_name: &str, info: &ExcepInfo) -> Result<(), Error> {
+        let name = WideString::new(property_name)?;
+        unsafe {
+            let mut raw = info.to_raw();
+            let hr = self.ptr.AddError(name.as_ptr(), &raw);
+            ExcepInfo::free_raw(&mut raw);
+            Error::map(hr, ())
+        }
+    }
+}
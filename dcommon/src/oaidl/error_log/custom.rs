@@ -0,0 +1,103 @@
+use crate::helpers::WideCStr;
+use crate::oaidl::error_log::ErrorLog;
+use crate::oaidl::excep_info::ExcepInfo;
+
+use std::sync::{Arc, Mutex};
+
+use com_impl::{com_impl, ComImpl, Refcount, VTable};
+use com_wrapper::ComWrapper;
+use winapi::shared::winerror::{E_FAIL, E_POINTER, S_OK};
+use winapi::shared::wtypesbase::LPCOLESTR;
+use winapi::um::oaidl::{IErrorLog, IErrorLogVtbl, EXCEPINFO};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+/// An error reported to a `CollectingErrorLog`.
+pub struct LoggedError {
+    pub property_name: String,
+    pub info: ExcepInfo,
+}
+
+#[derive(Clone, Default)]
+/// The Rust side of a `CollectingErrorLog`, for inspecting what was logged
+/// after the call that used the log returns.
+pub struct ErrorCollector {
+    entries: Arc<Mutex<Vec<LoggedError>>>,
+}
+
+impl ErrorCollector {
+    /// A copy of everything logged so far, in order.
+    pub fn entries(&self) -> Vec<LoggedError> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    /// Removes and returns everything logged so far.
+    pub fn take(&self) -> Vec<LoggedError> {
+        std::mem::take(&mut *self.entries.lock().unwrap())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().unwrap().is_empty()
+    }
+}
+
+#[repr(C)]
+#[derive(ComImpl)]
+/// An `IErrorLog` that records every error it is given.
+pub struct CollectingErrorLog {
+    vtbl: VTable<IErrorLogVtbl>,
+    refcount: Refcount,
+    entries: Arc<Mutex<Vec<LoggedError>>>,
+}
+
+impl CollectingErrorLog {
+    /// Creates an error log to pass to COM, and the collector its errors
+    /// can be read back from.
+    pub fn create() -> (ErrorLog, ErrorCollector) {
+        let collector = ErrorCollector::default();
+        unsafe {
+            let raw = Self::create_raw(collector.entries.clone());
+            let raw: *mut CollectingErrorLog = raw;
+            (ErrorLog::from_raw(raw as *mut IErrorLog), collector)
+        }
+    }
+}
+
+#[com_impl]
+unsafe impl IErrorLog for CollectingErrorLog {
+    #[panic(result = "E_FAIL")]
+    unsafe fn add_error(&self, name: LPCOLESTR, info: *const EXCEPINFO) -> i32 {
+        if info.is_null() {
+            return E_POINTER;
+        }
+        let property_name = if name.is_null() {
+            String::new()
+        } else {
+            WideCStr::from_ptr(name).to_string_lossy()
+        };
+        let info = ExcepInfo::from_raw(&*info);
+
+        self.entries.lock().unwrap().push(LoggedError {
+            property_name,
+            info,
+        });
+        S_OK
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn collecting_error_log() {
+    use crate::Error;
+
+    let (mut log, collector) = CollectingErrorLog::create();
+    assert!(collector.is_empty());
+
+    let info = ExcepInfo::new(Error::INVALIDARG).with_description("bad quality");
+    log.add_error("Quality", &info).unwrap();
+
+    let entries = collector.take();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].property_name, "Quality");
+    assert_eq!(entries[0].info, info);
+    assert!(collector.is_empty());
+}
@@ -0,0 +1,104 @@
+use crate::error::Error;
+use crate::idltypes::bstr::BStr;
+
+use winapi::shared::wtypes::BSTR;
+use winapi::um::oaidl::EXCEPINFO;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+/// An owned `EXCEPINFO`, describing an error raised by an automation object
+/// or reported to an `ErrorLog`.
+pub struct ExcepInfo {
+    /// An application-defined error code. Either this or `scode` is set.
+    pub code: u16,
+    /// The name of the object or application that raised the error.
+    pub source: String,
+    pub description: String,
+    /// The path of a help file with more information, if any.
+    pub help_file: String,
+    pub help_context: u32,
+    pub scode: Error,
+}
+
+impl ExcepInfo {
+    /// Describes `scode`, with every other field empty.
+    pub fn new(scode: Error) -> ExcepInfo {
+        ExcepInfo {
+            code: 0,
+            source: String::new(),
+            description: String::new(),
+            help_file: String::new(),
+            help_context: 0,
+            scode,
+        }
+    }
+
+    /// Sets the description.
+    pub fn with_description(mut self, description: &str) -> ExcepInfo {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets the source.
+    pub fn with_source(mut self, source: &str) -> ExcepInfo {
+        self.source = source.into();
+        self
+    }
+
+    /// Copies the strings out of `raw`, leaving it untouched. Deferred
+    /// fill-in is not performed.
+    ///
+    /// # Safety
+    ///
+    /// The strings in `raw` must be null or valid BSTRs.
+    pub unsafe fn from_raw(raw: &EXCEPINFO) -> ExcepInfo {
+        ExcepInfo {
+            code: raw.wCode,
+            source: BStr::read_raw_lossy(raw.bstrSource),
+            description: BStr::read_raw_lossy(raw.bstrDescription),
+            help_file: BStr::read_raw_lossy(raw.bstrHelpFile),
+            help_context: raw.dwHelpContext,
+            scode: Error(raw.scode),
+        }
+    }
+
+    /// Builds an `EXCEPINFO` whose strings are newly allocated BSTRs. Empty
+    /// strings are left null. Free the result with `free_raw`.
+    pub fn to_raw(&self) -> EXCEPINFO {
+        unsafe {
+            let mut raw: EXCEPINFO = std::mem::zeroed();
+            raw.wCode = self.code;
+            raw.bstrSource = alloc_bstr(&self.source);
+            raw.bstrDescription = alloc_bstr(&self.description);
+            raw.bstrHelpFile = alloc_bstr(&self.help_file);
+            raw.dwHelpContext = self.help_context;
+            raw.scode = self.scode.0;
+            raw
+        }
+    }
+
+    /// Frees the strings in an `EXCEPINFO` and sets them to null.
+    ///
+    /// # Safety
+    ///
+    /// The strings in `raw` must be null or BSTRs that `raw` owns.
+    pub unsafe fn free_raw(raw: &mut EXCEPINFO) {
+        for bstr in &mut [
+            &mut raw.bstrSource,
+            &mut raw.bstrDescription,
+            &mut raw.bstrHelpFile,
+        ] {
+            if !bstr.is_null() {
+                drop(BStr::from_raw(**bstr));
+                **bstr = std::ptr::null_mut();
+            }
+        }
+    }
+}
+
+unsafe fn alloc_bstr(s: &str) -> BSTR {
+    if s.is_empty() {
+        std::ptr::null_mut()
+    } else {
+        BStr::new(s).into_raw()
+    }
+}
@@ -0,0 +1,885 @@
+//! Structured storage in pure Rust: reading and writing compound files
+//! (MS-CFB), the container format of .msi, .doc and similar files.
+//!
+//! A `CompoundFile` wraps any `Read + Seek` backend, e.g. a `std::fs::File`
+//! or a `MemoryStream`. Its directory is loaded up front; stream data is
+//! read from the backend the first time it is touched. Changes stay in
+//! memory until `commit` writes the whole file back out, and `revert`
+//! throws them away by reloading the directory.
+//!
+//! `Storage` and `CompoundStream` handles share the file. Handles from
+//! before a `revert`, or to entries that have since been deleted, fail
+//! with `Error::STG_REVERTED`. On Windows a `CompoundStream` can be handed
+//! to COM with `into_stream`.
+
+use self::format::{compare_names, validate_name, Header};
+use crate::minwindef::FileTime;
+use crate::objidl::{from_io, io_error};
+use crate::Error;
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
+
+mod format;
+mod reader;
+mod writer;
+
+/// Whether an entry is a storage, which holds other entries, or a stream,
+/// which holds bytes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EntryKind {
+    Storage,
+    Stream,
+}
+
+/// The properties of one directory entry.
+#[derive(Clone, Debug)]
+pub struct EntryInfo {
+    pub name: String,
+    pub kind: EntryKind,
+    /// The length of a stream in bytes; always 0 for storages.
+    pub size: u64,
+    /// The CLSID in its in-memory `GUID` layout.
+    pub clsid: [u8; 16],
+    pub state_bits: u32,
+    pub ctime: FileTime,
+    pub mtime: FileTime,
+}
+
+struct Node {
+    name: String,
+    kind: EntryKind,
+    clsid: [u8; 16],
+    state_bits: u32,
+    ctime: FileTime,
+    mtime: FileTime,
+    /// Node indices, in no particular order.
+    children: Vec<usize>,
+    data: Data,
+}
+
+enum Data {
+    /// Stream data that is still only in the file, in sectors or mini
+    /// sectors.
+    Original {
+        chain: Vec<u32>,
+        size: u64,
+        mini: bool,
+    },
+    Memory(Vec<u8>),
+}
+
+impl Node {
+    fn new(name: String, kind: EntryKind) -> Node {
+        let now = match kind {
+            EntryKind::Storage => {
+                FileTime::try_from(SystemTime::now()).unwrap_or(FileTime::from(0))
+            }
+            EntryKind::Stream => FileTime::from(0),
+        };
+        Node {
+            name,
+            kind,
+            clsid: [0; 16],
+            state_bits: 0,
+            ctime: now,
+            mtime: now,
+            children: Vec::new(),
+            data: Data::Memory(Vec::new()),
+        }
+    }
+
+    fn size(&self) -> u64 {
+        match &self.data {
+            Data::Original { size, .. } => *size,
+            Data::Memory(data) => data.len() as u64,
+        }
+    }
+
+    fn info(&self) -> EntryInfo {
+        EntryInfo {
+            name: self.name.clone(),
+            kind: self.kind,
+            size: match self.kind {
+                EntryKind::Storage => 0,
+                EntryKind::Stream => self.size(),
+            },
+            clsid: self.clsid,
+            state_bits: self.state_bits,
+            ctime: self.ctime,
+            mtime: self.mtime,
+        }
+    }
+}
+
+struct Inner<F> {
+    file: F,
+    header: Header,
+    /// The sectors of the mini stream, which holds the small streams.
+    mini_chain: Vec<u32>,
+    /// Indexed by node; the root is 0. Deleted entries leave a `None`.
+    nodes: Vec<Option<Node>>,
+    /// Bumped on every revert, invalidating outstanding handles.
+    generation: u64,
+}
+
+impl<F> Inner<F> {
+    fn node(&self, index: usize) -> Result<&Node, Error> {
+        self.nodes[index].as_ref().ok_or(Error::STG_REVERTED)
+    }
+
+    fn node_mut(&mut self, index: usize) -> Result<&mut Node, Error> {
+        self.nodes[index].as_mut().ok_or(Error::STG_REVERTED)
+    }
+
+    /// Finds the child of `parent` called `name`, ignoring case.
+    fn find(&self, parent: usize, name: &str) -> Result<Option<usize>, Error> {
+        let node = self.node(parent)?;
+        Ok(node.children.iter().cloned().find(|&child| {
+            let child = self.nodes[child].as_ref().unwrap();
+            compare_names(&child.name, name) == Ordering::Equal
+        }))
+    }
+
+    fn open(&self, parent: usize, name: &str, kind: EntryKind) -> Result<usize, Error> {
+        match self.find(parent, name)? {
+            Some(index) if self.node(index)?.kind == kind => Ok(index),
+            _ => Err(Error::STG_FILENOTFOUND),
+        }
+    }
+
+    fn add(&mut self, parent: usize, name: &str, kind: EntryKind) -> Result<usize, Error> {
+        validate_name(name)?;
+        if self.find(parent, name)?.is_some() {
+            return Err(Error::STG_FILEALREADYEXISTS);
+        }
+        let index = self.nodes.len();
+        self.nodes.push(Some(Node::new(name.into(), kind)));
+        self.node_mut(parent)?.children.push(index);
+        Ok(index)
+    }
+
+    fn remove(&mut self, index: usize) {
+        if let Some(node) = self.nodes[index].take() {
+            for child in node.children {
+                self.remove(child);
+            }
+        }
+    }
+}
+
+impl<F: Read + Seek> Inner<F> {
+    /// Loads the data of stream `index` into memory if it isn't already.
+    fn materialize(&mut self, index: usize) -> Result<&mut Vec<u8>, Error> {
+        if let Data::Original { chain, size, mini } = &self.node(index)?.data {
+            let (chain, size, mini) = (chain.clone(), *size, *mini);
+            let data = self.read_original(&chain, size, mini)?;
+            self.node_mut(index)?.data = Data::Memory(data);
+        }
+        match &mut self.node_mut(index)?.data {
+            Data::Memory(data) => Ok(data),
+            Data::Original { .. } => unreachable!(),
+        }
+    }
+
+    fn read_original(&mut self, chain: &[u32], size: u64, mini: bool) -> Result<Vec<u8>, Error> {
+        let ssz = self.header.sector_size() as u64;
+        let unit = if mini {
+            format::MINI_SECTOR_SIZE as u64
+        } else {
+            ssz
+        };
+        let size = usize::try_from(size).map_err(|_| Error::STG_INSUFFICIENTMEMORY)?;
+        let mut data = vec![0; size];
+
+        for (piece, &sect) in data.chunks_mut(unit as usize).zip(chain) {
+            let offset = if mini {
+                // Mini sectors never straddle a sector of the mini stream.
+                let pos = u64::from(sect) * unit;
+                let big = self.mini_chain.get((pos / ssz) as usize);
+                let big = big.ok_or(Error::STG_DOCFILECORRUPT)?;
+                (u64::from(*big) + 1) * ssz + pos % ssz
+            } else {
+                (u64::from(sect) + 1) * ssz
+            };
+            self.file.seek(SeekFrom::Start(offset)).map_err(from_io)?;
+            self.file
+                .read_exact(piece)
+                .map_err(|_| Error::STG_DOCFILECORRUPT)?;
+        }
+        Ok(data)
+    }
+}
+
+/// A compound file opened over `F`.
+pub struct CompoundFile<F> {
+    inner: Arc<Mutex<Inner<F>>>,
+}
+
+impl<F: Read + Seek> CompoundFile<F> {
+    /// Opens an existing compound file. Fails with
+    /// `Error::STG_INVALIDHEADER` if `file` doesn't start with a compound
+    /// file header, and with `Error::STG_DOCFILECORRUPT` if its structures
+    /// are damaged.
+    pub fn open(mut file: F) -> Result<CompoundFile<F>, Error> {
+        let loaded = reader::load(&mut file)?;
+        Ok(CompoundFile {
+            inner: Arc::new(Mutex::new(Inner {
+                file,
+                header: loaded.header,
+                mini_chain: loaded.mini_chain,
+                nodes: loaded.nodes,
+                generation: 0,
+            })),
+        })
+    }
+
+    /// The root storage.
+    pub fn root(&self) -> Storage<F> {
+        Storage {
+            file: self.inner.clone(),
+            index: 0,
+            generation: self.inner.lock().unwrap().generation,
+        }
+    }
+
+    /// The major version of the format, 3 for 512-byte sectors or 4 for
+    /// 4096-byte sectors.
+    pub fn version(&self) -> u16 {
+        self.inner.lock().unwrap().header.major_version
+    }
+
+    /// Throws away every change since the last commit. Handles opened
+    /// before now are invalidated.
+    pub fn revert(&mut self) -> Result<(), Error> {
+        let mut inner = self.inner.lock().unwrap();
+        let loaded = reader::load(&mut inner.file)?;
+        inner.header = loaded.header;
+        inner.mini_chain = loaded.mini_chain;
+        inner.nodes = loaded.nodes;
+        inner.generation += 1;
+        Ok(())
+    }
+
+    /// Gives back the backend, discarding uncommitted changes. Fails with
+    /// `Error::STG_INUSE` while any `Storage` or `CompoundStream` is still
+    /// alive.
+    pub fn into_inner(self) -> Result<F, Error> {
+        match Arc::try_unwrap(self.inner) {
+            Ok(inner) => Ok(inner.into_inner().unwrap().file),
+            Err(_) => Err(Error::STG_INUSE),
+        }
+    }
+}
+
+impl<F: Read + Write + Seek> CompoundFile<F> {
+    /// Starts a new version 3 file in `file`, overwriting it from the start.
+    /// The empty file is written out immediately.
+    pub fn create(file: F) -> Result<CompoundFile<F>, Error> {
+        let root = Node::new("Root Entry".into(), EntryKind::Storage);
+        let mut cf = CompoundFile {
+            inner: Arc::new(Mutex::new(Inner {
+                file,
+                header: Header::new(3),
+                mini_chain: Vec::new(),
+                nodes: vec![Some(root)],
+                generation: 0,
+            })),
+        };
+        cf.commit()?;
+        Ok(cf)
+    }
+
+    /// Writes every change out to the backend.
+    ///
+    /// The whole file is rewritten, with each stream stored contiguously.
+    /// The file never shrinks, since `F` can't be truncated; any space left
+    /// over is marked free.
+    ///
+    /// The commit is not atomic: the file is overwritten in place, so an
+    /// error or crash part way through can leave it damaged. To update a
+    /// file on disk safely, commit into a copy and rename that over the
+    /// original.
+    pub fn commit(&mut self) -> Result<(), Error> {
+        let mut inner = self.inner.lock().unwrap();
+        let inner = &mut *inner;
+        for index in 0..inner.nodes.len() {
+            if inner.nodes[index].is_some() {
+                inner.materialize(index)?;
+            }
+        }
+
+        let ssz = inner.header.sector_size() as u64;
+        let len = inner.file.seek(SeekFrom::End(0)).map_err(from_io)?;
+        let min_sectors = len.saturating_sub(ssz).div_ceil(ssz);
+        let layout = writer::write(&mut inner.file, &inner.header, &inner.nodes, min_sectors)?;
+
+        // The data is on disk now, so it can be dropped from memory.
+        for (node, placement) in inner.nodes.iter_mut().zip(&layout.placements) {
+            let node = match node {
+                Some(node) if node.kind == EntryKind::Stream => node,
+                _ => continue,
+            };
+            let size = node.size();
+            node.data = match placement {
+                Some(placement) => {
+                    let unit = if placement.mini {
+                        format::MINI_SECTOR_SIZE as u64
+                    } else {
+                        ssz
+                    };
+                    let count = size.div_ceil(unit) as u32;
+                    Data::Original {
+                        chain: (placement.start..placement.start + count).collect(),
+                        size,
+                        mini: placement.mini,
+                    }
+                }
+                None => Data::Memory(Vec::new()),
+            };
+        }
+        inner.mini_chain = layout.mini_chain;
+        Ok(())
+    }
+}
+
+impl<F> std::fmt::Debug for CompoundFile<F> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("CompoundFile").finish()
+    }
+}
+
+/// Checks that a handle is still valid and locks its file.
+fn lock<F>(file: &Mutex<Inner<F>>, generation: u64) -> Result<MutexGuard<'_, Inner<F>>, Error> {
+    let inner = file.lock().unwrap();
+    if inner.generation != generation {
+        return Err(Error::STG_REVERTED);
+    }
+    Ok(inner)
+}
+
+/// A storage in a compound file, like a directory.
+///
+/// Names are compared without regard to case, are at most 31 UTF-16 code
+/// units long and may not contain `/`, `\`, `:` or `!`; invalid names fail
+/// with `Error::STG_INVALIDNAME`. Missing entries fail with
+/// `Error::STG_FILENOTFOUND` and duplicates with
+/// `Error::STG_FILEALREADYEXISTS`.
+pub struct Storage<F> {
+    file: Arc<Mutex<Inner<F>>>,
+    index: usize,
+    generation: u64,
+}
+
+impl<F> Storage<F> {
+    fn lock(&self) -> Result<MutexGuard<'_, Inner<F>>, Error> {
+        let inner = lock(&self.file, self.generation)?;
+        inner.node(self.index)?;
+        Ok(inner)
+    }
+
+    fn storage(&self, index: usize) -> Storage<F> {
+        Storage {
+            file: self.file.clone(),
+            index,
+            generation: self.generation,
+        }
+    }
+
+    fn stream(&self, index: usize) -> CompoundStream<F> {
+        CompoundStream {
+            file: self.file.clone(),
+            index,
+            generation: self.generation,
+            pos: 0,
+        }
+    }
+
+    pub fn name(&self) -> Result<String, Error> {
+        Ok(self.lock()?.node(self.index)?.name.clone())
+    }
+
+    pub fn info(&self) -> Result<EntryInfo, Error> {
+        Ok(self.lock()?.node(self.index)?.info())
+    }
+
+    /// The storage's direct children, in directory order.
+    pub fn entries(&self) -> Result<Vec<EntryInfo>, Error> {
+        let inner = self.lock()?;
+        let node = inner.node(self.index)?;
+        let mut entries = node
+            .children
+            .iter()
+            .map(|&child| inner.node(child).map(Node::info))
+            .collect::<Result<Vec<_>, Error>>()?;
+        entries.sort_by(|a, b| compare_names(&a.name, &b.name));
+        Ok(entries)
+    }
+
+    pub fn open_storage(&self, name: &str) -> Result<Storage<F>, Error> {
+        let index = self.lock()?.open(self.index, name, EntryKind::Storage)?;
+        Ok(self.storage(index))
+    }
+
+    pub fn create_storage(&self, name: &str) -> Result<Storage<F>, Error> {
+        let index = self.lock()?.add(self.index, name, EntryKind::Storage)?;
+        Ok(self.storage(index))
+    }
+
+    pub fn open_stream(&self, name: &str) -> Result<CompoundStream<F>, Error> {
+        let index = self.lock()?.open(self.index, name, EntryKind::Stream)?;
+        Ok(self.stream(index))
+    }
+
+    /// Creates an empty stream called `name`.
+    pub fn create_stream(&self, name: &str) -> Result<CompoundStream<F>, Error> {
+        let index = self.lock()?.add(self.index, name, EntryKind::Stream)?;
+        Ok(self.stream(index))
+    }
+
+    /// Deletes the entry called `name`, and everything in it if it's a
+    /// storage.
+    pub fn delete(&self, name: &str) -> Result<(), Error> {
+        let mut inner = self.lock()?;
+        let index = inner
+            .find(self.index, name)?
+            .ok_or(Error::STG_FILENOTFOUND)?;
+        inner.node_mut(self.index)?.children.retain(|&c| c != index);
+        inner.remove(index);
+        Ok(())
+    }
+
+    pub fn rename(&self, old_name: &str, new_name: &str) -> Result<(), Error> {
+        validate_name(new_name)?;
+        let mut inner = self.lock()?;
+        let index = inner
+            .find(self.index, old_name)?
+            .ok_or(Error::STG_FILENOTFOUND)?;
+        match inner.find(self.index, new_name)? {
+            Some(other) if other != index => return Err(Error::STG_FILEALREADYEXISTS),
+            _ => (),
+        }
+        inner.node_mut(index)?.name = new_name.into();
+        Ok(())
+    }
+
+    /// Sets the class of the storage, in its in-memory `GUID` layout.
+    pub fn set_clsid(&self, clsid: [u8; 16]) -> Result<(), Error> {
+        self.lock()?.node_mut(self.index)?.clsid = clsid;
+        Ok(())
+    }
+
+    /// Sets the state bits selected by `mask` to those of `bits`.
+    pub fn set_state_bits(&self, bits: u32, mask: u32) -> Result<(), Error> {
+        let mut inner = self.lock()?;
+        let node = inner.node_mut(self.index)?;
+        node.state_bits = (node.state_bits & !mask) | (bits & mask);
+        Ok(())
+    }
+}
+
+impl<F> Clone for Storage<F> {
+    fn clone(&self) -> Self {
+        self.storage(self.index)
+    }
+}
+
+impl<F> std::fmt::Debug for Storage<F> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("Storage")
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+/// A stream in a compound file. Clones share the data but have their own
+/// position.
+///
+/// The stream's data is loaded into memory the first time it's read or
+/// written. Like `MemoryStream`, it can be positioned past the end, and
+/// writes there fill the gap with zeros.
+pub struct CompoundStream<F> {
+    file: Arc<Mutex<Inner<F>>>,
+    index: usize,
+    generation: u64,
+    pos: u64,
+}
+
+impl<F> CompoundStream<F> {
+    fn lock(&self) -> Result<MutexGuard<'_, Inner<F>>, Error> {
+        let inner = lock(&self.file, self.generation)?;
+        inner.node(self.index)?;
+        Ok(inner)
+    }
+
+    pub fn name(&self) -> Result<String, Error> {
+        Ok(self.lock()?.node(self.index)?.name.clone())
+    }
+
+    pub fn info(&self) -> Result<EntryInfo, Error> {
+        Ok(self.lock()?.node(self.index)?.info())
+    }
+
+    /// The length of the stream in bytes.
+    pub fn len(&self) -> Result<u64, Error> {
+        Ok(self.lock()?.node(self.index)?.size())
+    }
+
+    pub fn is_empty(&self) -> Result<bool, Error> {
+        Ok(self.len()? == 0)
+    }
+
+    /// The current position, which may be past the end.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Moves the position. Fails with `Error::STG_INVALIDFUNCTION` if it
+    /// would end up before the start.
+    pub fn seek_to(&mut self, pos: SeekFrom) -> Result<u64, Error> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(pos) => {
+                self.pos = pos;
+                return Ok(pos);
+            }
+            SeekFrom::Current(offset) => (self.pos, offset),
+            SeekFrom::End(offset) => (self.len()?, offset),
+        };
+        let pos = if offset < 0 {
+            base.checked_sub(offset.unsigned_abs())
+        } else {
+            base.checked_add(offset as u64)
+        };
+        self.pos = pos.ok_or(Error::STG_INVALIDFUNCTION)?;
+        Ok(self.pos)
+    }
+}
+
+impl<F: Read + Seek> CompoundStream<F> {
+    /// Truncates or zero-extends the stream to `size` bytes. The position
+    /// is not changed.
+    pub fn set_len(&mut self, size: u64) -> Result<(), Error> {
+        let size = usize::try_from(size).map_err(|_| Error::STG_MEDIUMFULL)?;
+        self.lock()?.materialize(self.index)?.resize(size, 0);
+        Ok(())
+    }
+
+    fn read_at_pos(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        let n = {
+            let mut inner = self.lock()?;
+            let data = inner.materialize(self.index)?;
+            if self.pos >= data.len() as u64 {
+                return Ok(0);
+            }
+            let start = self.pos as usize;
+            let n = buf.len().min(data.len() - start);
+            buf[..n].copy_from_slice(&data[start..start + n]);
+            n
+        };
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn write_at_pos(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        let start = usize::try_from(self.pos).map_err(|_| Error::STG_MEDIUMFULL)?;
+        let end = start.checked_add(buf.len()).ok_or(Error::STG_MEDIUMFULL)?;
+        {
+            let mut inner = self.lock()?;
+            let data = inner.materialize(self.index)?;
+            if data.len() < end {
+                data.resize(end, 0);
+            }
+            data[start..end].copy_from_slice(buf);
+        }
+        self.pos = end as u64;
+        Ok(buf.len())
+    }
+}
+
+impl<F> Clone for CompoundStream<F> {
+    fn clone(&self) -> Self {
+        CompoundStream {
+            file: self.file.clone(),
+            index: self.index,
+            generation: self.generation,
+            pos: self.pos,
+        }
+    }
+}
+
+impl<F> std::fmt::Debug for CompoundStream<F> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("CompoundStream")
+            .field("index", &self.index)
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
+
+impl<F: Read + Seek> Read for CompoundStream<F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.read_at_pos(buf).map_err(io_error)
+    }
+}
+
+impl<F: Read + Seek> Write for CompoundStream<F> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_at_pos(buf).map_err(io_error)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<F> Seek for CompoundStream<F> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.seek_to(pos).map_err(io_error)
+    }
+}
+
+#[cfg(windows)]
+mod com {
+    use super::CompoundStream;
+    use crate::helpers::wide::CoTaskWString;
+    use crate::objidl::enums::{CommitFlags, LockType, StatFlags};
+    use crate::objidl::sequential_stream::custom::SequentialStream;
+    use crate::objidl::stream::custom::{self, CustomStream};
+    use crate::objidl::stream::stat::Stat;
+    use crate::objidl::stream::std_stream::copy_to;
+    use crate::objidl::Stream;
+    use crate::{Error, Status};
+
+    use std::io::{Read, Seek, SeekFrom};
+
+    impl<F: Read + Seek + 'static> CompoundStream<F> {
+        /// Wraps the stream in a COM `Stream`. Changes reach the file when
+        /// the `CompoundFile` is committed.
+        pub fn into_stream(self) -> Stream {
+            unsafe { CustomStream::new(self) }
+        }
+    }
+
+    impl<F: Read + Seek + 'static> SequentialStream for CompoundStream<F> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<u32, Error> {
+            self.read_at_pos(buf).map(|n| n as u32)
+        }
+
+        fn write(&mut self, buf: &[u8]) -> Result<u32, Error> {
+            self.write_at_pos(buf).map(|n| n as u32)
+        }
+    }
+
+    unsafe impl<F: Read + Seek + 'static> custom::Stream for CompoundStream<F> {
+        fn try_clone(&self) -> Result<Stream, Error> {
+            Ok(self.clone().into_stream())
+        }
+
+        fn commit(&mut self, _flags: CommitFlags) -> Result<Status, Error> {
+            Ok(Status::OK)
+        }
+
+        fn copy_to(
+            &mut self,
+            other: &mut Stream,
+            count: u64,
+            read: &mut u64,
+            written: &mut u64,
+        ) -> Result<Status, Error> {
+            copy_to(self, other, count, read, written)
+        }
+
+        fn lock_region(&mut self, _: u64, _: u64, _: LockType) -> Result<Status, Error> {
+            Err(Error::STG_INVALIDFUNCTION)
+        }
+
+        fn revert(&mut self) -> Result<Status, Error> {
+            Ok(Status::OK)
+        }
+
+        fn seek(&mut self, seek: SeekFrom) -> Result<u64, Error> {
+            self.seek_to(seek)
+        }
+
+        fn set_size(&mut self, newsize: u64) -> Result<Status, Error> {
+            self.set_len(newsize).map(|()| Status::OK)
+        }
+
+        fn stat(&mut self, stat: &mut Stat, flags: StatFlags) -> Result<Status, Error> {
+            let info = self.info()?;
+            let mut value = Stat::stream(info.size);
+            if flags.0 & StatFlags::NO_NAME.0 == 0 {
+                value.name = Some(CoTaskWString::from(&*info.name));
+            }
+            // The caller's STATSTG is uninitialized, so don't drop its name.
+            unsafe { std::ptr::write(stat, value) };
+            Ok(Status::OK)
+        }
+
+        fn unlock_region(&mut self, _: u64, _: u64, _: LockType) -> Result<Status, Error> {
+            Err(Error::STG_INVALIDFUNCTION)
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn compound_file_roundtrip() {
+    use crate::objidl::MemoryStream;
+
+    let backing = MemoryStream::new();
+    let mut cf = CompoundFile::create(backing.clone()).unwrap();
+    let root = cf.root();
+    let assets = root.create_storage("Assets").unwrap();
+    assets.set_clsid([7; 16]).unwrap();
+    let mut small = assets.create_stream("small").unwrap();
+    small.write_all(b"hello").unwrap();
+    let large: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+    root.create_stream("Large")
+        .unwrap()
+        .write_all(&large)
+        .unwrap();
+    for i in 0..20 {
+        root.create_stream(&format!("s{}", i)).unwrap();
+    }
+    assert_eq!(
+        root.create_stream("LARGE").unwrap_err(),
+        Error::STG_FILEALREADYEXISTS
+    );
+    assert_eq!(
+        root.create_stream("a/b").unwrap_err(),
+        Error::STG_INVALIDNAME
+    );
+    cf.commit().unwrap();
+    drop((root, assets, small));
+    assert_eq!(cf.into_inner().unwrap().len() % 512, 0);
+
+    let cf = CompoundFile::open(backing.clone()).unwrap();
+    let root = cf.root();
+    let entries = root.entries().unwrap();
+    assert_eq!(entries.len(), 22);
+    assert_eq!(entries[0].name, "s0");
+    let assets = root.open_storage("assets").unwrap();
+    assert_eq!(assets.info().unwrap().clsid, [7; 16]);
+    let mut s = String::new();
+    let mut small = assets.open_stream("Small").unwrap();
+    small.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "hello");
+    let mut data = Vec::new();
+    root.open_stream("Large")
+        .unwrap()
+        .read_to_end(&mut data)
+        .unwrap();
+    assert_eq!(data, large);
+    assert_eq!(
+        root.open_stream("Assets").unwrap_err(),
+        Error::STG_FILENOTFOUND
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn compound_file_edit_and_revert() {
+    use crate::objidl::MemoryStream;
+
+    let backing = MemoryStream::new();
+    let mut cf = CompoundFile::create(backing.clone()).unwrap();
+    let root = cf.root();
+    root.create_storage("dir")
+        .unwrap()
+        .create_stream("a")
+        .unwrap();
+    root.create_stream("b").unwrap().write_all(b"bee").unwrap();
+    cf.commit().unwrap();
+    let committed_len = backing.len();
+
+    root.rename("b", "c").unwrap();
+    root.delete("dir").unwrap();
+    let mut c = root.open_stream("c").unwrap();
+    c.seek(SeekFrom::End(0)).unwrap();
+    c.write_all(b"!").unwrap();
+    assert_eq!(root.entries().unwrap().len(), 1);
+
+    cf.revert().unwrap();
+    assert_eq!(root.entries().unwrap_err(), Error::STG_REVERTED);
+    drop(root);
+    let root = cf.root();
+    let names: Vec<_> = root
+        .entries()
+        .unwrap()
+        .into_iter()
+        .map(|e| e.name)
+        .collect();
+    assert_eq!(names, ["b", "dir"]);
+
+    let mut b = root.open_stream("b").unwrap();
+    b.set_len(1).unwrap();
+    root.delete("dir").unwrap();
+    cf.commit().unwrap();
+    assert_eq!(backing.len(), committed_len);
+    drop((root, b, c));
+    cf.into_inner().unwrap();
+
+    let cf = CompoundFile::open(backing).unwrap();
+    let root = cf.root();
+    assert_eq!(root.entries().unwrap().len(), 1);
+    assert_eq!(root.open_stream("b").unwrap().len(), Ok(1));
+}
+
+#[cfg(test)]
+#[test]
+fn compound_file_corrupt_difat() {
+    use crate::objidl::MemoryStream;
+
+    // A header claiming `num_fat` FAT sectors and `num_difat` DIFAT sectors,
+    // whose DIFAT chain starts at sector 0 and follows `links`, in a file
+    // of 300 empty sectors.
+    let open = |num_fat: u32, num_difat: u32, links: &[u32]| {
+        let mut header = Header::new(3);
+        header.num_fat_sectors = num_fat;
+        header.num_difat_sectors = num_difat;
+        header.first_difat_sector = 0;
+        let mut file = Vec::new();
+        header.write(&mut file);
+        file.resize(512 * 301, 0);
+        for (sect, &next) in links.iter().enumerate() {
+            let at = 512 * (sect + 2) - 4;
+            file[at..at + 4].copy_from_slice(&next.to_le_bytes());
+        }
+        CompoundFile::open(MemoryStream::from_vec(file)).unwrap_err()
+    };
+
+    // Counts that can't fit in the file, with a self-linked DIFAT sector.
+    assert_eq!(open(0x0100_0000, u32::MAX, &[0]), Error::STG_DOCFILECORRUPT);
+    // A DIFAT loop within the declared count.
+    assert_eq!(open(250, 5, &[0]), Error::STG_DOCFILECORRUPT);
+    // A chain longer than the declared count.
+    assert_eq!(open(250, 1, &[1, 2]), Error::STG_DOCFILECORRUPT);
+}
+
+#[cfg(test)]
+#[test]
+fn compound_file_deep_nesting() {
+    use crate::objidl::MemoryStream;
+
+    let backing = MemoryStream::new();
+    let mut cf = CompoundFile::create(backing.clone()).unwrap();
+    let mut storage = cf.root();
+    for _ in 0..20_000 {
+        storage = storage.create_storage("d").unwrap();
+    }
+    storage.create_stream("leaf").unwrap();
+    cf.commit().unwrap();
+    drop(storage);
+    cf.into_inner().unwrap();
+
+    let cf = CompoundFile::open(backing).unwrap();
+    let mut storage = cf.root();
+    for _ in 0..20_000 {
+        storage = storage.open_storage("d").unwrap();
+    }
+    assert!(storage.open_stream("leaf").is_ok());
+}
@@ -0,0 +1,282 @@
+//! The on-disk structures of MS-CFB: the header and directory entries.
+
+use crate::Error;
+
+use std::cmp::Ordering;
+use std::convert::TryInto;
+
+pub const SIGNATURE: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+pub const HEADER_SIZE: usize = 512;
+pub const DIR_ENTRY_SIZE: usize = 128;
+pub const MINI_SECTOR_SHIFT: u16 = 6;
+pub const MINI_SECTOR_SIZE: usize = 1 << MINI_SECTOR_SHIFT;
+pub const MINI_STREAM_CUTOFF: u64 = 4096;
+/// The number of FAT sector locations stored in the header.
+pub const HEADER_DIFAT_LEN: usize = 109;
+
+pub const MAXREGSECT: u32 = 0xFFFF_FFFA;
+pub const DIFSECT: u32 = 0xFFFF_FFFC;
+pub const FATSECT: u32 = 0xFFFF_FFFD;
+pub const ENDOFCHAIN: u32 = 0xFFFF_FFFE;
+pub const FREESECT: u32 = 0xFFFF_FFFF;
+pub const NOSTREAM: u32 = 0xFFFF_FFFF;
+
+pub const TYPE_UNALLOCATED: u8 = 0;
+pub const TYPE_STORAGE: u8 = 1;
+pub const TYPE_STREAM: u8 = 2;
+pub const TYPE_ROOT: u8 = 5;
+
+pub const COLOR_RED: u8 = 0;
+pub const COLOR_BLACK: u8 = 1;
+
+/// The longest name allowed, in UTF-16 code units, not counting the null.
+pub const MAX_NAME_LEN: usize = 31;
+
+pub fn u16_at(buf: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes(buf[at..at + 2].try_into().unwrap())
+}
+
+pub fn u32_at(buf: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(buf[at..at + 4].try_into().unwrap())
+}
+
+pub fn u64_at(buf: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(buf[at..at + 8].try_into().unwrap())
+}
+
+#[derive(Clone, Debug)]
+pub struct Header {
+    pub major_version: u16,
+    pub sector_shift: u16,
+    pub num_dir_sectors: u32,
+    pub num_fat_sectors: u32,
+    pub first_dir_sector: u32,
+    pub first_minifat_sector: u32,
+    pub num_minifat_sectors: u32,
+    pub first_difat_sector: u32,
+    pub num_difat_sectors: u32,
+    pub difat: [u32; HEADER_DIFAT_LEN],
+}
+
+impl Header {
+    /// A header for a file of the given major version, with every location
+    /// empty.
+    pub fn new(major_version: u16) -> Header {
+        Header {
+            major_version,
+            sector_shift: if major_version == 4 { 12 } else { 9 },
+            num_dir_sectors: 0,
+            num_fat_sectors: 0,
+            first_dir_sector: ENDOFCHAIN,
+            first_minifat_sector: ENDOFCHAIN,
+            num_minifat_sectors: 0,
+            first_difat_sector: ENDOFCHAIN,
+            num_difat_sectors: 0,
+            difat: [FREESECT; HEADER_DIFAT_LEN],
+        }
+    }
+
+    pub fn sector_size(&self) -> usize {
+        1 << self.sector_shift
+    }
+
+    /// Parses and validates the first 512 bytes of a file. Fails with
+    /// `Error::STG_INVALIDHEADER` if they aren't a compound file header
+    /// this implementation understands.
+    pub fn parse(buf: &[u8]) -> Result<Header, Error> {
+        if buf.len() < HEADER_SIZE || buf[..8] != SIGNATURE {
+            return Err(Error::STG_INVALIDHEADER);
+        }
+        let major_version = u16_at(buf, 0x1A);
+        let sector_shift = u16_at(buf, 0x1E);
+        let valid = match major_version {
+            3 => sector_shift == 9,
+            4 => sector_shift == 12,
+            _ => false,
+        };
+        if !valid || u16_at(buf, 0x1C) != 0xFFFE || u16_at(buf, 0x20) != MINI_SECTOR_SHIFT {
+            return Err(Error::STG_INVALIDHEADER);
+        }
+        if u64::from(u32_at(buf, 0x38)) != MINI_STREAM_CUTOFF {
+            return Err(Error::STG_INVALIDHEADER);
+        }
+
+        let mut difat = [FREESECT; HEADER_DIFAT_LEN];
+        for (i, sect) in difat.iter_mut().enumerate() {
+            *sect = u32_at(buf, 0x4C + i * 4);
+        }
+        Ok(Header {
+            major_version,
+            sector_shift,
+            num_dir_sectors: u32_at(buf, 0x28),
+            num_fat_sectors: u32_at(buf, 0x2C),
+            first_dir_sector: u32_at(buf, 0x30),
+            first_minifat_sector: u32_at(buf, 0x3C),
+            num_minifat_sectors: u32_at(buf, 0x40),
+            first_difat_sector: u32_at(buf, 0x44),
+            num_difat_sectors: u32_at(buf, 0x48),
+            difat,
+        })
+    }
+
+    /// Serializes the header, padded to a whole sector.
+    pub fn write(&self, out: &mut Vec<u8>) {
+        let start = out.len();
+        out.extend_from_slice(&SIGNATURE);
+        out.extend_from_slice(&[0; 16]);
+        out.extend_from_slice(&0x003Eu16.to_le_bytes());
+        out.extend_from_slice(&self.major_version.to_le_bytes());
+        out.extend_from_slice(&0xFFFEu16.to_le_bytes());
+        out.extend_from_slice(&self.sector_shift.to_le_bytes());
+        out.extend_from_slice(&MINI_SECTOR_SHIFT.to_le_bytes());
+        out.extend_from_slice(&[0; 6]);
+        for v in &[
+            self.num_dir_sectors,
+            self.num_fat_sectors,
+            self.first_dir_sector,
+            0,
+            MINI_STREAM_CUTOFF as u32,
+            self.first_minifat_sector,
+            self.num_minifat_sectors,
+            self.first_difat_sector,
+            self.num_difat_sectors,
+        ] {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        for sect in self.difat.iter() {
+            out.extend_from_slice(&sect.to_le_bytes());
+        }
+        debug_assert_eq!(out.len() - start, HEADER_SIZE);
+        out.resize(start + self.sector_size(), 0);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DirEntry {
+    pub name: String,
+    pub object_type: u8,
+    pub color: u8,
+    pub left: u32,
+    pub right: u32,
+    pub child: u32,
+    pub clsid: [u8; 16],
+    pub state_bits: u32,
+    pub ctime: u64,
+    pub mtime: u64,
+    pub start_sector: u32,
+    pub size: u64,
+}
+
+impl DirEntry {
+    /// An unallocated entry.
+    pub fn empty() -> DirEntry {
+        DirEntry {
+            name: String::new(),
+            object_type: TYPE_UNALLOCATED,
+            color: COLOR_RED,
+            left: NOSTREAM,
+            right: NOSTREAM,
+            child: NOSTREAM,
+            clsid: [0; 16],
+            state_bits: 0,
+            ctime: 0,
+            mtime: 0,
+            start_sector: 0,
+            size: 0,
+        }
+    }
+
+    /// Parses a 128-byte entry. Version 3 files only use the low 32 bits of
+    /// the size, and the high bits may hold garbage.
+    pub fn parse(buf: &[u8], major_version: u16) -> Result<DirEntry, Error> {
+        let name_len = usize::from(u16_at(buf, 0x40));
+        if name_len > 64 || name_len % 2 != 0 {
+            return Err(Error::STG_DOCFILECORRUPT);
+        }
+        let units: Vec<u16> = (0..name_len.saturating_sub(2) / 2)
+            .map(|i| u16_at(buf, i * 2))
+            .collect();
+
+        let mut clsid = [0; 16];
+        clsid.copy_from_slice(&buf[0x50..0x60]);
+        let size = u64_at(buf, 0x78);
+        Ok(DirEntry {
+            name: String::from_utf16_lossy(&units),
+            object_type: buf[0x42],
+            color: buf[0x43],
+            left: u32_at(buf, 0x44),
+            right: u32_at(buf, 0x48),
+            child: u32_at(buf, 0x4C),
+            clsid,
+            state_bits: u32_at(buf, 0x60),
+            ctime: u64_at(buf, 0x64),
+            mtime: u64_at(buf, 0x6C),
+            start_sector: u32_at(buf, 0x74),
+            size: if major_version == 3 {
+                size & 0xFFFF_FFFF
+            } else {
+                size
+            },
+        })
+    }
+
+    pub fn write(&self, out: &mut Vec<u8>) {
+        let start = out.len();
+        let units: Vec<u16> = self.name.encode_utf16().collect();
+        debug_assert!(units.len() <= MAX_NAME_LEN);
+        for unit in &units {
+            out.extend_from_slice(&unit.to_le_bytes());
+        }
+        out.resize(start + 64, 0);
+        let name_len = if units.is_empty() && self.object_type == TYPE_UNALLOCATED {
+            0
+        } else {
+            (units.len() as u16 + 1) * 2
+        };
+        out.extend_from_slice(&name_len.to_le_bytes());
+        out.push(self.object_type);
+        out.push(self.color);
+        out.extend_from_slice(&self.left.to_le_bytes());
+        out.extend_from_slice(&self.right.to_le_bytes());
+        out.extend_from_slice(&self.child.to_le_bytes());
+        out.extend_from_slice(&self.clsid);
+        out.extend_from_slice(&self.state_bits.to_le_bytes());
+        out.extend_from_slice(&self.ctime.to_le_bytes());
+        out.extend_from_slice(&self.mtime.to_le_bytes());
+        out.extend_from_slice(&self.start_sector.to_le_bytes());
+        out.extend_from_slice(&self.size.to_le_bytes());
+        debug_assert_eq!(out.len() - start, DIR_ENTRY_SIZE);
+    }
+}
+
+/// Checks that `name` can be stored in a directory entry, failing with
+/// `Error::STG_INVALIDNAME` otherwise.
+pub fn validate_name(name: &str) -> Result<(), Error> {
+    let len = name.encode_utf16().count();
+    if len == 0 || len > MAX_NAME_LEN || name.contains(&['/', '\\', ':', '!'][..]) {
+        return Err(Error::STG_INVALIDNAME);
+    }
+    Ok(())
+}
+
+/// The order of siblings in the directory tree: shorter names first, then
+/// by code unit after simple uppercasing.
+pub fn compare_names(a: &str, b: &str) -> Ordering {
+    let a: Vec<u16> = a.encode_utf16().collect();
+    let b: Vec<u16> = b.encode_utf16().collect();
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().map(|&c| upper(c)).cmp(b.iter().map(|&c| upper(c))))
+}
+
+fn upper(unit: u16) -> u16 {
+    let c = match std::char::from_u32(u32::from(unit)) {
+        Some(c) => c,
+        None => return unit,
+    };
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(u), None) if (u as u32) < 0x10000 => u as u16,
+        _ => unit,
+    }
+}
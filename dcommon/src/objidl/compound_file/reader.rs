@@ -0,0 +1,242 @@
+//! Parses a compound file into the in-memory directory model.
+
+use super::format::*;
+use super::{Data, EntryKind, Node};
+use crate::minwindef::FileTime;
+use crate::objidl::from_io;
+use crate::Error;
+
+use std::collections::HashSet;
+use std::io::{Read, Seek, SeekFrom};
+
+pub struct Loaded {
+    pub header: Header,
+    pub mini_chain: Vec<u32>,
+    pub nodes: Vec<Option<Node>>,
+}
+
+/// Reads sector `sect` of a file with `sector_size`-byte sectors.
+fn read_sector<F: Read + Seek>(
+    file: &mut F,
+    sector_size: usize,
+    sect: u32,
+    buf: &mut [u8],
+) -> Result<(), Error> {
+    let offset = (u64::from(sect) + 1) * sector_size as u64;
+    file.seek(SeekFrom::Start(offset)).map_err(from_io)?;
+    file.read_exact(buf).map_err(|_| Error::STG_DOCFILECORRUPT)
+}
+
+/// Follows a chain through `table`, failing on loops and bad links.
+pub fn follow(table: &[u32], start: u32) -> Result<Vec<u32>, Error> {
+    let mut chain = Vec::new();
+    let mut next = start;
+    while next != ENDOFCHAIN {
+        if next as usize >= table.len() || chain.len() >= table.len() {
+            return Err(Error::STG_DOCFILECORRUPT);
+        }
+        chain.push(next);
+        next = table[next as usize];
+    }
+    Ok(chain)
+}
+
+pub fn load<F: Read + Seek>(file: &mut F) -> Result<Loaded, Error> {
+    let mut buf = [0; HEADER_SIZE];
+    file.seek(SeekFrom::Start(0)).map_err(from_io)?;
+    file.read_exact(&mut buf)
+        .map_err(|_| Error::STG_INVALIDHEADER)?;
+    let header = Header::parse(&buf)?;
+    let ssz = header.sector_size();
+    let per_sector = ssz / 4;
+    let mut sector = vec![0; ssz];
+
+    // The header counts can't be trusted to size allocations until they are
+    // known to fit in the file.
+    let file_len = file.seek(SeekFrom::End(0)).map_err(from_io)?;
+    let file_sectors = (file_len / ssz as u64).saturating_sub(1);
+    if u64::from(header.num_fat_sectors) > file_sectors
+        || u64::from(header.num_difat_sectors) > file_sectors
+    {
+        return Err(Error::STG_DOCFILECORRUPT);
+    }
+
+    // Locate the FAT through the header and the DIFAT chain.
+    let num_fat = header.num_fat_sectors as usize;
+    let mut fat_sectors: Vec<u32> = header.difat.iter().take(num_fat).cloned().collect();
+    let mut next = header.first_difat_sector;
+    let mut difat_seen = HashSet::new();
+    while fat_sectors.len() < num_fat {
+        if next > MAXREGSECT
+            || difat_seen.len() >= header.num_difat_sectors as usize
+            || !difat_seen.insert(next)
+        {
+            return Err(Error::STG_DOCFILECORRUPT);
+        }
+        read_sector(file, ssz, next, &mut sector)?;
+        for i in 0..per_sector - 1 {
+            if fat_sectors.len() < num_fat {
+                fat_sectors.push(u32_at(&sector, i * 4));
+            }
+        }
+        next = u32_at(&sector, (per_sector - 1) * 4);
+    }
+
+    let mut fat = Vec::with_capacity(num_fat * per_sector);
+    for &sect in &fat_sectors {
+        if sect > MAXREGSECT {
+            return Err(Error::STG_DOCFILECORRUPT);
+        }
+        read_sector(file, ssz, sect, &mut sector)?;
+        fat.extend((0..per_sector).map(|i| u32_at(&sector, i * 4)));
+    }
+
+    let read_chain = |file: &mut F, chain: &[u32]| -> Result<Vec<u8>, Error> {
+        let mut data = vec![0; chain.len() * ssz];
+        for (i, &sect) in chain.iter().enumerate() {
+            read_sector(file, ssz, sect, &mut data[i * ssz..(i + 1) * ssz])?;
+        }
+        Ok(data)
+    };
+
+    let dir_chain = follow(&fat, header.first_dir_sector)?;
+    let dir_data = read_chain(file, &dir_chain)?;
+    let entries = dir_data
+        .chunks(DIR_ENTRY_SIZE)
+        .map(|chunk| DirEntry::parse(chunk, header.major_version))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let minifat = if header.num_minifat_sectors > 0 {
+        let chain = follow(&fat, header.first_minifat_sector)?;
+        let data = read_chain(file, &chain)?;
+        data.chunks(4).map(|c| u32_at(c, 0)).collect()
+    } else {
+        Vec::new()
+    };
+
+    let root = match entries.first() {
+        Some(root) if root.object_type == TYPE_ROOT => root,
+        _ => return Err(Error::STG_DOCFILECORRUPT),
+    };
+    let mini_chain = if root.size > 0 {
+        follow(&fat, root.start_sector)?
+    } else {
+        Vec::new()
+    };
+
+    let mut loader = Loader {
+        entries: &entries,
+        fat: &fat,
+        minifat: &minifat,
+        sector_size: ssz,
+        visited: vec![false; entries.len()],
+        nodes: Vec::new(),
+    };
+    loader.visited[0] = true;
+    loader.nodes.push(Some(Node {
+        name: root.name.clone(),
+        kind: EntryKind::Storage,
+        clsid: root.clsid,
+        state_bits: root.state_bits,
+        ctime: FileTime::from(root.ctime),
+        mtime: FileTime::from(root.mtime),
+        children: Vec::new(),
+        data: Data::Memory(Vec::new()),
+    }));
+
+    // Storages still to be filled in, as (node index, child tree root). A
+    // work list rather than recursion keeps deep nesting off the call stack.
+    let mut pending = vec![(0, root.child)];
+    while let Some((parent, child)) = pending.pop() {
+        let children = loader.children(child, &mut pending)?;
+        loader.nodes[parent].as_mut().unwrap().children = children;
+    }
+
+    Ok(Loaded {
+        header,
+        mini_chain,
+        nodes: loader.nodes,
+    })
+}
+
+struct Loader<'a> {
+    entries: &'a [DirEntry],
+    fat: &'a [u32],
+    minifat: &'a [u32],
+    sector_size: usize,
+    visited: Vec<bool>,
+    nodes: Vec<Option<Node>>,
+}
+
+impl<'a> Loader<'a> {
+    /// Loads every entry in the sibling tree rooted at `id`, returning their
+    /// node indices. Storages are added to `pending` to have their own
+    /// children loaded later.
+    fn children(&mut self, id: u32, pending: &mut Vec<(usize, u32)>) -> Result<Vec<usize>, Error> {
+        let mut result = Vec::new();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            if id == NOSTREAM {
+                continue;
+            }
+            let i = id as usize;
+            if i >= self.entries.len() || self.visited[i] {
+                return Err(Error::STG_DOCFILECORRUPT);
+            }
+            self.visited[i] = true;
+            let entry = &self.entries[i];
+            stack.push(entry.left);
+            stack.push(entry.right);
+            let index = self.load(entry)?;
+            if entry.object_type == TYPE_STORAGE {
+                pending.push((index, entry.child));
+            }
+            result.push(index);
+        }
+        Ok(result)
+    }
+
+    /// Adds a node for `entry`. The children of a storage are left empty.
+    fn load(&mut self, entry: &DirEntry) -> Result<usize, Error> {
+        let (kind, data) = match entry.object_type {
+            TYPE_STORAGE => (EntryKind::Storage, Data::Memory(Vec::new())),
+            TYPE_STREAM => {
+                let mini = entry.size < MINI_STREAM_CUTOFF;
+                let chain = if entry.size == 0 {
+                    Vec::new()
+                } else if mini {
+                    follow(self.minifat, entry.start_sector)?
+                } else {
+                    follow(self.fat, entry.start_sector)?
+                };
+                let unit = if mini {
+                    MINI_SECTOR_SIZE
+                } else {
+                    self.sector_size
+                };
+                if ((chain.len() * unit) as u64) < entry.size {
+                    return Err(Error::STG_DOCFILECORRUPT);
+                }
+                let data = Data::Original {
+                    chain,
+                    size: entry.size,
+                    mini,
+                };
+                (EntryKind::Stream, data)
+            }
+            _ => return Err(Error::STG_DOCFILECORRUPT),
+        };
+
+        self.nodes.push(Some(Node {
+            name: entry.name.clone(),
+            kind,
+            clsid: entry.clsid,
+            state_bits: entry.state_bits,
+            ctime: FileTime::from(entry.ctime),
+            mtime: FileTime::from(entry.mtime),
+            children: Vec::new(),
+            data,
+        }));
+        Ok(self.nodes.len() - 1)
+    }
+}
@@ -0,0 +1,377 @@
+//! Serializes the in-memory directory model as a complete compound file.
+//!
+//! Everything is laid out contiguously: the FAT, the DIFAT, the directory,
+//! the MiniFAT, the mini stream and then each large stream in directory
+//! order. Sibling trees are rebuilt balanced on every write.
+
+use super::format::*;
+use super::{Data, EntryKind, Node};
+use crate::objidl::from_io;
+use crate::Error;
+
+use std::io::{Seek, SeekFrom, Write};
+
+/// Where a stream's data ended up.
+#[derive(Copy, Clone, Debug)]
+pub struct Placement {
+    pub start: u32,
+    pub mini: bool,
+}
+
+pub struct Layout {
+    /// Indexed like the nodes; None for storages and empty streams.
+    pub placements: Vec<Option<Placement>>,
+    /// The sectors holding the mini stream, in order.
+    pub mini_chain: Vec<u32>,
+}
+
+/// The data of a stream node. Every stream must have been loaded into
+/// memory before writing.
+fn contents(node: &Node) -> &[u8] {
+    match &node.data {
+        Data::Memory(data) => data,
+        Data::Original { .. } => unreachable!("stream data must be loaded before writing"),
+    }
+}
+
+/// Writes the file described by `nodes`, whose root is node 0. The file is
+/// padded with free sectors to at least `min_sectors` sectors, so that it
+/// never has to shrink.
+pub fn write<W: Write + Seek>(
+    out: &mut W,
+    header: &Header,
+    nodes: &[Option<Node>],
+    min_sectors: u64,
+) -> Result<Layout, Error> {
+    let ssz = header.sector_size() as u64;
+    let per_sector = ssz / 4;
+    let entries_per_sector = ssz / DIR_ENTRY_SIZE as u64;
+
+    // Number the entries depth first, root first.
+    let mut order = vec![0];
+    let mut dir_id = vec![NOSTREAM; nodes.len()];
+    dir_id[0] = 0;
+    let mut i = 0;
+    while i < order.len() {
+        let node = nodes[order[i]].as_ref().unwrap();
+        for &child in &node.children {
+            dir_id[child] = order.len() as u32;
+            order.push(child);
+        }
+        i += 1;
+    }
+
+    // Size up the streams.
+    let mut placements: Vec<Option<Placement>> = vec![None; nodes.len()];
+    let mut mini_sectors = 0u64;
+    let mut large_sectors = 0u64;
+    for &id in &order[1..] {
+        let node = nodes[id].as_ref().unwrap();
+        if node.kind != EntryKind::Stream {
+            continue;
+        }
+        let len = contents(node).len() as u64;
+        if header.major_version == 3 && len > u64::from(u32::MAX) {
+            return Err(Error::STG_DOCFILETOOLARGE);
+        }
+        if len == 0 {
+            continue;
+        }
+        if len < MINI_STREAM_CUTOFF {
+            placements[id] = Some(Placement {
+                start: mini_sectors as u32,
+                mini: true,
+            });
+            mini_sectors += len.div_ceil(MINI_SECTOR_SIZE as u64);
+        } else {
+            // Large streams are numbered once the sector counts are known.
+            placements[id] = Some(Placement {
+                start: 0,
+                mini: false,
+            });
+            large_sectors += len.div_ceil(ssz);
+        }
+    }
+
+    let dir_sectors = (order.len() as u64).div_ceil(entries_per_sector);
+    let minifat_sectors = (mini_sectors * 4).div_ceil(ssz);
+    let mini_bytes = mini_sectors * MINI_SECTOR_SIZE as u64;
+    let ministream_sectors = mini_bytes.div_ceil(ssz);
+    let base = dir_sectors + minifat_sectors + ministream_sectors + large_sectors;
+
+    let (mut fat_sectors, mut difat_sectors) = (0, 0);
+    let total = loop {
+        let total = (base + fat_sectors + difat_sectors).max(min_sectors);
+        let fat = total.div_ceil(per_sector);
+        let difat = if fat > HEADER_DIFAT_LEN as u64 {
+            (fat - HEADER_DIFAT_LEN as u64).div_ceil(per_sector - 1)
+        } else {
+            0
+        };
+        if fat == fat_sectors && difat == difat_sectors {
+            break total;
+        }
+        fat_sectors = fat;
+        difat_sectors = difat;
+    };
+    if total > u64::from(MAXREGSECT) {
+        return Err(Error::STG_DOCFILETOOLARGE);
+    }
+
+    let difat_start = fat_sectors;
+    let dir_start = difat_start + difat_sectors;
+    let minifat_start = dir_start + dir_sectors;
+    let ministream_start = minifat_start + minifat_sectors;
+    let mut next_sector = ministream_start + ministream_sectors;
+
+    // Build the FAT.
+    let mut fat = vec![FREESECT; (fat_sectors * per_sector) as usize];
+    let chain = |fat: &mut Vec<u32>, start: u64, count: u64| {
+        for s in start..start + count {
+            fat[s as usize] = if s + 1 == start + count {
+                ENDOFCHAIN
+            } else {
+                s as u32 + 1
+            };
+        }
+    };
+    for s in 0..fat_sectors {
+        fat[s as usize] = FATSECT;
+    }
+    for s in difat_start..dir_start {
+        fat[s as usize] = DIFSECT;
+    }
+    chain(&mut fat, dir_start, dir_sectors);
+    chain(&mut fat, minifat_start, minifat_sectors);
+    chain(&mut fat, ministream_start, ministream_sectors);
+    for &id in &order[1..] {
+        if let Some(placement) = &mut placements[id] {
+            if !placement.mini {
+                let len = contents(nodes[id].as_ref().unwrap()).len() as u64;
+                let count = len.div_ceil(ssz);
+                placement.start = next_sector as u32;
+                chain(&mut fat, next_sector, count);
+                next_sector += count;
+            }
+        }
+    }
+
+    let mut header = header.clone();
+    header.num_fat_sectors = fat_sectors as u32;
+    header.difat = [FREESECT; HEADER_DIFAT_LEN];
+    for (i, slot) in header
+        .difat
+        .iter_mut()
+        .enumerate()
+        .take(fat_sectors as usize)
+    {
+        *slot = i as u32;
+    }
+    header.first_difat_sector = if difat_sectors > 0 {
+        difat_start as u32
+    } else {
+        ENDOFCHAIN
+    };
+    header.num_difat_sectors = difat_sectors as u32;
+    header.first_dir_sector = dir_start as u32;
+    header.num_dir_sectors = if header.major_version == 4 {
+        dir_sectors as u32
+    } else {
+        0
+    };
+    header.first_minifat_sector = if minifat_sectors > 0 {
+        minifat_start as u32
+    } else {
+        ENDOFCHAIN
+    };
+    header.num_minifat_sectors = minifat_sectors as u32;
+
+    let mut buf = Vec::with_capacity(ssz as usize);
+    out.seek(SeekFrom::Start(0)).map_err(from_io)?;
+    header.write(&mut buf);
+    let mut sink = Sink {
+        out,
+        buf,
+        sector_size: ssz as usize,
+    };
+    sink.flush_padded()?;
+
+    for value in &fat {
+        sink.buf.extend_from_slice(&value.to_le_bytes());
+    }
+    sink.flush_padded()?;
+
+    // DIFAT sectors list the FAT sectors the header has no room for.
+    for d in 0..difat_sectors {
+        let first = HEADER_DIFAT_LEN as u64 + d * (per_sector - 1);
+        for s in first..first + per_sector - 1 {
+            let value = if s < fat_sectors { s as u32 } else { FREESECT };
+            sink.buf.extend_from_slice(&value.to_le_bytes());
+        }
+        let next = if d + 1 < difat_sectors {
+            (difat_start + d + 1) as u32
+        } else {
+            ENDOFCHAIN
+        };
+        sink.buf.extend_from_slice(&next.to_le_bytes());
+    }
+    sink.flush_padded()?;
+
+    for (i, &id) in order.iter().enumerate() {
+        let node = nodes[id].as_ref().unwrap();
+        let mut entry = DirEntry::empty();
+        entry.name = node.name.clone();
+        entry.clsid = node.clsid;
+        entry.state_bits = node.state_bits;
+        entry.ctime = node.ctime.into();
+        entry.mtime = node.mtime.into();
+        entry.color = COLOR_BLACK;
+        if i == 0 {
+            entry.object_type = TYPE_ROOT;
+            entry.start_sector = if mini_bytes > 0 {
+                ministream_start as u32
+            } else {
+                ENDOFCHAIN
+            };
+            entry.size = mini_bytes;
+        } else if node.kind == EntryKind::Storage {
+            entry.object_type = TYPE_STORAGE;
+        } else {
+            entry.object_type = TYPE_STREAM;
+            entry.size = contents(node).len() as u64;
+            entry.start_sector = placements[id].map_or(ENDOFCHAIN, |p| p.start);
+        }
+        entry.write(&mut sink.buf);
+    }
+    // Fill in the sibling trees now that every entry has its slot.
+    for &id in &order {
+        let node = nodes[id].as_ref().unwrap();
+        if node.children.is_empty() {
+            continue;
+        }
+        let mut sorted: Vec<usize> = node.children.clone();
+        sorted.sort_by(|&a, &b| {
+            let a = &nodes[a].as_ref().unwrap().name;
+            let b = &nodes[b].as_ref().unwrap().name;
+            compare_names(a, b)
+        });
+        let depth = usize::BITS - 1 - sorted.len().leading_zeros();
+        let perfect = (sorted.len() + 1).is_power_of_two();
+        let root = build_tree(&mut sink.buf, &sorted, &dir_id, 0, depth, perfect);
+        let at = dir_id[id] as usize * DIR_ENTRY_SIZE + 0x4C;
+        sink.buf[at..at + 4].copy_from_slice(&root.to_le_bytes());
+    }
+    for _ in order.len() as u64..dir_sectors * entries_per_sector {
+        DirEntry::empty().write(&mut sink.buf);
+    }
+    sink.flush_padded()?;
+
+    let mut minifat = vec![FREESECT; (minifat_sectors * per_sector) as usize];
+    for &id in &order[1..] {
+        if let Some(Placement { start, mini: true }) = placements[id] {
+            let len = contents(nodes[id].as_ref().unwrap()).len() as u64;
+            let count = len.div_ceil(MINI_SECTOR_SIZE as u64);
+            chain(&mut minifat, u64::from(start), count);
+        }
+    }
+    for value in &minifat {
+        sink.buf.extend_from_slice(&value.to_le_bytes());
+    }
+    sink.flush_padded()?;
+
+    for &id in &order[1..] {
+        if let Some(Placement { mini: true, .. }) = placements[id] {
+            let data = contents(nodes[id].as_ref().unwrap());
+            sink.buf.extend_from_slice(data);
+            let padded = (data.len() as u64).div_ceil(MINI_SECTOR_SIZE as u64);
+            sink.buf.resize(
+                sink.buf.len() + padded as usize * MINI_SECTOR_SIZE - data.len(),
+                0,
+            );
+        }
+    }
+    sink.flush_padded()?;
+
+    for &id in &order[1..] {
+        if let Some(Placement { mini: false, .. }) = placements[id] {
+            sink.write_padded(contents(nodes[id].as_ref().unwrap()))?;
+        }
+    }
+    let padding = vec![0; ssz as usize];
+    for _ in next_sector..total {
+        sink.out.write_all(&padding).map_err(from_io)?;
+    }
+    sink.out.flush().map_err(from_io)?;
+
+    Ok(Layout {
+        placements,
+        mini_chain: (ministream_start..ministream_start + ministream_sectors)
+            .map(|s| s as u32)
+            .collect(),
+    })
+}
+
+/// Links `sorted[..]` into a balanced tree whose nodes at `max_depth` are
+/// red unless the tree is perfect, so every path has the same number of
+/// black nodes. Returns the directory id of the subtree's root.
+fn build_tree(
+    dir: &mut [u8],
+    sorted: &[usize],
+    dir_id: &[u32],
+    depth: u32,
+    max_depth: u32,
+    perfect: bool,
+) -> u32 {
+    if sorted.is_empty() {
+        return NOSTREAM;
+    }
+    let mid = sorted.len() / 2;
+    let left = build_tree(dir, &sorted[..mid], dir_id, depth + 1, max_depth, perfect);
+    let right = build_tree(
+        dir,
+        &sorted[mid + 1..],
+        dir_id,
+        depth + 1,
+        max_depth,
+        perfect,
+    );
+
+    let id = dir_id[sorted[mid]];
+    let at = id as usize * DIR_ENTRY_SIZE;
+    dir[at + 0x43] = if depth == max_depth && !perfect {
+        COLOR_RED
+    } else {
+        COLOR_BLACK
+    };
+    dir[at + 0x44..at + 0x48].copy_from_slice(&left.to_le_bytes());
+    dir[at + 0x48..at + 0x4C].copy_from_slice(&right.to_le_bytes());
+    id
+}
+
+/// Buffers one region of the file at a time, padding each to whole sectors.
+struct Sink<'a, W> {
+    out: &'a mut W,
+    buf: Vec<u8>,
+    sector_size: usize,
+}
+
+impl<'a, W: Write> Sink<'a, W> {
+    fn flush_padded(&mut self) -> Result<(), Error> {
+        let len = self.buf.len();
+        let padded = len.div_ceil(self.sector_size) * self.sector_size;
+        self.buf.resize(padded, 0);
+        self.out.write_all(&self.buf).map_err(from_io)?;
+        self.buf.clear();
+        Ok(())
+    }
+
+    fn write_padded(&mut self, data: &[u8]) -> Result<(), Error> {
+        self.out.write_all(data).map_err(from_io)?;
+        let rem = data.len() % self.sector_size;
+        if rem != 0 {
+            let padding = vec![0; self.sector_size - rem];
+            self.out.write_all(&padding).map_err(from_io)?;
+        }
+        Ok(())
+    }
+}
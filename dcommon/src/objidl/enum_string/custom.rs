@@ -1,8 +1,7 @@
+use super::source::IterSource;
 use super::EnumString;
 use crate::helpers::CoTaskWString;
-use crate::{Error, Status};
-
-use std::iter::Fuse;
+use crate::Status;
 
 use com_impl::{com_impl, ComImpl, Refcount, VTable};
 use com_wrapper::ComWrapper;
@@ -18,9 +17,7 @@ where
 {
     vtbl: VTable<IEnumStringVtbl>,
     refcount: Refcount,
-    iter: Fuse<I>,
-    original: Option<Fuse<I>>,
-    clone_fn: Option<fn(&Fuse<I>) -> Fuse<I>>,
+    source: IterSource<I>,
 }
 
 impl<I> CustomEnumString<I>
@@ -28,15 +25,7 @@ where
     I: Iterator<Item = CoTaskWString> + Clone + 'static,
 {
     pub fn create(iter: I) -> EnumString {
-        let iter = iter.fuse();
-        let orig = Some(iter.clone());
-        let clone_fn: Option<fn(&Fuse<I>) -> Fuse<I>> = Some(<Fuse<I> as Clone>::clone);
-        unsafe {
-            let raw = Self::create_raw(iter, orig, clone_fn);
-            let raw: *mut CustomEnumString<I> = raw;
-            let raw: *mut IEnumString = raw as *mut _;
-            EnumString::from_raw(raw)
-        }
+        Self::from_source(IterSource::new(iter))
     }
 }
 
@@ -45,9 +34,12 @@ where
     I: Iterator<Item = CoTaskWString> + 'static,
 {
     pub fn create_without_clone(iter: I) -> EnumString {
-        let iter = iter.fuse();
+        Self::from_source(IterSource::without_clone(iter))
+    }
+
+    pub(super) fn from_source(source: IterSource<I>) -> EnumString {
         unsafe {
-            let raw = Self::create_raw(iter, None, None);
+            let raw = Self::create_raw(source);
             let raw: *mut CustomEnumString<I> = raw;
             let raw: *mut IEnumString = raw as *mut _;
             EnumString::from_raw(raw)
@@ -64,7 +56,7 @@ where
     unsafe fn next(&mut self, ce: u32, out: *mut LPOLESTR, pce: *mut u32) -> i32 {
         let mut tot = 0;
         for i in 0..ce {
-            let s = match self.iter.next() {
+            let s = match self.source.next() {
                 Some(s) => s,
                 None => break,
             };
@@ -81,15 +73,12 @@ where
 
     #[panic(result = "E_FAIL")]
     unsafe fn clone(&self, other: *mut *mut IEnumString) -> i32 {
-        let clone_fn = match self.clone_fn {
-            Some(f) => f,
-            None => return Error::NOTIMPL.0,
+        let source = match self.source.try_clone() {
+            Ok(source) => source,
+            Err(err) => return err.0,
         };
 
-        let iter = clone_fn(&self.iter);
-        let original = self.original.as_ref().map(clone_fn);
-
-        let raw = Self::create_raw(iter, original, Some(clone_fn));
+        let raw = Self::create_raw(source);
         let raw: *mut CustomEnumString<I> = raw;
         let raw: *mut IEnumString = raw as *mut _;
         *other = raw;
@@ -99,24 +88,18 @@ where
 
     #[panic(result = "E_FAIL")]
     unsafe fn reset(&mut self) -> i32 {
-        let orig = match &self.original {
-            Some(orig) => orig,
-            None => return Error::NOTIMPL.0,
-        };
-        let clone_fn = match self.clone_fn {
-            Some(f) => f,
-            None => return Error::NOTIMPL.0,
-        };
-
-        self.iter = clone_fn(orig);
-        Status::OK.0
+        match self.source.reset() {
+            Ok(()) => Status::OK.0,
+            Err(err) => err.0,
+        }
     }
 
     #[panic(result = "E_FAIL")]
     unsafe fn skip(&mut self, count: u32) -> i32 {
-        for _ in 0..count {
-            self.iter.next();
+        if self.source.skip(count) < count {
+            Status::FALSE.0
+        } else {
+            Status::OK.0
         }
-        Status::OK.0
     }
 }
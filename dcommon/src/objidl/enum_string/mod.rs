@@ -1,30 +1,93 @@
 use crate::error::{Error, Status};
 use crate::helpers::wide::CoTaskWString;
+use crate::objidl::enum_string::source::IterSource;
 
+use std::collections::VecDeque;
+
+#[cfg(windows)]
 use com_wrapper::ComWrapper;
+#[cfg(windows)]
 use winapi::shared::wtypesbase::LPOLESTR;
+#[cfg(windows)]
 use winapi::um::objidlbase::IEnumString;
+#[cfg(windows)]
 use wio::com::ComPtr;
 
+#[cfg(windows)]
 pub mod custom;
+mod source;
 
+/// An enumerator of strings. On Windows this is an `IEnumString`; elsewhere
+/// it can only be built from Rust strings, with `from_strings` or
+/// `collect`.
+#[cfg(windows)]
 #[derive(ComWrapper)]
 #[com(debug)]
 pub struct EnumString {
     ptr: ComPtr<IEnumString>,
 }
 
+#[cfg(not(windows))]
+pub struct EnumString {
+    source: Box<dyn source::Source>,
+}
+
 impl EnumString {
+    /// Builds an enumerator over `strings` that supports `Reset` and
+    /// `Clone`.
+    ///
+    /// Each string is handed out nul-terminated, so one with an interior
+    /// nul is cut short there.
+    pub fn from_strings(strings: Vec<String>) -> EnumString {
+        fn convert(s: String) -> CoTaskWString {
+            CoTaskWString::from(&s[..])
+        }
+        let convert: fn(String) -> CoTaskWString = convert;
+        EnumString::from_source(IterSource::new(strings.into_iter().map(convert)))
+    }
+
+    #[cfg(windows)]
+    fn from_source<I>(source: IterSource<I>) -> EnumString
+    where
+        I: Iterator<Item = CoTaskWString> + 'static,
+    {
+        custom::CustomEnumString::from_source(source)
+    }
+
+    #[cfg(not(windows))]
+    fn from_source<I>(source: IterSource<I>) -> EnumString
+    where
+        I: Iterator<Item = CoTaskWString> + 'static,
+    {
+        EnumString {
+            source: Box::new(source),
+        }
+    }
+
+    /// Iterates over the remaining strings, fetching them in batches. A
+    /// string that isn't valid UTF-16 is yielded as
+    /// `Error::WIN32_INVALID_DATA`, and iteration carries on after it. An
+    /// error from the enumerator itself is yielded last.
+    pub fn iter(&mut self) -> Strings<'_> {
+        Strings {
+            inner: self,
+            buffer: VecDeque::new(),
+            done: false,
+        }
+    }
+
     pub fn next_elem(&mut self) -> Result<Option<CoTaskWString>, Error> {
-        use std::mem::replace;
         let mut buf = [None];
         Ok(match self.next_elems(&mut buf)? {
             0 => None,
-            1 => replace(&mut buf[0], None),
+            1 => buf[0].take(),
             _ => panic!("Unexpected value returned from `IEnumString::Next`."),
         })
     }
+}
 
+#[cfg(windows)]
+impl EnumString {
     pub fn next_elems(&mut self, strings: &mut [Option<CoTaskWString>]) -> Result<usize, Error> {
         assert!(strings.len() < std::u32::MAX as usize);
 
@@ -68,9 +131,147 @@ impl EnumString {
     }
 }
 
+#[cfg(not(windows))]
+impl EnumString {
+    pub fn next_elems(&mut self, strings: &mut [Option<CoTaskWString>]) -> Result<usize, Error> {
+        let mut fetched = 0;
+        for slot in strings {
+            match self.source.next() {
+                Some(s) => *slot = Some(s),
+                None => break,
+            }
+            fetched += 1;
+        }
+        Ok(fetched)
+    }
+
+    /// Returns `Status::FALSE` if fewer than `count` strings remained.
+    pub fn skip(&mut self, count: u32) -> Result<Status, Error> {
+        if self.source.skip(count) < count {
+            Ok(Status::FALSE)
+        } else {
+            Ok(Status::OK)
+        }
+    }
+
+    pub fn reset(&mut self) -> Result<Status, Error> {
+        self.source.reset().map(|()| Status::OK)
+    }
+
+    pub fn try_clone(&self) -> Result<EnumString, Error> {
+        let source = self.source.try_clone()?;
+        Ok(EnumString { source })
+    }
+}
+
+#[cfg(not(windows))]
+impl std::fmt::Debug for EnumString {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("EnumString").finish_non_exhaustive()
+    }
+}
+
+impl std::iter::FromIterator<String> for EnumString {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> EnumString {
+        EnumString::from_strings(iter.into_iter().collect())
+    }
+}
+
+/// Ends at the first error; use `EnumString::iter` to see errors.
 impl Iterator for EnumString {
     type Item = CoTaskWString;
     fn next(&mut self) -> Option<CoTaskWString> {
         self.next_elem().ok().unwrap_or(None)
     }
 }
+
+/// The number of strings `Strings` asks for in each call to `Next`.
+const BATCH_SIZE: usize = 16;
+
+/// A fallible iterator over the strings in an `EnumString`, returned by
+/// `EnumString::iter`.
+pub struct Strings<'a> {
+    inner: &'a mut EnumString,
+    buffer: VecDeque<Result<String, Error>>,
+    done: bool,
+}
+
+impl<'a> Strings<'a> {
+    fn fill(&mut self) {
+        let mut batch: [Option<CoTaskWString>; BATCH_SIZE] = Default::default();
+        let fetched = match self.inner.next_elems(&mut batch) {
+            Ok(fetched) => fetched,
+            Err(err) => {
+                self.done = true;
+                self.buffer.push_back(Err(err));
+                return;
+            }
+        };
+        if fetched < BATCH_SIZE {
+            self.done = true;
+        }
+        for s in batch.iter_mut().take(fetched).filter_map(Option::take) {
+            let s = String::from_utf16(s.data()).map_err(|_| Error::WIN32_INVALID_DATA);
+            self.buffer.push_back(s);
+        }
+    }
+}
+
+impl<'a> Iterator for Strings<'a> {
+    type Item = Result<String, Error>;
+
+    fn next(&mut self) -> Option<Result<String, Error>> {
+        if self.buffer.is_empty() && !self.done {
+            self.fill();
+        }
+        self.buffer.pop_front()
+    }
+}
+
+impl<'a> std::iter::FusedIterator for Strings<'a> {}
+
+#[cfg(test)]
+#[test]
+fn enum_string_from_strings() {
+    let names: Vec<String> = (0..20).map(|i| format!("/item{}", i)).collect();
+    let mut enumerator = EnumString::from_strings(names.clone());
+
+    let fetched: Result<Vec<String>, Error> = enumerator.iter().collect();
+    assert_eq!(fetched.unwrap(), names);
+    assert!(enumerator.iter().next().is_none());
+
+    enumerator.reset().unwrap();
+    EnumString::skip(&mut enumerator, 18).unwrap();
+    let mut clone = enumerator.try_clone().unwrap();
+    let rest: Vec<String> = clone.iter().map(Result::unwrap).collect();
+    assert_eq!(rest, &names[18..]);
+    assert_eq!(enumerator.next().unwrap().to_string(), "/item18");
+
+    assert_eq!(EnumString::skip(&mut enumerator, 1), Ok(Status::OK));
+    assert_eq!(EnumString::skip(&mut enumerator, 5), Ok(Status::FALSE));
+    assert!(enumerator.next().is_none());
+}
+
+#[cfg(test)]
+#[test]
+fn enum_string_invalid_entries() {
+    let mut strings: Vec<CoTaskWString> = (0..20)
+        .map(|i| CoTaskWString::from(&format!("s{}", i)[..]))
+        .collect();
+    strings[3] = CoTaskWString::create(&[0x61, 0xD800]);
+    strings[17] = CoTaskWString::create(&[0xDC00]);
+    let mut enumerator = EnumString::from_source(IterSource::new(strings.into_iter()));
+
+    let fetched: Vec<Result<String, Error>> = enumerator.iter().collect();
+    assert_eq!(fetched.len(), 20);
+    for (i, s) in fetched.into_iter().enumerate() {
+        match i {
+            3 | 17 => assert_eq!(s, Err(Error::WIN32_INVALID_DATA)),
+            _ => assert_eq!(s, Ok(format!("s{}", i))),
+        }
+    }
+
+    let mut enumerator = EnumString::from_strings(vec!["a\0b".into(), "c".into()]);
+    let fetched: Result<Vec<String>, Error> = enumerator.iter().collect();
+    assert_eq!(fetched.unwrap(), ["a", "c"]);
+}
@@ -0,0 +1,109 @@
+//! The state of an enumerator over a Rust iterator, shared by the COM
+//! object in `custom` and the portable `EnumString`.
+
+use crate::helpers::CoTaskWString;
+use crate::Error;
+
+use std::iter::Fuse;
+
+type CloneFn<I> = fn(&Fuse<I>) -> Fuse<I>;
+
+pub(crate) struct IterSource<I: Iterator> {
+    iter: Fuse<I>,
+    original: Option<Fuse<I>>,
+    clone_fn: Option<CloneFn<I>>,
+}
+
+impl<I> IterSource<I>
+where
+    I: Iterator<Item = CoTaskWString> + Clone,
+{
+    /// Enumerates `iter`, supporting `reset` and `try_clone`.
+    pub fn new(iter: I) -> IterSource<I> {
+        let iter = iter.fuse();
+        IterSource {
+            original: Some(iter.clone()),
+            iter,
+            clone_fn: Some(<Fuse<I> as Clone>::clone),
+        }
+    }
+}
+
+impl<I> IterSource<I>
+where
+    I: Iterator<Item = CoTaskWString>,
+{
+    /// Enumerates `iter`. `reset` and `try_clone` fail with
+    /// `Error::NOTIMPL`.
+    #[cfg(windows)]
+    pub fn without_clone(iter: I) -> IterSource<I> {
+        IterSource {
+            iter: iter.fuse(),
+            original: None,
+            clone_fn: None,
+        }
+    }
+
+    pub fn next(&mut self) -> Option<CoTaskWString> {
+        self.iter.next()
+    }
+
+    /// Skips up to `count` strings, returning how many there were.
+    pub fn skip(&mut self, count: u32) -> u32 {
+        let mut skipped = 0;
+        while skipped < count && self.iter.next().is_some() {
+            skipped += 1;
+        }
+        skipped
+    }
+
+    pub fn reset(&mut self) -> Result<(), Error> {
+        match (&self.original, self.clone_fn) {
+            (Some(orig), Some(clone_fn)) => {
+                self.iter = clone_fn(orig);
+                Ok(())
+            }
+            _ => Err(Error::NOTIMPL),
+        }
+    }
+
+    pub fn try_clone(&self) -> Result<IterSource<I>, Error> {
+        let clone_fn = self.clone_fn.ok_or(Error::NOTIMPL)?;
+        Ok(IterSource {
+            iter: clone_fn(&self.iter),
+            original: self.original.as_ref().map(clone_fn),
+            clone_fn: Some(clone_fn),
+        })
+    }
+}
+
+/// The object-safe interface the portable `EnumString` enumerates through.
+#[cfg(not(windows))]
+pub(crate) trait Source {
+    fn next(&mut self) -> Option<CoTaskWString>;
+    fn skip(&mut self, count: u32) -> u32;
+    fn reset(&mut self) -> Result<(), Error>;
+    fn try_clone(&self) -> Result<Box<dyn Source>, Error>;
+}
+
+#[cfg(not(windows))]
+impl<I> Source for IterSource<I>
+where
+    I: Iterator<Item = CoTaskWString> + 'static,
+{
+    fn next(&mut self) -> Option<CoTaskWString> {
+        IterSource::next(self)
+    }
+
+    fn skip(&mut self, count: u32) -> u32 {
+        IterSource::skip(self, count)
+    }
+
+    fn reset(&mut self) -> Result<(), Error> {
+        IterSource::reset(self)
+    }
+
+    fn try_clone(&self) -> Result<Box<dyn Source>, Error> {
+        Ok(Box::new(IterSource::try_clone(self)?))
+    }
+}
@@ -0,0 +1,265 @@
+//! A growable in-memory stream that owns its bytes.
+
+use crate::objidl::io_error;
+use crate::{Error, Status};
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+/// A stream over an owned `Vec<u8>`. Clones share the data but have their
+/// own position, like `IStream::Clone`.
+///
+/// Seeking past the end is allowed. Reads there return nothing, and writes
+/// there fill the gap with zeros first. On Windows, `into_stream` consumes
+/// the stream and hands it to COM. Clone it first to keep a handle on the
+/// data, e.g. to call `into_inner` on once an encoder has committed.
+pub struct MemoryStream {
+    data: Arc<Mutex<Vec<u8>>>,
+    pos: u64,
+}
+
+impl MemoryStream {
+    /// Creates an empty stream.
+    pub fn new() -> MemoryStream {
+        MemoryStream::default()
+    }
+
+    /// Creates a stream over `data`, positioned at the start.
+    pub fn from_vec(data: Vec<u8>) -> MemoryStream {
+        MemoryStream {
+            data: Arc::new(Mutex::new(data)),
+            pos: 0,
+        }
+    }
+
+    /// The length of the data in bytes.
+    pub fn len(&self) -> u64 {
+        self.data.lock().unwrap().len() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The current position, which may be past the end.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Truncates or zero-extends the data to `size` bytes. The position is
+    /// not changed.
+    pub fn set_size(&mut self, size: u64) -> Result<Status, Error> {
+        let size = to_usize(size)?;
+        self.data.lock().unwrap().resize(size, 0);
+        Ok(Status::OK)
+    }
+
+    /// Moves the position. Fails with `Error::STG_INVALIDFUNCTION` if it
+    /// would end up before the start.
+    pub fn seek_to(&mut self, pos: SeekFrom) -> Result<u64, Error> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(pos) => {
+                self.pos = pos;
+                return Ok(pos);
+            }
+            SeekFrom::Current(offset) => (self.pos, offset),
+            SeekFrom::End(offset) => (self.len(), offset),
+        };
+        let pos = if offset < 0 {
+            base.checked_sub(offset.unsigned_abs())
+        } else {
+            base.checked_add(offset as u64)
+        };
+        self.pos = pos.ok_or(Error::STG_INVALIDFUNCTION)?;
+        Ok(self.pos)
+    }
+
+    /// A copy of the data.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.data.lock().unwrap().clone()
+    }
+
+    /// Takes the data out of the stream, copying it if any clones are still
+    /// alive.
+    pub fn into_inner(self) -> Vec<u8> {
+        match Arc::try_unwrap(self.data) {
+            Ok(data) => data.into_inner().unwrap(),
+            Err(shared) => shared.lock().unwrap().clone(),
+        }
+    }
+
+    fn read_at_pos(&mut self, buf: &mut [u8]) -> usize {
+        let data = self.data.lock().unwrap();
+        if self.pos >= data.len() as u64 {
+            return 0;
+        }
+        let start = self.pos as usize;
+        let n = buf.len().min(data.len() - start);
+        buf[..n].copy_from_slice(&data[start..start + n]);
+        self.pos += n as u64;
+        n
+    }
+
+    fn write_at_pos(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        let start = to_usize(self.pos)?;
+        let end = start.checked_add(buf.len()).ok_or(Error::STG_MEDIUMFULL)?;
+        let mut data = self.data.lock().unwrap();
+        if data.len() < end {
+            data.resize(end, 0);
+        }
+        data[start..end].copy_from_slice(buf);
+        self.pos = end as u64;
+        Ok(buf.len())
+    }
+}
+
+impl From<Vec<u8>> for MemoryStream {
+    fn from(data: Vec<u8>) -> MemoryStream {
+        MemoryStream::from_vec(data)
+    }
+}
+
+impl std::fmt::Debug for MemoryStream {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("MemoryStream")
+            .field("len", &self.len())
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
+
+impl Read for MemoryStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(self.read_at_pos(buf))
+    }
+}
+
+impl Write for MemoryStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_at_pos(buf).map_err(io_error)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for MemoryStream {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.seek_to(pos).map_err(io_error)
+    }
+}
+
+fn to_usize(size: u64) -> Result<usize, Error> {
+    if size > usize::MAX as u64 {
+        Err(Error::STG_MEDIUMFULL)
+    } else {
+        Ok(size as usize)
+    }
+}
+
+#[cfg(windows)]
+mod com {
+    use super::MemoryStream;
+    use crate::objidl::enums::{CommitFlags, LockType, StatFlags};
+    use crate::objidl::sequential_stream::custom::SequentialStream;
+    use crate::objidl::stream::custom::{self, CustomStream};
+    use crate::objidl::stream::stat::Stat;
+    use crate::objidl::stream::std_stream::copy_to;
+    use crate::objidl::Stream;
+    use crate::{Error, Status};
+
+    use std::io::SeekFrom;
+
+    impl MemoryStream {
+        /// Wraps the stream in a COM `Stream`. Keep a clone to get the data
+        /// back out afterwards.
+        pub fn into_stream(self) -> Stream {
+            unsafe { CustomStream::new(self) }
+        }
+    }
+
+    impl SequentialStream for MemoryStream {
+        fn read(&mut self, buf: &mut [u8]) -> Result<u32, Error> {
+            Ok(self.read_at_pos(buf) as u32)
+        }
+
+        fn write(&mut self, buf: &[u8]) -> Result<u32, Error> {
+            self.write_at_pos(buf).map(|n| n as u32)
+        }
+    }
+
+    unsafe impl custom::Stream for MemoryStream {
+        fn try_clone(&self) -> Result<Stream, Error> {
+            Ok(self.clone().into_stream())
+        }
+
+        fn commit(&mut self, _flags: CommitFlags) -> Result<Status, Error> {
+            Ok(Status::OK)
+        }
+
+        fn copy_to(
+            &mut self,
+            other: &mut Stream,
+            count: u64,
+            read: &mut u64,
+            written: &mut u64,
+        ) -> Result<Status, Error> {
+            copy_to(self, other, count, read, written)
+        }
+
+        fn lock_region(&mut self, _: u64, _: u64, _: LockType) -> Result<Status, Error> {
+            Err(Error::STG_INVALIDFUNCTION)
+        }
+
+        fn revert(&mut self) -> Result<Status, Error> {
+            Ok(Status::OK)
+        }
+
+        fn seek(&mut self, seek: SeekFrom) -> Result<u64, Error> {
+            self.seek_to(seek)
+        }
+
+        fn set_size(&mut self, newsize: u64) -> Result<Status, Error> {
+            MemoryStream::set_size(self, newsize)
+        }
+
+        fn stat(&mut self, stat: &mut Stat, _flags: StatFlags) -> Result<Status, Error> {
+            // The caller's STATSTG is uninitialized, so don't drop its name.
+            unsafe { std::ptr::write(stat, Stat::stream(self.len())) };
+            Ok(Status::OK)
+        }
+
+        fn unlock_region(&mut self, _: u64, _: u64, _: LockType) -> Result<Status, Error> {
+            Err(Error::STG_INVALIDFUNCTION)
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn memory_stream_use() {
+    let mut stream = MemoryStream::new();
+    stream.write_all(b"abc").unwrap();
+    assert_eq!(stream.seek(SeekFrom::Current(2)).unwrap(), 5);
+    stream.write_all(b"de").unwrap();
+    assert_eq!(stream.to_vec(), b"abc\0\0de");
+
+    let mut clone = stream.clone();
+    clone.seek(SeekFrom::Start(1)).unwrap();
+    let mut buf = [0; 2];
+    clone.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"bc");
+    assert_eq!(stream.position(), 7);
+
+    assert!(stream.seek(SeekFrom::Current(-8)).is_err());
+    assert_eq!(stream.seek_to(SeekFrom::End(-1)), Ok(6));
+    assert_eq!(stream.read(&mut buf).unwrap(), 1);
+    assert_eq!(stream.read(&mut buf).unwrap(), 0);
+
+    stream.set_size(2).unwrap();
+    assert_eq!(clone.len(), 2);
+    drop(clone);
+    assert_eq!(stream.into_inner(), b"ab");
+}
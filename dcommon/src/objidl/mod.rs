@@ -1,10 +1,41 @@
+#[cfg(windows)]
 pub use self::{
-    enum_string::EnumString,
     sequential_stream::{ISequentialStream, SequentialStream},
     stream::Stream,
 };
 
+pub use self::enum_string::EnumString;
+pub use self::memory_stream::MemoryStream;
+pub use self::stream_views::{ReadOnlyStream, RegionStream, TeeStream};
+
+pub mod compound_file;
 pub mod enum_string;
 pub mod enums;
+pub mod memory_stream;
+pub mod property_set;
+#[cfg(windows)]
 pub mod sequential_stream;
 pub mod stream;
+pub mod stream_views;
+
+/// Wraps `err` so it survives a trip through `std::io`. On Windows,
+/// converting the `io::Error` back into an `Error` recovers it.
+pub(crate) fn io_error(err: crate::Error) -> std::io::Error {
+    std::io::Error::other(err)
+}
+
+/// Recovers the `Error` wrapped by `io_error`, for code that works on any
+/// `std::io` backend.
+pub(crate) fn from_io(err: std::io::Error) -> crate::Error {
+    #[cfg(windows)]
+    {
+        crate::Error::from(err)
+    }
+    #[cfg(not(windows))]
+    {
+        err.get_ref()
+            .and_then(|e| e.downcast_ref::<crate::Error>())
+            .copied()
+            .unwrap_or(crate::Error::FAIL)
+    }
+}
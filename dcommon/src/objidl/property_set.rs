@@ -0,0 +1,949 @@
+//! Serialized property sets (MS-OLEPS), the format of the
+//! `"\u{5}SummaryInformation"` and `"\u{5}DocumentSummaryInformation"`
+//! streams in compound files.
+//!
+//! `PropertySet::parse` decodes a whole property set stream into
+//! `PropValue`s and `PropertySet::to_bytes` encodes one again. Both work on
+//! every target; together with `objidl::compound_file` they can pull the
+//! title or author out of a legacy document without COM.
+//!
+//! Strings stored as `VarType::LPSTR` are kept as `PropValue::AnsiStr`
+//! bytes in the section's code page, so they round-trip exactly;
+//! `Section::string` decodes them. `VarType::BSTR` strings are decoded
+//! while parsing. Code pages 1200 (UTF-16), 65001 (UTF-8), 1252 and 28591
+//! are supported fully, and any other code page as long as the text is
+//! ASCII.
+
+use crate::idltypes::decimal::Decimal;
+use crate::idltypes::propvalue::{ClipboardData, PropValue};
+use crate::idltypes::vartype::VarType;
+use crate::minwindef::FileTime;
+use crate::objidl::from_io;
+use crate::{Error, GUID};
+
+use std::collections::BTreeMap;
+use std::convert::{TryFrom, TryInto};
+use std::io::{Read, Write};
+
+/// The name of the stream holding the summary information property set.
+pub const SUMMARY_INFORMATION_STREAM: &str = "\u{5}SummaryInformation";
+/// The name of the stream holding the document summary information and
+/// user-defined property sets.
+pub const DOC_SUMMARY_INFORMATION_STREAM: &str = "\u{5}DocumentSummaryInformation";
+
+/// {F29F85E0-4FF9-1068-AB91-08002B27B3D9}
+pub const FMTID_SUMMARY_INFORMATION: GUID = GUID {
+    Data1: 0xF29F85E0,
+    Data2: 0x4FF9,
+    Data3: 0x1068,
+    Data4: [0xAB, 0x91, 0x08, 0x00, 0x2B, 0x27, 0xB3, 0xD9],
+};
+/// {D5CDD502-2E9C-101B-9397-08002B2CF9AE}
+pub const FMTID_DOC_SUMMARY_INFORMATION: GUID = GUID {
+    Data1: 0xD5CDD502,
+    Data2: 0x2E9C,
+    Data3: 0x101B,
+    Data4: [0x93, 0x97, 0x08, 0x00, 0x2B, 0x2C, 0xF9, 0xAE],
+};
+/// {D5CDD505-2E9C-101B-9397-08002B2CF9AE}, the second section of the
+/// document summary information stream.
+pub const FMTID_USER_DEFINED_PROPERTIES: GUID = GUID {
+    Data1: 0xD5CDD505,
+    Data2: 0x2E9C,
+    Data3: 0x101B,
+    Data4: [0x93, 0x97, 0x08, 0x00, 0x2B, 0x2C, 0xF9, 0xAE],
+};
+
+/// The property holding a section's dictionary, `Section::dictionary`.
+pub const PID_DICTIONARY: u32 = 0;
+/// The property holding a section's code page, `Section::code_page`.
+pub const PID_CODEPAGE: u32 = 1;
+pub const PID_LOCALE: u32 = 0x8000_0000;
+pub const PID_BEHAVIOR: u32 = 0x8000_0003;
+
+pub const PIDSI_TITLE: u32 = 2;
+pub const PIDSI_SUBJECT: u32 = 3;
+pub const PIDSI_AUTHOR: u32 = 4;
+pub const PIDSI_KEYWORDS: u32 = 5;
+pub const PIDSI_COMMENTS: u32 = 6;
+pub const PIDSI_TEMPLATE: u32 = 7;
+pub const PIDSI_LASTAUTHOR: u32 = 8;
+pub const PIDSI_REVNUMBER: u32 = 9;
+pub const PIDSI_EDITTIME: u32 = 10;
+pub const PIDSI_LASTPRINTED: u32 = 11;
+pub const PIDSI_CREATE_DTM: u32 = 12;
+pub const PIDSI_LASTSAVE_DTM: u32 = 13;
+pub const PIDSI_PAGECOUNT: u32 = 14;
+pub const PIDSI_WORDCOUNT: u32 = 15;
+pub const PIDSI_CHARCOUNT: u32 = 16;
+pub const PIDSI_THUMBNAIL: u32 = 17;
+pub const PIDSI_APPNAME: u32 = 18;
+pub const PIDSI_DOC_SECURITY: u32 = 19;
+
+pub const CP_UTF16: u16 = 1200;
+pub const CP_UTF8: u16 = 65001;
+pub const CP_WINDOWS_1252: u16 = 1252;
+pub const CP_ISO_8859_1: u16 = 28591;
+
+const BYTE_ORDER: u16 = 0xFFFE;
+const HEADER_SIZE: usize = 28;
+const SECTION_ENTRY_SIZE: usize = 20;
+
+/// A property set stream: one or two sections of properties.
+#[derive(Clone, Debug)]
+pub struct PropertySet {
+    /// 0, or 1 if the set uses types that need it. `to_bytes` raises it
+    /// to 1 when necessary.
+    pub version: u16,
+    /// The OS version and kind that wrote the set.
+    pub system_identifier: u32,
+    pub clsid: GUID,
+    pub sections: Vec<Section>,
+}
+
+/// One section of a property set, identified by its FMTID.
+#[derive(Clone, Debug)]
+pub struct Section {
+    pub fmtid: GUID,
+    /// The code page of the section's `LPSTR` and `BSTR` strings and of
+    /// its dictionary.
+    pub code_page: u16,
+    /// Names for the property identifiers, if the section has any.
+    pub dictionary: BTreeMap<u32, String>,
+    /// Every property except the dictionary and the code page, by
+    /// identifier.
+    pub properties: BTreeMap<u32, PropValue>,
+}
+
+impl PropertySet {
+    /// An empty property set written by Windows 10.
+    pub fn new() -> PropertySet {
+        PropertySet {
+            version: 0,
+            system_identifier: 0x0002_000A,
+            clsid: GUID {
+                Data1: 0,
+                Data2: 0,
+                Data3: 0,
+                Data4: [0; 8],
+            },
+            sections: Vec::new(),
+        }
+    }
+
+    /// The section with the given FMTID.
+    pub fn section(&self, fmtid: &GUID) -> Option<&Section> {
+        self.sections.iter().find(|s| guid_eq(&s.fmtid, fmtid))
+    }
+
+    pub fn section_mut(&mut self, fmtid: &GUID) -> Option<&mut Section> {
+        self.sections.iter_mut().find(|s| guid_eq(&s.fmtid, fmtid))
+    }
+
+    /// Decodes a property set stream. Fails with `Error::STG_INVALIDHEADER`
+    /// if `data` doesn't start with a property set header, with
+    /// `Error::STG_DOCFILECORRUPT` if it's truncated or its offsets are
+    /// invalid, with `Error::DISP_BADVARTYPE` for value types that can't be
+    /// serialized and with `Error::WIN32_INVALID_DATA` for strings that
+    /// can't be decoded.
+    pub fn parse(data: &[u8]) -> Result<PropertySet, Error> {
+        let mut r = Reader::new(data);
+        if data.len() < HEADER_SIZE || r.u16()? != BYTE_ORDER {
+            return Err(Error::STG_INVALIDHEADER);
+        }
+        let version = r.u16()?;
+        if version > 1 {
+            return Err(Error::STG_INVALIDHEADER);
+        }
+        let system_identifier = r.u32()?;
+        let clsid = r.guid()?;
+        let count = r.u32()? as usize;
+        if count == 0 || count > data.len() / SECTION_ENTRY_SIZE {
+            return Err(Error::STG_INVALIDHEADER);
+        }
+
+        let mut headers = Vec::with_capacity(count);
+        for _ in 0..count {
+            headers.push((r.guid()?, r.u32()? as usize));
+        }
+        let sections = headers
+            .into_iter()
+            .map(|(fmtid, offset)| Section::parse(fmtid, data, offset))
+            .collect::<Result<_, Error>>()?;
+
+        Ok(PropertySet {
+            version,
+            system_identifier,
+            clsid,
+            sections,
+        })
+    }
+
+    /// Reads and decodes a whole property set stream.
+    pub fn read_from<R: Read>(mut reader: R) -> Result<PropertySet, Error> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).map_err(from_io)?;
+        PropertySet::parse(&data)
+    }
+
+    /// Encodes the property set. Fails with `Error::INVALIDARG` if a
+    /// section's `properties` contains the dictionary or code page, with
+    /// `Error::DISP_BADVARTYPE` for values that can't be serialized, with
+    /// `Error::DISP_TYPEMISMATCH` for vectors holding elements of the wrong
+    /// type and with `Error::WIN32_INVALID_DATA` for strings that the
+    /// section's code page can't represent.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let needs_v1 = self
+            .sections
+            .iter()
+            .flat_map(|s| s.properties.values())
+            .any(needs_version_1);
+
+        let mut out = Vec::new();
+        out.extend_from_slice(&BYTE_ORDER.to_le_bytes());
+        out.extend_from_slice(&self.version.max(needs_v1 as u16).to_le_bytes());
+        out.extend_from_slice(&self.system_identifier.to_le_bytes());
+        write_guid(&mut out, &self.clsid);
+        out.extend_from_slice(&(self.sections.len() as u32).to_le_bytes());
+
+        let mut offset = HEADER_SIZE + SECTION_ENTRY_SIZE * self.sections.len();
+        let mut bodies = Vec::with_capacity(self.sections.len());
+        for section in &self.sections {
+            let body = section.to_bytes()?;
+            write_guid(&mut out, &section.fmtid);
+            out.extend_from_slice(&(offset as u32).to_le_bytes());
+            offset += body.len();
+            bodies.push(body);
+        }
+        for body in bodies {
+            out.extend_from_slice(&body);
+        }
+        Ok(out)
+    }
+
+    /// Encodes the property set and writes it to `writer`.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        writer.write_all(&self.to_bytes()?).map_err(from_io)
+    }
+}
+
+impl Default for PropertySet {
+    fn default() -> PropertySet {
+        PropertySet::new()
+    }
+}
+
+impl Section {
+    /// An empty section whose strings are stored as UTF-16.
+    pub fn new(fmtid: GUID) -> Section {
+        Section {
+            fmtid,
+            code_page: CP_UTF16,
+            dictionary: BTreeMap::new(),
+            properties: BTreeMap::new(),
+        }
+    }
+
+    pub fn get(&self, pid: u32) -> Option<&PropValue> {
+        self.properties.get(&pid)
+    }
+
+    /// Replaces the value of a property, returning the old one.
+    pub fn set(&mut self, pid: u32, value: PropValue) -> Option<PropValue> {
+        self.properties.insert(pid, value)
+    }
+
+    /// The identifier of the property the dictionary calls `name`, ignoring
+    /// case.
+    pub fn property_id(&self, name: &str) -> Option<u32> {
+        let name = name.to_lowercase();
+        self.dictionary
+            .iter()
+            .find(|(_, n)| n.to_lowercase() == name)
+            .map(|(&pid, _)| pid)
+    }
+
+    /// The value of a string property, decoding `AnsiStr` values with the
+    /// section's code page. Returns `Ok(None)` if the property is missing or
+    /// isn't a string.
+    pub fn string(&self, pid: u32) -> Result<Option<String>, Error> {
+        Ok(match self.properties.get(&pid) {
+            Some(PropValue::AnsiStr(bytes)) => Some(self.decode(bytes)?),
+            Some(PropValue::Str(s)) | Some(PropValue::BStr(s)) => Some(s.clone()),
+            _ => None,
+        })
+    }
+
+    /// Decodes text in the section's code page. Fails with
+    /// `Error::WIN32_INVALID_DATA` if it isn't valid in that code page.
+    pub fn decode(&self, bytes: &[u8]) -> Result<String, Error> {
+        decode_str(self.code_page, bytes)
+    }
+
+    /// Encodes text in the section's code page, e.g. for an `AnsiStr`
+    /// value. Fails with `Error::WIN32_INVALID_DATA` if the code page can't
+    /// represent it.
+    pub fn encode(&self, s: &str) -> Result<Vec<u8>, Error> {
+        encode_str(self.code_page, s)
+    }
+
+    fn parse(fmtid: GUID, data: &[u8], offset: usize) -> Result<Section, Error> {
+        let data = data.get(offset..).ok_or(Error::STG_DOCFILECORRUPT)?;
+        let mut r = Reader::new(data);
+        let size = r.u32()? as usize;
+        let data = data.get(..size).ok_or(Error::STG_DOCFILECORRUPT)?;
+        let count = r.u32()? as usize;
+        if count > data.len() / 8 {
+            return Err(Error::STG_DOCFILECORRUPT);
+        }
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            entries.push((r.u32()?, r.u32()? as usize));
+        }
+
+        let value_at = |offset: usize, code_page: u16| {
+            let mut r = Reader::at(data, offset)?;
+            read_value(&mut r, code_page, true)
+        };
+
+        // Strings can't be decoded without the code page, so find it first.
+        let mut code_page = CP_WINDOWS_1252;
+        if let Some(&(_, offset)) = entries.iter().find(|e| e.0 == PID_CODEPAGE) {
+            code_page = match value_at(offset, code_page)? {
+                PropValue::I2(cp) => cp as u16,
+                PropValue::UI2(cp) => cp,
+                _ => return Err(Error::STG_DOCFILECORRUPT),
+            };
+        }
+
+        let mut section = Section::new(fmtid);
+        section.code_page = code_page;
+        for (pid, offset) in entries {
+            match pid {
+                PID_CODEPAGE => (),
+                PID_DICTIONARY => {
+                    let mut r = Reader::at(data, offset)?;
+                    section.dictionary = read_dictionary(&mut r, code_page)?;
+                }
+                _ => {
+                    section.properties.insert(pid, value_at(offset, code_page)?);
+                }
+            }
+        }
+        Ok(section)
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        if self.properties.contains_key(&PID_DICTIONARY)
+            || self.properties.contains_key(&PID_CODEPAGE)
+        {
+            return Err(Error::INVALIDARG);
+        }
+
+        let count = self.properties.len() + 1 + !self.dictionary.is_empty() as usize;
+        let mut out = vec![0; 8 + count * 8];
+        let mut entries = Vec::with_capacity(count);
+        if !self.dictionary.is_empty() {
+            entries.push((PID_DICTIONARY, out.len()));
+            write_dictionary(&mut out, &self.dictionary, self.code_page)?;
+        }
+        entries.push((PID_CODEPAGE, out.len()));
+        write_value(
+            &mut out,
+            &PropValue::I2(self.code_page as i16),
+            self.code_page,
+        )?;
+        for (&pid, value) in &self.properties {
+            entries.push((pid, out.len()));
+            write_value(&mut out, value, self.code_page)?;
+        }
+
+        let size = out.len() as u32;
+        out[0..4].copy_from_slice(&size.to_le_bytes());
+        out[4..8].copy_from_slice(&(count as u32).to_le_bytes());
+        for (i, (pid, offset)) in entries.into_iter().enumerate() {
+            let at = 8 + i * 8;
+            out[at..at + 4].copy_from_slice(&pid.to_le_bytes());
+            out[at + 4..at + 8].copy_from_slice(&(offset as u32).to_le_bytes());
+        }
+        Ok(out)
+    }
+}
+
+fn guid_eq(a: &GUID, b: &GUID) -> bool {
+    (a.Data1, a.Data2, a.Data3, a.Data4) == (b.Data1, b.Data2, b.Data3, b.Data4)
+}
+
+/// Whether `value` has a type that version 0 property sets can't hold.
+fn needs_version_1(value: &PropValue) -> bool {
+    match value {
+        PropValue::Vector(_, items) => {
+            let vt = value.vartype().primitive_type();
+            vt == VarType::I1 || items.iter().any(needs_version_1)
+        }
+        PropValue::I1(_) | PropValue::Int(_) | PropValue::UInt(_) | PropValue::Decimal(_) => true,
+        _ => false,
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Reader<'a> {
+        Reader { data, pos: 0 }
+    }
+
+    fn at(data: &'a [u8], pos: usize) -> Result<Reader<'a>, Error> {
+        if pos > data.len() {
+            return Err(Error::STG_DOCFILECORRUPT);
+        }
+        Ok(Reader { data, pos })
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let end = self.pos.checked_add(len).ok_or(Error::STG_DOCFILECORRUPT)?;
+        let bytes = self
+            .data
+            .get(self.pos..end)
+            .ok_or(Error::STG_DOCFILECORRUPT)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, Error> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Result<u64, Error> {
+        self.array().map(u64::from_le_bytes)
+    }
+
+    fn guid(&mut self) -> Result<GUID, Error> {
+        Ok(GUID {
+            Data1: self.u32()?,
+            Data2: self.u16()?,
+            Data3: self.u16()?,
+            Data4: self.array()?,
+        })
+    }
+
+    /// Skips the padding up to the next multiple of 4 bytes.
+    fn align(&mut self) -> Result<(), Error> {
+        let pad = (4 - self.pos % 4) % 4;
+        self.bytes(pad).map(|_| ())
+    }
+
+    /// Reads a 32-bit count of items that take at least `min_size` bytes
+    /// each, checking it against what's left.
+    fn count(&mut self, min_size: usize) -> Result<usize, Error> {
+        let count = self.u32()? as usize;
+        if count.saturating_mul(min_size) > self.data.len() - self.pos {
+            return Err(Error::STG_DOCFILECORRUPT);
+        }
+        Ok(count)
+    }
+}
+
+/// Reads a TypedPropertyValue: a type, two bytes of padding and the value,
+/// padded to a multiple of 4 bytes.
+fn read_value(r: &mut Reader, code_page: u16, allow_vector: bool) -> Result<PropValue, Error> {
+    let vt = VarType(r.u16()?);
+    r.u16()?;
+    let value = if vt.container_type() == VarType::VECTOR && allow_vector {
+        let vt = vt.primitive_type();
+        if !vector_element(vt) {
+            return Err(Error::DISP_BADVARTYPE);
+        }
+        let count = r.count(element_size(vt))?;
+        let mut items = Vec::with_capacity(count);
+        for _ in 0..count {
+            items.push(match vt {
+                VarType::VARIANT => read_value(r, code_page, false)?,
+                _ => read_scalar(r, vt, code_page)?,
+            });
+        }
+        PropValue::Vector(vt, items)
+    } else if vt.container_type() == VarType(0) && vt != VarType::VARIANT {
+        read_scalar(r, vt, code_page)?
+    } else {
+        return Err(Error::DISP_BADVARTYPE);
+    };
+    r.align()?;
+    Ok(value)
+}
+
+/// Reads a bare value of type `vt`, as in a vector. Only the types that
+/// have a variable size carry their own padding.
+fn read_scalar(r: &mut Reader, vt: VarType, code_page: u16) -> Result<PropValue, Error> {
+    Ok(match vt {
+        VarType::EMPTY => PropValue::Empty,
+        VarType::NULL => PropValue::Null,
+        VarType::I1 => PropValue::I1(r.u8()? as i8),
+        VarType::UI1 => PropValue::UI1(r.u8()?),
+        VarType::I2 => PropValue::I2(r.u16()? as i16),
+        VarType::UI2 => PropValue::UI2(r.u16()?),
+        VarType::I4 => PropValue::I4(r.u32()? as i32),
+        VarType::UI4 => PropValue::UI4(r.u32()?),
+        VarType::INT => PropValue::Int(r.u32()? as i32),
+        VarType::UINT => PropValue::UInt(r.u32()?),
+        VarType::I8 => PropValue::I8(r.u64()? as i64),
+        VarType::UI8 => PropValue::UI8(r.u64()?),
+        VarType::R4 => PropValue::R4(f32::from_bits(r.u32()?)),
+        VarType::R8 => PropValue::R8(f64::from_bits(r.u64()?)),
+        VarType::BOOL => PropValue::Bool(r.u16()? != 0),
+        VarType::ERROR => PropValue::Error(r.u32()? as i32),
+        VarType::CY => PropValue::Currency(r.u64()? as i64),
+        VarType::DATE => PropValue::Date(f64::from_bits(r.u64()?)),
+        VarType::FILETIME => PropValue::FileTime(FileTime {
+            low_part: r.u32()?,
+            high_part: r.u32()?,
+        }),
+        VarType::DECIMAL => PropValue::Decimal(Decimal {
+            reserved: r.u16()?,
+            scale: r.u8()?,
+            sign: r.u8()?,
+            high: r.u32()?,
+            low: r.u64()?,
+        }),
+        VarType::CLSID => PropValue::Clsid(r.guid()?),
+        VarType::BSTR => {
+            let bytes = read_code_page_string(r, code_page)?;
+            PropValue::BStr(decode_str(code_page, bytes)?)
+        }
+        VarType::LPSTR => PropValue::AnsiStr(read_code_page_string(r, code_page)?.to_vec()),
+        VarType::LPWSTR => {
+            let len = r.count(2)?;
+            let units = r.bytes(len * 2)?;
+            r.align()?;
+            PropValue::Str(decode_str(CP_UTF16, until_nul(units, CP_UTF16))?)
+        }
+        VarType::BLOB | VarType::BLOB_OBJECT => {
+            let len = r.count(1)?;
+            let data = r.bytes(len)?.to_vec();
+            r.align()?;
+            match vt {
+                VarType::BLOB => PropValue::Blob(data),
+                _ => PropValue::BlobObject(data),
+            }
+        }
+        VarType::CF => {
+            // The size includes the 4 bytes of the clipboard format.
+            let len = r.count(1)?;
+            let len = len.checked_sub(4).ok_or(Error::STG_DOCFILECORRUPT)?;
+            let format = r.u32()? as i32;
+            let data = r.bytes(len)?.to_vec();
+            r.align()?;
+            PropValue::ClipData(ClipboardData { format, data })
+        }
+        _ => return Err(Error::DISP_BADVARTYPE),
+    })
+}
+
+/// Reads a CodePageString, returning the bytes before the terminator.
+fn read_code_page_string<'a>(r: &mut Reader<'a>, code_page: u16) -> Result<&'a [u8], Error> {
+    let len = r.count(1)?;
+    let bytes = r.bytes(len)?;
+    r.align()?;
+    Ok(until_nul(bytes, code_page))
+}
+
+/// Cuts `bytes` at the first nul character.
+fn until_nul(bytes: &[u8], code_page: u16) -> &[u8] {
+    let end = if code_page == CP_UTF16 {
+        bytes
+            .chunks(2)
+            .position(|c| c == [0, 0])
+            .map_or(bytes.len(), |i| i * 2)
+    } else {
+        bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len())
+    };
+    &bytes[..end]
+}
+
+fn read_dictionary(r: &mut Reader, code_page: u16) -> Result<BTreeMap<u32, String>, Error> {
+    let count = r.count(8)?;
+    let unit = if code_page == CP_UTF16 { 2 } else { 1 };
+    let mut dictionary = BTreeMap::new();
+    for _ in 0..count {
+        let pid = r.u32()?;
+        let len = r.count(unit)?;
+        let name = r.bytes(len * unit)?;
+        if code_page == CP_UTF16 {
+            r.align()?;
+        }
+        dictionary.insert(pid, decode_str(code_page, until_nul(name, code_page))?);
+    }
+    Ok(dictionary)
+}
+
+/// Whether `VarType::VECTOR` can be combined with `vt`.
+fn vector_element(vt: VarType) -> bool {
+    matches!(
+        vt,
+        VarType::I1
+            | VarType::UI1
+            | VarType::I2
+            | VarType::UI2
+            | VarType::I4
+            | VarType::UI4
+            | VarType::I8
+            | VarType::UI8
+            | VarType::R4
+            | VarType::R8
+            | VarType::BOOL
+            | VarType::ERROR
+            | VarType::CY
+            | VarType::DATE
+            | VarType::FILETIME
+            | VarType::CLSID
+            | VarType::CF
+            | VarType::BSTR
+            | VarType::LPSTR
+            | VarType::LPWSTR
+            | VarType::VARIANT
+    )
+}
+
+/// The smallest number of bytes a vector element of type `vt` takes.
+fn element_size(vt: VarType) -> usize {
+    match vt {
+        VarType::I1 | VarType::UI1 => 1,
+        VarType::I2 | VarType::UI2 | VarType::BOOL => 2,
+        VarType::I8 | VarType::UI8 | VarType::R8 | VarType::CY | VarType::DATE => 8,
+        VarType::FILETIME | VarType::CF => 8,
+        VarType::CLSID => 16,
+        _ => 4,
+    }
+}
+
+fn align(out: &mut Vec<u8>) {
+    let len = (out.len() + 3) & !3;
+    out.resize(len, 0);
+}
+
+fn write_guid(out: &mut Vec<u8>, guid: &GUID) {
+    out.extend_from_slice(&guid.Data1.to_le_bytes());
+    out.extend_from_slice(&guid.Data2.to_le_bytes());
+    out.extend_from_slice(&guid.Data3.to_le_bytes());
+    out.extend_from_slice(&guid.Data4);
+}
+
+/// Writes a 32-bit length, failing if it doesn't fit.
+fn write_len(out: &mut Vec<u8>, len: usize) -> Result<(), Error> {
+    let len = u32::try_from(len).map_err(|_| Error::INVALIDARG)?;
+    out.extend_from_slice(&len.to_le_bytes());
+    Ok(())
+}
+
+fn write_value(out: &mut Vec<u8>, value: &PropValue, code_page: u16) -> Result<(), Error> {
+    out.extend_from_slice(&value.vartype().0.to_le_bytes());
+    out.extend_from_slice(&[0; 2]);
+    if let PropValue::Vector(vt, items) = value {
+        let vt = *vt;
+        if !vector_element(vt) {
+            return Err(Error::DISP_BADVARTYPE);
+        }
+        write_len(out, items.len())?;
+        for item in items {
+            if vt == VarType::VARIANT {
+                if let PropValue::Vector(..) = item {
+                    return Err(Error::DISP_TYPEMISMATCH);
+                }
+                write_value(out, item, code_page)?;
+            } else if item.vartype() != vt {
+                return Err(Error::DISP_TYPEMISMATCH);
+            } else {
+                write_scalar(out, item, code_page)?;
+            }
+        }
+    } else {
+        write_scalar(out, value, code_page)?;
+    }
+    align(out);
+    Ok(())
+}
+
+fn write_scalar(out: &mut Vec<u8>, value: &PropValue, code_page: u16) -> Result<(), Error> {
+    match value {
+        PropValue::Empty | PropValue::Null => {}
+        PropValue::I1(v) => out.push(*v as u8),
+        PropValue::UI1(v) => out.push(*v),
+        PropValue::I2(v) => out.extend_from_slice(&v.to_le_bytes()),
+        PropValue::UI2(v) => out.extend_from_slice(&v.to_le_bytes()),
+        PropValue::I4(v) | PropValue::Int(v) | PropValue::Error(v) => {
+            out.extend_from_slice(&v.to_le_bytes())
+        }
+        PropValue::UI4(v) | PropValue::UInt(v) => out.extend_from_slice(&v.to_le_bytes()),
+        PropValue::I8(v) | PropValue::Currency(v) => out.extend_from_slice(&v.to_le_bytes()),
+        PropValue::UI8(v) => out.extend_from_slice(&v.to_le_bytes()),
+        PropValue::R4(v) => out.extend_from_slice(&v.to_le_bytes()),
+        PropValue::R8(v) | PropValue::Date(v) => out.extend_from_slice(&v.to_le_bytes()),
+        PropValue::Bool(v) => {
+            out.extend_from_slice(&(if *v { 0xFFFFu16 } else { 0 }).to_le_bytes())
+        }
+        PropValue::FileTime(v) => {
+            out.extend_from_slice(&v.low_part.to_le_bytes());
+            out.extend_from_slice(&v.high_part.to_le_bytes());
+        }
+        PropValue::Decimal(v) => {
+            out.extend_from_slice(&v.reserved.to_le_bytes());
+            out.push(v.scale);
+            out.push(v.sign);
+            out.extend_from_slice(&v.high.to_le_bytes());
+            out.extend_from_slice(&v.low.to_le_bytes());
+        }
+        PropValue::Clsid(v) => write_guid(out, v),
+        PropValue::BStr(v) => write_code_page_string(out, &encode_str(code_page, v)?, code_page)?,
+        PropValue::AnsiStr(v) => write_code_page_string(out, v, code_page)?,
+        PropValue::Str(v) => {
+            let units: Vec<u16> = v.encode_utf16().chain(Some(0)).collect();
+            write_len(out, units.len())?;
+            for unit in units {
+                out.extend_from_slice(&unit.to_le_bytes());
+            }
+            align(out);
+        }
+        PropValue::Blob(v) | PropValue::BlobObject(v) => {
+            write_len(out, v.len())?;
+            out.extend_from_slice(v);
+            align(out);
+        }
+        PropValue::ClipData(v) => {
+            write_len(out, v.data.len().saturating_add(4))?;
+            out.extend_from_slice(&v.format.to_le_bytes());
+            out.extend_from_slice(&v.data);
+            align(out);
+        }
+        PropValue::Vector(..) | PropValue::ByRef(_) => return Err(Error::DISP_BADVARTYPE),
+        #[cfg(windows)]
+        _ => return Err(Error::DISP_BADVARTYPE),
+    }
+    Ok(())
+}
+
+fn write_code_page_string(out: &mut Vec<u8>, bytes: &[u8], code_page: u16) -> Result<(), Error> {
+    let nul: &[u8] = if code_page == CP_UTF16 { &[0, 0] } else { &[0] };
+    write_len(out, bytes.len() + nul.len())?;
+    out.extend_from_slice(bytes);
+    out.extend_from_slice(nul);
+    align(out);
+    Ok(())
+}
+
+fn write_dictionary(
+    out: &mut Vec<u8>,
+    dictionary: &BTreeMap<u32, String>,
+    code_page: u16,
+) -> Result<(), Error> {
+    write_len(out, dictionary.len())?;
+    for (pid, name) in dictionary {
+        out.extend_from_slice(&pid.to_le_bytes());
+        let mut bytes = encode_str(code_page, name)?;
+        if code_page == CP_UTF16 {
+            bytes.extend_from_slice(&[0, 0]);
+            write_len(out, bytes.len() / 2)?;
+            out.extend_from_slice(&bytes);
+            align(out);
+        } else {
+            bytes.push(0);
+            write_len(out, bytes.len())?;
+            out.extend_from_slice(&bytes);
+        }
+    }
+    align(out);
+    Ok(())
+}
+
+/// What code page 1252 maps 0x80 to 0x9F to. The five unassigned bytes map
+/// to the C1 control with the same value, like `MultiByteToWideChar` does.
+const CP1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}', '\u{178}',
+];
+
+fn decode_str(code_page: u16, bytes: &[u8]) -> Result<String, Error> {
+    match code_page {
+        CP_UTF16 => {
+            if bytes.len() % 2 != 0 {
+                return Err(Error::WIN32_INVALID_DATA);
+            }
+            let units: Vec<u16> = bytes
+                .chunks(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16(&units).map_err(|_| Error::WIN32_INVALID_DATA)
+        }
+        CP_UTF8 => String::from_utf8(bytes.to_vec()).map_err(|_| Error::WIN32_INVALID_DATA),
+        CP_WINDOWS_1252 => Ok(bytes
+            .iter()
+            .map(|&b| match b {
+                0x80..=0x9F => CP1252_HIGH[b as usize - 0x80],
+                _ => b as char,
+            })
+            .collect()),
+        CP_ISO_8859_1 => Ok(bytes.iter().map(|&b| b as char).collect()),
+        _ if bytes.is_ascii() => Ok(bytes.iter().map(|&b| b as char).collect()),
+        _ => Err(Error::WIN32_INVALID_DATA),
+    }
+}
+
+fn encode_str(code_page: u16, s: &str) -> Result<Vec<u8>, Error> {
+    match code_page {
+        CP_UTF16 => Ok(s.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+        CP_UTF8 => Ok(s.as_bytes().to_vec()),
+        CP_WINDOWS_1252 => s
+            .chars()
+            .map(|c| match c as u32 {
+                0..=0x7F | 0xA0..=0xFF => Ok(c as u8),
+                _ => CP1252_HIGH
+                    .iter()
+                    .position(|&h| h == c)
+                    .map(|i| 0x80 + i as u8)
+                    .ok_or(Error::WIN32_INVALID_DATA),
+            })
+            .collect(),
+        CP_ISO_8859_1 => s
+            .chars()
+            .map(|c| u8::try_from(c as u32).map_err(|_| Error::WIN32_INVALID_DATA))
+            .collect(),
+        _ if s.is_ascii() => Ok(s.as_bytes().to_vec()),
+        _ => Err(Error::WIN32_INVALID_DATA),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn parse_summary_information() {
+    #[rustfmt::skip]
+    let fixture: &[u8] = &[
+        // Header: byte order, version, system identifier, CLSID, 1 section.
+        0xFE, 0xFF, 0x00, 0x00, 0x0A, 0x00, 0x02, 0x00,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0x01, 0x00, 0x00, 0x00,
+        // FMTID_SummaryInformation at offset 48.
+        0xE0, 0x85, 0x9F, 0xF2, 0xF9, 0x4F, 0x68, 0x10,
+        0xAB, 0x91, 0x08, 0x00, 0x2B, 0x27, 0xB3, 0xD9,
+        0x30, 0x00, 0x00, 0x00,
+        // Section: 92 bytes, 4 properties.
+        0x5C, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+        0x01, 0x00, 0x00, 0x00, 0x28, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x30, 0x00, 0x00, 0x00,
+        0x0C, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00,
+        0x64, 0x00, 0x00, 0x00, 0x4C, 0x00, 0x00, 0x00,
+        // Code page 1252 as VT_I2.
+        0x02, 0x00, 0x00, 0x00, 0xE4, 0x04, 0x00, 0x00,
+        // Title: VT_LPSTR "Café".
+        0x1E, 0x00, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00,
+        0x43, 0x61, 0x66, 0xE9, 0x00, 0x00, 0x00, 0x00,
+        // Created: VT_FILETIME 1970-01-01.
+        0x40, 0x00, 0x00, 0x00, 0x00, 0x80, 0x3E, 0xD5, 0xDE, 0xB1, 0x9D, 0x01,
+        // 100: VT_VECTOR | VT_I2 [1, 2, 3], padded.
+        0x02, 0x10, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+        0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x00, 0x00,
+    ];
+
+    let set = PropertySet::parse(fixture).unwrap();
+    assert_eq!(set.system_identifier, 0x0002_000A);
+    let section = set.section(&FMTID_SUMMARY_INFORMATION).unwrap();
+    assert_eq!(section.code_page, CP_WINDOWS_1252);
+    assert_eq!(section.string(PIDSI_TITLE), Ok(Some("Café".to_string())));
+    match section.get(PIDSI_CREATE_DTM) {
+        Some(PropValue::FileTime(ft)) => assert_eq!(*ft, FileTime::UNIX_EPOCH),
+        other => panic!("unexpected {:?}", other),
+    }
+    match section.get(100) {
+        Some(PropValue::Vector(VarType::I2, items)) => assert_eq!(items.len(), 3),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(set.to_bytes().unwrap(), fixture);
+
+    assert_eq!(
+        PropertySet::parse(&fixture[..100]).unwrap_err(),
+        Error::STG_DOCFILECORRUPT
+    );
+    assert_eq!(
+        PropertySet::parse(&fixture[1..]).unwrap_err(),
+        Error::STG_INVALIDHEADER
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn property_set_roundtrip() {
+    let mut user = Section::new(FMTID_USER_DEFINED_PROPERTIES);
+    user.dictionary.insert(2, "Owner".into());
+    user.dictionary.insert(3, "Tags".into());
+    user.set(2, PropValue::AnsiStr(user.encode("Zoë").unwrap()));
+    user.set(
+        3,
+        PropValue::Vector(
+            VarType::VARIANT,
+            vec![
+                PropValue::Str("a".into()),
+                PropValue::I1(-1),
+                PropValue::Bool(true),
+            ],
+        ),
+    );
+    user.set(4, PropValue::Blob(vec![1, 2, 3, 4, 5]));
+    user.set(
+        5,
+        PropValue::ClipData(ClipboardData {
+            format: -1,
+            data: vec![9; 3],
+        }),
+    );
+
+    let mut doc = Section::new(FMTID_DOC_SUMMARY_INFORMATION);
+    doc.code_page = CP_WINDOWS_1252;
+    doc.set(2, PropValue::BStr("€uro".into()));
+    doc.set(3, PropValue::Decimal(Decimal::ONE));
+
+    let mut set = PropertySet::new();
+    set.sections = vec![doc, user];
+    let bytes = set.to_bytes().unwrap();
+    assert_eq!(bytes.len() % 4, 0);
+
+    let parsed = PropertySet::parse(&bytes).unwrap();
+    assert_eq!(parsed.version, 1);
+    let doc = parsed.section(&FMTID_DOC_SUMMARY_INFORMATION).unwrap();
+    assert_eq!(doc.string(2), Ok(Some("€uro".to_string())));
+    let user = parsed.section(&FMTID_USER_DEFINED_PROPERTIES).unwrap();
+    assert_eq!(user.property_id("owner"), Some(2));
+    assert_eq!(user.string(2), Ok(Some("Zoë".to_string())));
+    match user.get(3) {
+        Some(PropValue::Vector(VarType::VARIANT, items)) => match &items[..] {
+            [PropValue::Str(s), PropValue::I1(-1), PropValue::Bool(true)] => assert_eq!(s, "a"),
+            other => panic!("unexpected {:?}", other),
+        },
+        other => panic!("unexpected {:?}", other),
+    }
+    match user.get(5) {
+        Some(PropValue::ClipData(clip)) => assert_eq!(clip.data, [9; 3]),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(parsed.to_bytes().unwrap(), bytes);
+
+    let mut bad = Section::new(FMTID_SUMMARY_INFORMATION);
+    bad.code_page = CP_WINDOWS_1252;
+    bad.set(2, PropValue::BStr("日本".into()));
+    set.sections = vec![bad];
+    assert_eq!(set.to_bytes().unwrap_err(), Error::WIN32_INVALID_DATA);
+}
@@ -17,6 +17,11 @@ pub struct SequentialStream {
     ptr: ComPtr<objidlbase::ISequentialStream>,
 }
 
+/// The methods of `ISequentialStream`, implemented on top of `raw_sstream`.
+///
+/// # Safety
+///
+/// `raw_sstream` must return a valid stream.
 pub unsafe trait ISequentialStream {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
         assert!(
@@ -48,6 +53,11 @@ pub unsafe trait ISequentialStream {
         }
     }
 
+    /// The underlying COM interface.
+    ///
+    /// # Safety
+    ///
+    /// Calls through the interface bypass the checks the safe methods make.
     unsafe fn raw_sstream(&self) -> &objidlbase::ISequentialStream;
 }
 
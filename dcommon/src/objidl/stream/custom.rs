@@ -16,6 +16,12 @@ use winapi::um::objidlbase::{
 };
 use winapi::um::winnt::{LARGE_INTEGER, ULARGE_INTEGER};
 
+/// A stream implemented in Rust, which `CustomStream` exposes as an `IStream`.
+///
+/// # Safety
+///
+/// COM callers trust the results: `seek`, `stat` and the counts returned by
+/// reads and writes must describe the stream accurately.
 pub unsafe trait Stream: SequentialStream + 'static {
     // SequentialStream: fn read(&mut self, buf: &mut [u8]) -> Result<u32, Error>;
     // SequentialStream: fn write(&mut self, buf: &[u8]) -> Result<u32, Error>;
@@ -58,8 +64,12 @@ where
 }
 
 impl<S: Stream> CustomStream<S> {
-    /// Create a Stream from a custom implementation. Unsafe because the way a COM API may use your stream
-    /// can be very unpredictable, so it's up to the user to investigate and ensure their stream will be
+    /// Create a Stream from a custom implementation.
+    ///
+    /// # Safety
+    ///
+    /// The way a COM API may use your stream can be very unpredictable, so
+    /// it's up to the user to investigate and ensure their stream will be
     /// used in the correct way.
     pub unsafe fn new(stream: S) -> super::Stream {
         let raw = CustomStream::create_raw(stream);
@@ -1,21 +1,34 @@
+//! COM streams. Only `Stat` is available on non-Windows targets.
+
+#[cfg(windows)]
 use crate::objidl::{
     enums::{CommitFlags, LockType, StatFlags},
     ISequentialStream,
 };
+#[cfg(windows)]
 use crate::{Error, Status};
 
+#[cfg(windows)]
 use std::io::SeekFrom;
 
+#[cfg(windows)]
 use com_wrapper::ComWrapper;
+#[cfg(windows)]
 use winapi::um::objidlbase::{self, STREAM_SEEK_CUR, STREAM_SEEK_END, STREAM_SEEK_SET};
+#[cfg(windows)]
 use winapi::um::winnt::{LARGE_INTEGER, ULARGE_INTEGER};
+#[cfg(windows)]
 use wio::com::ComPtr;
 
 pub use self::stat::Stat;
 
+#[cfg(windows)]
 pub mod custom;
 pub mod stat;
+#[cfg(windows)]
+pub mod std_stream;
 
+#[cfg(windows)]
 #[repr(transparent)]
 #[derive(ComWrapper)]
 #[com(debug)]
@@ -23,6 +36,12 @@ pub struct Stream {
     ptr: ComPtr<objidlbase::IStream>,
 }
 
+#[cfg(windows)]
+/// The methods of `IStream`, implemented on top of `raw_stream`.
+///
+/// # Safety
+///
+/// `raw_stream` must return a valid stream.
 pub unsafe trait IStream {
     fn seek(&mut self, pos: SeekFrom) -> Result<u64, Error> {
         let (pos, flag) = match pos {
@@ -140,27 +159,36 @@ pub unsafe trait IStream {
         }
     }
 
+    /// The underlying COM interface.
+    ///
+    /// # Safety
+    ///
+    /// Calls through the interface bypass the checks the safe methods make.
     unsafe fn raw_stream(&self) -> &objidlbase::IStream;
 }
 
+#[cfg(windows)]
 unsafe impl ISequentialStream for Stream {
     unsafe fn raw_sstream(&self) -> &objidlbase::ISequentialStream {
         &self.ptr
     }
 }
 
+#[cfg(windows)]
 unsafe impl IStream for Stream {
     unsafe fn raw_stream(&self) -> &objidlbase::IStream {
         &self.ptr
     }
 }
 
+#[cfg(windows)]
 impl std::io::Read for Stream {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         ISequentialStream::read(self, buf).map_err(|e| e.into())
     }
 }
 
+#[cfg(windows)]
 impl std::io::Write for Stream {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
         ISequentialStream::write(self, buf).map_err(|e| e.into())
@@ -173,6 +201,7 @@ impl std::io::Write for Stream {
     }
 }
 
+#[cfg(windows)]
 impl std::io::Seek for Stream {
     fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
         IStream::seek(self, pos).map_err(|e| e.into())
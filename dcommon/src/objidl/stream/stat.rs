@@ -1,9 +1,9 @@
 use crate::helpers::wide::CoTaskWString;
 use crate::minwindef::FileTime;
 use crate::objidl::enums::{LockType, StorageType};
+use crate::GUID;
 
 use checked_enum::UncheckedEnum;
-use winapi::shared::guiddef::CLSID;
 
 #[repr(C)]
 pub struct Stat {
@@ -15,7 +15,40 @@ pub struct Stat {
     pub atime: FileTime,
     pub grf_mode: u32,
     pub grf_locks_supported: LockType,
-    pub clsid: CLSID,
+    pub clsid: GUID,
     pub state_bits: u32,
     pub reserved: u32,
 }
+
+impl Stat {
+    /// Describes an unnamed stream of `byte_size` bytes, with no times,
+    /// mode or locking support.
+    pub fn stream(byte_size: u64) -> Stat {
+        Stat {
+            name: None,
+            kind: (StorageType::Stream as u32).into(),
+            byte_size,
+            mtime: FileTime::from(0),
+            ctime: FileTime::from(0),
+            atime: FileTime::from(0),
+            grf_mode: 0,
+            grf_locks_supported: LockType(0),
+            clsid: unsafe { std::mem::zeroed() },
+            state_bits: 0,
+            reserved: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn stat_name() {
+    let mut stat = Stat::stream(42);
+    assert!(stat.name.is_none());
+
+    stat.name = Some(CoTaskWString::from("contents"));
+    assert_eq!(stat.byte_size, 42);
+    assert_eq!(stat.name.as_ref().unwrap().to_string(), "contents");
+    let name = stat.name.take().unwrap();
+    assert_eq!(String::from(&name), "contents");
+}
@@ -0,0 +1,221 @@
+use crate::objidl::enums::{CommitFlags, LockType, StatFlags};
+use crate::objidl::sequential_stream::custom::SequentialStream;
+use crate::objidl::stream::custom::{self, CustomStream};
+use crate::objidl::stream::stat::Stat;
+use crate::{Error, Status};
+
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+
+/// Implements the custom `Stream` trait for anything that is `Read`,
+/// `Write` and `Seek`, so it can be handed to COM as a `Stream`.
+///
+/// Locking is not supported and fails with `Error::STG_INVALIDFUNCTION`.
+/// `set_size` can grow the stream by writing zeros at the end, but can't
+/// shrink it. `commit` flushes the writer, and `revert` does nothing.
+pub struct StdStream<T> {
+    inner: T,
+    clone_fn: Option<fn(&T) -> T>,
+}
+
+impl<T> StdStream<T>
+where
+    T: Read + Write + Seek + 'static,
+{
+    /// Wraps `inner`. `try_clone` will fail with `Error::NOTIMPL`.
+    pub fn new(inner: T) -> StdStream<T> {
+        StdStream {
+            inner,
+            clone_fn: None,
+        }
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Wraps the adapter in a COM `Stream`.
+    pub fn into_stream(self) -> super::Stream {
+        // The adapter upholds every requirement of the `IStream` contract
+        // that `CustomStream` leaves to its implementor.
+        unsafe { CustomStream::new(self) }
+    }
+
+    /// The length of the stream, leaving the position where it was.
+    fn byte_size(&mut self) -> Result<u64, Error> {
+        let pos = self.inner.stream_position()?;
+        let size = self.inner.seek(SeekFrom::End(0))?;
+        self.inner.seek(SeekFrom::Start(pos))?;
+        Ok(size)
+    }
+}
+
+impl<T> StdStream<T>
+where
+    T: Read + Write + Seek + Clone + 'static,
+{
+    /// Wraps `inner`, implementing `try_clone` with `T::clone`. Whether the
+    /// clone shares data with the original is up to `T`.
+    pub fn cloneable(inner: T) -> StdStream<T> {
+        StdStream {
+            inner,
+            clone_fn: Some(T::clone),
+        }
+    }
+}
+
+impl<T> SequentialStream for StdStream<T>
+where
+    T: Read + Write + Seek + 'static,
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<u32, Error> {
+        // IStream only returns fewer bytes than asked for at the end.
+        let mut total = 0;
+        while total < buf.len() {
+            match self.inner.read(&mut buf[total..]) {
+                Ok(0) => break,
+                Ok(n) => total += n,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(total as u32)
+    }
+
+    fn write(&mut self, buf: &[u8]) -> Result<u32, Error> {
+        self.inner.write_all(buf)?;
+        Ok(buf.len() as u32)
+    }
+}
+
+unsafe impl<T> custom::Stream for StdStream<T>
+where
+    T: Read + Write + Seek + 'static,
+{
+    fn try_clone(&self) -> Result<super::Stream, Error> {
+        let clone_fn = self.clone_fn.ok_or(Error::NOTIMPL)?;
+        let clone = StdStream {
+            inner: clone_fn(&self.inner),
+            clone_fn: self.clone_fn,
+        };
+        Ok(clone.into_stream())
+    }
+
+    fn commit(&mut self, _flags: CommitFlags) -> Result<Status, Error> {
+        self.inner.flush()?;
+        Ok(Status::OK)
+    }
+
+    fn copy_to(
+        &mut self,
+        other: &mut super::Stream,
+        count: u64,
+        read: &mut u64,
+        written: &mut u64,
+    ) -> Result<Status, Error> {
+        copy_to(&mut self.inner, other, count, read, written)
+    }
+
+    fn lock_region(&mut self, _: u64, _: u64, _: LockType) -> Result<Status, Error> {
+        Err(Error::STG_INVALIDFUNCTION)
+    }
+
+    fn revert(&mut self) -> Result<Status, Error> {
+        Ok(Status::OK)
+    }
+
+    fn seek(&mut self, seek: SeekFrom) -> Result<u64, Error> {
+        Ok(self.inner.seek(seek)?)
+    }
+
+    fn set_size(&mut self, newsize: u64) -> Result<Status, Error> {
+        let size = self.byte_size()?;
+        if newsize < size {
+            return Err(Error::STG_INVALIDFUNCTION);
+        }
+        if newsize > size {
+            let pos = self.inner.stream_position()?;
+            self.inner.seek(SeekFrom::End(0))?;
+            std::io::copy(
+                &mut std::io::repeat(0).take(newsize - size),
+                &mut self.inner,
+            )?;
+            self.inner.seek(SeekFrom::Start(pos))?;
+        }
+        Ok(Status::OK)
+    }
+
+    fn stat(&mut self, stat: &mut Stat, _flags: StatFlags) -> Result<Status, Error> {
+        let byte_size = self.byte_size()?;
+        let value = Stat::stream(byte_size);
+        // The caller's STATSTG is uninitialized, so don't drop its name.
+        unsafe { std::ptr::write(stat, value) };
+        Ok(Status::OK)
+    }
+
+    fn unlock_region(&mut self, _: u64, _: u64, _: LockType) -> Result<Status, Error> {
+        Err(Error::STG_INVALIDFUNCTION)
+    }
+}
+
+/// Copies up to `count` bytes from `src` to `other`, for implementing
+/// `copy_to`. `read` and `written` are updated as the copy progresses.
+pub(crate) fn copy_to<R: Read>(
+    src: &mut R,
+    other: &mut super::Stream,
+    count: u64,
+    read: &mut u64,
+    written: &mut u64,
+) -> Result<Status, Error> {
+    let mut buf = vec![0; 0x10000];
+    while *read < count {
+        let chunk = (count - *read).min(buf.len() as u64) as usize;
+        let n = match src.read(&mut buf[..chunk]) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        *read += n as u64;
+        other.write_all(&buf[..n])?;
+        *written += n as u64;
+    }
+    Ok(Status::OK)
+}
+
+#[cfg(test)]
+#[test]
+fn std_stream_adapter() {
+    use crate::objidl::stream::IStream;
+    use std::io::Cursor;
+
+    let mut stream = StdStream::new(Cursor::new(Vec::new())).into_stream();
+    stream.write_all(b"hello").unwrap();
+    assert_eq!(IStream::seek(&mut stream, SeekFrom::Start(1)), Ok(1));
+    assert_eq!(stream.stat(StatFlags::NO_NAME).unwrap().byte_size, 5);
+
+    let mut buf = [0; 8];
+    assert_eq!(stream.read(&mut buf).unwrap(), 4);
+    assert_eq!(&buf[..4], b"ello");
+
+    assert_eq!(stream.set_size(8), Ok(Status::OK));
+    assert_eq!(stream.stat(StatFlags::NO_NAME).unwrap().byte_size, 8);
+    assert_eq!(
+        stream.lock_region(0, 1, LockType::WRITE),
+        Err(Error::STG_INVALIDFUNCTION)
+    );
+    assert_eq!(stream.try_clone().err(), Some(Error::NOTIMPL));
+
+    let cloneable = StdStream::cloneable(Cursor::new(b"abc".to_vec()));
+    let mut clone = cloneable.into_stream().try_clone().unwrap();
+    let mut s = String::new();
+    clone.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "abc");
+}
@@ -0,0 +1,355 @@
+//! Streams that present part of, or a restricted view of, another stream.
+//!
+//! The views are built on `std::io` so they can wrap any backend, including
+//! `MemoryStream`, on every target. A COM `Stream` implements the same traits,
+//! so it can be wrapped directly. On Windows each view can be handed back to
+//! COM with `into_stream`, which goes through `StdStream`; errors such as
+//! `Error::STG_ACCESSDENIED` survive that trip unchanged.
+
+use crate::objidl::{from_io, io_error};
+use crate::Error;
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+/// Applies `pos` to a position in a stream of `len` bytes, failing with
+/// `Error::STG_INVALIDFUNCTION` for positions before the start.
+fn seek_within(current: u64, len: u64, pos: SeekFrom) -> Result<u64, Error> {
+    let (base, offset) = match pos {
+        SeekFrom::Start(pos) => return Ok(pos),
+        SeekFrom::Current(offset) => (current, offset),
+        SeekFrom::End(offset) => (len, offset),
+    };
+    let pos = if offset < 0 {
+        base.checked_sub(offset.unsigned_abs())
+    } else {
+        base.checked_add(offset as u64)
+    };
+    pos.ok_or(Error::STG_INVALIDFUNCTION)
+}
+
+/// A fixed-size window of `len` bytes starting at `offset` in another
+/// stream, like `IWICStream::InitializeFromIStreamRegion`.
+///
+/// Positions are relative to the start of the region, and the end of the
+/// region is the end of the stream. Writes can't extend the region; a write
+/// that starts at or past the end fails with `Error::STG_MEDIUMFULL`. The
+/// inner stream is repositioned before every access, so it may be moved in
+/// between.
+#[derive(Debug)]
+pub struct RegionStream<S> {
+    inner: S,
+    offset: u64,
+    len: u64,
+    pos: u64,
+}
+
+impl<S: Seek> RegionStream<S> {
+    /// Creates a view of `len` bytes of `inner` starting at `offset`. Fails
+    /// with `Error::INVALIDARG` if the region would end past the end of
+    /// `inner`, and with the inner stream's error if its length can't be
+    /// determined.
+    pub fn new(mut inner: S, offset: u64, len: u64) -> Result<RegionStream<S>, Error> {
+        let end = offset.checked_add(len).ok_or(Error::INVALIDARG)?;
+        let inner_len = inner.seek(SeekFrom::End(0)).map_err(from_io)?;
+        if end > inner_len {
+            return Err(Error::INVALIDARG);
+        }
+        Ok(RegionStream {
+            inner,
+            offset,
+            len,
+            pos: 0,
+        })
+    }
+
+    /// The size of the region in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Where the region starts in the inner stream.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// The number of bytes between the position and the end of the region,
+    /// after moving the inner stream to the position.
+    fn prepare(&mut self) -> io::Result<u64> {
+        let remaining = self.len.saturating_sub(self.pos);
+        if remaining > 0 {
+            self.inner.seek(SeekFrom::Start(self.offset + self.pos))?;
+        }
+        Ok(remaining)
+    }
+}
+
+impl<S: Read + Seek> Read for RegionStream<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.prepare()?;
+        let max = remaining.min(buf.len() as u64) as usize;
+        if max == 0 {
+            return Ok(0);
+        }
+        let n = self.inner.read(&mut buf[..max])?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<S: Write + Seek> Write for RegionStream<S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let remaining = self.prepare()?;
+        if remaining == 0 {
+            return Err(io_error(Error::STG_MEDIUMFULL));
+        }
+        let max = remaining.min(buf.len() as u64) as usize;
+        let n = self.inner.write(&buf[..max])?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<S: Seek> Seek for RegionStream<S> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.pos = seek_within(self.pos, self.len, pos).map_err(io_error)?;
+        Ok(self.pos)
+    }
+}
+
+/// A view of a stream that can be read and sought but not written. Writes
+/// fail with `Error::STG_ACCESSDENIED`, and so does growing the stream with
+/// `IStream::SetSize`.
+#[derive(Debug)]
+pub struct ReadOnlyStream<S> {
+    inner: S,
+}
+
+impl<S> ReadOnlyStream<S> {
+    pub fn new(inner: S) -> ReadOnlyStream<S> {
+        ReadOnlyStream { inner }
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Read> Read for ReadOnlyStream<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<S> Write for ReadOnlyStream<S> {
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+        Err(io_error(Error::STG_ACCESSDENIED))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<S: Seek> Seek for ReadOnlyStream<S> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+/// A stream that copies everything written to it into a second sink.
+///
+/// Reads and seeks only affect the primary stream; the sink just receives
+/// the written bytes in order, so it sees overwrites as appended data. A
+/// write counts once the primary stream has accepted it. If the sink then
+/// fails, the bytes stay written and the sink's error is returned by the
+/// next write or flush instead.
+#[derive(Debug)]
+pub struct TeeStream<S, W> {
+    inner: S,
+    sink: W,
+    sink_error: Option<io::Error>,
+}
+
+impl<S, W> TeeStream<S, W> {
+    pub fn new(inner: S, sink: W) -> TeeStream<S, W> {
+        TeeStream {
+            inner,
+            sink,
+            sink_error: None,
+        }
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn sink(&self) -> &W {
+        &self.sink
+    }
+
+    /// Returns the primary stream and the sink.
+    pub fn into_parts(self) -> (S, W) {
+        (self.inner, self.sink)
+    }
+}
+
+impl<S: Read, W> Read for TeeStream<S, W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<S: Write, W: Write> Write for TeeStream<S, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(err) = self.sink_error.take() {
+            return Err(err);
+        }
+        let n = self.inner.write(buf)?;
+        if let Err(err) = self.sink.write_all(&buf[..n]) {
+            self.sink_error = Some(err);
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if let Some(err) = self.sink_error.take() {
+            return Err(err);
+        }
+        self.inner.flush()?;
+        self.sink.flush()
+    }
+}
+
+impl<S: Seek, W> Seek for TeeStream<S, W> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[cfg(windows)]
+mod com {
+    use super::{ReadOnlyStream, RegionStream, TeeStream};
+    use crate::objidl::stream::std_stream::StdStream;
+    use crate::objidl::Stream;
+
+    use std::io::{Read, Seek, Write};
+
+    impl<S: Read + Write + Seek + 'static> RegionStream<S> {
+        /// Wraps the view in a COM `Stream`.
+        pub fn into_stream(self) -> Stream {
+            StdStream::new(self).into_stream()
+        }
+    }
+
+    impl<S: Read + Seek + 'static> ReadOnlyStream<S> {
+        /// Wraps the view in a COM `Stream`.
+        pub fn into_stream(self) -> Stream {
+            StdStream::new(self).into_stream()
+        }
+    }
+
+    impl<S: Read + Write + Seek + 'static, W: Write + 'static> TeeStream<S, W> {
+        /// Wraps the stream in a COM `Stream`.
+        pub fn into_stream(self) -> Stream {
+            StdStream::new(self).into_stream()
+        }
+    }
+}
+
+#[cfg(test)]
+fn stream_error(err: io::Error) -> Option<Error> {
+    err.get_ref()?.downcast_ref::<Error>().copied()
+}
+
+#[cfg(test)]
+#[test]
+fn region_stream() {
+    use crate::objidl::MemoryStream;
+
+    let backing = MemoryStream::from_vec(b"0123456789".to_vec());
+    let mut region = RegionStream::new(backing.clone(), 2, 5).unwrap();
+
+    let mut s = String::new();
+    region.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "23456");
+    assert_eq!(region.seek(SeekFrom::End(-2)).unwrap(), 3);
+    assert_eq!(region.stream_position().unwrap(), 3);
+
+    assert_eq!(region.write(b"abcd").unwrap(), 2);
+    let err = region.write(b"cd").unwrap_err();
+    assert_eq!(stream_error(err), Some(Error::STG_MEDIUMFULL));
+    assert_eq!(backing.to_vec(), b"01234ab789");
+
+    let err = region.seek(SeekFrom::Current(-6)).unwrap_err();
+    assert_eq!(stream_error(err), Some(Error::STG_INVALIDFUNCTION));
+    assert_eq!(region.seek(SeekFrom::Start(9)).unwrap(), 9);
+    assert_eq!(region.read(&mut [0; 4]).unwrap(), 0);
+
+    assert!(RegionStream::new(backing.clone(), 0, 10).is_ok());
+    assert!(RegionStream::new(backing.clone(), 10, 0).is_ok());
+    let err = RegionStream::new(backing.clone(), 6, 5).unwrap_err();
+    assert_eq!(err, Error::INVALIDARG);
+    let err = RegionStream::new(backing, 11, 0).unwrap_err();
+    assert_eq!(err, Error::INVALIDARG);
+}
+
+#[cfg(test)]
+#[test]
+fn read_only_and_tee_streams() {
+    use crate::objidl::MemoryStream;
+
+    let mut ro = ReadOnlyStream::new(MemoryStream::from_vec(b"abc".to_vec()));
+    assert_eq!(ro.seek(SeekFrom::End(0)).unwrap(), 3);
+    let err = ro.write(b"d").unwrap_err();
+    assert_eq!(stream_error(err), Some(Error::STG_ACCESSDENIED));
+
+    let mut tee = TeeStream::new(MemoryStream::new(), Vec::new());
+    tee.write_all(b"hello").unwrap();
+    tee.seek(SeekFrom::Start(0)).unwrap();
+    tee.write_all(b"J").unwrap();
+    let (primary, sink) = tee.into_parts();
+    assert_eq!(primary.into_inner(), b"Jello");
+    assert_eq!(sink, b"helloJ");
+
+    // The sink has room for 3 bytes. The write that overflows it still
+    // succeeds, since the primary stream took it; the next call fails.
+    let mut sink = [0u8; 3];
+    let mut tee = TeeStream::new(MemoryStream::new(), &mut sink[..]);
+    assert_eq!(tee.write(b"ab").unwrap(), 2);
+    assert_eq!(tee.write(b"cd").unwrap(), 2);
+    assert_eq!(
+        tee.write(b"e").unwrap_err().kind(),
+        io::ErrorKind::WriteZero
+    );
+    assert_eq!(tee.write(b"e").unwrap(), 1);
+    let (primary, _) = tee.into_parts();
+    assert_eq!(primary.into_inner(), b"abcde");
+    assert_eq!(sink, *b"abc");
+}
@@ -1,7 +1,18 @@
+use crate::error::Error;
+use crate::helpers::WideString;
+use crate::idltypes::variant::oleaut32::VariantClear;
+use crate::idltypes::variant::Variant;
+use crate::idltypes::vartype::VarType;
+use crate::ocidl::property_bag2::proptype::PropertyType;
+
 use com_wrapper::ComWrapper;
-use winapi::um::ocidl::IPropertyBag2;
+use winapi::shared::guiddef::CLSID;
+use winapi::shared::winerror::S_OK;
+use winapi::um::combaseapi::CoTaskMemFree;
+use winapi::um::ocidl::{IPropertyBag2, PROPBAG2};
 use wio::com::ComPtr;
 
+pub mod custom;
 pub mod proptype;
 
 #[repr(transparent)]
@@ -11,4 +22,160 @@ pub struct PropertyBag2 {
     ptr: ComPtr<IPropertyBag2>,
 }
 
-impl PropertyBag2 {}
+impl PropertyBag2 {
+    /// The number of properties in the bag.
+    pub fn count_properties(&mut self) -> Result<u32, Error> {
+        unsafe {
+            let mut count = 0;
+            let hr = self.ptr.CountProperties(&mut count);
+            Error::map(hr, count)
+        }
+    }
+
+    /// Describes the property at `index`, which must be less than
+    /// `count_properties()`.
+    pub fn property_info(&mut self, index: u32) -> Result<PropertyInfo, Error> {
+        unsafe {
+            let mut bag: PROPBAG2 = std::mem::zeroed();
+            let mut fetched = 0;
+            let hr = self.ptr.GetPropertyInfo(index, 1, &mut bag, &mut fetched);
+            Error::map(hr, ())?;
+            if fetched == 0 {
+                return Err(Error::INVALIDARG);
+            }
+            Ok(PropertyInfo::from_raw(bag))
+        }
+    }
+
+    /// Describes every property in the bag, in order.
+    pub fn properties(&mut self) -> Result<Vec<PropertyInfo>, Error> {
+        let count = self.count_properties()?;
+        (0..count).map(|i| self.property_info(i)).collect()
+    }
+
+    /// Reads the property called `name`, asking the bag to convert it to
+    /// `T`. Fails with `Error::DISP_TYPEMISMATCH` if the bag returns a type
+    /// that `T` doesn't accept, and with the bag's error for the property if
+    /// it can't be read or converted.
+    pub fn read<'a, T>(&mut self, name: &str) -> Result<T, Error>
+    where
+        T: PropertyType<'a>,
+    {
+        let mut wname = WideString::new(name)?;
+        unsafe {
+            let mut bag: PROPBAG2 = std::mem::zeroed();
+            bag.pstrName = wname.as_mut_ptr();
+            bag.vt = T::VARTYPES[0].0;
+
+            let mut var: Variant = std::mem::zeroed();
+            let mut result = S_OK;
+            let hr = self.ptr.Read(
+                1,
+                &bag,
+                std::ptr::null(),
+                &mut var as *mut Variant as *mut _,
+                &mut result,
+            );
+            // Bags fail the whole call when a property fails, so prefer the
+            // more specific per-property result.
+            if let Err(err) = Error::map(result, ()).and(Error::map(hr, ())) {
+                VariantClear(&mut var);
+                return Err(err);
+            }
+
+            let value = T::try_from_variant(&var);
+            VariantClear(&mut var);
+            value.ok_or(Error::DISP_TYPEMISMATCH)
+        }
+    }
+
+    /// Writes `value` to the property called `name`.
+    pub fn write<'a, T>(&mut self, name: &str, value: &T) -> Result<(), Error>
+    where
+        T: PropertyType<'a>,
+    {
+        let mut wname = WideString::new(name)?;
+        unsafe {
+            let mut bag: PROPBAG2 = std::mem::zeroed();
+            bag.pstrName = wname.as_mut_ptr();
+            bag.vt = T::VARTYPES[0].0;
+
+            let mut var = value.to_variant();
+            let hr = self.ptr.Write(1, &bag, &var as *const Variant as *const _);
+            VariantClear(&mut var);
+            Error::map(hr, ())
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Describes a property in a `PropertyBag2`.
+pub struct PropertyInfo {
+    pub name: String,
+    pub vartype: VarType,
+    /// One of the `PROPBAG2_TYPE_*` constants.
+    pub prop_type: u32,
+    /// The clipboard format of the data, if it is a stream or storage.
+    pub clipboard_format: u16,
+    /// A hint the bag can use to find the property faster.
+    pub hint: u32,
+    /// The class of an object-valued property.
+    pub clsid: CLSID,
+}
+
+impl PropertyInfo {
+    /// Takes ownership of the name in `bag`, freeing it.
+    unsafe fn from_raw(bag: PROPBAG2) -> PropertyInfo {
+        let name = if bag.pstrName.is_null() {
+            String::new()
+        } else {
+            let len = crate::helpers::wide::wstrlen(bag.pstrName);
+            let name = String::from_utf16_lossy(std::slice::from_raw_parts(bag.pstrName, len));
+            CoTaskMemFree(bag.pstrName as _);
+            name
+        };
+
+        PropertyInfo {
+            name,
+            vartype: VarType(bag.vt),
+            prop_type: bag.dwType,
+            clipboard_format: bag.cfType,
+            hint: bag.dwHint,
+            clsid: bag.clsid,
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn property_bag_type_mismatch() {
+    use crate::idltypes::bstr::BStr;
+    use crate::ocidl::property_bag2::custom::CustomPropertyBag2;
+    use crate::GUID;
+
+    let props = vec![
+        (
+            "Name".to_string(),
+            Variant::new_bstr(BStr::new("not a guid")),
+        ),
+        ("Count".to_string(), Variant::new_i32(3)),
+    ];
+    let mut bag = CustomPropertyBag2::create(props);
+
+    // The bag converts the value, but it doesn't parse as a GUID.
+    assert_eq!(bag.read::<String>("Name"), Ok("not a guid".to_string()));
+    assert_eq!(
+        bag.read::<GUID>("Name").unwrap_err(),
+        Error::DISP_TYPEMISMATCH
+    );
+    // The bag itself can't convert the value.
+    assert_eq!(
+        bag.read::<i32>("Name").unwrap_err(),
+        Error::DISP_TYPEMISMATCH
+    );
+    assert_eq!(bag.read::<String>("Count"), Ok("3".to_string()));
+
+    let info = bag.properties().unwrap();
+    let types: Vec<_> = info.iter().map(|p| (&p.name[..], p.vartype)).collect();
+    assert_eq!(types, [("Count", VarType::I4), ("Name", VarType::BSTR)]);
+}
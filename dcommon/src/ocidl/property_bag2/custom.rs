@@ -0,0 +1,294 @@
+use crate::helpers::{wrap_ref_to_raw_mut_com, CoTaskWString, WideCStr};
+use crate::idltypes::variant::oleaut32::{VariantChangeType, VariantClear, VariantCopy};
+use crate::idltypes::variant::Variant;
+use crate::idltypes::vartype::VarType;
+use crate::oaidl::error_log::ErrorLog;
+use crate::oaidl::excep_info::ExcepInfo;
+use crate::ocidl::property_bag2::PropertyBag2;
+use crate::Error;
+
+use std::collections::BTreeMap;
+
+use com_impl::{com_impl, ComImpl, Refcount, VTable};
+use com_wrapper::ComWrapper;
+use winapi::ctypes::c_void;
+use winapi::shared::guiddef::GUID;
+use winapi::shared::winerror::{E_FAIL, S_OK};
+use winapi::shared::wtypesbase::LPCOLESTR;
+use winapi::um::oaidl::{IErrorLog, VARIANT};
+use winapi::um::ocidl::{
+    IPropertyBag2, IPropertyBag2Vtbl, PROPBAG2, PROPBAG2_TYPE_DATA, PROPBAG2_TYPE_OBJECT,
+};
+use winapi::um::unknwnbase::{IUnknown, IUnknownVtbl};
+use winapi::Interface;
+
+#[repr(C)]
+#[derive(ComImpl)]
+/// An `IPropertyBag2` backed by a map of names to variants. Properties are
+/// enumerated in name order, and `Write` adds any property that is missing.
+pub struct CustomPropertyBag2 {
+    vtbl: VTable<IPropertyBag2Vtbl>,
+    refcount: Refcount,
+    props: BTreeMap<String, Variant>,
+}
+
+impl CustomPropertyBag2 {
+    /// Creates a bag holding `props`. The bag takes ownership of anything
+    /// the variants point to and frees it with `VariantClear`.
+    pub fn create<I>(props: I) -> PropertyBag2
+    where
+        I: IntoIterator<Item = (String, Variant)>,
+    {
+        let mut map = BTreeMap::new();
+        for (name, var) in props {
+            if let Some(mut old) = map.insert(name, var) {
+                unsafe { VariantClear(&mut old) };
+            }
+        }
+        unsafe {
+            let raw = Self::create_raw(map);
+            let raw: *mut CustomPropertyBag2 = raw;
+            PropertyBag2::from_raw(raw as *mut IPropertyBag2)
+        }
+    }
+
+    /// Copies the property into `out`, converting it to `vt` unless that is
+    /// `VarType::EMPTY`.
+    unsafe fn read_one(&self, name: &str, vt: VarType, out: *mut Variant) -> Result<(), Error> {
+        let var = self.props.get(name).ok_or(Error::INVALIDARG)?;
+        let hr = if vt == VarType::EMPTY || vt == var.vartype() {
+            VariantCopy(out, var)
+        } else {
+            VariantChangeType(out, var, 0, vt)
+        };
+        Error::map(hr, ())
+    }
+
+    /// Loads `object` from the bag stored under `name`.
+    unsafe fn load_one(
+        &self,
+        name: &str,
+        object: *const IUnknown,
+        errlog: *const IErrorLog,
+    ) -> Result<(), Error> {
+        let var = self.props.get(name).ok_or(Error::INVALIDARG)?;
+        let unk = match var.vartype() {
+            VarType::UNKNOWN => var.v.data.punk_val,
+            VarType::DISPATCH => var.v.data.pdisp_val as *mut IUnknown,
+            _ => return Err(Error::DISP_TYPEMISMATCH),
+        };
+        if unk.is_null() || object.is_null() {
+            return Err(Error::INVALIDARG);
+        }
+
+        let mut bag: *mut c_void = std::ptr::null_mut();
+        let hr = (*unk).QueryInterface(&IPropertyBag2::uuidof(), &mut bag);
+        Error::map(hr, ())?;
+
+        let mut persist: *mut c_void = std::ptr::null_mut();
+        let hr = (*object).QueryInterface(&IID_IPERSIST_PROPERTY_BAG2, &mut persist);
+        if hr < 0 {
+            (*(bag as *mut IUnknown)).Release();
+            return Err(Error(hr));
+        }
+
+        let persist = persist as *mut IPersistPropertyBag2;
+        let hr = ((*(*persist).vtbl).load)(persist, bag as *mut IPropertyBag2, errlog);
+        ((*(*persist).vtbl).parent.Release)(persist as *mut IUnknown);
+        (*(bag as *mut IUnknown)).Release();
+        Error::map(hr, ())
+    }
+}
+
+impl Drop for CustomPropertyBag2 {
+    fn drop(&mut self) {
+        for var in self.props.values_mut() {
+            unsafe { VariantClear(var) };
+        }
+    }
+}
+
+#[com_impl]
+unsafe impl IPropertyBag2 for CustomPropertyBag2 {
+    #[panic(result = "E_FAIL")]
+    unsafe fn read(
+        &self,
+        count: u32,
+        bags: *const PROPBAG2,
+        errlog: *const IErrorLog,
+        values: *mut VARIANT,
+        results: *mut i32,
+    ) -> i32 {
+        let mut status = S_OK;
+        for i in 0..count as usize {
+            let bag = &*bags.add(i);
+            let out = values.add(i) as *mut Variant;
+            *out = std::mem::zeroed();
+
+            let name = read_name(bag.pstrName);
+            let result = match &name {
+                Some(name) => self.read_one(name, VarType(bag.vt), out),
+                None => Err(Error::INVALIDARG),
+            };
+
+            let hr = match result {
+                Ok(()) => S_OK,
+                Err(err) => {
+                    log_error(errlog, name.as_ref().map_or("", |n| n), err);
+                    status = E_FAIL;
+                    err.0
+                }
+            };
+            if !results.is_null() {
+                *results.add(i) = hr;
+            }
+        }
+        status
+    }
+
+    #[panic(result = "E_FAIL")]
+    unsafe fn write(&mut self, count: u32, bags: *const PROPBAG2, values: *const VARIANT) -> i32 {
+        for i in 0..count as usize {
+            let bag = &*bags.add(i);
+            let name = match read_name(bag.pstrName) {
+                Some(name) => name,
+                None => return Error::INVALIDARG.0,
+            };
+
+            let mut var: Variant = std::mem::zeroed();
+            let hr = VariantCopy(&mut var, values.add(i) as *const Variant);
+            if hr < 0 {
+                return hr;
+            }
+            if let Some(mut old) = self.props.insert(name, var) {
+                VariantClear(&mut old);
+            }
+        }
+        S_OK
+    }
+
+    #[panic(result = "E_FAIL")]
+    unsafe fn count_properties(&self, count: *mut u32) -> i32 {
+        *count = self.props.len() as u32;
+        S_OK
+    }
+
+    #[panic(result = "E_FAIL")]
+    unsafe fn get_property_info(
+        &self,
+        index: u32,
+        count: u32,
+        bags: *mut PROPBAG2,
+        fetched: *mut u32,
+    ) -> i32 {
+        *fetched = 0;
+        let start = index as usize;
+        if start >= self.props.len() {
+            return Error::INVALIDARG.0;
+        }
+
+        let props = self.props.iter().skip(start).take(count as usize);
+        for (i, (name, var)) in props.enumerate() {
+            let vt = var.vartype();
+            let prop_type = match vt {
+                VarType::UNKNOWN | VarType::DISPATCH => PROPBAG2_TYPE_OBJECT,
+                _ => PROPBAG2_TYPE_DATA,
+            };
+
+            let bag = &mut *bags.add(i);
+            *bag = std::mem::zeroed();
+            bag.dwType = prop_type;
+            bag.vt = vt.0;
+            bag.dwHint = (start + i) as u32;
+            bag.pstrName = CoTaskWString::from(&name[..]).into_raw();
+            *fetched += 1;
+        }
+        S_OK
+    }
+
+    #[panic(result = "E_FAIL")]
+    unsafe fn load_object(
+        &self,
+        name: LPCOLESTR,
+        _hint: u32,
+        object: *const IUnknown,
+        errlog: *const IErrorLog,
+    ) -> i32 {
+        let name = match read_name(name) {
+            Some(name) => name,
+            None => return Error::INVALIDARG.0,
+        };
+
+        match self.load_one(&name, object, errlog) {
+            Ok(()) => S_OK,
+            Err(err) => {
+                log_error(errlog, &name, err);
+                err.0
+            }
+        }
+    }
+}
+
+unsafe fn read_name(name: LPCOLESTR) -> Option<String> {
+    if name.is_null() {
+        None
+    } else {
+        Some(WideCStr::from_ptr(name).to_string_lossy())
+    }
+}
+
+/// Reports a failure to the caller's error log, if it passed one.
+unsafe fn log_error(errlog: *const IErrorLog, name: &str, err: Error) {
+    if errlog.is_null() {
+        return;
+    }
+    let mut errlog = errlog as *mut IErrorLog;
+    let log = wrap_ref_to_raw_mut_com::<ErrorLog>(&mut errlog);
+    // Failing to log shouldn't hide the original error.
+    let _ = log.add_error(name, &ExcepInfo::new(err));
+}
+
+/// {22F55881-280B-11d0-A8A9-00A0C90C2004}
+const IID_IPERSIST_PROPERTY_BAG2: GUID = GUID {
+    Data1: 0x22f5_5881,
+    Data2: 0x280b,
+    Data3: 0x11d0,
+    Data4: [0xa8, 0xa9, 0x00, 0xa0, 0xc9, 0x0c, 0x20, 0x04],
+};
+
+/// The parts of `IPersistPropertyBag2` that `LoadObject` needs, which
+/// winapi doesn't declare.
+#[repr(C)]
+struct IPersistPropertyBag2 {
+    vtbl: *const IPersistPropertyBag2Vtbl,
+}
+
+#[repr(C)]
+struct IPersistPropertyBag2Vtbl {
+    parent: IUnknownVtbl,
+    _get_class_id: unsafe extern "system" fn(*mut IPersistPropertyBag2, *mut GUID) -> i32,
+    _init_new: unsafe extern "system" fn(*mut IPersistPropertyBag2) -> i32,
+    load: unsafe extern "system" fn(
+        *mut IPersistPropertyBag2,
+        *mut IPropertyBag2,
+        *const IErrorLog,
+    ) -> i32,
+}
+
+#[cfg(test)]
+#[test]
+fn custom_property_bag_roundtrip() {
+    let props = vec![("Quality".to_string(), Variant::new_f32(0.5))];
+    let mut bag = CustomPropertyBag2::create(props);
+
+    bag.write("Name", &"test".to_string()).unwrap();
+    assert_eq!(bag.count_properties(), Ok(2));
+    assert_eq!(bag.read::<f32>("Quality"), Ok(0.5));
+    assert_eq!(bag.read::<String>("Name"), Ok("test".to_string()));
+    assert_eq!(bag.read::<f64>("Quality"), Ok(0.5));
+    assert!(bag.read::<i32>("Missing").is_err());
+
+    let names: Vec<_> = bag.properties().unwrap();
+    let names: Vec<_> = names.iter().map(|p| &p.name[..]).collect();
+    assert_eq!(names, ["Name", "Quality"]);
+}
@@ -1,12 +1,68 @@
-use crate::idltypes::variant::Variant;
+use crate::idltypes::bstr::BStr;
+use crate::idltypes::variant::{InnerVariantData, Variant};
 use crate::idltypes::vartype::VarType;
+use crate::GUID;
 
+/// A type that can be read from and written to a `PropertyBag2`.
+///
+/// # Safety
+///
+/// `from_variant` reads the variant's data through the union member for its
+/// type, so it must only do so for the types listed in `VARTYPES`. Unless
+/// `to_variant` is overridden, `Self` must also have the layout of the union
+/// member for `VARTYPES[0]`.
 pub unsafe trait PropertyType<'a>
 where
     Self: 'a,
 {
+    /// The variant types `from_variant` accepts.
     const VARTYPES: &'static [VarType];
+
+    /// Reads the value. Panics if the variant's type is not in `VARTYPES`.
     fn from_variant(var: &Variant) -> Self;
+
+    /// Reads the value, returning None if the variant has the wrong type or
+    /// holds a value that can't be represented.
+    fn try_from_variant(var: &Variant) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::VARTYPES.contains(&var.vartype()) {
+            Some(Self::from_variant(var))
+        } else {
+            None
+        }
+    }
+
+    /// Builds a variant holding the value. Variants that own memory, like
+    /// BSTRs, must be freed by the caller with `VariantClear`.
+    ///
+    /// The default copies the value bit for bit into a variant of type
+    /// `VARTYPES[0]`. It panics for types that need dropping, since the
+    /// variant can't take over memory they own; those must override it.
+    fn to_variant(&self) -> Variant
+    where
+        Self: Sized,
+    {
+        let vartype = Self::VARTYPES[0];
+        assert!(
+            !std::mem::needs_drop::<Self>(),
+            "PropertyType::to_variant must be overridden for types that own memory"
+        );
+        assert_eq!(vartype.container_type(), VarType::EMPTY);
+        assert!(std::mem::size_of::<Self>() <= std::mem::size_of::<InnerVariantData>());
+        unsafe {
+            let mut var: Variant = std::mem::zeroed();
+            var.v.vartype = vartype;
+            let dst = &mut var.v.data as *mut InnerVariantData as *mut u8;
+            std::ptr::copy_nonoverlapping(
+                self as *const Self as *const u8,
+                dst,
+                std::mem::size_of::<Self>(),
+            );
+            var
+        }
+    }
 }
 
 unsafe impl PropertyType<'static> for i8 {
@@ -21,6 +77,10 @@ unsafe impl PropertyType<'static> for i8 {
             }
         }
     }
+
+    fn to_variant(&self) -> Variant {
+        Variant::new_i8(*self)
+    }
 }
 
 unsafe impl PropertyType<'static> for u8 {
@@ -36,6 +96,10 @@ unsafe impl PropertyType<'static> for u8 {
             }
         }
     }
+
+    fn to_variant(&self) -> Variant {
+        Variant::new_u8(*self)
+    }
 }
 
 unsafe impl PropertyType<'static> for i16 {
@@ -50,6 +114,10 @@ unsafe impl PropertyType<'static> for i16 {
             }
         }
     }
+
+    fn to_variant(&self) -> Variant {
+        Variant::new_i16(*self)
+    }
 }
 
 unsafe impl PropertyType<'static> for u16 {
@@ -65,6 +133,10 @@ unsafe impl PropertyType<'static> for u16 {
             }
         }
     }
+
+    fn to_variant(&self) -> Variant {
+        Variant::new_u16(*self)
+    }
 }
 
 unsafe impl PropertyType<'static> for i32 {
@@ -86,6 +158,10 @@ unsafe impl PropertyType<'static> for i32 {
             }
         }
     }
+
+    fn to_variant(&self) -> Variant {
+        Variant::new_i32(*self)
+    }
 }
 
 unsafe impl PropertyType<'static> for u32 {
@@ -107,6 +183,10 @@ unsafe impl PropertyType<'static> for u32 {
             }
         }
     }
+
+    fn to_variant(&self) -> Variant {
+        Variant::new_u32(*self)
+    }
 }
 
 unsafe impl PropertyType<'static> for i64 {
@@ -121,6 +201,10 @@ unsafe impl PropertyType<'static> for i64 {
             }
         }
     }
+
+    fn to_variant(&self) -> Variant {
+        Variant::new_i64(*self)
+    }
 }
 
 unsafe impl PropertyType<'static> for u64 {
@@ -136,4 +220,235 @@ unsafe impl PropertyType<'static> for u64 {
             }
         }
     }
+
+    fn to_variant(&self) -> Variant {
+        Variant::new_u64(*self)
+    }
+}
+
+unsafe impl PropertyType<'static> for f32 {
+    const VARTYPES: &'static [VarType] = &[VarType::R4, VarType(VarType::BYREF.0 | VarType::R4.0)];
+    fn from_variant(var: &Variant) -> Self {
+        unsafe {
+            let vt = var.vartype();
+            match (vt.container_type(), vt.primitive_type()) {
+                (VarType::EMPTY, VarType::R4) => var.v.data.flt_val,
+                (VarType::BYREF, VarType::R4) => *var.v.data.pflt_val,
+                _ => panic!(),
+            }
+        }
+    }
+
+    fn to_variant(&self) -> Variant {
+        Variant::new_f32(*self)
+    }
+}
+
+unsafe impl PropertyType<'static> for f64 {
+    const VARTYPES: &'static [VarType] = &[VarType::R8, VarType(VarType::BYREF.0 | VarType::R8.0)];
+    fn from_variant(var: &Variant) -> Self {
+        unsafe {
+            let vt = var.vartype();
+            match (vt.container_type(), vt.primitive_type()) {
+                (VarType::EMPTY, VarType::R8) => var.v.data.dbl_val,
+                (VarType::BYREF, VarType::R8) => *var.v.data.pdbl_val,
+                _ => panic!(),
+            }
+        }
+    }
+
+    fn to_variant(&self) -> Variant {
+        Variant::new_f64(*self)
+    }
+}
+
+unsafe impl PropertyType<'static> for bool {
+    const VARTYPES: &'static [VarType] =
+        &[VarType::BOOL, VarType(VarType::BYREF.0 | VarType::BOOL.0)];
+    fn from_variant(var: &Variant) -> Self {
+        unsafe {
+            let vt = var.vartype();
+            match (vt.container_type(), vt.primitive_type()) {
+                (VarType::EMPTY, VarType::BOOL) => var.v.data.bool_val != 0,
+                (VarType::BYREF, VarType::BOOL) => *var.v.data.pbool_val != 0,
+                _ => panic!(),
+            }
+        }
+    }
+
+    fn to_variant(&self) -> Variant {
+        Variant::new_bool(*self)
+    }
+}
+
+unsafe impl PropertyType<'static> for String {
+    const VARTYPES: &'static [VarType] =
+        &[VarType::BSTR, VarType(VarType::BYREF.0 | VarType::BSTR.0)];
+    fn from_variant(var: &Variant) -> Self {
+        unsafe {
+            let vt = var.vartype();
+            let bstr = match (vt.container_type(), vt.primitive_type()) {
+                (VarType::EMPTY, VarType::BSTR) => var.v.data.bstr_val,
+                (VarType::BYREF, VarType::BSTR) => *var.v.data.pbstr_val,
+                _ => panic!(),
+            };
+            BStr::read_raw_lossy(bstr)
+        }
+    }
+
+    fn to_variant(&self) -> Variant {
+        Variant::new_bstr(BStr::new(self))
+    }
+}
+
+/// GUIDs are stored as BSTRs in registry format, e.g.
+/// `{6FDDC324-4E03-4BFE-B185-3D77768DC90F}`, since a VARIANT can't hold a
+/// CLSID directly.
+unsafe impl PropertyType<'static> for GUID {
+    const VARTYPES: &'static [VarType] = String::VARTYPES;
+    fn from_variant(var: &Variant) -> Self {
+        GUID::try_from_variant(var).expect("variant does not hold a GUID")
+    }
+
+    fn try_from_variant(var: &Variant) -> Option<Self> {
+        parse_guid(&String::try_from_variant(var)?)
+    }
+
+    fn to_variant(&self) -> Variant {
+        Variant::new_bstr(BStr::new(&format_guid(self)))
+    }
+}
+
+fn format_guid(guid: &GUID) -> String {
+    let d4 = &guid.Data4;
+    format!(
+        "{{{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}}}",
+        guid.Data1, guid.Data2, guid.Data3, d4[0], d4[1], d4[2], d4[3], d4[4], d4[5], d4[6], d4[7]
+    )
+}
+
+fn parse_guid(s: &str) -> Option<GUID> {
+    let s = s.trim();
+    let s = if s.starts_with('{') && s.ends_with('}') {
+        &s[1..s.len() - 1]
+    } else {
+        s
+    };
+
+    let groups: Vec<&str> = s.split('-').collect();
+    let lens = [8, 4, 4, 4, 12];
+    if groups.len() != lens.len()
+        || groups.iter().zip(&lens).any(|(g, &len)| g.len() != len)
+        || !groups
+            .iter()
+            .all(|g| g.bytes().all(|c| c.is_ascii_hexdigit()))
+    {
+        return None;
+    }
+
+    let tail = format!("{}{}", groups[3], groups[4]);
+    let mut data4 = [0u8; 8];
+    for (i, b) in data4.iter_mut().enumerate() {
+        *b = u8::from_str_radix(&tail[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(GUID {
+        Data1: u32::from_str_radix(groups[0], 16).ok()?,
+        Data2: u16::from_str_radix(groups[1], 16).ok()?,
+        Data3: u16::from_str_radix(groups[2], 16).ok()?,
+        Data4: data4,
+    })
+}
+
+#[cfg(test)]
+#[test]
+fn proptype_vartypes() {
+    use crate::idltypes::variant::oleaut32::VariantClear;
+
+    fn check<T>(value: T, byref: impl FnOnce(&mut Variant, &mut T))
+    where
+        T: PropertyType<'static> + PartialEq + std::fmt::Debug + Clone,
+    {
+        let mut var = value.to_variant();
+        assert_eq!(var.vartype(), T::VARTYPES[0]);
+        assert_eq!(T::try_from_variant(&var), Some(value.clone()));
+
+        // The same value behind a BYREF pointer.
+        let mut target = value.clone();
+        let mut by_ref: Variant = unsafe { std::mem::zeroed() };
+        byref(&mut by_ref, &mut target);
+        by_ref.v.vartype = VarType(VarType::BYREF.0 | T::VARTYPES[0].0);
+        assert!(T::VARTYPES.contains(&by_ref.vartype()));
+        assert_eq!(T::from_variant(&by_ref), value);
+
+        let mut wrong: Variant = unsafe { std::mem::zeroed() };
+        wrong.v.vartype = VarType::NULL;
+        assert_eq!(T::try_from_variant(&wrong), None);
+
+        unsafe { VariantClear(&mut var) };
+    }
+
+    unsafe {
+        check(-5i8, |v, t| v.v.data.pc_val = t);
+        check(200u8, |v, t| v.v.data.pb_val = t);
+        check(-300i16, |v, t| v.v.data.pi_val = t);
+        check(60_000u16, |v, t| v.v.data.pui_val = t);
+        check(-70_000i32, |v, t| v.v.data.pl_val = t);
+        check(4_000_000_000u32, |v, t| v.v.data.pul_val = t);
+        check(-1i64 << 40, |v, t| v.v.data.pll_val = t);
+        check(1u64 << 63, |v, t| v.v.data.pull_val = t);
+        check(0.25f32, |v, t| v.v.data.pflt_val = t);
+        check(-1.5f64, |v, t| v.v.data.pdbl_val = t);
+    }
+
+    let mut var = true.to_variant();
+    assert_eq!(var.vartype(), VarType::BOOL);
+    assert_eq!(bool::try_from_variant(&var), Some(true));
+    assert_eq!(unsafe { var.v.data.bool_val }, -1);
+    assert_eq!(i32::try_from_variant(&var), None);
+
+    var = "héllo".to_string().to_variant();
+    assert_eq!(var.vartype(), VarType::BSTR);
+    assert_eq!(String::try_from_variant(&var), Some("héllo".to_string()));
+    assert!(GUID::try_from_variant(&var).is_none());
+    assert_eq!(u8::try_from_variant(&var), None);
+    unsafe { VariantClear(&mut var) };
+
+    let guid = GUID {
+        Data1: 0x6FDD_C324,
+        Data2: 0x4E03,
+        Data3: 0x4BFE,
+        Data4: [0xB1, 0x85, 0x3D, 0x77, 0x76, 0x8D, 0xC9, 0x0F],
+    };
+    let mut var = guid.to_variant();
+    assert_eq!(
+        String::try_from_variant(&var).unwrap(),
+        "{6FDDC324-4E03-4BFE-B185-3D77768DC90F}"
+    );
+    let parsed = GUID::try_from_variant(&var).unwrap();
+    assert_eq!(format_guid(&parsed), format_guid(&guid));
+    unsafe { VariantClear(&mut var) };
+
+    assert!(parse_guid("6fddc324-4e03-4bfe-b185-3d77768dc90f").is_some());
+    assert!(parse_guid("{6FDDC324-4E03-4BFE-B185-3D77768DC90}").is_none());
+    assert!(parse_guid("{6FDDC324-4E03-4BFE-B185-3D77768DC90G}").is_none());
+}
+
+#[cfg(test)]
+#[test]
+fn proptype_default_to_variant() {
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    #[repr(transparent)]
+    struct Level(i32);
+
+    unsafe impl PropertyType<'static> for Level {
+        const VARTYPES: &'static [VarType] = &[VarType::I4];
+        fn from_variant(var: &Variant) -> Self {
+            Level(unsafe { var.v.data.l_val })
+        }
+    }
+
+    let var = Level(-7).to_variant();
+    assert_eq!(var.vartype(), VarType::I4);
+    assert_eq!(i32::try_from_variant(&var), Some(-7));
+    assert_eq!(Level::try_from_variant(&var), Some(Level(-7)));
 }
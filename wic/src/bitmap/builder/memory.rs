@@ -72,6 +72,12 @@ impl<'a> MemoryBuilder<'a> {
         unsafe { self.with_data_unchecked(data, stride) }
     }
 
+    /// Like `with_data`, but skips the checks on the buffer's size.
+    ///
+    /// # Safety
+    ///
+    /// `data` must hold at least `stride * height` bytes, and `stride` must
+    /// cover a row of pixels in the bitmap's format.
     pub unsafe fn with_data_unchecked(mut self, data: &'a [u8], stride: u32) -> Self {
         self.data = Some((data, stride));
         self
@@ -1,12 +1,11 @@
 use std::ffi::OsStr;
 
 use com_wrapper::ComWrapper;
-use dcommon::helpers::wstrnlen;
+use dcommon::helpers::{wstrnlen, WideString};
 use dcommon::Error;
 use dcommon::GUID;
 use winapi::um::wincodec::IWICBitmapCodecInfo;
 use wio::com::ComPtr;
-use wio::wide::ToWide;
 
 #[repr(transparent)]
 #[derive(ComWrapper)]
@@ -110,7 +109,10 @@ impl BitmapCodecInfo {
     }
 
     pub fn matches_mimetype(&self, s: impl AsRef<OsStr>) -> bool {
-        let s = s.as_ref().to_wide_null();
+        let s = match WideString::new(s) {
+            Ok(s) => s,
+            Err(_) => return false,
+        };
 
         unsafe {
             let mut b = 0;
@@ -0,0 +1,56 @@
+use crate::bitmap_source::BitmapSource;
+use crate::enums::BitmapTransformOptions;
+use crate::imaging_factory::ImagingFactory;
+
+use com_wrapper::ComWrapper;
+use dcommon::helpers::{deref_com_wrapper, deref_com_wrapper_mut};
+use dcommon::{Error, Status};
+use winapi::um::wincodec::IWICBitmapFlipRotator;
+use wio::com::ComPtr;
+
+/// Rotates and flips a source. `software::PixelBuffer::flip_rotate` does
+/// the same in Rust.
+#[repr(transparent)]
+#[derive(ComWrapper)]
+#[com(debug)]
+pub struct BitmapFlipRotator {
+    ptr: ComPtr<IWICBitmapFlipRotator>,
+}
+
+impl BitmapFlipRotator {
+    pub fn create(factory: &ImagingFactory) -> Result<Self, Error> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            let hr = (*factory.get_raw()).CreateBitmapFlipRotator(&mut ptr);
+            Error::map_if(hr, || Self::from_raw(ptr))
+        }
+    }
+
+    pub fn initialize(
+        &mut self,
+        source: &BitmapSource,
+        options: BitmapTransformOptions,
+    ) -> Result<Status, Error> {
+        unsafe {
+            let hr = self.ptr.Initialize(source.get_raw(), options.0);
+            Error::map_status(hr)
+        }
+    }
+
+    pub fn into_source(self) -> BitmapSource {
+        unsafe { BitmapSource::from_raw(self.ptr.into_raw() as _) }
+    }
+}
+
+impl std::ops::Deref for BitmapFlipRotator {
+    type Target = BitmapSource;
+    fn deref(&self) -> &Self::Target {
+        unsafe { deref_com_wrapper(self) }
+    }
+}
+
+impl std::ops::DerefMut for BitmapFlipRotator {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { deref_com_wrapper_mut(self) }
+    }
+}
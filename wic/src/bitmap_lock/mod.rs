@@ -1,4 +1,5 @@
 use crate::descriptions::{pixel_format::PixelFormatDescription, PixelFormat};
+use crate::image_view::{ImageView, ImageViewMut, Pixel};
 
 use com_wrapper::ComWrapper;
 use dcommon::Error;
@@ -23,6 +24,15 @@ impl BitmapLock {
         }
     }
 
+    /// The distance between the starts of two rows, in bytes.
+    pub fn stride(&self) -> Result<u32, Error> {
+        unsafe {
+            let mut stride = 0;
+            let hr = self.ptr.GetStride(&mut stride);
+            Error::map(hr, stride)
+        }
+    }
+
     pub fn pixel_format(&self) -> Result<PixelFormat, Error> {
         unsafe {
             let mut guid = std::mem::zeroed();
@@ -35,7 +45,43 @@ impl BitmapLock {
         self.pixel_format().ok().and_then(|f| f.description())
     }
 
+    /// The locked pixels and their length in bytes.
+    ///
+    /// # Safety
+    ///
+    /// The pointer is only valid while the lock is alive.
     pub unsafe fn data_ptr(&mut self) -> Result<(*mut u8, u32), Error> {
+        self.raw_data()
+    }
+
+    /// Views the locked pixels as `P`. The view borrows the lock, so it
+    /// can't outlive it. Fails with `Error::WIC_UNSUPPORTEDPIXELFORMAT` if
+    /// the bitmap's format doesn't match `P`.
+    pub fn view<P: Pixel>(&self) -> Result<ImageView<'_, P>, Error> {
+        let size = self.size()?;
+        let stride = self.stride()?;
+        let format = self.pixel_format()?;
+        unsafe {
+            let (ptr, len) = self.raw_data()?;
+            let data = std::slice::from_raw_parts(ptr, len as usize);
+            ImageView::new(data, format, size.width, size.height, stride as usize)
+        }
+    }
+
+    /// Like `view`, but allows writing. The lock must have been taken with
+    /// `BitmapLockFlags::WRITE` for the changes to reach the bitmap.
+    pub fn view_mut<P: Pixel>(&mut self) -> Result<ImageViewMut<'_, P>, Error> {
+        let size = self.size()?;
+        let stride = self.stride()?;
+        let format = self.pixel_format()?;
+        unsafe {
+            let (ptr, len) = self.raw_data()?;
+            let data = std::slice::from_raw_parts_mut(ptr, len as usize);
+            ImageViewMut::new(data, format, size.width, size.height, stride as usize)
+        }
+    }
+
+    unsafe fn raw_data(&self) -> Result<(*mut u8, u32), Error> {
         let mut len = 0;
         let mut ptr = std::ptr::null_mut();
         let hr = self.ptr.GetDataPointer(&mut len, &mut ptr);
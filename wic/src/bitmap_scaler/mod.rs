@@ -0,0 +1,61 @@
+use crate::bitmap_source::BitmapSource;
+use crate::enums::BitmapInterpolationMode;
+use crate::imaging_factory::ImagingFactory;
+
+use com_wrapper::ComWrapper;
+use dcommon::helpers::{deref_com_wrapper, deref_com_wrapper_mut};
+use dcommon::{Error, Status};
+use math2d::Sizeu;
+use winapi::um::wincodec::IWICBitmapScaler;
+use wio::com::ComPtr;
+
+/// Resamples a source to a new size. `software::PixelBuffer::scale` does
+/// the same in Rust.
+#[repr(transparent)]
+#[derive(ComWrapper)]
+#[com(debug)]
+pub struct BitmapScaler {
+    ptr: ComPtr<IWICBitmapScaler>,
+}
+
+impl BitmapScaler {
+    pub fn create(factory: &ImagingFactory) -> Result<Self, Error> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            let hr = (*factory.get_raw()).CreateBitmapScaler(&mut ptr);
+            Error::map_if(hr, || Self::from_raw(ptr))
+        }
+    }
+
+    pub fn initialize(
+        &mut self,
+        source: &BitmapSource,
+        size: impl Into<Sizeu>,
+        mode: BitmapInterpolationMode,
+    ) -> Result<Status, Error> {
+        let size = size.into();
+        unsafe {
+            let hr = self
+                .ptr
+                .Initialize(source.get_raw(), size.width, size.height, mode as u32);
+            Error::map_status(hr)
+        }
+    }
+
+    pub fn into_source(self) -> BitmapSource {
+        unsafe { BitmapSource::from_raw(self.ptr.into_raw() as _) }
+    }
+}
+
+impl std::ops::Deref for BitmapScaler {
+    type Target = BitmapSource;
+    fn deref(&self) -> &Self::Target {
+        unsafe { deref_com_wrapper(self) }
+    }
+}
+
+impl std::ops::DerefMut for BitmapScaler {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { deref_com_wrapper_mut(self) }
+    }
+}
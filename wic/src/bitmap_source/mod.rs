@@ -1,7 +1,12 @@
 use crate::bitmap::Bitmap;
-use crate::descriptions::{pixel_format::PixelFormatDescription, PixelFormat};
+use crate::descriptions::pixel_format::{Category, ChannelKind, PixelFormatDescription};
+use crate::descriptions::PixelFormat;
+use crate::enums::{BitmapDitherType, BitmapPaletteType};
+use crate::format_converter::FormatConverter;
+use crate::image_view::{ImageViewMut, Pixel};
 use crate::imaging_factory::ImagingFactory;
 use crate::palette::Palette;
+use crate::software::{PixelBuffer, PixelConverter};
 
 use com_wrapper::ComWrapper;
 use dcommon::{Error, Status};
@@ -77,6 +82,12 @@ impl BitmapSource {
         unsafe { self.unchecked_copy_pixels(rect, buffer, stride) }
     }
 
+    /// Like `copy_pixels`, but skips the checks on the buffer's size.
+    ///
+    /// # Safety
+    ///
+    /// `buffer` must hold at least `stride` bytes for each row of
+    /// `source_rect`, and `stride` must cover a row of pixels.
     pub unsafe fn unchecked_copy_pixels(
         &self,
         source_rect: impl Into<Recti>,
@@ -90,7 +101,252 @@ impl BitmapSource {
         Error::map_status(hr)
     }
 
+    /// Like `copy_pixels`, but into a typed view whose size must match
+    /// `source_rect`. Fails with `Error::WIC_UNSUPPORTEDPIXELFORMAT` if this
+    /// bitmap's format doesn't match `P`.
+    pub fn copy_pixels_into<P: Pixel>(
+        &self,
+        source_rect: impl Into<Recti>,
+        view: &mut ImageViewMut<P>,
+    ) -> Result<Status, Error> {
+        let rect = source_rect.into();
+        if !P::matches(self.pixel_format()?) {
+            return Err(Error::WIC_UNSUPPORTEDPIXELFORMAT);
+        }
+        if rect.width() as u32 != view.width() || rect.height() as u32 != view.height() {
+            return Err(Error::INVALIDARG);
+        }
+        let stride = view.stride() as u32;
+        // The view has already checked that every row fits.
+        unsafe { self.unchecked_copy_pixels(rect, view.as_bytes_mut(), stride) }
+    }
+
+    /// Creates a software converter from this bitmap's format to `target`,
+    /// copying the palette if the format is indexed.
+    pub fn software_converter(
+        &self,
+        factory: &ImagingFactory,
+        target: PixelFormat,
+    ) -> Result<PixelConverter, Error> {
+        let format = self.pixel_format()?;
+        let mut converter = PixelConverter::new(format, target)?;
+        if let Some(desc) = format.description() {
+            if desc.category.0 & Category::INDEXED.0 != 0 {
+                let mut palette = Palette::create(factory)?;
+                self.copy_palette(&mut palette)?;
+                converter = converter.with_source_palette(&palette.colors()?);
+            }
+        }
+        Ok(converter)
+    }
+
+    /// Like `copy_pixels`, but converts the pixels in software with
+    /// `converter`, whose source format must be this bitmap's.
+    pub fn copy_pixels_converted(
+        &self,
+        converter: &PixelConverter,
+        source_rect: impl Into<Recti>,
+        buffer: &mut [u8],
+        stride: u32,
+    ) -> Result<Status, Error> {
+        let rect = source_rect.into();
+        let format = self.pixel_format()?;
+        if format != converter.source_format() {
+            return Err(Error::WIC_UNSUPPORTEDPIXELFORMAT);
+        }
+        let desc = format
+            .description()
+            .ok_or(Error::WIC_UNSUPPORTEDPIXELFORMAT)?;
+        if rect.width() < 0 || rect.height() < 0 {
+            return Err(Error::INVALIDARG);
+        }
+        let (width, height) = (rect.width() as u32, rect.height() as u32);
+        let native_stride = desc.stride(width).ok_or(Error::WIC_INSUFFICIENTBUFFER)?;
+        let native_len = native_stride
+            .checked_mul(height as usize)
+            .ok_or(Error::WIC_INSUFFICIENTBUFFER)?;
+        let native_stride_u32 =
+            u32::try_from(native_stride).map_err(|_| Error::WIC_INSUFFICIENTBUFFER)?;
+        let mut native = vec![0; native_len];
+        let status = self.copy_pixels(rect, &mut native, native_stride_u32)?;
+        converter.convert(
+            &native,
+            native_stride,
+            buffer,
+            stride as usize,
+            width,
+            height,
+        )?;
+        Ok(status)
+    }
+
+    /// Like `copy_pixels`, but converts the pixels to `format`. WIC's
+    /// `FormatConverter` is used if it supports the conversion, and
+    /// `PixelConverter` otherwise.
+    ///
+    /// If `format` is indexed, `palette` gives its colors. Without one, WIC
+    /// generates a median cut palette from the pixels, and the software
+    /// fallback fails with `WINCODEC_ERR_PALETTEUNAVAILABLE`. `palette` is
+    /// ignored for other formats.
+    pub fn copy_pixels_as(
+        &self,
+        factory: &ImagingFactory,
+        format: PixelFormat,
+        palette: Option<&Palette>,
+        source_rect: impl Into<Recti>,
+        buffer: &mut [u8],
+        stride: u32,
+    ) -> Result<Status, Error> {
+        let rect = source_rect.into();
+        let source_format = self.pixel_format()?;
+        let indexed = is_indexed(format);
+        let palette = if indexed { palette } else { None };
+        let palette_type = if indexed && palette.is_none() {
+            BitmapPaletteType::MedianCut
+        } else {
+            BitmapPaletteType::Custom
+        };
+        let mut native = FormatConverter::create(factory)?;
+        if native.can_convert(source_format, format).unwrap_or(false) {
+            native.initialize(
+                self,
+                format,
+                BitmapDitherType::None,
+                palette,
+                0.0,
+                palette_type,
+            )?;
+            return native.copy_pixels(rect, buffer, stride);
+        }
+
+        self.copy_pixels_as_software(factory, format, palette, rect, buffer, stride)
+    }
+
+    fn copy_pixels_as_software(
+        &self,
+        factory: &ImagingFactory,
+        format: PixelFormat,
+        palette: Option<&Palette>,
+        rect: Recti,
+        buffer: &mut [u8],
+        stride: u32,
+    ) -> Result<Status, Error> {
+        let mut converter = self.software_converter(factory, format)?;
+        if is_indexed(format) {
+            let palette = palette.ok_or(Error::WIC_PALETTEUNAVAILABLE)?;
+            converter = converter.with_target_palette(&palette.colors()?);
+        }
+        self.copy_pixels_converted(&converter, rect, buffer, stride)
+    }
+
+    /// Copies the pixels in `source_rect` into a `PixelBuffer`, for use with
+    /// the software transforms.
+    pub fn to_pixel_buffer(&self, source_rect: impl Into<Recti>) -> Result<PixelBuffer, Error> {
+        let rect = source_rect.into();
+        let (width, height) = (rect.width() as u32, rect.height() as u32);
+        let mut buffer = PixelBuffer::new(self.pixel_format()?, width, height)?;
+        let stride = buffer.stride() as u32;
+        self.copy_pixels(rect, buffer.data_mut(), stride)?;
+        Ok(buffer)
+    }
+
     pub fn clone_to_bitmap(&self, factory: &ImagingFactory) -> Result<Bitmap, Error> {
         Bitmap::create(factory).from_source(self).build()
     }
 }
+
+fn is_indexed(format: PixelFormat) -> bool {
+    format
+        .description()
+        .is_some_and(|desc| desc.channel(ChannelKind::Index).is_some())
+}
+
+#[cfg(all(test, windows))]
+fn test_factory() -> ImagingFactory {
+    use std::ptr;
+    use winapi::shared::winerror::SUCCEEDED;
+    use winapi::um::combaseapi::{CoCreateInstance, CoInitializeEx};
+    use winapi::um::objbase::COINIT_MULTITHREADED;
+    use winapi::um::wincodec::{CLSID_WICImagingFactory, IWICImagingFactory};
+    use winapi::Interface;
+
+    unsafe {
+        CoInitializeEx(ptr::null_mut(), COINIT_MULTITHREADED);
+        let mut ptr: *mut IWICImagingFactory = ptr::null_mut();
+        let hr = CoCreateInstance(
+            &CLSID_WICImagingFactory,
+            ptr::null_mut(),
+            winapi::shared::wtypesbase::CLSCTX_INPROC_SERVER,
+            &IWICImagingFactory::uuidof(),
+            &mut ptr as *mut _ as *mut _,
+        );
+        assert!(SUCCEEDED(hr));
+        ImagingFactory::from_raw(ptr)
+    }
+}
+
+#[cfg(all(test, windows))]
+#[test]
+fn copy_pixels_as_indexed() {
+    use crate::descriptions::Color;
+
+    let factory = test_factory();
+    let bgra = PixelFormat::from_label("GUID_WICPixelFormat32bppBGRA").unwrap();
+    let indexed = PixelFormat::from_label("GUID_WICPixelFormat8bppIndexed").unwrap();
+    let data = [0, 0, 255, 255, 255, 0, 0, 255];
+    let source = Bitmap::create(&factory)
+        .from_memory()
+        .with_size((2, 1))
+        .with_format(bgra)
+        .with_data(&data, 8)
+        .build()
+        .unwrap()
+        .into_source();
+    let mut palette = Palette::create(&factory).unwrap();
+    palette
+        .initialize_custom(&[Color::rgb(0, 0, 255), Color::rgb(255, 0, 0)])
+        .unwrap();
+    let rect = Recti {
+        left: 0,
+        top: 0,
+        right: 2,
+        bottom: 1,
+    };
+
+    let mut out = [9; 2];
+    let palette = Some(&palette);
+    source
+        .copy_pixels_as(&factory, indexed, palette, rect, &mut out, 2)
+        .unwrap();
+    assert_eq!(out, [1, 0]);
+
+    let mut out = [9; 2];
+    source
+        .copy_pixels_as_software(&factory, indexed, palette, rect, &mut out, 2)
+        .unwrap();
+    assert_eq!(out, [1, 0]);
+
+    let err = source.copy_pixels_as_software(&factory, indexed, None, rect, &mut out, 2);
+    assert_eq!(err.unwrap_err(), Error::WIC_PALETTEUNAVAILABLE);
+
+    // Without a palette, WIC picks one from the pixels.
+    let mut out = [9; 2];
+    source
+        .copy_pixels_as(&factory, indexed, None, rect, &mut out, 2)
+        .unwrap();
+    assert_ne!(out[0], out[1]);
+
+    let mut negative = [0; 8];
+    let err = source.copy_pixels_converted(
+        &source.software_converter(&factory, bgra).unwrap(),
+        Recti {
+            left: 2,
+            top: 0,
+            right: 0,
+            bottom: 1,
+        },
+        &mut negative,
+        8,
+    );
+    assert_eq!(err.unwrap_err(), Error::INVALIDARG);
+}
@@ -11,6 +11,10 @@ pub struct BitmapPattern<'a> {
 }
 
 impl<'a> BitmapPattern<'a> {
+    /// # Safety
+    ///
+    /// `raw.Pattern` and `raw.Mask` must each point to `raw.Length` bytes
+    /// that stay valid for `'a`.
     pub unsafe fn from_raw(raw: &WICBitmapPattern) -> BitmapPattern<'a> {
         BitmapPattern {
             position: *raw.Position.QuadPart(),
@@ -4,6 +4,11 @@ pub struct Color {
     pub value: u32,
 }
 
+/// A color value that can be handed to WIC as a palette entry.
+///
+/// # Safety
+///
+/// The type must have the size and layout of a `u32` in 0xAARRGGBB form.
 pub unsafe trait ColorType: Sized {}
 unsafe impl ColorType for Color {}
 unsafe impl ColorType for u32 {}
@@ -24,7 +29,7 @@ impl Color {
         (self.value >> 8) as u8
     }
     pub fn b(self) -> u8 {
-        (self.value >> 0) as u8
+        self.value as u8
     }
     pub fn a(self) -> u8 {
         (self.value >> 24) as u8
@@ -37,7 +42,7 @@ impl Color {
         self.value = (self.value & 0xFF_FF_00_FF) | ((val as u32) << 8);
     }
     pub fn set_b(&mut self, val: u8) {
-        self.value = (self.value & 0xFF_FF_FF_00) | ((val as u32) << 0);
+        self.value = (self.value & 0xFF_FF_FF_00) | val as u32;
     }
     pub fn set_a(&mut self, val: u8) {
         self.value = (self.value & 0x00_FF_FF_FF) | ((val as u32) << 24);
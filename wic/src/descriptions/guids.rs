@@ -0,0 +1,130 @@
+//! The WIC pixel format GUIDs.
+//!
+//! These are defined here rather than taken from winapi so that the pixel
+//! format descriptions and the software implementations are available on
+//! every target. The values are the same as winapi's, which also makes them
+//! interchangeable with its constants on Windows.
+
+#![allow(non_upper_case_globals)]
+
+use crate::GUID;
+
+macro_rules! guids {
+    ($($name:ident = $d1:expr, $d2:expr, $d3:expr, [$($d4:expr),*];)*) => {
+        $(
+            pub const $name: GUID = GUID {
+                Data1: $d1,
+                Data2: $d2,
+                Data3: $d3,
+                Data4: [$($d4),*],
+            };
+        )*
+    };
+}
+
+guids! {
+    GUID_WICPixelFormatDontCare = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x00];
+    GUID_WICPixelFormat1bppIndexed = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x01];
+    GUID_WICPixelFormat2bppIndexed = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x02];
+    GUID_WICPixelFormat4bppIndexed = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x03];
+    GUID_WICPixelFormat8bppIndexed = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x04];
+    GUID_WICPixelFormatBlackWhite = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x05];
+    GUID_WICPixelFormat2bppGray = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x06];
+    GUID_WICPixelFormat4bppGray = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x07];
+    GUID_WICPixelFormat8bppGray = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x08];
+    GUID_WICPixelFormat8bppAlpha = 0xe6cd0116, 0xeeba, 0x4161, [0xaa, 0x85, 0x27, 0xdd, 0x9f, 0xb3, 0xa8, 0x95];
+    GUID_WICPixelFormat16bppBGR555 = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x09];
+    GUID_WICPixelFormat16bppBGR565 = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x0a];
+    GUID_WICPixelFormat16bppBGRA5551 = 0x05ec7c2b, 0xf1e6, 0x4961, [0xad, 0x46, 0xe1, 0xcc, 0x81, 0x0a, 0x87, 0xd2];
+    GUID_WICPixelFormat16bppGray = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x0b];
+    GUID_WICPixelFormat24bppBGR = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x0c];
+    GUID_WICPixelFormat24bppRGB = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x0d];
+    GUID_WICPixelFormat32bppBGR = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x0e];
+    GUID_WICPixelFormat32bppBGRA = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x0f];
+    GUID_WICPixelFormat32bppPBGRA = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x10];
+    GUID_WICPixelFormat32bppGrayFloat = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x11];
+    GUID_WICPixelFormat32bppRGB = 0xd98c6b95, 0x3efe, 0x47d6, [0xbb, 0x25, 0xeb, 0x17, 0x48, 0xab, 0x0c, 0xf1];
+    GUID_WICPixelFormat32bppRGBA = 0xf5c7ad2d, 0x6a8d, 0x43dd, [0xa7, 0xa8, 0xa2, 0x99, 0x35, 0x26, 0x1a, 0xe9];
+    GUID_WICPixelFormat32bppPRGBA = 0x3cc4a650, 0xa527, 0x4d37, [0xa9, 0x16, 0x31, 0x42, 0xc7, 0xeb, 0xed, 0xba];
+    GUID_WICPixelFormat48bppRGB = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x15];
+    GUID_WICPixelFormat48bppBGR = 0xe605a384, 0xb468, 0x46ce, [0xbb, 0x2e, 0x36, 0xf1, 0x80, 0xe6, 0x43, 0x13];
+    GUID_WICPixelFormat64bppRGB = 0xa1182111, 0x186d, 0x4d42, [0xbc, 0x6a, 0x9c, 0x83, 0x03, 0xa8, 0xdf, 0xf9];
+    GUID_WICPixelFormat64bppRGBA = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x16];
+    GUID_WICPixelFormat64bppBGRA = 0x1562ff7c, 0xd352, 0x46f9, [0x97, 0x9e, 0x42, 0x97, 0x6b, 0x79, 0x22, 0x46];
+    GUID_WICPixelFormat64bppPRGBA = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x17];
+    GUID_WICPixelFormat64bppPBGRA = 0x8c518e8e, 0xa4ec, 0x468b, [0xae, 0x70, 0xc9, 0xa3, 0x5a, 0x9c, 0x55, 0x30];
+    GUID_WICPixelFormat16bppGrayFixedPoint = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x13];
+    GUID_WICPixelFormat32bppBGR101010 = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x14];
+    GUID_WICPixelFormat48bppRGBFixedPoint = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x12];
+    GUID_WICPixelFormat48bppBGRFixedPoint = 0x49ca140e, 0xcab6, 0x493b, [0x9d, 0xdf, 0x60, 0x18, 0x7c, 0x37, 0x53, 0x2a];
+    GUID_WICPixelFormat96bppRGBFixedPoint = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x18];
+    GUID_WICPixelFormat96bppRGBFloat = 0xe3fed78f, 0xe8db, 0x4acf, [0x84, 0xc1, 0xe9, 0x7f, 0x61, 0x36, 0xb3, 0x27];
+    GUID_WICPixelFormat128bppRGBAFloat = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x19];
+    GUID_WICPixelFormat128bppPRGBAFloat = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x1a];
+    GUID_WICPixelFormat128bppRGBFloat = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x1b];
+    GUID_WICPixelFormat32bppCMYK = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x1c];
+    GUID_WICPixelFormat64bppRGBAFixedPoint = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x1d];
+    GUID_WICPixelFormat64bppBGRAFixedPoint = 0x356de33c, 0x54d2, 0x4a23, [0xbb, 0x4, 0x9b, 0x7b, 0xf9, 0xb1, 0xd4, 0x2d];
+    GUID_WICPixelFormat64bppRGBFixedPoint = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x40];
+    GUID_WICPixelFormat128bppRGBAFixedPoint = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x1e];
+    GUID_WICPixelFormat128bppRGBFixedPoint = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x41];
+    GUID_WICPixelFormat64bppRGBAHalf = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x3a];
+    GUID_WICPixelFormat64bppPRGBAHalf = 0x58ad26c2, 0xc623, 0x4d9d, [0xb3, 0x20, 0x38, 0x7e, 0x49, 0xf8, 0xc4, 0x42];
+    GUID_WICPixelFormat64bppRGBHalf = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x42];
+    GUID_WICPixelFormat48bppRGBHalf = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x3b];
+    GUID_WICPixelFormat32bppRGBE = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x3d];
+    GUID_WICPixelFormat16bppGrayHalf = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x3e];
+    GUID_WICPixelFormat32bppGrayFixedPoint = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x3f];
+    GUID_WICPixelFormat32bppRGBA1010102 = 0x25238D72, 0xFCF9, 0x4522, [0xb5, 0x14, 0x55, 0x78, 0xe5, 0xad, 0x55, 0xe0];
+    GUID_WICPixelFormat32bppRGBA1010102XR = 0x00DE6B9A, 0xC101, 0x434b, [0xb5, 0x02, 0xd0, 0x16, 0x5e, 0xe1, 0x12, 0x2c];
+    GUID_WICPixelFormat64bppCMYK = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x1f];
+    GUID_WICPixelFormat24bpp3Channels = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x20];
+    GUID_WICPixelFormat32bpp4Channels = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x21];
+    GUID_WICPixelFormat40bpp5Channels = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x22];
+    GUID_WICPixelFormat48bpp6Channels = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x23];
+    GUID_WICPixelFormat56bpp7Channels = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x24];
+    GUID_WICPixelFormat64bpp8Channels = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x25];
+    GUID_WICPixelFormat48bpp3Channels = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x26];
+    GUID_WICPixelFormat64bpp4Channels = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x27];
+    GUID_WICPixelFormat80bpp5Channels = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x28];
+    GUID_WICPixelFormat96bpp6Channels = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x29];
+    GUID_WICPixelFormat112bpp7Channels = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x2a];
+    GUID_WICPixelFormat128bpp8Channels = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x2b];
+    GUID_WICPixelFormat40bppCMYKAlpha = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x2c];
+    GUID_WICPixelFormat80bppCMYKAlpha = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x2d];
+    GUID_WICPixelFormat32bpp3ChannelsAlpha = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x2e];
+    GUID_WICPixelFormat40bpp4ChannelsAlpha = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x2f];
+    GUID_WICPixelFormat48bpp5ChannelsAlpha = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x30];
+    GUID_WICPixelFormat56bpp6ChannelsAlpha = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x31];
+    GUID_WICPixelFormat64bpp7ChannelsAlpha = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x32];
+    GUID_WICPixelFormat72bpp8ChannelsAlpha = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x33];
+    GUID_WICPixelFormat64bpp3ChannelsAlpha = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x34];
+    GUID_WICPixelFormat80bpp4ChannelsAlpha = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x35];
+    GUID_WICPixelFormat96bpp5ChannelsAlpha = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x36];
+    GUID_WICPixelFormat112bpp6ChannelsAlpha = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x37];
+    GUID_WICPixelFormat128bpp7ChannelsAlpha = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x38];
+    GUID_WICPixelFormat144bpp8ChannelsAlpha = 0x6fddc324, 0x4e03, 0x4bfe, [0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, 0x39];
+    GUID_WICPixelFormat8bppY = 0x91B4DB54, 0x2DF9, 0x42F0, [0xB4, 0x49, 0x29, 0x09, 0xBB, 0x3D, 0xF8, 0x8E];
+    GUID_WICPixelFormat8bppCb = 0x1339F224, 0x6BFE, 0x4C3E, [0x93, 0x02, 0xE4, 0xF3, 0xA6, 0xD0, 0xCA, 0x2A];
+    GUID_WICPixelFormat8bppCr = 0xB8145053, 0x2116, 0x49F0, [0x88, 0x35, 0xED, 0x84, 0x4B, 0x20, 0x5C, 0x51];
+    GUID_WICPixelFormat16bppCbCr = 0xFF95BA6E, 0x11E0, 0x4263, [0xBB, 0x45, 0x01, 0x72, 0x1F, 0x34, 0x60, 0xA4];
+    GUID_WICPixelFormat16bppYQuantizedDctCoefficients = 0xA355F433, 0x48E8, 0x4A42, [0x84, 0xD8, 0xE2, 0xAA, 0x26, 0xCA, 0x80, 0xA4];
+    GUID_WICPixelFormat16bppCbQuantizedDctCoefficients = 0xD2C4FF61, 0x56A5, 0x49C2, [0x8B, 0x5C, 0x4C, 0x19, 0x25, 0x96, 0x48, 0x37];
+    GUID_WICPixelFormat16bppCrQuantizedDctCoefficients = 0x2FE354F0, 0x1680, 0x42D8, [0x92, 0x31, 0xE7, 0x3C, 0x05, 0x65, 0xBF, 0xC1];
+}
+
+/// Added in Windows 8.1, after the version of winapi this crate uses.
+pub const GUID_WICPixelFormat32bppR10G10B10A2: GUID = GUID {
+    Data1: 0x604e_1bb5,
+    Data2: 0x8a3c,
+    Data3: 0x4b65,
+    Data4: [0xb1, 0x1c, 0xbc, 0x0b, 0x8d, 0xd7, 0x5b, 0x7f],
+};
+
+/// Added in Windows 8.1, after the version of winapi this crate uses.
+pub const GUID_WICPixelFormat32bppR10G10B10A2HDR10: GUID = GUID {
+    Data1: 0x9c21_5c5d,
+    Data2: 0x1acc,
+    Data3: 0x4f0e,
+    Data4: [0xa4, 0xbc, 0x70, 0xfb, 0x3a, 0xe8, 0xfd, 0x28],
+};
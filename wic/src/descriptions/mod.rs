@@ -1,5 +1,9 @@
-pub use self::{bitmap_pattern::BitmapPattern, color::*, pixel_format::PixelFormat};
+#[cfg(windows)]
+pub use self::bitmap_pattern::BitmapPattern;
+pub use self::{color::*, pixel_format::PixelFormat};
 
+#[cfg(windows)]
 pub mod bitmap_pattern;
 pub mod color;
+pub mod guids;
 pub mod pixel_format;
@@ -1,10 +1,23 @@
 use self::BitsPerChannel::*;
 use self::Category as C;
+use self::ChannelEncoding as E;
+use self::ChannelKind::*;
 use self::StorageType::*;
 
+pub use crate::descriptions::guids::{
+    GUID_WICPixelFormat32bppR10G10B10A2, GUID_WICPixelFormat32bppR10G10B10A2HDR10,
+};
+
+use crate::descriptions::guids::*;
+use crate::GUID;
+
+#[cfg(windows)]
 use dxgi::enums::Format::*;
-use winapi::shared::guiddef::{IsEqualGUID, GUID};
-use winapi::um::wincodec::*;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+#[cfg(not(windows))]
+use self::no_dxgi::*;
 
 #[repr(transparent)]
 #[derive(Copy, Clone)]
@@ -12,12 +25,161 @@ pub struct PixelFormat {
     pub guid: GUID,
 }
 
+impl PartialEq for PixelFormat {
+    fn eq(&self, other: &PixelFormat) -> bool {
+        guid_key(&self.guid) == guid_key(&other.guid)
+    }
+}
+
+impl Eq for PixelFormat {}
+
 impl PixelFormat {
     pub fn description(&self) -> Option<&'static PixelFormatDescription> {
-        NATIVE_PIXEL_FORMATS
+        let i = *index().by_guid.get(&guid_key(&self.guid))?;
+        Some(&NATIVE_PIXEL_FORMATS[i].1)
+    }
+
+    /// Looks a format up by the name of its GUID constant, such as
+    /// `"GUID_WICPixelFormat32bppPBGRA"`.
+    pub fn from_label(label: &str) -> Option<PixelFormat> {
+        let i = *index().by_label.get(label)?;
+        Some(PixelFormat::from_index(i))
+    }
+
+    /// The WIC format with the same memory layout as `format`. Where several
+    /// share it, such as straight and premultiplied alpha, the first in
+    /// `NATIVE_PIXEL_FORMATS` is returned, which is the straight one.
+    #[cfg(windows)]
+    pub fn from_dxgi(format: dxgi::enums::Format) -> Option<PixelFormat> {
+        let i = *index().by_dxgi.get(&(format as u32))?.first()?;
+        Some(PixelFormat::from_index(i))
+    }
+
+    /// Picks the format with the DXGI equivalent `target` that pixels in
+    /// this format should be converted to. This format itself is preferred,
+    /// then formats the conversion to is lossless, then table order.
+    ///
+    /// Returns `None` if no WIC format corresponds to `target`.
+    #[cfg(windows)]
+    pub fn closest_convertible(&self, target: dxgi::enums::Format) -> Option<Conversion> {
+        let source = self.description();
+        index()
+            .by_dxgi
+            .get(&(target as u32))?
             .iter()
-            .find(|(guid, _)| IsEqualGUID(guid, &self.guid))
-            .map(|(_, desc)| desc)
+            .map(|&i| {
+                let (guid, desc) = &NATIVE_PIXEL_FORMATS[i];
+                let identity = guid_key(guid) == guid_key(&self.guid);
+                Conversion {
+                    format: PixelFormat::from_index(i),
+                    required: !identity,
+                    lossless: identity || source.is_some_and(|s| converts_losslessly(s, desc)),
+                }
+            })
+            .min_by_key(|c| (c.required, !c.lossless))
+    }
+
+    fn from_index(i: usize) -> PixelFormat {
+        PixelFormat {
+            guid: NATIVE_PIXEL_FORMATS[i].0,
+        }
+    }
+}
+
+/// The result of `PixelFormat::closest_convertible`.
+#[cfg(windows)]
+#[derive(Copy, Clone)]
+pub struct Conversion {
+    /// The format to convert to.
+    pub format: PixelFormat,
+    /// Whether `format` differs from the source format.
+    pub required: bool,
+    /// Whether every source pixel survives the conversion, up to rounding.
+    pub lossless: bool,
+}
+
+struct Lookup {
+    by_guid: HashMap<GuidKey, usize>,
+    by_label: HashMap<&'static str, usize>,
+    #[cfg(windows)]
+    by_dxgi: HashMap<u32, Vec<usize>>,
+}
+
+type GuidKey = (u32, u16, u16, [u8; 8]);
+
+fn guid_key(guid: &GUID) -> GuidKey {
+    (guid.Data1, guid.Data2, guid.Data3, guid.Data4)
+}
+
+/// Maps GUIDs, labels and DXGI formats to positions in
+/// `NATIVE_PIXEL_FORMATS`, built on first use.
+fn index() -> &'static Lookup {
+    static INDEX: OnceLock<Lookup> = OnceLock::new();
+    INDEX.get_or_init(|| {
+        let mut index = Lookup {
+            by_guid: HashMap::new(),
+            by_label: HashMap::new(),
+            #[cfg(windows)]
+            by_dxgi: HashMap::new(),
+        };
+        for (i, (guid, desc)) in NATIVE_PIXEL_FORMATS.iter().enumerate() {
+            index.by_guid.entry(guid_key(guid)).or_insert(i);
+            index.by_label.entry(desc.label).or_insert(i);
+            #[cfg(windows)]
+            if desc.dxgi_format as u32 != dxgi::enums::Format::Unknown as u32 {
+                index
+                    .by_dxgi
+                    .entry(desc.dxgi_format as u32)
+                    .or_default()
+                    .push(i);
+            }
+        }
+        index
+    })
+}
+
+/// Whether converting from `source` to `target` keeps every channel at the
+/// same or better precision.
+#[cfg(windows)]
+fn converts_losslessly(source: &PixelFormatDescription, target: &PixelFormatDescription) -> bool {
+    let premultiplied = |d: &PixelFormatDescription| d.channels.iter().any(|c| c.premultiplied);
+    if source.channel(Alpha).is_some() && premultiplied(source) != premultiplied(target) {
+        return false;
+    }
+    source.channels.iter().all(|channel| match channel.kind {
+        Padding | Exponent => true,
+        // Palette entries are 8-bit BGRA.
+        Index => [Red, Green, Blue, Alpha].iter().all(|&kind| {
+            target
+                .channel(kind)
+                .is_some_and(|t| encoding_fits(8, E::Unorm, t))
+        }),
+        Gray if target.channel(Gray).is_none() => [Red, Green, Blue].iter().all(|&kind| {
+            target
+                .channel(kind)
+                .is_some_and(|t| encoding_fits(channel.bit_width, channel.encoding, t))
+        }),
+        kind => target
+            .channel(kind)
+            .is_some_and(|t| encoding_fits(channel.bit_width, channel.encoding, t)),
+    })
+}
+
+/// Whether any `bits`-wide value in `encoding` can be stored in `target`
+/// and read back unchanged.
+#[cfg(windows)]
+fn encoding_fits(bits: u16, encoding: ChannelEncoding, target: &ChannelLayout) -> bool {
+    let width = target.bit_width;
+    match (encoding, target.encoding) {
+        (E::Unorm, E::Unorm) | (E::Integer, E::Integer) => bits <= width,
+        // A half has 11 significant bits and a float 24.
+        (E::Unorm, E::Float) => bits <= if width == 16 { 10 } else { 16 },
+        (E::Unorm, E::Fixed(fraction)) => bits <= fraction as u16,
+        (E::Float, E::Float) => bits <= width,
+        (E::Fixed(a), E::Fixed(b)) => a <= b && bits - a as u16 <= width - b as u16,
+        (E::Fixed(_), E::Float) => width == 32 && bits <= 24,
+        (E::ExtendedRange { .. }, E::Float) | (E::SharedExponent, E::Float) => width == 32,
+        (a, b) => a == b && bits <= width,
     }
 }
 
@@ -30,7 +192,86 @@ pub struct PixelFormatDescription {
     pub bits_per_pixel: u16,
     pub storage_type: StorageType,
     pub category: Category,
+    /// The DXGI format with the same memory layout, or `Unknown`.
+    #[cfg(windows)]
     pub dxgi_format: dxgi::enums::Format,
+    /// Where each channel sits in a pixel, in memory order. Includes any
+    /// padding, so the widths add up to `bits_per_pixel`.
+    pub channels: &'static [ChannelLayout],
+}
+
+impl PixelFormatDescription {
+    /// The number of bytes a row of `width` pixels takes, without padding,
+    /// or None if it doesn't fit in a `usize`.
+    pub fn stride(&self, width: u32) -> Option<usize> {
+        let bits = (width as usize).checked_mul(self.bits_per_pixel as usize)?;
+        Some(bits.div_ceil(8))
+    }
+
+    /// The first channel of the given kind, if the format has one.
+    pub fn channel(&self, kind: ChannelKind) -> Option<&'static ChannelLayout> {
+        self.channels.iter().find(|channel| channel.kind == kind)
+    }
+}
+
+/// One channel of a pixel.
+///
+/// Offsets count from the least significant bit of the pixel read as a
+/// little-endian integer, which for byte-aligned channels is the same as
+/// counting bytes in memory.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ChannelLayout {
+    pub kind: ChannelKind,
+    pub bit_offset: u16,
+    pub bit_width: u16,
+    pub encoding: ChannelEncoding,
+    /// Whether the value has already been multiplied by alpha.
+    pub premultiplied: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ChannelKind {
+    Red,
+    Green,
+    Blue,
+    Alpha,
+    Gray,
+    Cyan,
+    Magenta,
+    Yellow,
+    Black,
+    /// The Y of YCbCr.
+    Luma,
+    /// The Cb of YCbCr.
+    ChromaBlue,
+    /// The Cr of YCbCr.
+    ChromaRed,
+    /// The exponent shared by the other channels of an RGBE pixel.
+    Exponent,
+    /// An index into the palette.
+    Index,
+    /// Bits that carry no data.
+    Padding,
+    /// A channel of an N-channel format, which has no fixed meaning.
+    Channel(u8),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ChannelEncoding {
+    /// An unsigned integer where the maximum value is 1.0.
+    Unorm,
+    /// A signed two's complement number with this many fractional bits.
+    Fixed(u8),
+    /// An IEEE 754 half or single precision float.
+    Float,
+    /// An unsigned integer taken as-is, such as a palette index.
+    Integer,
+    /// An unsigned integer `v` meaning `(v - bias) / scale`, as in the
+    /// extended range (XR) formats.
+    ExtendedRange { bias: u16, scale: u16 },
+    /// An 8-bit mantissa scaled by the pixel's `Exponent` channel, which is
+    /// biased by 128 (Radiance RGBE).
+    SharedExponent,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -57,6 +298,7 @@ pub enum Category {
     PREMULTIPLIED,
     CMYK,
     N_CHANNEL,
+    YCBCR,
 
     NONE = 0,
 }
@@ -74,6 +316,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::NONE,
             Unknown,
+            CH_DONT_CARE,
         ),
     ),
     // Indexed Pixel Formats
@@ -88,6 +331,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::INDEXED,
             Unknown,
+            CH_1BPP_INDEXED,
         ),
     ),
     (
@@ -101,6 +345,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::INDEXED,
             Unknown,
+            CH_2BPP_INDEXED,
         ),
     ),
     (
@@ -114,6 +359,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::INDEXED,
             Unknown,
+            CH_4BPP_INDEXED,
         ),
     ),
     (
@@ -127,6 +373,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::INDEXED,
             Unknown,
+            CH_8BPP_INDEXED,
         ),
     ),
     // Packed Bit Pixel Formats
@@ -141,6 +388,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C(C::PACKED.0 | C::BGR.0),
             Unknown,
+            CH_16BPP_BGR555,
         ),
     ),
     (
@@ -157,6 +405,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C(C::PACKED.0 | C::BGR.0),
             B5G6R5Unorm,
+            CH_16BPP_BGR565,
         ),
     ),
     (
@@ -173,6 +422,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C(C::PACKED.0 | C::BGR.0 | C::ALPHA.0),
             B5G5R5A1Unorm,
+            CH_16BPP_BGRA5551,
         ),
     ),
     (
@@ -186,6 +436,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C(C::PACKED.0 | C::BGR.0),
             Unknown,
+            CH_32BPP_BGR101010,
         ),
     ),
     (
@@ -193,7 +444,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
         define_desc_p(
             "GUID_WICPixelFormat32bppRGBA1010102",
             "Packed 32bpp RGBA 10,10,10,2",
-            3,
+            4,
             10,
             10,
             10,
@@ -202,6 +453,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C(C::PACKED.0 | C::RGB.0 | C::ALPHA.0),
             R10G10B10A2Unorm,
+            CH_32BPP_RGBA1010102,
         ),
     ),
     (
@@ -209,7 +461,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
         define_desc_p(
             "GUID_WICPixelFormat32bppRGBA1010102XR",
             "Packed 32bpp RGBA 10,10,10,2 XR",
-            3,
+            4,
             10,
             10,
             10,
@@ -217,7 +469,42 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             32,
             Unsigned,
             C(C::PACKED.0 | C::RGB.0 | C::ALPHA.0),
-            Unknown,
+            R10G10B10XrBiasA2Unorm,
+            CH_32BPP_RGBA1010102XR,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat32bppR10G10B10A2,
+        define_desc_p(
+            "GUID_WICPixelFormat32bppR10G10B10A2",
+            "Packed 32bpp RGBA 10,10,10,2 (DXGI order)",
+            4,
+            10,
+            10,
+            10,
+            2,
+            32,
+            Unsigned,
+            C(C::PACKED.0 | C::RGB.0 | C::ALPHA.0),
+            R10G10B10A2Unorm,
+            CH_32BPP_R10G10B10A2,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat32bppR10G10B10A2HDR10,
+        define_desc_p(
+            "GUID_WICPixelFormat32bppR10G10B10A2HDR10",
+            "Packed 32bpp RGBA 10,10,10,2 HDR10 (ST.2084)",
+            4,
+            10,
+            10,
+            10,
+            2,
+            32,
+            Unsigned,
+            C(C::PACKED.0 | C::RGB.0 | C::ALPHA.0),
+            R10G10B10A2Unorm,
+            CH_32BPP_R10G10B10A2_HDR10,
         ),
     ),
     // Grayscale Pixel Formats
@@ -232,6 +519,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::GRAYSCALE,
             Unknown,
+            CH_BLACK_WHITE,
         ),
     ),
     (
@@ -245,6 +533,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::GRAYSCALE,
             Unknown,
+            CH_2BPP_GRAY,
         ),
     ),
     (
@@ -258,6 +547,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::GRAYSCALE,
             Unknown,
+            CH_4BPP_GRAY,
         ),
     ),
     (
@@ -271,6 +561,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::GRAYSCALE,
             R8Unorm,
+            CH_8BPP_GRAY,
         ),
     ),
     (
@@ -284,6 +575,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::GRAYSCALE,
             R16Unorm,
+            CH_16BPP_GRAY,
         ),
     ),
     (
@@ -297,6 +589,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Fixed,
             C::GRAYSCALE,
             Unknown,
+            CH_16BPP_GRAY_FIXED_POINT,
         ),
     ),
     (
@@ -310,6 +603,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Float,
             C::GRAYSCALE,
             R16Float,
+            CH_16BPP_GRAY_HALF,
         ),
     ),
     (
@@ -323,6 +617,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Float,
             C::GRAYSCALE,
             R32Float,
+            CH_32BPP_GRAY_FLOAT,
         ),
     ),
     (
@@ -336,6 +631,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Fixed,
             C::GRAYSCALE,
             Unknown,
+            CH_32BPP_GRAY_FIXED_POINT,
         ),
     ),
     // Standard RGB/BGR Pixel Formats
@@ -350,6 +646,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::RGB,
             Unknown,
+            CH_24BPP_RGB,
         ),
     ),
     (
@@ -363,6 +660,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::BGR,
             Unknown,
+            CH_24BPP_BGR,
         ),
     ),
     (
@@ -375,7 +673,8 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             32,
             Unsigned,
             C::BGR,
-            Unknown,
+            B8G8R8X8Unorm,
+            CH_32BPP_BGR,
         ),
     ),
     (
@@ -389,6 +688,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C(C::RGB.0 | C::ALPHA.0),
             R8G8B8A8Unorm,
+            CH_32BPP_RGBA,
         ),
     ),
     (
@@ -402,6 +702,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C(C::BGR.0 | C::ALPHA.0),
             B8G8R8A8Unorm,
+            CH_32BPP_BGRA,
         ),
     ),
     (
@@ -415,6 +716,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Float,
             C(C::RGB.0 | C::PACKED.0),
             Unknown,
+            CH_32BPP_RGBE,
         ),
     ),
     (
@@ -428,6 +730,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C(C::RGB.0 | C::ALPHA.0 | C::PREMULTIPLIED.0),
             R8G8B8A8Unorm,
+            CH_32BPP_PRGBA,
         ),
     ),
     (
@@ -441,6 +744,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C(C::BGR.0 | C::ALPHA.0 | C::PREMULTIPLIED.0),
             B8G8R8A8Unorm,
+            CH_32BPP_PBGRA,
         ),
     ),
     (
@@ -454,6 +758,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::RGB,
             Unknown,
+            CH_48BPP_RGB,
         ),
     ),
     (
@@ -467,6 +772,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::BGR,
             Unknown,
+            CH_48BPP_BGR,
         ),
     ),
     (
@@ -480,6 +786,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Fixed,
             C::RGB,
             Unknown,
+            CH_48BPP_RGB_FIXED_POINT,
         ),
     ),
     (
@@ -493,6 +800,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Fixed,
             C::BGR,
             Unknown,
+            CH_48BPP_BGR_FIXED_POINT,
         ),
     ),
     (
@@ -506,6 +814,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Float,
             C::RGB,
             Unknown,
+            CH_48BPP_RGB_HALF,
         ),
     ),
     (
@@ -519,6 +828,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C(C::RGB.0 | C::ALPHA.0),
             R16G16B16A16Unorm,
+            CH_64BPP_RGBA,
         ),
     ),
     (
@@ -531,7 +841,8 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             64,
             Unsigned,
             C(C::BGR.0 | C::ALPHA.0),
-            R16G16B16A16Unorm,
+            Unknown,
+            CH_64BPP_BGRA,
         ),
     ),
     (
@@ -545,19 +856,21 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C(C::RGB.0 | C::ALPHA.0 | C::PREMULTIPLIED.0),
             R16G16B16A16Unorm,
+            CH_64BPP_PRGBA,
         ),
     ),
     (
         GUID_WICPixelFormat64bppPBGRA,
         define_desc(
             "GUID_WICPixelFormat64bppPBGRA",
-            "BGRA 64bpp",
+            "BGRA 64bpp Premultiplied Alpha",
             4,
             16,
             64,
             Unsigned,
             C(C::BGR.0 | C::ALPHA.0 | C::PREMULTIPLIED.0),
             Unknown,
+            CH_64BPP_PBGRA,
         ),
     ),
     (
@@ -571,6 +884,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Fixed,
             C::RGB,
             Unknown,
+            CH_64BPP_RGB_FIXED_POINT,
         ),
     ),
     (
@@ -584,6 +898,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Fixed,
             C(C::RGB.0 | C::ALPHA.0),
             Unknown,
+            CH_64BPP_RGBA_FIXED_POINT,
         ),
     ),
     (
@@ -597,6 +912,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Fixed,
             C(C::BGR.0 | C::ALPHA.0),
             Unknown,
+            CH_64BPP_BGRA_FIXED_POINT,
         ),
     ),
     (
@@ -610,6 +926,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Float,
             C::RGB,
             Unknown,
+            CH_64BPP_RGB_HALF,
         ),
     ),
     (
@@ -623,6 +940,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Float,
             C(C::RGB.0 | C::ALPHA.0),
             R16G16B16A16Float,
+            CH_64BPP_RGBA_HALF,
         ),
     ),
     (
@@ -636,6 +954,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Fixed,
             C::RGB,
             Unknown,
+            CH_96BPP_RGB_FIXED_POINT,
         ),
     ),
     (
@@ -649,6 +968,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Float,
             C::RGB,
             Unknown,
+            CH_128BPP_RGB_FLOAT,
         ),
     ),
     (
@@ -662,6 +982,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Float,
             C(C::RGB.0 | C::ALPHA.0),
             R32G32B32A32Float,
+            CH_128BPP_RGBA_FLOAT,
         ),
     ),
     (
@@ -675,6 +996,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Float,
             C(C::RGB.0 | C::ALPHA.0 | C::PREMULTIPLIED.0),
             R32G32B32A32Float,
+            CH_128BPP_PRGBA_FLOAT,
         ),
     ),
     (
@@ -688,6 +1010,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Fixed,
             C::RGB,
             Unknown,
+            CH_128BPP_RGB_FIXED_POINT,
         ),
     ),
     (
@@ -701,9 +1024,24 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Fixed,
             C(C::RGB.0 | C::ALPHA.0),
             Unknown,
+            CH_128BPP_RGBA_FIXED_POINT,
         ),
     ),
     // Windows 8 + Windows 7 Platform Update
+    (
+        GUID_WICPixelFormat8bppAlpha,
+        define_desc(
+            "GUID_WICPixelFormat8bppAlpha",
+            "Alpha 8bpp",
+            1,
+            8,
+            8,
+            Unsigned,
+            C::ALPHA,
+            A8Unorm,
+            CH_8BPP_ALPHA,
+        ),
+    ),
     (
         GUID_WICPixelFormat32bppRGB,
         define_desc(
@@ -715,6 +1053,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::RGB,
             Unknown,
+            CH_32BPP_RGB,
         ),
     ),
     (
@@ -728,19 +1067,21 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::RGB,
             Unknown,
+            CH_64BPP_RGB,
         ),
     ),
     (
         GUID_WICPixelFormat96bppRGBFloat,
         define_desc(
             "GUID_WICPixelFormat96bppRGBFloat",
-            "RGB 64bpp Float",
+            "RGB 96bpp Float",
             3,
             32,
             96,
             Float,
             C::RGB,
-            Unknown,
+            R32G32B32Float,
+            CH_96BPP_RGB_FLOAT,
         ),
     ),
     (
@@ -754,6 +1095,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Float,
             C(C::RGB.0 | C::ALPHA.0 | C::PREMULTIPLIED.0),
             R16G16B16A16Float,
+            CH_64BPP_PRGBA_HALF,
         ),
     ),
     // CMYK
@@ -768,6 +1110,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::CMYK,
             Unknown,
+            CH_32BPP_CMYK,
         ),
     ),
     (
@@ -781,6 +1124,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::CMYK,
             Unknown,
+            CH_64BPP_CMYK,
         ),
     ),
     (
@@ -794,6 +1138,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C(C::CMYK.0 | C::ALPHA.0),
             Unknown,
+            CH_40BPP_CMYK_ALPHA,
         ),
     ),
     (
@@ -807,6 +1152,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C(C::CMYK.0 | C::ALPHA.0),
             Unknown,
+            CH_80BPP_CMYK_ALPHA,
         ),
     ),
     // N-Channel
@@ -821,6 +1167,7 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::N_CHANNEL,
             Unknown,
+            CH_24BPP_3CHANNELS,
         ),
     ),
     (
@@ -834,12 +1181,380 @@ pub static NATIVE_PIXEL_FORMATS: &[(GUID, PixelFormatDescription)] = &[
             Unsigned,
             C::N_CHANNEL,
             Unknown,
+            CH_48BPP_3CHANNELS,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat32bpp3ChannelsAlpha,
+        define_desc(
+            "GUID_WICPixelFormat32bpp3ChannelsAlpha",
+            "3-Channel 32bpp with Alpha",
+            4,
+            8,
+            32,
+            Unsigned,
+            C(C::N_CHANNEL.0 | C::ALPHA.0),
+            Unknown,
+            CH_32BPP_3CHANNELS_ALPHA,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat64bpp3ChannelsAlpha,
+        define_desc(
+            "GUID_WICPixelFormat64bpp3ChannelsAlpha",
+            "3-Channel 64bpp with Alpha",
+            4,
+            16,
+            64,
+            Unsigned,
+            C(C::N_CHANNEL.0 | C::ALPHA.0),
+            Unknown,
+            CH_64BPP_3CHANNELS_ALPHA,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat32bpp4Channels,
+        define_desc(
+            "GUID_WICPixelFormat32bpp4Channels",
+            "4-Channel 32bpp",
+            4,
+            8,
+            32,
+            Unsigned,
+            C::N_CHANNEL,
+            Unknown,
+            CH_32BPP_4CHANNELS,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat64bpp4Channels,
+        define_desc(
+            "GUID_WICPixelFormat64bpp4Channels",
+            "4-Channel 64bpp",
+            4,
+            16,
+            64,
+            Unsigned,
+            C::N_CHANNEL,
+            Unknown,
+            CH_64BPP_4CHANNELS,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat40bpp4ChannelsAlpha,
+        define_desc(
+            "GUID_WICPixelFormat40bpp4ChannelsAlpha",
+            "4-Channel 40bpp with Alpha",
+            5,
+            8,
+            40,
+            Unsigned,
+            C(C::N_CHANNEL.0 | C::ALPHA.0),
+            Unknown,
+            CH_40BPP_4CHANNELS_ALPHA,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat80bpp4ChannelsAlpha,
+        define_desc(
+            "GUID_WICPixelFormat80bpp4ChannelsAlpha",
+            "4-Channel 80bpp with Alpha",
+            5,
+            16,
+            80,
+            Unsigned,
+            C(C::N_CHANNEL.0 | C::ALPHA.0),
+            Unknown,
+            CH_80BPP_4CHANNELS_ALPHA,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat40bpp5Channels,
+        define_desc(
+            "GUID_WICPixelFormat40bpp5Channels",
+            "5-Channel 40bpp",
+            5,
+            8,
+            40,
+            Unsigned,
+            C::N_CHANNEL,
+            Unknown,
+            CH_40BPP_5CHANNELS,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat80bpp5Channels,
+        define_desc(
+            "GUID_WICPixelFormat80bpp5Channels",
+            "5-Channel 80bpp",
+            5,
+            16,
+            80,
+            Unsigned,
+            C::N_CHANNEL,
+            Unknown,
+            CH_80BPP_5CHANNELS,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat48bpp5ChannelsAlpha,
+        define_desc(
+            "GUID_WICPixelFormat48bpp5ChannelsAlpha",
+            "5-Channel 48bpp with Alpha",
+            6,
+            8,
+            48,
+            Unsigned,
+            C(C::N_CHANNEL.0 | C::ALPHA.0),
+            Unknown,
+            CH_48BPP_5CHANNELS_ALPHA,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat96bpp5ChannelsAlpha,
+        define_desc(
+            "GUID_WICPixelFormat96bpp5ChannelsAlpha",
+            "5-Channel 96bpp with Alpha",
+            6,
+            16,
+            96,
+            Unsigned,
+            C(C::N_CHANNEL.0 | C::ALPHA.0),
+            Unknown,
+            CH_96BPP_5CHANNELS_ALPHA,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat48bpp6Channels,
+        define_desc(
+            "GUID_WICPixelFormat48bpp6Channels",
+            "6-Channel 48bpp",
+            6,
+            8,
+            48,
+            Unsigned,
+            C::N_CHANNEL,
+            Unknown,
+            CH_48BPP_6CHANNELS,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat96bpp6Channels,
+        define_desc(
+            "GUID_WICPixelFormat96bpp6Channels",
+            "6-Channel 96bpp",
+            6,
+            16,
+            96,
+            Unsigned,
+            C::N_CHANNEL,
+            Unknown,
+            CH_96BPP_6CHANNELS,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat56bpp6ChannelsAlpha,
+        define_desc(
+            "GUID_WICPixelFormat56bpp6ChannelsAlpha",
+            "6-Channel 56bpp with Alpha",
+            7,
+            8,
+            56,
+            Unsigned,
+            C(C::N_CHANNEL.0 | C::ALPHA.0),
+            Unknown,
+            CH_56BPP_6CHANNELS_ALPHA,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat112bpp6ChannelsAlpha,
+        define_desc(
+            "GUID_WICPixelFormat112bpp6ChannelsAlpha",
+            "6-Channel 112bpp with Alpha",
+            7,
+            16,
+            112,
+            Unsigned,
+            C(C::N_CHANNEL.0 | C::ALPHA.0),
+            Unknown,
+            CH_112BPP_6CHANNELS_ALPHA,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat56bpp7Channels,
+        define_desc(
+            "GUID_WICPixelFormat56bpp7Channels",
+            "7-Channel 56bpp",
+            7,
+            8,
+            56,
+            Unsigned,
+            C::N_CHANNEL,
+            Unknown,
+            CH_56BPP_7CHANNELS,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat112bpp7Channels,
+        define_desc(
+            "GUID_WICPixelFormat112bpp7Channels",
+            "7-Channel 112bpp",
+            7,
+            16,
+            112,
+            Unsigned,
+            C::N_CHANNEL,
+            Unknown,
+            CH_112BPP_7CHANNELS,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat64bpp7ChannelsAlpha,
+        define_desc(
+            "GUID_WICPixelFormat64bpp7ChannelsAlpha",
+            "7-Channel 64bpp with Alpha",
+            8,
+            8,
+            64,
+            Unsigned,
+            C(C::N_CHANNEL.0 | C::ALPHA.0),
+            Unknown,
+            CH_64BPP_7CHANNELS_ALPHA,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat128bpp7ChannelsAlpha,
+        define_desc(
+            "GUID_WICPixelFormat128bpp7ChannelsAlpha",
+            "7-Channel 128bpp with Alpha",
+            8,
+            16,
+            128,
+            Unsigned,
+            C(C::N_CHANNEL.0 | C::ALPHA.0),
+            Unknown,
+            CH_128BPP_7CHANNELS_ALPHA,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat64bpp8Channels,
+        define_desc(
+            "GUID_WICPixelFormat64bpp8Channels",
+            "8-Channel 64bpp",
+            8,
+            8,
+            64,
+            Unsigned,
+            C::N_CHANNEL,
+            Unknown,
+            CH_64BPP_8CHANNELS,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat128bpp8Channels,
+        define_desc(
+            "GUID_WICPixelFormat128bpp8Channels",
+            "8-Channel 128bpp",
+            8,
+            16,
+            128,
+            Unsigned,
+            C::N_CHANNEL,
+            Unknown,
+            CH_128BPP_8CHANNELS,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat72bpp8ChannelsAlpha,
+        define_desc(
+            "GUID_WICPixelFormat72bpp8ChannelsAlpha",
+            "8-Channel 72bpp with Alpha",
+            9,
+            8,
+            72,
+            Unsigned,
+            C(C::N_CHANNEL.0 | C::ALPHA.0),
+            Unknown,
+            CH_72BPP_8CHANNELS_ALPHA,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat144bpp8ChannelsAlpha,
+        define_desc(
+            "GUID_WICPixelFormat144bpp8ChannelsAlpha",
+            "8-Channel 144bpp with Alpha",
+            9,
+            16,
+            144,
+            Unsigned,
+            C(C::N_CHANNEL.0 | C::ALPHA.0),
+            Unknown,
+            CH_144BPP_8CHANNELS_ALPHA,
+        ),
+    ),
+    // Planar YCbCr (Windows 8.1)
+    (
+        GUID_WICPixelFormat8bppY,
+        define_desc(
+            "GUID_WICPixelFormat8bppY",
+            "Planar Y 8bpp",
+            1,
+            8,
+            8,
+            Unsigned,
+            C::YCBCR,
+            R8Unorm,
+            CH_8BPP_Y,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat8bppCb,
+        define_desc(
+            "GUID_WICPixelFormat8bppCb",
+            "Planar Cb 8bpp",
+            1,
+            8,
+            8,
+            Unsigned,
+            C::YCBCR,
+            R8Unorm,
+            CH_8BPP_CB,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat8bppCr,
+        define_desc(
+            "GUID_WICPixelFormat8bppCr",
+            "Planar Cr 8bpp",
+            1,
+            8,
+            8,
+            Unsigned,
+            C::YCBCR,
+            R8Unorm,
+            CH_8BPP_CR,
+        ),
+    ),
+    (
+        GUID_WICPixelFormat16bppCbCr,
+        define_desc(
+            "GUID_WICPixelFormat16bppCbCr",
+            "Planar CbCr 16bpp",
+            2,
+            8,
+            16,
+            Unsigned,
+            C::YCBCR,
+            R8G8Unorm,
+            CH_16BPP_CBCR,
         ),
     ),
-    // TODO: FINISH THESE FUCKERS WITH FRESH CONCERTA IN YOUR SYSTEM
-    // https://docs.microsoft.com/en-us/windows/desktop/wic/-wic-codec-native-pixel-formats
+    // The *QuantizedDctCoefficients formats hold JPEG coefficients rather
+    // than pixels, so they have no description.
 ];
 
+#[allow(clippy::too_many_arguments)]
+#[cfg_attr(not(windows), allow(unused_variables))]
 const fn define_desc(
     label: &'static str,
     description: &'static str,
@@ -848,7 +1563,8 @@ const fn define_desc(
     bits_per_pixel: u16,
     storage_type: StorageType,
     category: Category,
-    dxgi_format: dxgi::enums::Format,
+    dxgi_format: DxgiFormat,
+    channels: &'static [ChannelLayout],
 ) -> PixelFormatDescription {
     PixelFormatDescription {
         label,
@@ -857,11 +1573,15 @@ const fn define_desc(
         bits_per_channel: Uniform(bpc),
         bits_per_pixel,
         storage_type,
+        #[cfg(windows)]
         dxgi_format,
         category,
+        channels,
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+#[cfg_attr(not(windows), allow(unused_variables))]
 const fn define_desc_p(
     label: &'static str,
     description: &'static str,
@@ -873,7 +1593,8 @@ const fn define_desc_p(
     bits_per_pixel: u16,
     storage_type: StorageType,
     category: Category,
-    dxgi_format: dxgi::enums::Format,
+    dxgi_format: DxgiFormat,
+    channels: &'static [ChannelLayout],
 ) -> PixelFormatDescription {
     PixelFormatDescription {
         label,
@@ -882,7 +1603,795 @@ const fn define_desc_p(
         bits_per_channel: PerChannel(bpc0, bpc1, bpc2, bpc3),
         bits_per_pixel,
         storage_type,
+        #[cfg(windows)]
         dxgi_format,
         category,
+        channels,
+    }
+}
+
+#[cfg(windows)]
+type DxgiFormat = dxgi::enums::Format;
+
+/// The DXGI column of `NATIVE_PIXEL_FORMATS` is only kept on Windows, where
+/// the dxgi crate is available. Elsewhere its entries name these instead.
+#[cfg(not(windows))]
+type DxgiFormat = ();
+
+#[cfg(not(windows))]
+#[allow(non_upper_case_globals)]
+mod no_dxgi {
+    pub const Unknown: () = ();
+    pub const A8Unorm: () = ();
+    pub const B5G5R5A1Unorm: () = ();
+    pub const B5G6R5Unorm: () = ();
+    pub const B8G8R8A8Unorm: () = ();
+    pub const B8G8R8X8Unorm: () = ();
+    pub const R10G10B10A2Unorm: () = ();
+    pub const R10G10B10XrBiasA2Unorm: () = ();
+    pub const R16Float: () = ();
+    pub const R16G16B16A16Float: () = ();
+    pub const R16G16B16A16Unorm: () = ();
+    pub const R16Unorm: () = ();
+    pub const R32Float: () = ();
+    pub const R32G32B32A32Float: () = ();
+    pub const R32G32B32Float: () = ();
+    pub const R8G8B8A8Unorm: () = ();
+    pub const R8G8Unorm: () = ();
+    pub const R8Unorm: () = ();
+}
+
+const fn channel(
+    kind: ChannelKind,
+    bit_offset: u16,
+    bit_width: u16,
+    encoding: ChannelEncoding,
+) -> ChannelLayout {
+    ChannelLayout {
+        kind,
+        bit_offset,
+        bit_width,
+        encoding,
+        premultiplied: false,
+    }
+}
+
+const fn premultiplied(
+    kind: ChannelKind,
+    bit_offset: u16,
+    bit_width: u16,
+    encoding: ChannelEncoding,
+) -> ChannelLayout {
+    ChannelLayout {
+        kind,
+        bit_offset,
+        bit_width,
+        encoding,
+        premultiplied: true,
+    }
+}
+
+/// DXGI's 10-bit XR encoding, covering about -0.75..1.25.
+const XR: ChannelEncoding = ChannelEncoding::ExtendedRange {
+    bias: 384,
+    scale: 510,
+};
+
+const CH_DONT_CARE: &[ChannelLayout] = &[];
+
+const CH_1BPP_INDEXED: &[ChannelLayout] = &[channel(Index, 0, 1, E::Integer)];
+
+const CH_2BPP_INDEXED: &[ChannelLayout] = &[channel(Index, 0, 2, E::Integer)];
+
+const CH_4BPP_INDEXED: &[ChannelLayout] = &[channel(Index, 0, 4, E::Integer)];
+
+const CH_8BPP_INDEXED: &[ChannelLayout] = &[channel(Index, 0, 8, E::Integer)];
+
+const CH_16BPP_BGR555: &[ChannelLayout] = &[
+    channel(Blue, 0, 5, E::Unorm),
+    channel(Green, 5, 5, E::Unorm),
+    channel(Red, 10, 5, E::Unorm),
+    channel(Padding, 15, 1, E::Unorm),
+];
+
+const CH_16BPP_BGR565: &[ChannelLayout] = &[
+    channel(Blue, 0, 5, E::Unorm),
+    channel(Green, 5, 6, E::Unorm),
+    channel(Red, 11, 5, E::Unorm),
+];
+
+const CH_16BPP_BGRA5551: &[ChannelLayout] = &[
+    channel(Blue, 0, 5, E::Unorm),
+    channel(Green, 5, 5, E::Unorm),
+    channel(Red, 10, 5, E::Unorm),
+    channel(Alpha, 15, 1, E::Unorm),
+];
+
+const CH_32BPP_BGR101010: &[ChannelLayout] = &[
+    channel(Blue, 0, 10, E::Unorm),
+    channel(Green, 10, 10, E::Unorm),
+    channel(Red, 20, 10, E::Unorm),
+    channel(Padding, 30, 2, E::Unorm),
+];
+
+const CH_32BPP_RGBA1010102: &[ChannelLayout] = &[
+    channel(Red, 0, 10, E::Unorm),
+    channel(Green, 10, 10, E::Unorm),
+    channel(Blue, 20, 10, E::Unorm),
+    channel(Alpha, 30, 2, E::Unorm),
+];
+
+const CH_32BPP_RGBA1010102XR: &[ChannelLayout] = &[
+    channel(Red, 0, 10, XR),
+    channel(Green, 10, 10, XR),
+    channel(Blue, 20, 10, XR),
+    channel(Alpha, 30, 2, E::Unorm),
+];
+
+const CH_32BPP_R10G10B10A2: &[ChannelLayout] = &[
+    channel(Red, 0, 10, E::Unorm),
+    channel(Green, 10, 10, E::Unorm),
+    channel(Blue, 20, 10, E::Unorm),
+    channel(Alpha, 30, 2, E::Unorm),
+];
+
+const CH_32BPP_R10G10B10A2_HDR10: &[ChannelLayout] = &[
+    channel(Red, 0, 10, E::Unorm),
+    channel(Green, 10, 10, E::Unorm),
+    channel(Blue, 20, 10, E::Unorm),
+    channel(Alpha, 30, 2, E::Unorm),
+];
+
+const CH_BLACK_WHITE: &[ChannelLayout] = &[channel(Gray, 0, 1, E::Unorm)];
+
+const CH_2BPP_GRAY: &[ChannelLayout] = &[channel(Gray, 0, 2, E::Unorm)];
+
+const CH_4BPP_GRAY: &[ChannelLayout] = &[channel(Gray, 0, 4, E::Unorm)];
+
+const CH_8BPP_GRAY: &[ChannelLayout] = &[channel(Gray, 0, 8, E::Unorm)];
+
+const CH_16BPP_GRAY: &[ChannelLayout] = &[channel(Gray, 0, 16, E::Unorm)];
+
+const CH_16BPP_GRAY_FIXED_POINT: &[ChannelLayout] = &[channel(Gray, 0, 16, E::Fixed(13))];
+
+const CH_16BPP_GRAY_HALF: &[ChannelLayout] = &[channel(Gray, 0, 16, E::Float)];
+
+const CH_32BPP_GRAY_FLOAT: &[ChannelLayout] = &[channel(Gray, 0, 32, E::Float)];
+
+const CH_32BPP_GRAY_FIXED_POINT: &[ChannelLayout] = &[channel(Gray, 0, 32, E::Fixed(24))];
+
+const CH_24BPP_RGB: &[ChannelLayout] = &[
+    channel(Red, 0, 8, E::Unorm),
+    channel(Green, 8, 8, E::Unorm),
+    channel(Blue, 16, 8, E::Unorm),
+];
+
+const CH_24BPP_BGR: &[ChannelLayout] = &[
+    channel(Blue, 0, 8, E::Unorm),
+    channel(Green, 8, 8, E::Unorm),
+    channel(Red, 16, 8, E::Unorm),
+];
+
+const CH_32BPP_BGR: &[ChannelLayout] = &[
+    channel(Blue, 0, 8, E::Unorm),
+    channel(Green, 8, 8, E::Unorm),
+    channel(Red, 16, 8, E::Unorm),
+    channel(Padding, 24, 8, E::Unorm),
+];
+
+const CH_32BPP_RGBA: &[ChannelLayout] = &[
+    channel(Red, 0, 8, E::Unorm),
+    channel(Green, 8, 8, E::Unorm),
+    channel(Blue, 16, 8, E::Unorm),
+    channel(Alpha, 24, 8, E::Unorm),
+];
+
+const CH_32BPP_BGRA: &[ChannelLayout] = &[
+    channel(Blue, 0, 8, E::Unorm),
+    channel(Green, 8, 8, E::Unorm),
+    channel(Red, 16, 8, E::Unorm),
+    channel(Alpha, 24, 8, E::Unorm),
+];
+
+const CH_32BPP_RGBE: &[ChannelLayout] = &[
+    channel(Red, 0, 8, E::SharedExponent),
+    channel(Green, 8, 8, E::SharedExponent),
+    channel(Blue, 16, 8, E::SharedExponent),
+    channel(Exponent, 24, 8, E::Integer),
+];
+
+const CH_32BPP_PRGBA: &[ChannelLayout] = &[
+    premultiplied(Red, 0, 8, E::Unorm),
+    premultiplied(Green, 8, 8, E::Unorm),
+    premultiplied(Blue, 16, 8, E::Unorm),
+    channel(Alpha, 24, 8, E::Unorm),
+];
+
+const CH_32BPP_PBGRA: &[ChannelLayout] = &[
+    premultiplied(Blue, 0, 8, E::Unorm),
+    premultiplied(Green, 8, 8, E::Unorm),
+    premultiplied(Red, 16, 8, E::Unorm),
+    channel(Alpha, 24, 8, E::Unorm),
+];
+
+const CH_48BPP_RGB: &[ChannelLayout] = &[
+    channel(Red, 0, 16, E::Unorm),
+    channel(Green, 16, 16, E::Unorm),
+    channel(Blue, 32, 16, E::Unorm),
+];
+
+const CH_48BPP_BGR: &[ChannelLayout] = &[
+    channel(Blue, 0, 16, E::Unorm),
+    channel(Green, 16, 16, E::Unorm),
+    channel(Red, 32, 16, E::Unorm),
+];
+
+const CH_48BPP_RGB_FIXED_POINT: &[ChannelLayout] = &[
+    channel(Red, 0, 16, E::Fixed(13)),
+    channel(Green, 16, 16, E::Fixed(13)),
+    channel(Blue, 32, 16, E::Fixed(13)),
+];
+
+const CH_48BPP_BGR_FIXED_POINT: &[ChannelLayout] = &[
+    channel(Blue, 0, 16, E::Fixed(13)),
+    channel(Green, 16, 16, E::Fixed(13)),
+    channel(Red, 32, 16, E::Fixed(13)),
+];
+
+const CH_48BPP_RGB_HALF: &[ChannelLayout] = &[
+    channel(Red, 0, 16, E::Float),
+    channel(Green, 16, 16, E::Float),
+    channel(Blue, 32, 16, E::Float),
+];
+
+const CH_64BPP_RGBA: &[ChannelLayout] = &[
+    channel(Red, 0, 16, E::Unorm),
+    channel(Green, 16, 16, E::Unorm),
+    channel(Blue, 32, 16, E::Unorm),
+    channel(Alpha, 48, 16, E::Unorm),
+];
+
+const CH_64BPP_BGRA: &[ChannelLayout] = &[
+    channel(Blue, 0, 16, E::Unorm),
+    channel(Green, 16, 16, E::Unorm),
+    channel(Red, 32, 16, E::Unorm),
+    channel(Alpha, 48, 16, E::Unorm),
+];
+
+const CH_64BPP_PRGBA: &[ChannelLayout] = &[
+    premultiplied(Red, 0, 16, E::Unorm),
+    premultiplied(Green, 16, 16, E::Unorm),
+    premultiplied(Blue, 32, 16, E::Unorm),
+    channel(Alpha, 48, 16, E::Unorm),
+];
+
+const CH_64BPP_PBGRA: &[ChannelLayout] = &[
+    premultiplied(Blue, 0, 16, E::Unorm),
+    premultiplied(Green, 16, 16, E::Unorm),
+    premultiplied(Red, 32, 16, E::Unorm),
+    channel(Alpha, 48, 16, E::Unorm),
+];
+
+const CH_64BPP_RGB_FIXED_POINT: &[ChannelLayout] = &[
+    channel(Red, 0, 16, E::Fixed(13)),
+    channel(Green, 16, 16, E::Fixed(13)),
+    channel(Blue, 32, 16, E::Fixed(13)),
+    channel(Padding, 48, 16, E::Fixed(13)),
+];
+
+const CH_64BPP_RGBA_FIXED_POINT: &[ChannelLayout] = &[
+    channel(Red, 0, 16, E::Fixed(13)),
+    channel(Green, 16, 16, E::Fixed(13)),
+    channel(Blue, 32, 16, E::Fixed(13)),
+    channel(Alpha, 48, 16, E::Fixed(13)),
+];
+
+const CH_64BPP_BGRA_FIXED_POINT: &[ChannelLayout] = &[
+    channel(Blue, 0, 16, E::Fixed(13)),
+    channel(Green, 16, 16, E::Fixed(13)),
+    channel(Red, 32, 16, E::Fixed(13)),
+    channel(Alpha, 48, 16, E::Fixed(13)),
+];
+
+const CH_64BPP_RGB_HALF: &[ChannelLayout] = &[
+    channel(Red, 0, 16, E::Float),
+    channel(Green, 16, 16, E::Float),
+    channel(Blue, 32, 16, E::Float),
+    channel(Padding, 48, 16, E::Float),
+];
+
+const CH_64BPP_RGBA_HALF: &[ChannelLayout] = &[
+    channel(Red, 0, 16, E::Float),
+    channel(Green, 16, 16, E::Float),
+    channel(Blue, 32, 16, E::Float),
+    channel(Alpha, 48, 16, E::Float),
+];
+
+const CH_96BPP_RGB_FIXED_POINT: &[ChannelLayout] = &[
+    channel(Red, 0, 32, E::Fixed(24)),
+    channel(Green, 32, 32, E::Fixed(24)),
+    channel(Blue, 64, 32, E::Fixed(24)),
+];
+
+const CH_128BPP_RGB_FLOAT: &[ChannelLayout] = &[
+    channel(Red, 0, 32, E::Float),
+    channel(Green, 32, 32, E::Float),
+    channel(Blue, 64, 32, E::Float),
+    channel(Padding, 96, 32, E::Float),
+];
+
+const CH_128BPP_RGBA_FLOAT: &[ChannelLayout] = &[
+    channel(Red, 0, 32, E::Float),
+    channel(Green, 32, 32, E::Float),
+    channel(Blue, 64, 32, E::Float),
+    channel(Alpha, 96, 32, E::Float),
+];
+
+const CH_128BPP_PRGBA_FLOAT: &[ChannelLayout] = &[
+    premultiplied(Red, 0, 32, E::Float),
+    premultiplied(Green, 32, 32, E::Float),
+    premultiplied(Blue, 64, 32, E::Float),
+    channel(Alpha, 96, 32, E::Float),
+];
+
+const CH_128BPP_RGB_FIXED_POINT: &[ChannelLayout] = &[
+    channel(Red, 0, 32, E::Fixed(24)),
+    channel(Green, 32, 32, E::Fixed(24)),
+    channel(Blue, 64, 32, E::Fixed(24)),
+    channel(Padding, 96, 32, E::Fixed(24)),
+];
+
+const CH_128BPP_RGBA_FIXED_POINT: &[ChannelLayout] = &[
+    channel(Red, 0, 32, E::Fixed(24)),
+    channel(Green, 32, 32, E::Fixed(24)),
+    channel(Blue, 64, 32, E::Fixed(24)),
+    channel(Alpha, 96, 32, E::Fixed(24)),
+];
+
+const CH_32BPP_RGB: &[ChannelLayout] = &[
+    channel(Red, 0, 8, E::Unorm),
+    channel(Green, 8, 8, E::Unorm),
+    channel(Blue, 16, 8, E::Unorm),
+    channel(Padding, 24, 8, E::Unorm),
+];
+
+const CH_64BPP_RGB: &[ChannelLayout] = &[
+    channel(Red, 0, 16, E::Unorm),
+    channel(Green, 16, 16, E::Unorm),
+    channel(Blue, 32, 16, E::Unorm),
+    channel(Padding, 48, 16, E::Unorm),
+];
+
+const CH_96BPP_RGB_FLOAT: &[ChannelLayout] = &[
+    channel(Red, 0, 32, E::Float),
+    channel(Green, 32, 32, E::Float),
+    channel(Blue, 64, 32, E::Float),
+];
+
+const CH_64BPP_PRGBA_HALF: &[ChannelLayout] = &[
+    premultiplied(Red, 0, 16, E::Float),
+    premultiplied(Green, 16, 16, E::Float),
+    premultiplied(Blue, 32, 16, E::Float),
+    channel(Alpha, 48, 16, E::Float),
+];
+
+const CH_8BPP_ALPHA: &[ChannelLayout] = &[channel(Alpha, 0, 8, E::Unorm)];
+
+const CH_32BPP_CMYK: &[ChannelLayout] = &[
+    channel(Cyan, 0, 8, E::Unorm),
+    channel(Magenta, 8, 8, E::Unorm),
+    channel(Yellow, 16, 8, E::Unorm),
+    channel(Black, 24, 8, E::Unorm),
+];
+
+const CH_64BPP_CMYK: &[ChannelLayout] = &[
+    channel(Cyan, 0, 16, E::Unorm),
+    channel(Magenta, 16, 16, E::Unorm),
+    channel(Yellow, 32, 16, E::Unorm),
+    channel(Black, 48, 16, E::Unorm),
+];
+
+const CH_40BPP_CMYK_ALPHA: &[ChannelLayout] = &[
+    channel(Cyan, 0, 8, E::Unorm),
+    channel(Magenta, 8, 8, E::Unorm),
+    channel(Yellow, 16, 8, E::Unorm),
+    channel(Black, 24, 8, E::Unorm),
+    channel(Alpha, 32, 8, E::Unorm),
+];
+
+const CH_80BPP_CMYK_ALPHA: &[ChannelLayout] = &[
+    channel(Cyan, 0, 16, E::Unorm),
+    channel(Magenta, 16, 16, E::Unorm),
+    channel(Yellow, 32, 16, E::Unorm),
+    channel(Black, 48, 16, E::Unorm),
+    channel(Alpha, 64, 16, E::Unorm),
+];
+
+const CH_24BPP_3CHANNELS: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 8, E::Unorm),
+    channel(Channel(1), 8, 8, E::Unorm),
+    channel(Channel(2), 16, 8, E::Unorm),
+];
+
+const CH_48BPP_3CHANNELS: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 16, E::Unorm),
+    channel(Channel(1), 16, 16, E::Unorm),
+    channel(Channel(2), 32, 16, E::Unorm),
+];
+
+const CH_32BPP_3CHANNELS_ALPHA: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 8, E::Unorm),
+    channel(Channel(1), 8, 8, E::Unorm),
+    channel(Channel(2), 16, 8, E::Unorm),
+    channel(Alpha, 24, 8, E::Unorm),
+];
+
+const CH_64BPP_3CHANNELS_ALPHA: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 16, E::Unorm),
+    channel(Channel(1), 16, 16, E::Unorm),
+    channel(Channel(2), 32, 16, E::Unorm),
+    channel(Alpha, 48, 16, E::Unorm),
+];
+
+const CH_32BPP_4CHANNELS: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 8, E::Unorm),
+    channel(Channel(1), 8, 8, E::Unorm),
+    channel(Channel(2), 16, 8, E::Unorm),
+    channel(Channel(3), 24, 8, E::Unorm),
+];
+
+const CH_64BPP_4CHANNELS: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 16, E::Unorm),
+    channel(Channel(1), 16, 16, E::Unorm),
+    channel(Channel(2), 32, 16, E::Unorm),
+    channel(Channel(3), 48, 16, E::Unorm),
+];
+
+const CH_40BPP_4CHANNELS_ALPHA: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 8, E::Unorm),
+    channel(Channel(1), 8, 8, E::Unorm),
+    channel(Channel(2), 16, 8, E::Unorm),
+    channel(Channel(3), 24, 8, E::Unorm),
+    channel(Alpha, 32, 8, E::Unorm),
+];
+
+const CH_80BPP_4CHANNELS_ALPHA: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 16, E::Unorm),
+    channel(Channel(1), 16, 16, E::Unorm),
+    channel(Channel(2), 32, 16, E::Unorm),
+    channel(Channel(3), 48, 16, E::Unorm),
+    channel(Alpha, 64, 16, E::Unorm),
+];
+
+const CH_40BPP_5CHANNELS: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 8, E::Unorm),
+    channel(Channel(1), 8, 8, E::Unorm),
+    channel(Channel(2), 16, 8, E::Unorm),
+    channel(Channel(3), 24, 8, E::Unorm),
+    channel(Channel(4), 32, 8, E::Unorm),
+];
+
+const CH_80BPP_5CHANNELS: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 16, E::Unorm),
+    channel(Channel(1), 16, 16, E::Unorm),
+    channel(Channel(2), 32, 16, E::Unorm),
+    channel(Channel(3), 48, 16, E::Unorm),
+    channel(Channel(4), 64, 16, E::Unorm),
+];
+
+const CH_48BPP_5CHANNELS_ALPHA: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 8, E::Unorm),
+    channel(Channel(1), 8, 8, E::Unorm),
+    channel(Channel(2), 16, 8, E::Unorm),
+    channel(Channel(3), 24, 8, E::Unorm),
+    channel(Channel(4), 32, 8, E::Unorm),
+    channel(Alpha, 40, 8, E::Unorm),
+];
+
+const CH_96BPP_5CHANNELS_ALPHA: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 16, E::Unorm),
+    channel(Channel(1), 16, 16, E::Unorm),
+    channel(Channel(2), 32, 16, E::Unorm),
+    channel(Channel(3), 48, 16, E::Unorm),
+    channel(Channel(4), 64, 16, E::Unorm),
+    channel(Alpha, 80, 16, E::Unorm),
+];
+
+const CH_48BPP_6CHANNELS: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 8, E::Unorm),
+    channel(Channel(1), 8, 8, E::Unorm),
+    channel(Channel(2), 16, 8, E::Unorm),
+    channel(Channel(3), 24, 8, E::Unorm),
+    channel(Channel(4), 32, 8, E::Unorm),
+    channel(Channel(5), 40, 8, E::Unorm),
+];
+
+const CH_96BPP_6CHANNELS: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 16, E::Unorm),
+    channel(Channel(1), 16, 16, E::Unorm),
+    channel(Channel(2), 32, 16, E::Unorm),
+    channel(Channel(3), 48, 16, E::Unorm),
+    channel(Channel(4), 64, 16, E::Unorm),
+    channel(Channel(5), 80, 16, E::Unorm),
+];
+
+const CH_56BPP_6CHANNELS_ALPHA: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 8, E::Unorm),
+    channel(Channel(1), 8, 8, E::Unorm),
+    channel(Channel(2), 16, 8, E::Unorm),
+    channel(Channel(3), 24, 8, E::Unorm),
+    channel(Channel(4), 32, 8, E::Unorm),
+    channel(Channel(5), 40, 8, E::Unorm),
+    channel(Alpha, 48, 8, E::Unorm),
+];
+
+const CH_112BPP_6CHANNELS_ALPHA: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 16, E::Unorm),
+    channel(Channel(1), 16, 16, E::Unorm),
+    channel(Channel(2), 32, 16, E::Unorm),
+    channel(Channel(3), 48, 16, E::Unorm),
+    channel(Channel(4), 64, 16, E::Unorm),
+    channel(Channel(5), 80, 16, E::Unorm),
+    channel(Alpha, 96, 16, E::Unorm),
+];
+
+const CH_56BPP_7CHANNELS: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 8, E::Unorm),
+    channel(Channel(1), 8, 8, E::Unorm),
+    channel(Channel(2), 16, 8, E::Unorm),
+    channel(Channel(3), 24, 8, E::Unorm),
+    channel(Channel(4), 32, 8, E::Unorm),
+    channel(Channel(5), 40, 8, E::Unorm),
+    channel(Channel(6), 48, 8, E::Unorm),
+];
+
+const CH_112BPP_7CHANNELS: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 16, E::Unorm),
+    channel(Channel(1), 16, 16, E::Unorm),
+    channel(Channel(2), 32, 16, E::Unorm),
+    channel(Channel(3), 48, 16, E::Unorm),
+    channel(Channel(4), 64, 16, E::Unorm),
+    channel(Channel(5), 80, 16, E::Unorm),
+    channel(Channel(6), 96, 16, E::Unorm),
+];
+
+const CH_64BPP_7CHANNELS_ALPHA: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 8, E::Unorm),
+    channel(Channel(1), 8, 8, E::Unorm),
+    channel(Channel(2), 16, 8, E::Unorm),
+    channel(Channel(3), 24, 8, E::Unorm),
+    channel(Channel(4), 32, 8, E::Unorm),
+    channel(Channel(5), 40, 8, E::Unorm),
+    channel(Channel(6), 48, 8, E::Unorm),
+    channel(Alpha, 56, 8, E::Unorm),
+];
+
+const CH_128BPP_7CHANNELS_ALPHA: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 16, E::Unorm),
+    channel(Channel(1), 16, 16, E::Unorm),
+    channel(Channel(2), 32, 16, E::Unorm),
+    channel(Channel(3), 48, 16, E::Unorm),
+    channel(Channel(4), 64, 16, E::Unorm),
+    channel(Channel(5), 80, 16, E::Unorm),
+    channel(Channel(6), 96, 16, E::Unorm),
+    channel(Alpha, 112, 16, E::Unorm),
+];
+
+const CH_64BPP_8CHANNELS: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 8, E::Unorm),
+    channel(Channel(1), 8, 8, E::Unorm),
+    channel(Channel(2), 16, 8, E::Unorm),
+    channel(Channel(3), 24, 8, E::Unorm),
+    channel(Channel(4), 32, 8, E::Unorm),
+    channel(Channel(5), 40, 8, E::Unorm),
+    channel(Channel(6), 48, 8, E::Unorm),
+    channel(Channel(7), 56, 8, E::Unorm),
+];
+
+const CH_128BPP_8CHANNELS: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 16, E::Unorm),
+    channel(Channel(1), 16, 16, E::Unorm),
+    channel(Channel(2), 32, 16, E::Unorm),
+    channel(Channel(3), 48, 16, E::Unorm),
+    channel(Channel(4), 64, 16, E::Unorm),
+    channel(Channel(5), 80, 16, E::Unorm),
+    channel(Channel(6), 96, 16, E::Unorm),
+    channel(Channel(7), 112, 16, E::Unorm),
+];
+
+const CH_72BPP_8CHANNELS_ALPHA: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 8, E::Unorm),
+    channel(Channel(1), 8, 8, E::Unorm),
+    channel(Channel(2), 16, 8, E::Unorm),
+    channel(Channel(3), 24, 8, E::Unorm),
+    channel(Channel(4), 32, 8, E::Unorm),
+    channel(Channel(5), 40, 8, E::Unorm),
+    channel(Channel(6), 48, 8, E::Unorm),
+    channel(Channel(7), 56, 8, E::Unorm),
+    channel(Alpha, 64, 8, E::Unorm),
+];
+
+const CH_144BPP_8CHANNELS_ALPHA: &[ChannelLayout] = &[
+    channel(Channel(0), 0, 16, E::Unorm),
+    channel(Channel(1), 16, 16, E::Unorm),
+    channel(Channel(2), 32, 16, E::Unorm),
+    channel(Channel(3), 48, 16, E::Unorm),
+    channel(Channel(4), 64, 16, E::Unorm),
+    channel(Channel(5), 80, 16, E::Unorm),
+    channel(Channel(6), 96, 16, E::Unorm),
+    channel(Channel(7), 112, 16, E::Unorm),
+    channel(Alpha, 128, 16, E::Unorm),
+];
+
+const CH_8BPP_Y: &[ChannelLayout] = &[channel(Luma, 0, 8, E::Unorm)];
+
+const CH_8BPP_CB: &[ChannelLayout] = &[channel(ChromaBlue, 0, 8, E::Unorm)];
+
+const CH_8BPP_CR: &[ChannelLayout] = &[channel(ChromaRed, 0, 8, E::Unorm)];
+
+const CH_16BPP_CBCR: &[ChannelLayout] = &[
+    channel(ChromaBlue, 0, 8, E::Unorm),
+    channel(ChromaRed, 8, 8, E::Unorm),
+];
+
+#[cfg(test)]
+#[test]
+fn lookups() {
+    let label = |format: Option<PixelFormat>| format.unwrap().description().unwrap().label;
+    assert_eq!(
+        label(PixelFormat::from_label("GUID_WICPixelFormat32bppPBGRA")),
+        "GUID_WICPixelFormat32bppPBGRA"
+    );
+    assert!(PixelFormat::from_label("GUID_WICPixelFormat31bppNothing").is_none());
+
+    for (guid, desc) in NATIVE_PIXEL_FORMATS {
+        let format = PixelFormat { guid: *guid };
+        assert_eq!(format.description().unwrap().label, desc.label);
+    }
+}
+
+#[cfg(all(test, windows))]
+#[test]
+fn dxgi_lookups() {
+    let label = |format: Option<PixelFormat>| format.unwrap().description().unwrap().label;
+    assert_eq!(
+        label(PixelFormat::from_dxgi(R8G8B8A8Unorm)),
+        "GUID_WICPixelFormat32bppRGBA"
+    );
+    assert!(PixelFormat::from_dxgi(Unknown).is_none());
+
+    for (_, desc) in NATIVE_PIXEL_FORMATS {
+        if desc.dxgi_format as u32 != Unknown as u32 {
+            let found = PixelFormat::from_dxgi(desc.dxgi_format).unwrap();
+            let found = found.description().unwrap();
+            assert_eq!(found.dxgi_format as u32, desc.dxgi_format as u32);
+        }
+    }
+}
+
+#[cfg(all(test, windows))]
+#[test]
+fn closest_convertible() {
+    let closest = |source: GUID, target| {
+        let c = PixelFormat { guid: source }
+            .closest_convertible(target)
+            .unwrap();
+        (
+            c.format.description().unwrap().label,
+            c.required,
+            c.lossless,
+        )
+    };
+    assert_eq!(
+        closest(GUID_WICPixelFormat32bppPRGBA, R8G8B8A8Unorm),
+        ("GUID_WICPixelFormat32bppPRGBA", false, true)
+    );
+    assert_eq!(
+        closest(GUID_WICPixelFormat32bppPBGRA, R8G8B8A8Unorm),
+        ("GUID_WICPixelFormat32bppPRGBA", true, true)
+    );
+    assert_eq!(
+        closest(GUID_WICPixelFormat24bppBGR, R8G8B8A8Unorm),
+        ("GUID_WICPixelFormat32bppRGBA", true, true)
+    );
+    assert_eq!(
+        closest(GUID_WICPixelFormat8bppIndexed, B8G8R8A8Unorm),
+        ("GUID_WICPixelFormat32bppBGRA", true, true)
+    );
+    assert_eq!(
+        closest(GUID_WICPixelFormat8bppGray, R16G16B16A16Float),
+        ("GUID_WICPixelFormat64bppRGBAHalf", true, true)
+    );
+    assert_eq!(
+        closest(GUID_WICPixelFormat64bppRGBA, R8G8B8A8Unorm),
+        ("GUID_WICPixelFormat32bppRGBA", true, false)
+    );
+    assert!(PixelFormat {
+        guid: GUID_WICPixelFormat32bppBGRA
+    }
+    .closest_convertible(Unknown)
+    .is_none());
+}
+
+#[cfg(test)]
+#[test]
+fn table_is_consistent() {
+    for (i, (guid, desc)) in NATIVE_PIXEL_FORMATS.iter().enumerate() {
+        for (other, _) in &NATIVE_PIXEL_FORMATS[i + 1..] {
+            assert!(
+                guid_key(guid) != guid_key(other),
+                "{} is listed twice",
+                desc.label
+            );
+        }
+        let bits = match desc.bits_per_channel {
+            Uniform(bits) => bits * desc.channel_count,
+            PerChannel(a, b, c, d) => a + b + c + d,
+        };
+        assert!(bits <= desc.bits_per_pixel, "{}", desc.label);
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn channels_match_descriptions() {
+    for (_, desc) in NATIVE_PIXEL_FORMATS {
+        let mut offset = 0;
+        for channel in desc.channels {
+            assert_eq!(channel.bit_offset, offset, "{}", desc.label);
+            assert!(channel.bit_width > 0, "{}", desc.label);
+            offset += channel.bit_width;
+        }
+        assert_eq!(offset, desc.bits_per_pixel, "{}", desc.label);
+
+        let widths: Vec<u16> = desc
+            .channels
+            .iter()
+            .filter(|c| c.kind != Padding)
+            .map(|c| c.bit_width)
+            .collect();
+        assert_eq!(widths.len(), desc.channel_count as usize, "{}", desc.label);
+        match desc.bits_per_channel {
+            Uniform(bits) => assert!(widths.iter().all(|&w| w == bits), "{}", desc.label),
+            PerChannel(a, b, c, d) => {
+                let expected = [a, b, c, d];
+                assert_eq!(&expected[..widths.len()], &widths[..], "{}", desc.label);
+            }
+        }
+
+        let has = |flag: Category| desc.category.0 & flag.0 != 0;
+        assert_eq!(
+            desc.channel(Alpha).is_some(),
+            has(C::ALPHA),
+            "{}",
+            desc.label
+        );
+        assert_eq!(
+            desc.channel(Index).is_some(),
+            has(C::INDEXED),
+            "{}",
+            desc.label
+        );
+        assert_eq!(
+            desc.channels.iter().any(|c| c.premultiplied),
+            has(C::PREMULTIPLIED),
+            "{}",
+            desc.label
+        );
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn row_strides() {
+    let desc = |guid| PixelFormat { guid }.description().unwrap();
+    assert_eq!(desc(GUID_WICPixelFormatBlackWhite).stride(9), Some(2));
+    assert_eq!(desc(GUID_WICPixelFormat24bppRGB).stride(3), Some(9));
+
+    let widest = desc(GUID_WICPixelFormat144bpp8ChannelsAlpha).stride(u32::MAX);
+    if std::mem::size_of::<usize>() < 8 {
+        assert_eq!(widest, None);
+    } else {
+        assert_eq!(widest, Some(u32::MAX as usize * 18));
     }
 }
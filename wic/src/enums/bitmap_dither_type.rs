@@ -0,0 +1,17 @@
+#[auto_enum::auto_enum(u32, checked)]
+pub enum BitmapDitherType {
+    None = 0,
+    Ordered4x4 = 0x1,
+    Ordered8x8 = 0x2,
+    Ordered16x16 = 0x3,
+    Spiral4x4 = 0x4,
+    Spiral8x8 = 0x5,
+    DualSpiral4x4 = 0x6,
+    DualSpiral8x8 = 0x7,
+    ErrorDiffusion = 0x8,
+}
+
+#[allow(non_upper_case_globals)]
+impl BitmapDitherType {
+    pub const Solid: BitmapDitherType = BitmapDitherType::None;
+}
@@ -0,0 +1,8 @@
+#[auto_enum::auto_enum(u32, checked)]
+pub enum BitmapInterpolationMode {
+    NearestNeighbor = 0,
+    Linear = 0x1,
+    Cubic = 0x2,
+    Fant = 0x3,
+    HighQualityCubic = 0x4,
+}
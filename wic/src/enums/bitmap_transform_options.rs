@@ -0,0 +1,11 @@
+/// A clockwise rotation, optionally followed by flips. The rotations are
+/// values, not bits, so combine at most one with the flip flags.
+#[auto_enum::enum_flags]
+pub enum BitmapTransformOptions {
+    ROTATE0 = 0,
+    ROTATE90 = 0x1,
+    ROTATE180 = 0x2,
+    ROTATE270 = 0x3,
+    FLIP_HORIZONTAL = 0x8,
+    FLIP_VERTICAL = 0x10,
+}
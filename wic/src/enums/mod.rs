@@ -1,11 +1,15 @@
 pub use self::{
-    bitmap_create_cache::*, bitmap_decoder_caps::*, bitmap_encoder_cache_opt::*,
-    bitmap_lock_flags::*, bitmap_palette_type::*, decode_options::*,
+    bitmap_create_cache::*, bitmap_decoder_caps::*, bitmap_dither_type::*,
+    bitmap_encoder_cache_opt::*, bitmap_interpolation_mode::*, bitmap_lock_flags::*,
+    bitmap_palette_type::*, bitmap_transform_options::*, decode_options::*,
 };
 
 mod bitmap_create_cache;
 mod bitmap_decoder_caps;
+mod bitmap_dither_type;
 mod bitmap_encoder_cache_opt;
+mod bitmap_interpolation_mode;
 mod bitmap_lock_flags;
 mod bitmap_palette_type;
+mod bitmap_transform_options;
 mod decode_options;
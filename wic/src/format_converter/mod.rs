@@ -0,0 +1,81 @@
+use crate::bitmap_source::BitmapSource;
+use crate::descriptions::PixelFormat;
+use crate::enums::{BitmapDitherType, BitmapPaletteType};
+use crate::imaging_factory::ImagingFactory;
+use crate::palette::Palette;
+
+use com_wrapper::ComWrapper;
+use dcommon::helpers::{deref_com_wrapper, deref_com_wrapper_mut};
+use dcommon::{Error, Status};
+use winapi::um::wincodec::IWICFormatConverter;
+use wio::com::ComPtr;
+
+/// Converts a source to another pixel format. `software::PixelConverter`
+/// does the same in Rust.
+#[repr(transparent)]
+#[derive(ComWrapper)]
+#[com(debug)]
+pub struct FormatConverter {
+    ptr: ComPtr<IWICFormatConverter>,
+}
+
+impl FormatConverter {
+    pub fn create(factory: &ImagingFactory) -> Result<Self, Error> {
+        unsafe {
+            let mut ptr = std::ptr::null_mut();
+            let hr = (*factory.get_raw()).CreateFormatConverter(&mut ptr);
+            Error::map_if(hr, || Self::from_raw(ptr))
+        }
+    }
+
+    /// `palette` is used when `format` is indexed; without one, a palette of
+    /// type `palette_translate` is generated. Pixels with alpha below
+    /// `alpha_threshold_percent` become transparent in indexed output.
+    pub fn initialize(
+        &mut self,
+        source: &BitmapSource,
+        format: PixelFormat,
+        dither: BitmapDitherType,
+        palette: Option<&Palette>,
+        alpha_threshold_percent: f64,
+        palette_translate: BitmapPaletteType,
+    ) -> Result<Status, Error> {
+        let palette = palette.map(|p| p.get_raw()).unwrap_or(std::ptr::null_mut());
+        unsafe {
+            let hr = self.ptr.Initialize(
+                source.get_raw(),
+                &format.guid,
+                dither as u32,
+                palette,
+                alpha_threshold_percent,
+                palette_translate as u32,
+            );
+            Error::map_status(hr)
+        }
+    }
+
+    pub fn can_convert(&self, source: PixelFormat, target: PixelFormat) -> Result<bool, Error> {
+        unsafe {
+            let mut can = 0;
+            let hr = self.ptr.CanConvert(&source.guid, &target.guid, &mut can);
+            Error::map(hr, can != 0)
+        }
+    }
+
+    pub fn into_source(self) -> BitmapSource {
+        unsafe { BitmapSource::from_raw(self.ptr.into_raw() as _) }
+    }
+}
+
+impl std::ops::Deref for FormatConverter {
+    type Target = BitmapSource;
+    fn deref(&self) -> &Self::Target {
+        unsafe { deref_com_wrapper(self) }
+    }
+}
+
+impl std::ops::DerefMut for FormatConverter {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { deref_com_wrapper_mut(self) }
+    }
+}
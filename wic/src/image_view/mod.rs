@@ -0,0 +1,466 @@
+//! Safe, typed access to pixels in memory, such as the data of a
+//! `BitmapLock` or the buffer passed to `BitmapSource::copy_pixels`.
+//!
+//! A view is checked when it is created: the pixel format must match the
+//! pixel type, the memory must be large enough for every row and suitably
+//! aligned for the pixel type. After that, rows can be used as plain slices.
+
+use crate::descriptions::PixelFormat;
+
+use dcommon::Error;
+use math2d::Recti;
+use std::marker::PhantomData;
+use std::mem::{align_of, size_of};
+
+pub use self::pixel::*;
+
+pub mod pixel;
+
+/// A read-only view of an image whose pixels are `P`.
+pub struct ImageView<'a, P: Pixel> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+    stride: usize,
+    _pixel: PhantomData<P>,
+}
+
+impl<'a, P: Pixel> ImageView<'a, P> {
+    /// Views `data` as an image of `format` with rows `stride` bytes apart.
+    ///
+    /// Fails with `Error::WIC_UNSUPPORTEDPIXELFORMAT` if `P` can't hold
+    /// pixels of `format`, `Error::WIC_INSUFFICIENTBUFFER` if the rows don't
+    /// fit in `data`, and `Error::INVALIDARG` if `data` or `stride` isn't
+    /// aligned for `P`.
+    pub fn new(
+        data: &'a [u8],
+        format: PixelFormat,
+        width: u32,
+        height: u32,
+        stride: usize,
+    ) -> Result<Self, Error> {
+        check::<P>(data, format, width, height, stride)?;
+        Ok(ImageView {
+            data,
+            width,
+            height,
+            stride,
+            _pixel: PhantomData,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The distance between the starts of two rows, in bytes.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// The pixels of row `y`. Panics if `y` is out of bounds.
+    pub fn row(&self, y: u32) -> &'a [P] {
+        assert!(y < self.height, "row {} out of bounds", y);
+        unsafe { cast(row_bytes::<P>(self.data, y, self.width, self.stride)) }
+    }
+
+    /// Iterates over the rows from top to bottom.
+    pub fn rows(&self) -> Rows<'a, P> {
+        Rows {
+            view: *self,
+            next: 0,
+        }
+    }
+
+    /// The pixel at `(x, y)`, or `None` if it is out of bounds.
+    pub fn get(&self, x: u32, y: u32) -> Option<P> {
+        if y < self.height {
+            self.row(y).get(x as usize).copied()
+        } else {
+            None
+        }
+    }
+
+    /// A view of the pixels in `rect`, or `None` if it doesn't fit.
+    pub fn sub_view(&self, rect: impl Into<Recti>) -> Option<ImageView<'a, P>> {
+        let (offset, width, height) =
+            sub_rect::<P>(rect.into(), self.width, self.height, self.stride)?;
+        Some(ImageView {
+            data: &self.data[offset.min(self.data.len())..],
+            width,
+            height,
+            stride: self.stride,
+            _pixel: PhantomData,
+        })
+    }
+}
+
+impl<'a, P: Pixel> Clone for ImageView<'a, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, P: Pixel> Copy for ImageView<'a, P> {}
+
+/// A mutable view of an image whose pixels are `P`.
+pub struct ImageViewMut<'a, P: Pixel> {
+    data: &'a mut [u8],
+    width: u32,
+    height: u32,
+    stride: usize,
+    _pixel: PhantomData<P>,
+}
+
+impl<'a, P: Pixel> ImageViewMut<'a, P> {
+    /// Views `data` as an image of `format`. Fails in the same cases as
+    /// `ImageView::new`.
+    pub fn new(
+        data: &'a mut [u8],
+        format: PixelFormat,
+        width: u32,
+        height: u32,
+        stride: usize,
+    ) -> Result<Self, Error> {
+        check::<P>(data, format, width, height, stride)?;
+        Ok(ImageViewMut {
+            data,
+            width,
+            height,
+            stride,
+            _pixel: PhantomData,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// The memory behind the view, starting at its first pixel.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.data
+    }
+
+    /// The memory behind the view. Bytes between the end of a row and the
+    /// start of the next may belong to pixels outside the view.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        self.data
+    }
+
+    pub fn as_view(&self) -> ImageView<'_, P> {
+        ImageView {
+            data: self.data,
+            width: self.width,
+            height: self.height,
+            stride: self.stride,
+            _pixel: PhantomData,
+        }
+    }
+
+    /// The pixels of row `y`. Panics if `y` is out of bounds.
+    pub fn row(&self, y: u32) -> &[P] {
+        self.as_view().row(y)
+    }
+
+    /// The pixels of row `y`. Panics if `y` is out of bounds.
+    pub fn row_mut(&mut self, y: u32) -> &mut [P] {
+        assert!(y < self.height, "row {} out of bounds", y);
+        unsafe { cast_mut(row_bytes_mut::<P>(self.data, y, self.width, self.stride)) }
+    }
+
+    /// Iterates over the rows from top to bottom.
+    pub fn rows(&self) -> Rows<'_, P> {
+        self.as_view().rows()
+    }
+
+    /// Iterates over the rows from top to bottom.
+    pub fn rows_mut(&mut self) -> RowsMut<'_, P> {
+        RowsMut {
+            data: self.data,
+            width: self.width,
+            remaining: self.height,
+            stride: self.stride,
+            _pixel: PhantomData,
+        }
+    }
+
+    /// The pixel at `(x, y)`, or `None` if it is out of bounds.
+    pub fn get(&self, x: u32, y: u32) -> Option<P> {
+        self.as_view().get(x, y)
+    }
+
+    /// Sets the pixel at `(x, y)`. Panics if it is out of bounds.
+    pub fn put(&mut self, x: u32, y: u32, pixel: P) {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.row_mut(y)[x as usize] = pixel;
+    }
+
+    /// Sets every pixel to `pixel`.
+    pub fn fill(&mut self, pixel: P) {
+        for row in self.rows_mut() {
+            for p in row {
+                *p = pixel;
+            }
+        }
+    }
+
+    /// A view of the pixels in `rect`, or `None` if it doesn't fit.
+    pub fn sub_view(&self, rect: impl Into<Recti>) -> Option<ImageView<'_, P>> {
+        self.as_view().sub_view(rect)
+    }
+
+    /// A mutable view of the pixels in `rect`, or `None` if it doesn't fit.
+    pub fn sub_view_mut(&mut self, rect: impl Into<Recti>) -> Option<ImageViewMut<'_, P>> {
+        let (offset, width, height) =
+            sub_rect::<P>(rect.into(), self.width, self.height, self.stride)?;
+        let len = self.data.len();
+        Some(ImageViewMut {
+            data: &mut self.data[offset.min(len)..],
+            width,
+            height,
+            stride: self.stride,
+            _pixel: PhantomData,
+        })
+    }
+}
+
+/// The rows of an `ImageView`, from top to bottom.
+pub struct Rows<'a, P: Pixel> {
+    view: ImageView<'a, P>,
+    next: u32,
+}
+
+impl<'a, P: Pixel> Iterator for Rows<'a, P> {
+    type Item = &'a [P];
+
+    fn next(&mut self) -> Option<&'a [P]> {
+        if self.next < self.view.height {
+            self.next += 1;
+            Some(self.view.row(self.next - 1))
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.view.height - self.next) as usize;
+        (len, Some(len))
+    }
+}
+
+impl<'a, P: Pixel> ExactSizeIterator for Rows<'a, P> {}
+
+/// The rows of an `ImageViewMut`, from top to bottom.
+pub struct RowsMut<'a, P: Pixel> {
+    data: &'a mut [u8],
+    width: u32,
+    remaining: u32,
+    stride: usize,
+    _pixel: PhantomData<P>,
+}
+
+impl<'a, P: Pixel> Iterator for RowsMut<'a, P> {
+    type Item = &'a mut [P];
+
+    fn next(&mut self) -> Option<&'a mut [P]> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let data = std::mem::take(&mut self.data);
+        let row = if self.remaining > 0 {
+            let (row, rest) = data.split_at_mut(self.stride);
+            self.data = rest;
+            row
+        } else {
+            data
+        };
+        let len = self.width as usize * size_of::<P>();
+        Some(unsafe { cast_mut(&mut row[..len]) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+impl<'a, P: Pixel> ExactSizeIterator for RowsMut<'a, P> {}
+
+fn check<P: Pixel>(
+    data: &[u8],
+    format: PixelFormat,
+    width: u32,
+    height: u32,
+    stride: usize,
+) -> Result<(), Error> {
+    if !P::matches(format) {
+        return Err(Error::WIC_UNSUPPORTEDPIXELFORMAT);
+    }
+    let row = (width as usize)
+        .checked_mul(size_of::<P>())
+        .ok_or(Error::WIC_INSUFFICIENTBUFFER)?;
+    let needed = match height {
+        0 => Some(0),
+        _ => stride
+            .checked_mul(height as usize - 1)
+            .and_then(|n| n.checked_add(row)),
+    };
+    match needed {
+        Some(needed) if stride >= row && data.len() >= needed => (),
+        _ => return Err(Error::WIC_INSUFFICIENTBUFFER),
+    }
+    let align = align_of::<P>();
+    if (data.as_ptr() as usize) % align != 0 || stride % align != 0 {
+        return Err(Error::INVALIDARG);
+    }
+    Ok(())
+}
+
+/// The byte offset, width and height of `rect` in an image, if it fits.
+fn sub_rect<P: Pixel>(
+    rect: Recti,
+    width: u32,
+    height: u32,
+    stride: usize,
+) -> Option<(usize, u32, u32)> {
+    if rect.left < 0 || rect.top < 0 || rect.right < rect.left || rect.bottom < rect.top {
+        return None;
+    }
+    if rect.right as u32 > width || rect.bottom as u32 > height {
+        return None;
+    }
+    let offset = rect.top as usize * stride + rect.left as usize * size_of::<P>();
+    let width = (rect.right - rect.left) as u32;
+    let height = (rect.bottom - rect.top) as u32;
+    Some((offset, width, height))
+}
+
+fn row_bytes<P: Pixel>(data: &[u8], y: u32, width: u32, stride: usize) -> &[u8] {
+    if width == 0 {
+        return &[];
+    }
+    let start = y as usize * stride;
+    &data[start..start + width as usize * size_of::<P>()]
+}
+
+fn row_bytes_mut<P: Pixel>(data: &mut [u8], y: u32, width: u32, stride: usize) -> &mut [u8] {
+    if width == 0 {
+        return &mut [];
+    }
+    let start = y as usize * stride;
+    &mut data[start..start + width as usize * size_of::<P>()]
+}
+
+/// Safety: `bytes` must be aligned for `P`, which `check` ensures for
+/// every row of a view.
+unsafe fn cast<P: Pixel>(bytes: &[u8]) -> &[P] {
+    std::slice::from_raw_parts(bytes.as_ptr() as *const P, bytes.len() / size_of::<P>())
+}
+
+unsafe fn cast_mut<P: Pixel>(bytes: &mut [u8]) -> &mut [P] {
+    std::slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut P, bytes.len() / size_of::<P>())
+}
+
+#[cfg(test)]
+fn format(guid: crate::GUID) -> PixelFormat {
+    PixelFormat { guid }
+}
+
+#[cfg(test)]
+#[test]
+fn views() {
+    use crate::descriptions::guids::*;
+
+    let mut data = vec![0u8; 3 * 16];
+    let bgra = format(GUID_WICPixelFormat32bppPBGRA);
+    let mut view = ImageViewMut::<Bgra8>::new(&mut data, bgra, 3, 3, 16).unwrap();
+    view.fill(Bgra8 {
+        b: 1,
+        g: 2,
+        r: 3,
+        a: 4,
+    });
+    let red = Bgra8 {
+        b: 0,
+        g: 0,
+        r: 255,
+        a: 255,
+    };
+    {
+        let mut sub = view
+            .sub_view_mut(Recti {
+                left: 1,
+                top: 1,
+                right: 3,
+                bottom: 3,
+            })
+            .unwrap();
+        assert_eq!((sub.width(), sub.height()), (2, 2));
+        sub.put(1, 1, red);
+        assert_eq!(sub.rows_mut().count(), 2);
+    }
+    assert_eq!(view.get(2, 2), Some(red));
+    assert_eq!(view.get(1, 1).unwrap().b, 1);
+    assert_eq!(view.get(3, 0), None);
+    assert_eq!(data[2 * 16 + 2 * 4 + 2], 255);
+
+    let view = ImageView::<Bgra8>::new(&data, bgra, 3, 3, 16).unwrap();
+    assert_eq!(view.rows().len(), 3);
+    assert!(view.rows().all(|row| row.len() == 3));
+    let outside = Recti {
+        left: 2,
+        top: 0,
+        right: 4,
+        bottom: 1,
+    };
+    assert!(view.sub_view(outside).is_none());
+}
+
+#[cfg(test)]
+#[test]
+fn checks() {
+    use crate::descriptions::guids::*;
+
+    let data = vec![0u8; 64];
+    let rgba = format(GUID_WICPixelFormat32bppRGBA);
+    let check = |format, width, height, stride| {
+        ImageView::<Rgba8>::new(&data, format, width, height, stride).err()
+    };
+    assert_eq!(check(rgba, 4, 4, 16), None);
+    assert_eq!(check(rgba, 4, 5, 16), Some(Error::WIC_INSUFFICIENTBUFFER));
+    assert_eq!(check(rgba, 5, 1, 16), Some(Error::WIC_INSUFFICIENTBUFFER));
+    assert_eq!(
+        check(format(GUID_WICPixelFormat32bppBGRA), 4, 4, 16),
+        Some(Error::WIC_UNSUPPORTEDPIXELFORMAT)
+    );
+    assert_eq!(
+        check(format(GUID_WICPixelFormat32bppBGR), 4, 4, 16),
+        Some(Error::WIC_UNSUPPORTEDPIXELFORMAT)
+    );
+
+    let half = format(GUID_WICPixelFormat64bppRGBAHalf);
+    let odd = if (data.as_ptr() as usize) % 2 == 0 {
+        1
+    } else {
+        0
+    };
+    assert_eq!(
+        ImageView::<RgbaF16>::new(&data[odd..], half, 1, 1, 8).err(),
+        Some(Error::INVALIDARG)
+    );
+    assert!(RgbaF16::matches(format(GUID_WICPixelFormat64bppPRGBAHalf)));
+    let pixel = RgbaF16::from_f32([0.5, -2.0, 1.0, 1.0]);
+    assert_eq!(pixel.to_f32(), [0.5, -2.0, 1.0, 1.0]);
+}
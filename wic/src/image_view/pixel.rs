@@ -0,0 +1,252 @@
+//! Pixel types that can be read straight out of image memory.
+
+use crate::descriptions::pixel_format::ChannelEncoding::{self, *};
+use crate::descriptions::pixel_format::ChannelKind::{self, *};
+use crate::descriptions::PixelFormat;
+use crate::software::convert::{f32_to_half, half_to_f32};
+
+/// A pixel whose memory layout matches one or more WIC pixel formats, so
+/// rows of image memory can be used as slices of it.
+///
+/// # Safety
+///
+/// The type must be `repr(C)` without padding, every bit pattern must be a
+/// valid value, and `CHANNELS` must describe its fields in order.
+pub unsafe trait Pixel: Copy + 'static {
+    /// The channels as `(kind, bit width, encoding)`, in memory order.
+    const CHANNELS: &'static [(ChannelKind, u16, ChannelEncoding)];
+
+    /// Whether pixels of `format` can be read as this type. Premultiplied
+    /// formats match the same types as their straight alpha counterparts.
+    fn matches(format: PixelFormat) -> bool {
+        let desc = match format.description() {
+            Some(desc) => desc,
+            None => return false,
+        };
+        desc.channels.len() == Self::CHANNELS.len()
+            && desc
+                .channels
+                .iter()
+                .zip(Self::CHANNELS)
+                .all(|(channel, &(kind, bits, encoding))| {
+                    channel.kind == kind
+                        && channel.bit_width == bits
+                        && channel.encoding == encoding
+                })
+    }
+}
+
+macro_rules! impl_pixel {
+    ($ty:ident, [$(($kind:ident, $bits:expr, $enc:ident)),*]) => {
+        unsafe impl Pixel for $ty {
+            const CHANNELS: &'static [(ChannelKind, u16, ChannelEncoding)] =
+                &[$(($kind, $bits, $enc)),*];
+        }
+    };
+}
+
+/// 8bpp gray.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Gray8(pub u8);
+impl_pixel!(Gray8, [(Gray, 8, Unorm)]);
+
+/// 16bpp gray.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Gray16(pub u16);
+impl_pixel!(Gray16, [(Gray, 16, Unorm)]);
+
+/// 32bpp floating-point gray.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct GrayF32(pub f32);
+impl_pixel!(GrayF32, [(Gray, 32, Float)]);
+
+/// 24bpp RGB.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgb8 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+impl_pixel!(Rgb8, [(Red, 8, Unorm), (Green, 8, Unorm), (Blue, 8, Unorm)]);
+
+/// 24bpp BGR.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bgr8 {
+    pub b: u8,
+    pub g: u8,
+    pub r: u8,
+}
+impl_pixel!(Bgr8, [(Blue, 8, Unorm), (Green, 8, Unorm), (Red, 8, Unorm)]);
+
+/// 32bpp RGBA, straight or premultiplied.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgba8 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+impl_pixel!(
+    Rgba8,
+    [
+        (Red, 8, Unorm),
+        (Green, 8, Unorm),
+        (Blue, 8, Unorm),
+        (Alpha, 8, Unorm)
+    ]
+);
+
+/// 32bpp BGRA, straight or premultiplied.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bgra8 {
+    pub b: u8,
+    pub g: u8,
+    pub r: u8,
+    pub a: u8,
+}
+impl_pixel!(
+    Bgra8,
+    [
+        (Blue, 8, Unorm),
+        (Green, 8, Unorm),
+        (Red, 8, Unorm),
+        (Alpha, 8, Unorm)
+    ]
+);
+
+/// 64bpp RGBA, straight or premultiplied.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgba16 {
+    pub r: u16,
+    pub g: u16,
+    pub b: u16,
+    pub a: u16,
+}
+impl_pixel!(
+    Rgba16,
+    [
+        (Red, 16, Unorm),
+        (Green, 16, Unorm),
+        (Blue, 16, Unorm),
+        (Alpha, 16, Unorm)
+    ]
+);
+
+/// 64bpp BGRA, straight or premultiplied.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bgra16 {
+    pub b: u16,
+    pub g: u16,
+    pub r: u16,
+    pub a: u16,
+}
+impl_pixel!(
+    Bgra16,
+    [
+        (Blue, 16, Unorm),
+        (Green, 16, Unorm),
+        (Red, 16, Unorm),
+        (Alpha, 16, Unorm)
+    ]
+);
+
+/// 64bpp half-float RGBA, straight or premultiplied. The fields hold the
+/// raw bits of each half; use `to_f32` and `from_f32` to get at the values.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RgbaF16 {
+    pub r: u16,
+    pub g: u16,
+    pub b: u16,
+    pub a: u16,
+}
+impl_pixel!(
+    RgbaF16,
+    [
+        (Red, 16, Float),
+        (Green, 16, Float),
+        (Blue, 16, Float),
+        (Alpha, 16, Float)
+    ]
+);
+
+impl RgbaF16 {
+    /// Rounds each channel to the nearest half.
+    pub fn from_f32([r, g, b, a]: [f32; 4]) -> RgbaF16 {
+        RgbaF16 {
+            r: f32_to_half(r),
+            g: f32_to_half(g),
+            b: f32_to_half(b),
+            a: f32_to_half(a),
+        }
+    }
+
+    pub fn to_f32(self) -> [f32; 4] {
+        [
+            half_to_f32(self.r),
+            half_to_f32(self.g),
+            half_to_f32(self.b),
+            half_to_f32(self.a),
+        ]
+    }
+}
+
+/// 96bpp floating-point RGB.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct RgbF32 {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+impl_pixel!(
+    RgbF32,
+    [(Red, 32, Float), (Green, 32, Float), (Blue, 32, Float)]
+);
+
+/// 128bpp floating-point RGBA, straight or premultiplied.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct RgbaF32 {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+impl_pixel!(
+    RgbaF32,
+    [
+        (Red, 32, Float),
+        (Green, 32, Float),
+        (Blue, 32, Float),
+        (Alpha, 32, Float)
+    ]
+);
+
+/// 32bpp CMYK.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Cmyk8 {
+    pub c: u8,
+    pub m: u8,
+    pub y: u8,
+    pub k: u8,
+}
+impl_pixel!(
+    Cmyk8,
+    [
+        (Cyan, 8, Unorm),
+        (Magenta, 8, Unorm),
+        (Yellow, 8, Unorm),
+        (Black, 8, Unorm)
+    ]
+);
@@ -1,14 +1,26 @@
+//! Safe bindings for the Windows Imaging Component APIs.
+//!
+//! The pixel format descriptions in `descriptions`, the enums, `image_view`
+//! and the software implementations in `software` are also available on
+//! non-Windows targets.
+
+pub use crate::image_view::{ImageView, ImageViewMut};
+
+#[cfg(windows)]
 pub use crate::{
     bitmap::Bitmap,
     bitmap_clipper::BitmapClipper,
     bitmap_codec_info::BitmapCodecInfo,
     bitmap_decoder::{info::BitmapDecoderInfo, BitmapDecoder},
     bitmap_encoder::{info::BitmapEncoderInfo, BitmapEncoder},
+    bitmap_flip_rotator::BitmapFlipRotator,
     bitmap_frame_decode::BitmapFrameDecode,
     bitmap_frame_encode::BitmapFrameEncode,
     bitmap_lock::BitmapLock,
+    bitmap_scaler::BitmapScaler,
     bitmap_source::BitmapSource,
     color_context::ColorContext,
+    format_converter::FormatConverter,
     imaging_factory::ImagingFactory,
     metadata_query_reader::MetadataQueryReader,
     palette::Palette,
@@ -17,19 +29,41 @@ pub use crate::{
 
 pub use dcommon::GUID;
 
+#[cfg(windows)]
 pub mod bitmap;
+#[cfg(windows)]
 pub mod bitmap_clipper;
+#[cfg(windows)]
 pub mod bitmap_codec_info;
+#[cfg(windows)]
 pub mod bitmap_decoder;
+#[cfg(windows)]
 pub mod bitmap_encoder;
+#[cfg(windows)]
+pub mod bitmap_flip_rotator;
+#[cfg(windows)]
 pub mod bitmap_frame_decode;
+#[cfg(windows)]
 pub mod bitmap_frame_encode;
+#[cfg(windows)]
 pub mod bitmap_lock;
+#[cfg(windows)]
+pub mod bitmap_scaler;
+#[cfg(windows)]
 pub mod bitmap_source;
+#[cfg(windows)]
 pub mod color_context;
 pub mod descriptions;
 pub mod enums;
+#[cfg(windows)]
+pub mod format_converter;
+pub mod image_view;
+#[cfg(windows)]
 pub mod imaging_factory;
+#[cfg(windows)]
 pub mod metadata_query_reader;
+#[cfg(windows)]
 pub mod palette;
+pub mod software;
+#[cfg(windows)]
 pub mod stream;
@@ -3,12 +3,13 @@ use crate::GUID;
 use std::ffi::OsStr;
 
 use com_wrapper::ComWrapper;
+use dcommon::helpers::WideString;
+use dcommon::idltypes::propvalue::PropValue;
 use dcommon::idltypes::propvariant::PropVariant;
 use dcommon::objidl::EnumString;
 use dcommon::Error;
 use winapi::um::wincodec::IWICMetadataQueryReader;
 use wio::com::ComPtr;
-use wio::wide::ToWide;
 
 #[repr(transparent)]
 #[derive(ComWrapper)]
@@ -40,7 +41,7 @@ impl MetadataQueryReader {
     }
 
     pub fn metadata_by_name(&mut self, name: impl AsRef<OsStr>) -> Result<PropVariant, Error> {
-        let prop_name = name.as_ref().to_wide_null();
+        let prop_name = WideString::new(name)?;
         unsafe {
             let mut prop = std::mem::zeroed();
             let hr = self.ptr.GetMetadataByName(prop_name.as_ptr(), &mut prop);
@@ -48,6 +49,13 @@ impl MetadataQueryReader {
         }
     }
 
+    /// Like `metadata_by_name`, but copies the result into an owned
+    /// `PropValue` and frees the raw value.
+    pub fn metadata_value_by_name(&mut self, name: impl AsRef<OsStr>) -> Result<PropValue, Error> {
+        let prop = self.metadata_by_name(name)?;
+        unsafe { PropValue::from_raw(prop) }
+    }
+
     pub fn enumerator(&mut self) -> Result<EnumString, Error> {
         unsafe {
             let mut ptr = std::ptr::null_mut();
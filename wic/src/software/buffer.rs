@@ -0,0 +1,209 @@
+use crate::descriptions::PixelFormat;
+use crate::image_view::{ImageView, ImageViewMut, Pixel};
+use crate::software::PixelConverter;
+
+use dcommon::Error;
+
+/// An image in memory, in any pixel format with a description. The software
+/// implementations of the WIC transforms work on these.
+#[derive(Clone)]
+pub struct PixelBuffer {
+    format: PixelFormat,
+    width: u32,
+    height: u32,
+    stride: usize,
+    data: Vec<u8>,
+}
+
+impl PixelBuffer {
+    /// Creates a zeroed image with rows packed as tightly as the format
+    /// allows. Fails with `Error::OUTOFMEMORY` if its size doesn't fit in a
+    /// `usize`.
+    pub fn new(format: PixelFormat, width: u32, height: u32) -> Result<PixelBuffer, Error> {
+        let stride = min_stride(format, width)?.ok_or(Error::OUTOFMEMORY)?;
+        let len = stride
+            .checked_mul(height as usize)
+            .ok_or(Error::OUTOFMEMORY)?;
+        let data = vec![0; len];
+        Ok(PixelBuffer {
+            format,
+            width,
+            height,
+            stride,
+            data,
+        })
+    }
+
+    /// Wraps existing pixels. Fails with `Error::WIC_INSUFFICIENTBUFFER` if
+    /// `stride` or `data` is too small.
+    pub fn from_data(
+        format: PixelFormat,
+        width: u32,
+        height: u32,
+        stride: usize,
+        data: Vec<u8>,
+    ) -> Result<PixelBuffer, Error> {
+        let row = min_stride(format, width)?.ok_or(Error::WIC_INSUFFICIENTBUFFER)?;
+        let needed = match height {
+            0 => Some(0),
+            _ => stride
+                .checked_mul(height as usize - 1)
+                .and_then(|n| n.checked_add(row)),
+        };
+        match needed {
+            Some(needed) if stride >= row && data.len() >= needed => (),
+            _ => return Err(Error::WIC_INSUFFICIENTBUFFER),
+        }
+        Ok(PixelBuffer {
+            format,
+            width,
+            height,
+            stride,
+            data,
+        })
+    }
+
+    pub fn format(&self) -> PixelFormat {
+        self.format
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// The bytes of row `y`, without the padding at the end of the stride.
+    pub fn row(&self, y: u32) -> &[u8] {
+        let start = y as usize * self.stride;
+        &self.data[start..start + self.row_len()]
+    }
+
+    pub fn row_mut(&mut self, y: u32) -> &mut [u8] {
+        let start = y as usize * self.stride;
+        let len = self.row_len();
+        &mut self.data[start..start + len]
+    }
+
+    /// Views the pixels as `P`, which must match the buffer's format.
+    pub fn view<P: Pixel>(&self) -> Result<ImageView<'_, P>, Error> {
+        ImageView::new(
+            &self.data,
+            self.format,
+            self.width,
+            self.height,
+            self.stride,
+        )
+    }
+
+    pub fn view_mut<P: Pixel>(&mut self) -> Result<ImageViewMut<'_, P>, Error> {
+        ImageViewMut::new(
+            &mut self.data,
+            self.format,
+            self.width,
+            self.height,
+            self.stride,
+        )
+    }
+
+    /// Converts the image to the target format of `converter`, whose source
+    /// format must be this image's.
+    pub fn convert(&self, converter: &PixelConverter) -> Result<PixelBuffer, Error> {
+        if converter.source_format() != self.format {
+            return Err(Error::WIC_UNSUPPORTEDPIXELFORMAT);
+        }
+        let mut out = PixelBuffer::new(converter.target_format(), self.width, self.height)?;
+        converter.convert(
+            &self.data,
+            self.stride,
+            &mut out.data,
+            out.stride,
+            self.width,
+            self.height,
+        )?;
+        Ok(out)
+    }
+
+    pub(super) fn bits_per_pixel(&self) -> usize {
+        match self.format.description() {
+            Some(desc) => desc.bits_per_pixel as usize,
+            None => 0,
+        }
+    }
+
+    fn row_len(&self) -> usize {
+        (self.width as usize * self.bits_per_pixel()).div_ceil(8)
+    }
+}
+
+/// The packed row size, or None if it doesn't fit in a `usize`.
+fn min_stride(format: PixelFormat, width: u32) -> Result<Option<usize>, Error> {
+    match format.description() {
+        Some(desc) if desc.bits_per_pixel > 0 => Ok(desc.stride(width)),
+        _ => Err(Error::WIC_UNSUPPORTEDPIXELFORMAT),
+    }
+}
+
+/// Copies pixel `sx` of `source` to pixel `tx` of `target`. Pixels smaller
+/// than a byte are packed from the most significant bit.
+pub(super) fn copy_pixel(source: &[u8], sx: usize, target: &mut [u8], tx: usize, bits: usize) {
+    if bits % 8 == 0 {
+        let bytes = bits / 8;
+        target[tx * bytes..(tx + 1) * bytes].copy_from_slice(&source[sx * bytes..(sx + 1) * bytes]);
+        return;
+    }
+    let mask = (1u8 << bits) - 1;
+    let read_shift = 8 - bits - (sx * bits) % 8;
+    let value = (source[sx * bits / 8] >> read_shift) & mask;
+    let write_shift = 8 - bits - (tx * bits) % 8;
+    let byte = &mut target[tx * bits / 8];
+    *byte = (*byte & !(mask << write_shift)) | (value << write_shift);
+}
+
+#[cfg(test)]
+#[test]
+fn from_data_checks_size() {
+    use crate::descriptions::guids::GUID_WICPixelFormat24bppRGB;
+
+    let format = PixelFormat {
+        guid: GUID_WICPixelFormat24bppRGB,
+    };
+    let image = PixelBuffer::from_data(format, 2, 2, 8, vec![0; 14]).unwrap();
+    assert_eq!(image.row(1).len(), 6);
+    let error = |width, height, stride, len| {
+        PixelBuffer::from_data(format, width, height, stride, vec![0; len]).err()
+    };
+    assert_eq!(error(2, 2, 8, 13), Some(Error::WIC_INSUFFICIENTBUFFER));
+    assert_eq!(error(2, 2, 5, 64), Some(Error::WIC_INSUFFICIENTBUFFER));
+    assert_eq!(
+        error(2, 3, usize::MAX, 64),
+        Some(Error::WIC_INSUFFICIENTBUFFER)
+    );
+    assert_eq!(error(2, 0, 6, 0), None);
+
+    let wide = PixelFormat {
+        guid: crate::descriptions::guids::GUID_WICPixelFormat144bpp8ChannelsAlpha,
+    };
+    assert_eq!(
+        PixelBuffer::new(wide, u32::MAX, u32::MAX).err(),
+        Some(Error::OUTOFMEMORY)
+    );
+}
@@ -0,0 +1,1061 @@
+//! Pixel format conversion in plain Rust, driven by the descriptions in
+//! `NATIVE_PIXEL_FORMATS`.
+//!
+//! Pixels are decoded to RGBA with straight alpha, moved between colour
+//! spaces if needed, and encoded again. As in WIC, unsigned integer formats
+//! hold sRGB-encoded values, while fixed-point and floating-point formats
+//! hold linear (scRGB) values that may go outside `0.0..=1.0`.
+
+use crate::descriptions::guids::GUID_WICPixelFormat32bppR10G10B10A2HDR10;
+use crate::descriptions::pixel_format::{
+    ChannelEncoding, ChannelKind, PixelFormat, StorageType, NATIVE_PIXEL_FORMATS,
+};
+use crate::descriptions::Color;
+
+use dcommon::Error;
+
+/// Converts rows of pixels from one format to another without WIC.
+///
+/// Indexed formats need a palette, given with `with_source_palette` or
+/// `with_target_palette`. Converting to an indexed format picks the nearest
+/// palette entry.
+#[derive(Clone)]
+pub struct PixelConverter {
+    source: PixelFormat,
+    target: PixelFormat,
+    plan: Plan,
+    source_palette: Vec<Color>,
+    target_palette: Vec<Color>,
+    alpha_threshold: f32,
+}
+
+#[derive(Copy, Clone)]
+enum Plan {
+    /// Both formats are the same; rows are copied as they are.
+    Copy {
+        bits_per_pixel: usize,
+    },
+    Convert {
+        source: Layout,
+        target: Layout,
+    },
+}
+
+impl PixelConverter {
+    /// Fails with `Error::WIC_UNSUPPORTEDPIXELFORMAT` if either format
+    /// can't be converted in software.
+    pub fn new(source: PixelFormat, target: PixelFormat) -> Result<PixelConverter, Error> {
+        let plan = if source == target {
+            let desc = source
+                .description()
+                .ok_or(Error::WIC_UNSUPPORTEDPIXELFORMAT)?;
+            if desc.bits_per_pixel == 0 {
+                return Err(Error::WIC_UNSUPPORTEDPIXELFORMAT);
+            }
+            Plan::Copy {
+                bits_per_pixel: desc.bits_per_pixel as usize,
+            }
+        } else {
+            Plan::Convert {
+                source: Layout::of(source).ok_or(Error::WIC_UNSUPPORTEDPIXELFORMAT)?,
+                target: Layout::of(target).ok_or(Error::WIC_UNSUPPORTEDPIXELFORMAT)?,
+            }
+        };
+
+        Ok(PixelConverter {
+            source,
+            target,
+            plan,
+            source_palette: Vec::new(),
+            target_palette: Vec::new(),
+            alpha_threshold: 0.0,
+        })
+    }
+
+    /// Whether `new` would succeed for these formats.
+    pub fn can_convert(source: PixelFormat, target: PixelFormat) -> bool {
+        PixelConverter::new(source, target).is_ok()
+    }
+
+    /// Every format the converter can read and write.
+    pub fn supported_formats() -> impl Iterator<Item = PixelFormat> {
+        NATIVE_PIXEL_FORMATS
+            .iter()
+            .map(|&(guid, _)| PixelFormat { guid })
+            .filter(|&format| Layout::of(format).is_some())
+    }
+
+    /// Sets the palette for an indexed source format.
+    pub fn with_source_palette(mut self, colors: &[Color]) -> Self {
+        self.source_palette = colors.to_vec();
+        self
+    }
+
+    /// Sets the palette for an indexed target format.
+    pub fn with_target_palette(mut self, colors: &[Color]) -> Self {
+        self.target_palette = colors.to_vec();
+        self
+    }
+
+    /// Like the alpha threshold of `FormatConverter`: when converting to an
+    /// indexed format, pixels whose alpha is below `percent` are matched to
+    /// transparent palette entries and the rest to opaque ones.
+    pub fn with_alpha_threshold(mut self, percent: f64) -> Self {
+        self.alpha_threshold = percent as f32;
+        self
+    }
+
+    pub fn source_format(&self) -> PixelFormat {
+        self.source
+    }
+
+    pub fn target_format(&self) -> PixelFormat {
+        self.target
+    }
+
+    /// Converts one row of `width` pixels.
+    pub fn convert_row(&self, source: &[u8], target: &mut [u8], width: u32) -> Result<(), Error> {
+        let mut scratch = Vec::new();
+        self.check_palettes()?;
+        self.check_row(source.len(), target.len(), width)?;
+        self.convert_row_with(source, target, width as usize, &mut scratch);
+        Ok(())
+    }
+
+    /// Converts `height` rows of `width` pixels. Fails with
+    /// `Error::WIC_INSUFFICIENTBUFFER` if either buffer is too small for its
+    /// stride, or `Error::WIC_PALETTEUNAVAILABLE` if an indexed format has
+    /// no palette.
+    pub fn convert(
+        &self,
+        source: &[u8],
+        source_stride: usize,
+        target: &mut [u8],
+        target_stride: usize,
+        width: u32,
+        height: u32,
+    ) -> Result<(), Error> {
+        self.check_palettes()?;
+        if height == 0 {
+            return Ok(());
+        }
+        self.check_row(source_stride, target_stride, width)?;
+        let last = height as usize - 1;
+        let needed = |stride: usize, format| -> Result<Option<usize>, Error> {
+            let row = self.row_bytes(format, width)?;
+            Ok(stride.checked_mul(last).and_then(|n| n.checked_add(row)))
+        };
+        match (
+            needed(source_stride, self.source)?,
+            needed(target_stride, self.target)?,
+        ) {
+            (Some(s), Some(t)) if source.len() >= s && target.len() >= t => (),
+            _ => return Err(Error::WIC_INSUFFICIENTBUFFER),
+        }
+
+        let mut scratch = Vec::new();
+        for y in 0..height as usize {
+            let source = &source[y * source_stride..];
+            let target = &mut target[y * target_stride..];
+            self.convert_row_with(source, target, width as usize, &mut scratch);
+        }
+        Ok(())
+    }
+
+    /// Fails with `Error::WIC_INSUFFICIENTBUFFER` if a row is too large to
+    /// fit in memory.
+    fn row_bytes(&self, format: PixelFormat, width: u32) -> Result<usize, Error> {
+        match format.description() {
+            Some(desc) => desc.stride(width).ok_or(Error::WIC_INSUFFICIENTBUFFER),
+            None => Ok(0),
+        }
+    }
+
+    fn check_row(&self, source: usize, target: usize, width: u32) -> Result<(), Error> {
+        if source < self.row_bytes(self.source, width)?
+            || target < self.row_bytes(self.target, width)?
+        {
+            return Err(Error::WIC_INSUFFICIENTBUFFER);
+        }
+        Ok(())
+    }
+
+    fn check_palettes(&self) -> Result<(), Error> {
+        if let Plan::Convert { source, target } = self.plan {
+            let missing = |layout: Layout, palette: &[Color]| {
+                matches!(layout, Layout::Indexed { .. }) && palette.is_empty()
+            };
+            if missing(source, &self.source_palette) || missing(target, &self.target_palette) {
+                return Err(Error::WIC_PALETTEUNAVAILABLE);
+            }
+        }
+        Ok(())
+    }
+
+    fn convert_row_with(
+        &self,
+        source: &[u8],
+        target: &mut [u8],
+        width: usize,
+        scratch: &mut Vec<[f32; 4]>,
+    ) {
+        let (source_layout, target_layout) = match self.plan {
+            Plan::Copy { bits_per_pixel } => {
+                copy_bits(source, target, width * bits_per_pixel);
+                return;
+            }
+            Plan::Convert { source, target } => (source, target),
+        };
+
+        scratch.clear();
+        source_layout.decode(source, width, &self.source_palette, scratch);
+        match (source_layout.is_linear(), target_layout.is_linear()) {
+            (false, true) => scratch.iter_mut().for_each(|p| map_rgb(p, srgb_to_linear)),
+            (true, false) => scratch.iter_mut().for_each(|p| map_rgb(p, linear_to_srgb)),
+            _ => (),
+        }
+        if self.alpha_threshold > 0.0 {
+            if let Layout::Indexed { .. } = target_layout {
+                for p in scratch.iter_mut() {
+                    p[3] = if p[3] * 100.0 < self.alpha_threshold {
+                        0.0
+                    } else {
+                        1.0
+                    };
+                }
+            }
+        }
+        target_layout.encode(scratch, target, &self.target_palette);
+    }
+}
+
+/// Reads and writes rows as RGBA with straight alpha, leaving the values in
+/// the format's own colour space. Indexed formats aren't supported.
+#[derive(Copy, Clone)]
+pub(super) struct RowCodec {
+    layout: Layout,
+}
+
+impl RowCodec {
+    pub(super) fn new(format: PixelFormat) -> Option<RowCodec> {
+        match Layout::of(format)? {
+            Layout::Indexed { .. } => None,
+            layout => Some(RowCodec { layout }),
+        }
+    }
+
+    pub(super) fn decode(self, row: &[u8], width: usize, out: &mut Vec<[f32; 4]>) {
+        self.layout.decode(row, width, &[], out)
+    }
+
+    pub(super) fn encode(self, pixels: &[[f32; 4]], row: &mut [u8]) {
+        self.layout.encode(pixels, row, &[])
+    }
+}
+
+/// How a format stores its pixels, worked out from its description.
+#[derive(Copy, Clone, Debug)]
+enum Layout {
+    Indexed {
+        bits: usize,
+    },
+    Gray {
+        sample: Sample,
+    },
+    /// Channels in `order`, then alpha or an unused channel if `slots` is 4.
+    Rgb {
+        sample: Sample,
+        order: Order,
+        slots: usize,
+        alpha: bool,
+        premultiplied: bool,
+    },
+    Cmyk {
+        sample: Sample,
+        alpha: bool,
+    },
+    /// Bit fields from the least significant bit up, in `order` then alpha.
+    Packed {
+        order: Order,
+        bits: [u32; 4],
+        bytes: usize,
+        extended_range: bool,
+    },
+    /// Radiance RGBE: three mantissas sharing an exponent.
+    Rgbe,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Order {
+    Rgb,
+    Bgr,
+}
+
+/// How a single channel value is stored.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Sample {
+    /// An unsigned normalized integer of 1, 2, 4, 8 or 16 bits. Values
+    /// smaller than a byte are packed from the most significant bit.
+    Unorm(usize),
+    /// A signed fixed-point number with 13 fractional bits.
+    Fixed16,
+    /// A signed fixed-point number with 24 fractional bits.
+    Fixed32,
+    Half,
+    Float,
+}
+
+impl Layout {
+    fn of(format: PixelFormat) -> Option<Layout> {
+        // HDR10 is PQ-encoded, which none of the layouts below handle.
+        let hdr10 = PixelFormat {
+            guid: GUID_WICPixelFormat32bppR10G10B10A2HDR10,
+        };
+        if format == hdr10 {
+            return None;
+        }
+        let desc = format.description()?;
+        let has = |kind: ChannelKind| desc.channel(kind).is_some();
+        let offset = |kind: ChannelKind| desc.channel(kind).map(|c| c.bit_offset);
+        let order = if offset(ChannelKind::Blue) < offset(ChannelKind::Red) {
+            Order::Bgr
+        } else {
+            Order::Rgb
+        };
+        let mut values = desc
+            .channels
+            .iter()
+            .filter(|channel| channel.kind != ChannelKind::Padding);
+        let first = values.clone().next()?.bit_width;
+
+        if has(ChannelKind::Index) {
+            return match desc.bits_per_pixel {
+                1 | 2 | 4 | 8 => Some(Layout::Indexed {
+                    bits: desc.bits_per_pixel as usize,
+                }),
+                _ => None,
+            };
+        }
+        if has(ChannelKind::Exponent) {
+            return Some(Layout::Rgbe);
+        }
+        if has(ChannelKind::Red) && values.clone().any(|c| c.bit_width % 8 != 0) {
+            let bytes = desc.bits_per_pixel as usize / 8;
+            let mut bits = [0; 4];
+            for (field, channel) in bits.iter_mut().zip(&mut values) {
+                *field = channel.bit_width as u32;
+            }
+            if (bytes != 2 && bytes != 4) || values.next().is_some() {
+                return None;
+            }
+            return Some(Layout::Packed {
+                order,
+                bits,
+                bytes,
+                extended_range: desc.channels.iter().any(|channel| {
+                    matches!(channel.encoding, ChannelEncoding::ExtendedRange { .. })
+                }),
+            });
+        }
+
+        let sample = Sample::new(first, desc.storage_type)?;
+        if has(ChannelKind::Gray) {
+            return Some(Layout::Gray { sample });
+        }
+        if sample.bits() < 8 {
+            return None;
+        }
+        let slots = desc.bits_per_pixel as usize / sample.bits();
+        let alpha = has(ChannelKind::Alpha);
+        if has(ChannelKind::Cyan) {
+            return match (slots, alpha) {
+                (4, false) | (5, true) => Some(Layout::Cmyk { sample, alpha }),
+                _ => None,
+            };
+        }
+        if has(ChannelKind::Red) {
+            let valid = if alpha {
+                slots == 4
+            } else {
+                slots == 3 || slots == 4
+            };
+            if !valid {
+                return None;
+            }
+            return Some(Layout::Rgb {
+                sample,
+                order,
+                slots,
+                alpha,
+                premultiplied: desc.channels.iter().any(|channel| channel.premultiplied),
+            });
+        }
+        None
+    }
+
+    /// Whether the colour channels hold linear rather than sRGB values.
+    fn is_linear(self) -> bool {
+        match self {
+            Layout::Gray { sample } | Layout::Rgb { sample, .. } | Layout::Cmyk { sample, .. } => {
+                !matches!(sample, Sample::Unorm(_))
+            }
+            Layout::Rgbe => true,
+            Layout::Indexed { .. } | Layout::Packed { .. } => false,
+        }
+    }
+
+    fn decode(self, row: &[u8], width: usize, palette: &[Color], out: &mut Vec<[f32; 4]>) {
+        match self {
+            Layout::Indexed { bits } => out.extend((0..width).map(|x| {
+                let index = read_bits(row, x * bits, bits) as usize;
+                let color = palette.get(index).cloned().unwrap_or(Color { value: 0 });
+                [
+                    color.r() as f32 / 255.0,
+                    color.g() as f32 / 255.0,
+                    color.b() as f32 / 255.0,
+                    color.a() as f32 / 255.0,
+                ]
+            })),
+            Layout::Gray { sample } => out.extend((0..width).map(|x| {
+                let v = sample.read(row, x);
+                [v, v, v, 1.0]
+            })),
+            Layout::Rgb {
+                sample,
+                order,
+                slots,
+                alpha,
+                premultiplied,
+            } => out.extend((0..width).map(|x| {
+                let base = x * slots;
+                let c0 = sample.read(row, base);
+                let g = sample.read(row, base + 1);
+                let c2 = sample.read(row, base + 2);
+                let a = if alpha {
+                    sample.read(row, base + 3)
+                } else {
+                    1.0
+                };
+                let mut p = match order {
+                    Order::Rgb => [c0, g, c2, a],
+                    Order::Bgr => [c2, g, c0, a],
+                };
+                if premultiplied {
+                    unpremultiply(&mut p);
+                }
+                p
+            })),
+            Layout::Cmyk { sample, alpha } => out.extend((0..width).map(|x| {
+                let slots = if alpha { 5 } else { 4 };
+                let base = x * slots;
+                let k = 1.0 - sample.read(row, base + 3);
+                let a = if alpha {
+                    sample.read(row, base + 4)
+                } else {
+                    1.0
+                };
+                [
+                    (1.0 - sample.read(row, base)) * k,
+                    (1.0 - sample.read(row, base + 1)) * k,
+                    (1.0 - sample.read(row, base + 2)) * k,
+                    a,
+                ]
+            })),
+            Layout::Packed {
+                order,
+                bits,
+                bytes,
+                extended_range,
+            } => out.extend((0..width).map(|x| {
+                let value = read_le(&row[x * bytes..], bytes);
+                let mut fields = [0.0; 4];
+                let mut shift = 0;
+                for (field, &n) in fields.iter_mut().zip(&bits) {
+                    let max = (1u32 << n) - 1;
+                    let v = (value >> shift) & max;
+                    *field = if extended_range && n == 10 {
+                        (v as f32 - 384.0) / 510.0
+                    } else {
+                        v as f32 / max.max(1) as f32
+                    };
+                    shift += n;
+                }
+                let a = if bits[3] > 0 { fields[3] } else { 1.0 };
+                match order {
+                    Order::Rgb => [fields[0], fields[1], fields[2], a],
+                    Order::Bgr => [fields[2], fields[1], fields[0], a],
+                }
+            })),
+            Layout::Rgbe => out.extend((0..width).map(|x| {
+                let p = &row[x * 4..x * 4 + 4];
+                if p[3] == 0 {
+                    return [0.0, 0.0, 0.0, 1.0];
+                }
+                let scale = 2f32.powi(p[3] as i32 - (128 + 8));
+                [
+                    p[0] as f32 * scale,
+                    p[1] as f32 * scale,
+                    p[2] as f32 * scale,
+                    1.0,
+                ]
+            })),
+        }
+    }
+
+    fn encode(self, pixels: &[[f32; 4]], row: &mut [u8], palette: &[Color]) {
+        match self {
+            Layout::Indexed { bits } => {
+                for (x, p) in pixels.iter().enumerate() {
+                    let index = nearest(palette, p);
+                    write_bits(row, x * bits, bits, index as u32);
+                }
+            }
+            Layout::Gray { sample } => {
+                for (x, p) in pixels.iter().enumerate() {
+                    sample.write(row, x, luma(p));
+                }
+            }
+            Layout::Rgb {
+                sample,
+                order,
+                slots,
+                alpha,
+                premultiplied,
+            } => {
+                for (x, &p) in pixels.iter().enumerate() {
+                    let mut p = p;
+                    if premultiplied {
+                        premultiply(&mut p);
+                    }
+                    let [c0, g, c2] = match order {
+                        Order::Rgb => [p[0], p[1], p[2]],
+                        Order::Bgr => [p[2], p[1], p[0]],
+                    };
+                    let base = x * slots;
+                    sample.write(row, base, c0);
+                    sample.write(row, base + 1, g);
+                    sample.write(row, base + 2, c2);
+                    if slots == 4 {
+                        // The unused channel of formats like 32bppBGR is
+                        // filled as if it were opaque alpha.
+                        sample.write(row, base + 3, if alpha { p[3] } else { 1.0 });
+                    }
+                }
+            }
+            Layout::Cmyk { sample, alpha } => {
+                let slots = if alpha { 5 } else { 4 };
+                for (x, p) in pixels.iter().enumerate() {
+                    let [r, g, b] = [clamp01(p[0]), clamp01(p[1]), clamp01(p[2])];
+                    let k = 1.0 - r.max(g).max(b);
+                    let base = x * slots;
+                    for (i, &c) in [r, g, b].iter().enumerate() {
+                        let v = if k < 1.0 {
+                            (1.0 - c - k) / (1.0 - k)
+                        } else {
+                            0.0
+                        };
+                        sample.write(row, base + i, v);
+                    }
+                    sample.write(row, base + 3, k);
+                    if alpha {
+                        sample.write(row, base + 4, p[3]);
+                    }
+                }
+            }
+            Layout::Packed {
+                order,
+                bits,
+                bytes,
+                extended_range,
+            } => {
+                for (x, p) in pixels.iter().enumerate() {
+                    let fields = match order {
+                        Order::Rgb => [p[0], p[1], p[2], p[3]],
+                        Order::Bgr => [p[2], p[1], p[0], p[3]],
+                    };
+                    let mut value = 0;
+                    let mut shift = 0;
+                    for (&v, &n) in fields.iter().zip(&bits) {
+                        let max = (1u32 << n) - 1;
+                        let raw = if extended_range && n == 10 {
+                            (v * 510.0 + 384.0).round().clamp(0.0, max as f32) as u32
+                        } else {
+                            quantize(v, max)
+                        };
+                        value |= raw << shift;
+                        shift += n;
+                    }
+                    write_le(&mut row[x * bytes..], bytes, value);
+                }
+            }
+            Layout::Rgbe => {
+                for (x, p) in pixels.iter().enumerate() {
+                    row[x * 4..x * 4 + 4].copy_from_slice(&encode_rgbe(p));
+                }
+            }
+        }
+    }
+}
+
+impl Sample {
+    fn new(bits: u16, storage: StorageType) -> Option<Sample> {
+        Some(match (storage, bits) {
+            (StorageType::Unsigned, 1) | (StorageType::Unsigned, 2) => Sample::Unorm(bits as usize),
+            (StorageType::Unsigned, 4) | (StorageType::Unsigned, 8) => Sample::Unorm(bits as usize),
+            (StorageType::Unsigned, 16) => Sample::Unorm(16),
+            (StorageType::Fixed, 16) => Sample::Fixed16,
+            (StorageType::Fixed, 32) => Sample::Fixed32,
+            (StorageType::Float, 16) => Sample::Half,
+            (StorageType::Float, 32) => Sample::Float,
+            _ => return None,
+        })
+    }
+
+    fn bits(self) -> usize {
+        match self {
+            Sample::Unorm(bits) => bits,
+            Sample::Fixed16 | Sample::Half => 16,
+            Sample::Fixed32 | Sample::Float => 32,
+        }
+    }
+
+    /// Reads the `index`th value in `row`.
+    fn read(self, row: &[u8], index: usize) -> f32 {
+        match self {
+            Sample::Unorm(8) => row[index] as f32 / 255.0,
+            Sample::Unorm(16) => read_le(&row[index * 2..], 2) as f32 / 65535.0,
+            Sample::Unorm(bits) => {
+                let max = (1u32 << bits) - 1;
+                read_bits(row, index * bits, bits) as f32 / max as f32
+            }
+            Sample::Fixed16 => read_le(&row[index * 2..], 2) as u16 as i16 as f32 / 8192.0,
+            Sample::Fixed32 => (read_le(&row[index * 4..], 4) as i32 as f64 / 16_777_216.0) as f32,
+            Sample::Half => half_to_f32(read_le(&row[index * 2..], 2) as u16),
+            Sample::Float => f32::from_bits(read_le(&row[index * 4..], 4)),
+        }
+    }
+
+    /// Writes the `index`th value in `row`, clamping it to the range the
+    /// format can hold.
+    fn write(self, row: &mut [u8], index: usize, v: f32) {
+        match self {
+            Sample::Unorm(8) => row[index] = quantize(v, 255) as u8,
+            Sample::Unorm(16) => write_le(&mut row[index * 2..], 2, quantize(v, 65535)),
+            Sample::Unorm(bits) => {
+                let max = (1u32 << bits) - 1;
+                write_bits(row, index * bits, bits, quantize(v, max));
+            }
+            Sample::Fixed16 => {
+                let raw = (v * 8192.0).round().clamp(i16::MIN as f32, i16::MAX as f32);
+                write_le(&mut row[index * 2..], 2, raw as i16 as u16 as u32);
+            }
+            Sample::Fixed32 => {
+                let raw = (v as f64 * 16_777_216.0)
+                    .round()
+                    .clamp(i32::MIN as f64, i32::MAX as f64);
+                write_le(&mut row[index * 4..], 4, raw as i32 as u32);
+            }
+            Sample::Half => write_le(&mut row[index * 2..], 2, f32_to_half(v) as u32),
+            Sample::Float => write_le(&mut row[index * 4..], 4, v.to_bits()),
+        }
+    }
+}
+
+fn read_le(bytes: &[u8], len: usize) -> u32 {
+    bytes[..len]
+        .iter()
+        .rev()
+        .fold(0, |acc, &b| (acc << 8) | b as u32)
+}
+
+fn write_le(bytes: &mut [u8], len: usize, value: u32) {
+    for (i, b) in bytes[..len].iter_mut().enumerate() {
+        *b = (value >> (i * 8)) as u8;
+    }
+}
+
+/// Reads `bits` (at most 8) bits starting at bit `offset`, counting from the
+/// most significant bit of the first byte.
+fn read_bits(row: &[u8], offset: usize, bits: usize) -> u32 {
+    let shift = 8 - bits - offset % 8;
+    (row[offset / 8] as u32 >> shift) & ((1 << bits) - 1)
+}
+
+fn write_bits(row: &mut [u8], offset: usize, bits: usize, value: u32) {
+    let shift = 8 - bits - offset % 8;
+    let mask = (((1u32 << bits) - 1) << shift) as u8;
+    let byte = &mut row[offset / 8];
+    *byte = (*byte & !mask) | ((value << shift) as u8 & mask);
+}
+
+/// Copies the first `bits` bits of `source`, leaving the rest of the last
+/// byte of `target` alone.
+pub(super) fn copy_bits(source: &[u8], target: &mut [u8], bits: usize) {
+    let whole = bits / 8;
+    target[..whole].copy_from_slice(&source[..whole]);
+    let rest = bits % 8;
+    if rest > 0 {
+        let mask = !(0xFFu8 >> rest);
+        target[whole] = (target[whole] & !mask) | (source[whole] & mask);
+    }
+}
+
+fn clamp01(v: f32) -> f32 {
+    v.clamp(0.0, 1.0)
+}
+
+fn quantize(v: f32, max: u32) -> u32 {
+    (clamp01(v) * max as f32 + 0.5) as u32
+}
+
+pub(super) fn premultiply(p: &mut [f32; 4]) {
+    let a = p[3];
+    map_rgb(p, |c| c * a);
+}
+
+pub(super) fn unpremultiply(p: &mut [f32; 4]) {
+    let a = p[3];
+    if a > 0.0 {
+        map_rgb(p, |c| c / a);
+    } else {
+        map_rgb(p, |_| 0.0);
+    }
+}
+
+fn map_rgb(p: &mut [f32; 4], f: impl Fn(f32) -> f32) {
+    for c in &mut p[..3] {
+        *c = f(*c);
+    }
+}
+
+/// The Rec. 709 luminance of a pixel.
+fn luma(p: &[f32; 4]) -> f32 {
+    0.2126 * p[0] + 0.7152 * p[1] + 0.0722 * p[2]
+}
+
+/// The sRGB transfer functions, extended to negative values by symmetry as
+/// scRGB does.
+fn srgb_to_linear(v: f32) -> f32 {
+    let a = v.abs();
+    let l = if a <= 0.04045 {
+        a / 12.92
+    } else {
+        ((a + 0.055) / 1.055).powf(2.4)
+    };
+    l.copysign(v)
+}
+
+fn linear_to_srgb(v: f32) -> f32 {
+    let a = v.abs();
+    let s = if a <= 0.003_130_8 {
+        a * 12.92
+    } else {
+        1.055 * a.powf(1.0 / 2.4) - 0.055
+    };
+    s.copysign(v)
+}
+
+/// The palette entry closest to `p`, which is in sRGB.
+fn nearest(palette: &[Color], p: &[f32; 4]) -> usize {
+    let target = [
+        quantize(p[0], 255) as i32,
+        quantize(p[1], 255) as i32,
+        quantize(p[2], 255) as i32,
+        quantize(p[3], 255) as i32,
+    ];
+    let distance = |c: &Color| {
+        let entry = [c.r() as i32, c.g() as i32, c.b() as i32, c.a() as i32];
+        entry
+            .iter()
+            .zip(&target)
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<i32>()
+    };
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, c)| distance(c))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+fn encode_rgbe(p: &[f32; 4]) -> [u8; 4] {
+    let [r, g, b] = [p[0].max(0.0), p[1].max(0.0), p[2].max(0.0)];
+    let max = r.max(g).max(b);
+    if max.is_nan() || max < 1e-32 {
+        return [0; 4];
+    }
+    // max = mantissa * 2^exp with mantissa in [0.5, 1).
+    let mut exp = max.log2().floor() as i32 + 1;
+    if max / 2f32.powi(exp) >= 1.0 {
+        exp += 1;
+    }
+    let exp = exp.clamp(-127, 127);
+    let scale = 256.0 / 2f32.powi(exp);
+    let m = |c: f32| (c * scale).min(255.0) as u8;
+    [m(r), m(g), m(b), (exp + 128) as u8]
+}
+
+pub(crate) fn half_to_f32(h: u16) -> f32 {
+    let sign = ((h as u32) & 0x8000) << 16;
+    let exp = ((h >> 10) & 0x1F) as u32;
+    let man = (h & 0x3FF) as u32;
+    let bits = match exp {
+        0 if man == 0 => sign,
+        0 => {
+            // Subnormal: man * 2^-24.
+            let v = man as f32 / 16_777_216.0;
+            return if sign != 0 { -v } else { v };
+        }
+        31 => sign | 0x7F80_0000 | (man << 13),
+        _ => sign | ((exp + 127 - 15) << 23) | (man << 13),
+    };
+    f32::from_bits(bits)
+}
+
+pub(crate) fn f32_to_half(v: f32) -> u16 {
+    let bits = v.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let a = v.abs();
+    if v.is_nan() {
+        return sign | 0x7E00;
+    }
+    if a >= 65520.0 {
+        return sign | 0x7C00;
+    }
+    if a < 6.103_515_6e-5 {
+        // Below the smallest normal half, 2^-14.
+        return sign | (a * 16_777_216.0).round() as u16;
+    }
+    let exp = ((bits >> 23) & 0xFF) + 15 - 127;
+    let man = bits & 0x7F_FFFF;
+    let mut h = (exp << 10) | (man >> 13);
+    let rest = man & 0x1FFF;
+    if rest > 0x1000 || (rest == 0x1000 && h & 1 == 1) {
+        // Rounding may carry into the exponent, which is still correct.
+        h += 1;
+    }
+    sign | h as u16
+}
+
+#[cfg(test)]
+fn convert_pixels(source: &crate::GUID, target: &crate::GUID, data: &[u8], width: u32) -> Vec<u8> {
+    let source = PixelFormat { guid: *source };
+    let target = PixelFormat { guid: *target };
+    let converter = PixelConverter::new(source, target).unwrap();
+    let mut out = vec![0; target.description().unwrap().stride(width).unwrap()];
+    converter.convert_row(data, &mut out, width).unwrap();
+    out
+}
+
+#[cfg(test)]
+#[test]
+fn swizzle_and_premultiply() {
+    use crate::descriptions::guids::*;
+
+    let bgra = [10, 20, 30, 255, 200, 100, 0, 128];
+    let rgba = convert_pixels(
+        &GUID_WICPixelFormat32bppBGRA,
+        &GUID_WICPixelFormat32bppRGBA,
+        &bgra,
+        2,
+    );
+    assert_eq!(rgba, [30, 20, 10, 255, 0, 100, 200, 128]);
+
+    let pbgra = convert_pixels(
+        &GUID_WICPixelFormat32bppBGRA,
+        &GUID_WICPixelFormat32bppPBGRA,
+        &bgra,
+        2,
+    );
+    assert_eq!(pbgra, [10, 20, 30, 255, 100, 50, 0, 128]);
+    let back = convert_pixels(
+        &GUID_WICPixelFormat32bppPBGRA,
+        &GUID_WICPixelFormat32bppBGRA,
+        &pbgra,
+        2,
+    );
+    assert_eq!(back, [10, 20, 30, 255, 199, 100, 0, 128]);
+
+    let rgb = convert_pixels(
+        &GUID_WICPixelFormat32bppBGRA,
+        &GUID_WICPixelFormat24bppRGB,
+        &bgra,
+        2,
+    );
+    assert_eq!(rgb, [30, 20, 10, 0, 100, 200]);
+    let bgrx = convert_pixels(
+        &GUID_WICPixelFormat24bppRGB,
+        &GUID_WICPixelFormat32bppBGR,
+        &rgb,
+        2,
+    );
+    assert_eq!(bgrx, [10, 20, 30, 255, 200, 100, 0, 255]);
+}
+
+#[cfg(test)]
+#[test]
+fn indexed_gray_and_packed() {
+    use crate::descriptions::guids::*;
+
+    let palette = [
+        Color::rgb(0, 0, 0),
+        Color::rgb(255, 0, 0),
+        Color::rgba(0, 0, 255, 0),
+    ];
+    let source = PixelFormat {
+        guid: GUID_WICPixelFormat2bppIndexed,
+    };
+    let target = PixelFormat {
+        guid: GUID_WICPixelFormat32bppRGBA,
+    };
+    let converter = PixelConverter::new(source, target).unwrap();
+    let mut out = [0; 12];
+    let indices = [0b0110_0000];
+    assert_eq!(
+        converter.convert_row(&indices, &mut out, 3).unwrap_err(),
+        Error::WIC_PALETTEUNAVAILABLE
+    );
+    let converter = converter.with_source_palette(&palette);
+    converter.convert_row(&indices, &mut out, 3).unwrap();
+    assert_eq!(out, [255, 0, 0, 255, 0, 0, 255, 0, 0, 0, 0, 255]);
+
+    let back = PixelConverter::new(target, source)
+        .unwrap()
+        .with_target_palette(&palette);
+    let mut indices = [0xFF];
+    back.convert_row(&out, &mut indices, 3).unwrap();
+    assert_eq!(indices, [0b0110_0011]);
+
+    let gray = convert_pixels(
+        &GUID_WICPixelFormat24bppRGB,
+        &GUID_WICPixelFormat8bppGray,
+        &[77, 77, 77, 255, 255, 255],
+        2,
+    );
+    assert_eq!(gray, [77, 255]);
+    let bw = convert_pixels(
+        &GUID_WICPixelFormat8bppGray,
+        &GUID_WICPixelFormatBlackWhite,
+        &[0, 255, 10, 250],
+        4,
+    );
+    assert_eq!(bw, [0b0101_0000]);
+
+    let rgb565 = convert_pixels(
+        &GUID_WICPixelFormat24bppRGB,
+        &GUID_WICPixelFormat16bppBGR565,
+        &[255, 0, 8],
+        1,
+    );
+    assert_eq!(rgb565, 0xF801u16.to_le_bytes());
+    let rgb = convert_pixels(
+        &GUID_WICPixelFormat16bppBGR565,
+        &GUID_WICPixelFormat24bppRGB,
+        &rgb565,
+        1,
+    );
+    assert_eq!(rgb, [255, 0, 8]);
+}
+
+#[cfg(test)]
+#[test]
+fn float_formats() {
+    use crate::descriptions::guids::*;
+
+    let rgba = [255, 188, 0, 51];
+    let float = convert_pixels(
+        &GUID_WICPixelFormat32bppRGBA,
+        &GUID_WICPixelFormat128bppRGBAFloat,
+        &rgba,
+        1,
+    );
+    let values: Vec<f32> = float
+        .chunks(4)
+        .map(|c| f32::from_bits(read_le(c, 4)))
+        .collect();
+    assert_eq!(values[0], 1.0);
+    assert!((values[1] - 0.5).abs() < 0.01);
+    assert_eq!(values[2], 0.0);
+    assert_eq!(values[3], 0.2);
+
+    let half = convert_pixels(
+        &GUID_WICPixelFormat128bppRGBAFloat,
+        &GUID_WICPixelFormat64bppRGBAHalf,
+        &float,
+        1,
+    );
+    assert_eq!(&half[..2], &0x3C00u16.to_le_bytes());
+    let fixed = convert_pixels(
+        &GUID_WICPixelFormat64bppRGBAHalf,
+        &GUID_WICPixelFormat64bppRGBAFixedPoint,
+        &half,
+        1,
+    );
+    assert_eq!(&fixed[..2], &8192u16.to_le_bytes());
+    let back = convert_pixels(
+        &GUID_WICPixelFormat64bppRGBAFixedPoint,
+        &GUID_WICPixelFormat32bppRGBA,
+        &fixed,
+        1,
+    );
+    assert_eq!(back, rgba);
+
+    for &v in &[0.0f32, 1.0, -2.5, 0.333, 65504.0, 1e-6, 1e-8] {
+        let h = half_to_f32(f32_to_half(v));
+        assert!((h - v).abs() <= v.abs() / 1024.0 + 6e-8, "{} => {}", v, h);
+    }
+
+    let rgbe = convert_pixels(
+        &GUID_WICPixelFormat128bppRGBFloat,
+        &GUID_WICPixelFormat32bppRGBE,
+        &[
+            0, 0, 0x80, 0x3F, 0, 0, 0, 0x40, 0, 0, 0x80, 0x3E, 0, 0, 0, 0,
+        ],
+        1,
+    );
+    assert_eq!(rgbe, [64, 128, 16, 130]);
+    let float = convert_pixels(
+        &GUID_WICPixelFormat32bppRGBE,
+        &GUID_WICPixelFormat128bppRGBFloat,
+        &rgbe,
+        1,
+    );
+    assert_eq!(f32::from_bits(read_le(&float[4..], 4)), 2.0);
+
+    let cmyk = [0, 255, 255, 0, 0, 0, 0, 255];
+    let rgb = convert_pixels(
+        &GUID_WICPixelFormat32bppCMYK,
+        &GUID_WICPixelFormat24bppRGB,
+        &cmyk,
+        2,
+    );
+    assert_eq!(rgb, [255, 0, 0, 0, 0, 0]);
+}
+
+#[cfg(test)]
+#[test]
+fn convert_checks_buffer_sizes() {
+    use crate::descriptions::guids::*;
+
+    let converter = PixelConverter::new(
+        PixelFormat {
+            guid: GUID_WICPixelFormat8bppGray,
+        },
+        PixelFormat {
+            guid: GUID_WICPixelFormat32bppBGRA,
+        },
+    )
+    .unwrap();
+    let source = [0; 4];
+    let mut target = [0; 16];
+    converter.convert(&source, 2, &mut target, 8, 2, 2).unwrap();
+    assert_eq!(
+        converter.convert(&source, 2, &mut target, 8, 2, 3),
+        Err(Error::WIC_INSUFFICIENTBUFFER)
+    );
+    assert_eq!(
+        converter.convert(&source, usize::MAX, &mut target, 8, 2, 3),
+        Err(Error::WIC_INSUFFICIENTBUFFER)
+    );
+}
@@ -0,0 +1,69 @@
+use crate::enums::BitmapTransformOptions;
+use crate::software::buffer::{copy_pixel, PixelBuffer};
+
+impl PixelBuffer {
+    /// Rotates the image clockwise and then flips it, like
+    /// `BitmapFlipRotator`.
+    pub fn flip_rotate(&self, options: BitmapTransformOptions) -> PixelBuffer {
+        let rotation = options.0 & 0x3;
+        let flip_h = options.0 & BitmapTransformOptions::FLIP_HORIZONTAL.0 != 0;
+        let flip_v = options.0 & BitmapTransformOptions::FLIP_VERTICAL.0 != 0;
+
+        let (w, h) = (self.width() as usize, self.height() as usize);
+        let (out_w, out_h) = match rotation {
+            1 | 3 => (h, w),
+            _ => (w, h),
+        };
+        let mut out = PixelBuffer::new(self.format(), out_w as u32, out_h as u32)
+            .expect("the format of an existing buffer is valid");
+        let bits = self.bits_per_pixel();
+
+        for oy in 0..out_h {
+            let fy = if flip_v { out_h - 1 - oy } else { oy };
+            let target = out.row_mut(oy as u32);
+            for ox in 0..out_w {
+                let fx = if flip_h { out_w - 1 - ox } else { ox };
+                let (sx, sy) = match rotation {
+                    1 => (fy, h - 1 - fx),
+                    2 => (w - 1 - fx, h - 1 - fy),
+                    3 => (w - 1 - fy, fx),
+                    _ => (fx, fy),
+                };
+                copy_pixel(self.row(sy as u32), sx, target, ox, bits);
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn flip_rotate() {
+    use crate::descriptions::guids::GUID_WICPixelFormat8bppGray;
+    use crate::descriptions::PixelFormat;
+
+    let format = PixelFormat {
+        guid: GUID_WICPixelFormat8bppGray,
+    };
+    // 1 2 3
+    // 4 5 6
+    let image = PixelBuffer::from_data(format, 3, 2, 4, vec![1, 2, 3, 0, 4, 5, 6, 0]).unwrap();
+    let transform = |options| {
+        let out = image.flip_rotate(options);
+        (out.width(), out.height(), out.into_data())
+    };
+
+    use BitmapTransformOptions as T;
+    assert_eq!(transform(T::ROTATE0), (3, 2, vec![1, 2, 3, 4, 5, 6]));
+    assert_eq!(transform(T::ROTATE90), (2, 3, vec![4, 1, 5, 2, 6, 3]));
+    assert_eq!(transform(T::ROTATE180), (3, 2, vec![6, 5, 4, 3, 2, 1]));
+    assert_eq!(transform(T::ROTATE270), (2, 3, vec![3, 6, 2, 5, 1, 4]));
+    assert_eq!(
+        transform(T::FLIP_HORIZONTAL),
+        (3, 2, vec![3, 2, 1, 6, 5, 4])
+    );
+    assert_eq!(transform(T::FLIP_VERTICAL), (3, 2, vec![4, 5, 6, 1, 2, 3]));
+    // A transpose, as for EXIF orientation 5.
+    let transpose = T(T::ROTATE90.0 | T::FLIP_HORIZONTAL.0);
+    assert_eq!(transform(transpose), (2, 3, vec![1, 4, 2, 5, 3, 6]));
+}
@@ -0,0 +1,14 @@
+//! Image processing done in Rust rather than by WIC, for when a native
+//! component is unavailable or unsuitable, and for checking results against
+//! WIC on any platform.
+//!
+//! `PixelConverter` matches `FormatConverter`, and `PixelBuffer::scale` and
+//! `PixelBuffer::flip_rotate` match `BitmapScaler` and `BitmapFlipRotator`.
+
+pub use self::buffer::PixelBuffer;
+pub use self::convert::PixelConverter;
+
+pub mod buffer;
+pub mod convert;
+mod flip_rotate;
+mod scale;
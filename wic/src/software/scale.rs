@@ -0,0 +1,205 @@
+use crate::enums::BitmapInterpolationMode;
+use crate::software::buffer::{copy_pixel, PixelBuffer};
+use crate::software::convert::{premultiply, unpremultiply, RowCodec};
+
+use dcommon::Error;
+
+impl PixelBuffer {
+    /// Resamples the image to `width` by `height`, like `BitmapScaler`.
+    ///
+    /// Filtering happens on premultiplied values in the format's own colour
+    /// space. Indexed formats, and others the software converter can't read,
+    /// always use nearest-neighbour sampling.
+    pub fn scale(
+        &self,
+        width: u32,
+        height: u32,
+        mode: BitmapInterpolationMode,
+    ) -> Result<PixelBuffer, Error> {
+        if width == 0 || height == 0 {
+            return Err(Error::INVALIDARG);
+        }
+        let mut out = PixelBuffer::new(self.format(), width, height)?;
+        let codec = match mode {
+            BitmapInterpolationMode::NearestNeighbor => None,
+            _ => RowCodec::new(self.format()),
+        };
+        let codec = match codec {
+            Some(codec) if self.width() > 0 && self.height() > 0 => codec,
+            _ => {
+                self.scale_nearest(&mut out);
+                return Ok(out);
+            }
+        };
+
+        let columns = weights(mode, self.width(), width);
+        let mut rows = Vec::with_capacity(self.height() as usize);
+        let mut scratch = Vec::new();
+        for y in 0..self.height() {
+            scratch.clear();
+            codec.decode(self.row(y), self.width() as usize, &mut scratch);
+            scratch.iter_mut().for_each(premultiply);
+            rows.push(resample(&columns, |x| scratch[x]));
+        }
+
+        for (y, taps) in weights(mode, self.height(), height).iter().enumerate() {
+            let mut row: Vec<[f32; 4]> = (0..width as usize)
+                .map(|x| apply(taps, |sy| rows[sy][x]))
+                .collect();
+            row.iter_mut().for_each(unpremultiply);
+            codec.encode(&row, out.row_mut(y as u32));
+        }
+        Ok(out)
+    }
+
+    fn scale_nearest(&self, out: &mut PixelBuffer) {
+        if self.width() == 0 || self.height() == 0 {
+            return;
+        }
+        let bits = self.bits_per_pixel();
+        let xs: Vec<usize> = (0..out.width())
+            .map(|x| nearest(x, self.width(), out.width()))
+            .collect();
+        for y in 0..out.height() {
+            let source = self.row(nearest(y, self.height(), out.height()) as u32);
+            let target = out.row_mut(y);
+            for (x, &sx) in xs.iter().enumerate() {
+                copy_pixel(source, sx, target, x, bits);
+            }
+        }
+    }
+}
+
+/// The source pixel whose centre is nearest the centre of pixel `i`.
+fn nearest(i: u32, source: u32, target: u32) -> usize {
+    let s = ((i as u64 * 2 + 1) * source as u64) / (target as u64 * 2);
+    s.min(source as u64 - 1) as usize
+}
+
+/// The source pixels and weights that make up each target pixel along one
+/// axis. Samples outside the image are clamped to its edge.
+fn weights(mode: BitmapInterpolationMode, source: u32, target: u32) -> Vec<Vec<(usize, f32)>> {
+    let scale = source as f64 / target as f64;
+    let last = source as i64 - 1;
+    (0..target)
+        .map(|i| {
+            let mut taps = Vec::new();
+            let mut push = |j: i64, w: f64| {
+                if w != 0.0 {
+                    taps.push((j.clamp(0, last) as usize, w));
+                }
+            };
+            if let BitmapInterpolationMode::Fant = mode {
+                // The area of each source pixel the target pixel covers.
+                let start = i as f64 * scale;
+                let end = (i + 1) as f64 * scale;
+                for j in start.floor() as i64..end.ceil() as i64 {
+                    push(j, end.min(j as f64 + 1.0) - start.max(j as f64));
+                }
+            } else {
+                let (radius, stretch) = match mode {
+                    BitmapInterpolationMode::Linear => (1.0, 1.0),
+                    BitmapInterpolationMode::HighQualityCubic => (2.0, scale.max(1.0)),
+                    _ => (2.0, 1.0),
+                };
+                let kernel = |x: f64| match mode {
+                    BitmapInterpolationMode::Linear => 1.0 - x.abs(),
+                    _ => cubic(x),
+                };
+                let center = (i as f64 + 0.5) * scale - 0.5;
+                let support = radius * stretch;
+                for j in (center - support).ceil() as i64..=(center + support).floor() as i64 {
+                    push(j, kernel((j as f64 - center) / stretch));
+                }
+            }
+
+            let total: f64 = taps.iter().map(|&(_, w)| w).sum();
+            taps.into_iter()
+                .map(|(j, w)| (j, (w / total) as f32))
+                .collect()
+        })
+        .collect()
+}
+
+/// The Keys cubic convolution kernel with `a = -0.5`.
+fn cubic(x: f64) -> f64 {
+    let a = -0.5;
+    let x = x.abs();
+    if x < 1.0 {
+        ((a + 2.0) * x - (a + 3.0)) * x * x + 1.0
+    } else if x < 2.0 {
+        ((a * x - 5.0 * a) * x + 8.0 * a) * x - 4.0 * a
+    } else {
+        0.0
+    }
+}
+
+fn resample(columns: &[Vec<(usize, f32)>], pixel: impl Fn(usize) -> [f32; 4]) -> Vec<[f32; 4]> {
+    columns.iter().map(|taps| apply(taps, &pixel)).collect()
+}
+
+fn apply(taps: &[(usize, f32)], pixel: impl Fn(usize) -> [f32; 4]) -> [f32; 4] {
+    let mut sum = [0.0; 4];
+    for &(j, w) in taps {
+        let p = pixel(j);
+        for (s, c) in sum.iter_mut().zip(&p) {
+            *s += c * w;
+        }
+    }
+    sum
+}
+
+#[cfg(test)]
+fn gray_image(width: u32, height: u32, data: &[u8]) -> PixelBuffer {
+    use crate::descriptions::guids::GUID_WICPixelFormat8bppGray;
+    use crate::descriptions::PixelFormat;
+
+    let format = PixelFormat {
+        guid: GUID_WICPixelFormat8bppGray,
+    };
+    PixelBuffer::from_data(format, width, height, width as usize, data.to_vec()).unwrap()
+}
+
+#[cfg(test)]
+#[test]
+fn scale_modes() {
+    use crate::descriptions::guids::*;
+    use crate::descriptions::PixelFormat;
+    use crate::enums::BitmapInterpolationMode::*;
+
+    let image = gray_image(4, 1, &[0, 100, 200, 40]);
+    assert_eq!(
+        image.scale(2, 1, NearestNeighbor).unwrap().data(),
+        [100, 40]
+    );
+    assert_eq!(image.scale(2, 1, Linear).unwrap().data(), [50, 120]);
+    assert_eq!(image.scale(2, 1, Fant).unwrap().data(), [50, 120]);
+    assert_eq!(image.scale(2, 1, Cubic).unwrap().data(), [44, 126]);
+    assert_eq!(image.scale(1, 1, HighQualityCubic).unwrap().data(), [83]);
+    for &mode in &[NearestNeighbor, Linear, Cubic, Fant, HighQualityCubic] {
+        assert_eq!(image.scale(4, 1, mode).unwrap().data(), image.data());
+    }
+
+    let image = gray_image(2, 2, &[0, 255, 255, 0]);
+    let up = image.scale(4, 4, Linear).unwrap();
+    assert_eq!(up.row(0), [0, 64, 191, 255]);
+    assert_eq!(up.row(1), [64, 96, 159, 191]);
+    assert_eq!(
+        image.scale(4, 4, NearestNeighbor).unwrap().row(3),
+        [255, 255, 0, 0]
+    );
+
+    // Transparent pixels don't bleed their colour into opaque ones.
+    let format = PixelFormat {
+        guid: GUID_WICPixelFormat32bppBGRA,
+    };
+    let data = vec![0, 0, 255, 255, 255, 0, 0, 0];
+    let image = PixelBuffer::from_data(format, 2, 1, 8, data).unwrap();
+    assert_eq!(image.scale(1, 1, Fant).unwrap().data(), [0, 0, 255, 128]);
+
+    let bw = PixelFormat {
+        guid: GUID_WICPixelFormatBlackWhite,
+    };
+    let image = PixelBuffer::from_data(bw, 4, 1, 1, vec![0b1010_0000]).unwrap();
+    assert_eq!(image.scale(8, 1, Linear).unwrap().data(), [0b1100_1100]);
+}
@@ -4,12 +4,11 @@ use std::ffi::OsStr;
 use std::ptr::NonNull;
 
 use com_wrapper::ComWrapper;
-use dcommon::helpers::{deref_com_wrapper, deref_com_wrapper_mut};
+use dcommon::helpers::{deref_com_wrapper, deref_com_wrapper_mut, WideString};
 use dcommon::objidl::{self, enums::AccessMask};
 use dcommon::{Error, Status};
 use winapi::um::wincodec::IWICStream;
 use wio::com::ComPtr;
-use wio::wide::ToWide;
 
 #[repr(transparent)]
 #[derive(ComWrapper)]
@@ -55,13 +54,17 @@ impl Stream {
         filename: impl AsRef<OsStr>,
         access: AccessMask,
     ) -> Result<Status, Error> {
-        let filename = filename.as_ref().to_wide_null();
+        let filename = WideString::new(filename)?;
         unsafe {
             let hr = self.ptr.InitializeFromFilename(filename.as_ptr(), access.0);
             Error::map_status(hr)
         }
     }
 
+    /// Makes the stream read from and write to `data`.
+    ///
+    /// # Safety
+    ///
     /// The caller is responsible for ensuring the memory passed lives for the life of the stream.
     /// It may be recommended to use a custom implementation of IStream that owns your memory
     /// instead and use initialize_from_stream. You must also ensure `data` is not longer than